@@ -1,7 +1,7 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::IntoResponse,
 };
 use serde::{Deserialize, Serialize};
@@ -16,11 +16,14 @@ use crate::domain::{
     },
     ids::{InstrumentId, ScoreId, StaffId, VoiceId},
     instrument::Instrument,
+    instrument_catalog::{self, CatalogInstrument},
+    lint::ScoreLinter,
     score::Score,
     staff::Staff,
     value_objects::{BPM, Clef, KeySignature, Pitch, Tick},
     voice::Voice,
 };
+use crate::layout::{LayoutConfig, compute_layout, render::SvgOptions, render::render_svg};
 use crate::ports::persistence::ScoreRepository;
 
 /// Application state with repository
@@ -38,6 +41,11 @@ pub struct ScoreListResponse {
     pub scores: Vec<String>, // UUIDs as strings
 }
 
+#[derive(Debug, Serialize)]
+pub struct InstrumentCatalogResponse {
+    pub instruments: &'static [CatalogInstrument],
+}
+
 // Note: ScoreDto, InstrumentDto, and StaffDto are now imported from shared adapters::dtos module
 
 #[derive(Debug, Deserialize)]
@@ -119,6 +127,61 @@ pub async fn get_score(
     Ok(Json(score_dto))
 }
 
+/// GET /scores/{score_id}/render.svg - Render score layout as SVG
+pub async fn render_score_svg(
+    State(repo): State<AppState>,
+    Path(score_id): Path<String>,
+) -> Result<impl IntoResponse, PersistenceError> {
+    let id = ScoreId::parse(&score_id)
+        .map_err(|e| PersistenceError::NotFound(format!("Invalid UUID: {}", e)))?;
+
+    let score = repo
+        .find_by_id(id)?
+        .ok_or_else(|| PersistenceError::NotFound(format!("Score {} not found", score_id)))?;
+
+    // Lay out the same DTO the frontend receives
+    let score_json = serde_json::to_value(ScoreDto::from(&score))
+        .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+    let layout = compute_layout(&score_json, &LayoutConfig::default());
+    let svg = render_svg(&layout, &SvgOptions::default());
+
+    Ok(([(header::CONTENT_TYPE, "image/svg+xml")], svg))
+}
+
+/// GET /scores/{score_id}/lint - Check the score and report findings
+pub async fn lint_score(
+    State(repo): State<AppState>,
+    Path(score_id): Path<String>,
+) -> Result<impl IntoResponse, PersistenceError> {
+    let id = ScoreId::parse(&score_id)
+        .map_err(|e| PersistenceError::NotFound(format!("Invalid UUID: {}", e)))?;
+
+    let score = repo
+        .find_by_id(id)?
+        .ok_or_else(|| PersistenceError::NotFound(format!("Score {} not found", score_id)))?;
+
+    Ok(Json(ScoreLinter::new().lint(&score)))
+}
+
+/// POST /scores/{score_id}/fill-rests - Fill every voice's gaps with rests
+pub async fn fill_rests(
+    State(repo): State<AppState>,
+    Path(score_id): Path<String>,
+) -> Result<impl IntoResponse, PersistenceError> {
+    let id = ScoreId::parse(&score_id)
+        .map_err(|e| PersistenceError::NotFound(format!("Invalid UUID: {}", e)))?;
+
+    let mut score = repo
+        .find_by_id(id)?
+        .ok_or_else(|| PersistenceError::NotFound(format!("Score {} not found", score_id)))?;
+
+    score.fill_rests();
+    let score_dto = ScoreDto::from(&score);
+    repo.save(score)?;
+
+    Ok(Json(score_dto))
+}
+
 /// DELETE /scores/{score_id} - Delete score
 pub async fn delete_score(
     State(repo): State<AppState>,
@@ -132,6 +195,22 @@ pub async fn delete_score(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// GET /instruments - List the built-in instrument catalog
+pub async fn list_instruments() -> impl IntoResponse {
+    Json(InstrumentCatalogResponse {
+        instruments: instrument_catalog::catalog(),
+    })
+}
+
+/// GET /instruments/{instrument_id} - Get a catalog instrument by id
+pub async fn get_instrument(
+    Path(instrument_id): Path<String>,
+) -> Result<impl IntoResponse, DomainError> {
+    instrument_catalog::find(&instrument_id)
+        .map(Json)
+        .ok_or_else(|| DomainError::NotFound(format!("Instrument {} not found", instrument_id)))
+}
+
 /// POST /scores/{score_id}/instruments - Add instrument
 pub async fn add_instrument(
     State(repo): State<AppState>,
@@ -281,7 +360,8 @@ pub async fn add_tempo_event(
     let tempo_event = TempoEvent::new(
         Tick::new(payload.tick),
         BPM::new(payload.bpm).map_err(|e| DomainError::ValidationError(e.to_string()))?,
-    );
+    )
+    .with_mark(None, true); // Tempo changes added by the user are printed as ♩ = N
 
     score.add_tempo_event(tempo_event.clone())?;
 
@@ -7,8 +7,8 @@ use tower_http::trace::TraceLayer;
 
 use super::handlers::{
     AppState, add_clef_event, add_instrument, add_key_signature_event, add_note, add_staff,
-    add_tempo_event, add_time_signature_event, add_voice, create_score, delete_score, get_score,
-    list_scores,
+    add_tempo_event, add_time_signature_event, add_voice, create_score, delete_score, fill_rests,
+    get_instrument, get_score, lint_score, list_instruments, list_scores, render_score_svg,
 };
 use super::import::import_musicxml;
 
@@ -20,8 +20,14 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/v1/scores", get(list_scores))
         .route("/api/v1/scores/:score_id", get(get_score))
         .route("/api/v1/scores/:score_id", delete(delete_score))
+        .route("/api/v1/scores/:score_id/render.svg", get(render_score_svg))
+        .route("/api/v1/scores/:score_id/lint", get(lint_score))
+        .route("/api/v1/scores/:score_id/fill-rests", post(fill_rests))
         // MusicXML import
         .route("/api/v1/scores/import-musicxml", post(import_musicxml))
+        // Instrument catalog
+        .route("/api/v1/instruments", get(list_instruments))
+        .route("/api/v1/instruments/:instrument_id", get(get_instrument))
         // Instrument operations
         .route("/api/v1/scores/:score_id/instruments", post(add_instrument))
         // Staff operations
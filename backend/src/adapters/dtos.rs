@@ -2,11 +2,21 @@
 // These DTOs add computed fields like active_clef to domain entities
 
 use crate::domain::{
-    events::{global::GlobalStructuralEvent, staff::StaffStructuralEvent},
-    instrument::Instrument,
+    events::{
+        chord_symbol::ChordSymbolEvent,
+        direction::{RehearsalMark, TextDirection},
+        global::GlobalStructuralEvent,
+        grace::NoteTiming,
+        pedal::PedalRegion,
+        staff::StaffStructuralEvent,
+        tempo::FermataHold,
+    },
+    instrument::{Instrument, MidiSettings},
+    part_group::PartGroup,
     repeat::{RepeatBarline, VoltaBracket},
     score::{OctaveShiftRegion, Score},
-    staff::Staff,
+    staff::{DEFAULT_STAFF_LINES, Staff},
+    tablature::Tablature,
     value_objects::Clef,
     voice::Voice,
 };
@@ -21,6 +31,36 @@ pub struct StaffDto {
     pub active_clef: Clef, // NEW: Derived from first ClefEvent
     pub staff_structural_events: Vec<StaffStructuralEvent>,
     pub voices: Vec<Voice>,
+    /// Chord symbols anchored to this staff (v9)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chord_symbols: Vec<ChordSymbolEvent>,
+    /// Pedal regions for notation and playback sustain (v10)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pedal_regions: Vec<PedalRegion>,
+    /// Sounding onset/duration of grace notes and of the notes they take
+    /// time from, for playback and practice scoring (v12)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub grace_timings: Vec<NoteTiming>,
+    /// Text directions anchored to this staff (v15)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub text_directions: Vec<TextDirection>,
+    /// Number of staff lines (v18)
+    #[serde(
+        default = "default_staff_lines",
+        skip_serializing_if = "is_default_staff_lines"
+    )]
+    pub staff_lines: u8,
+    /// String tuning of a tablature staff (v19)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tablature: Option<Tablature>,
+}
+
+fn default_staff_lines() -> u8 {
+    DEFAULT_STAFF_LINES
+}
+
+fn is_default_staff_lines(lines: &u8) -> bool {
+    *lines == DEFAULT_STAFF_LINES
 }
 
 impl From<&Staff> for StaffDto {
@@ -40,6 +80,12 @@ impl From<&Staff> for StaffDto {
             active_clef,
             staff_structural_events: staff.staff_structural_events.clone(),
             voices: staff.voices.clone(),
+            chord_symbols: staff.chord_symbols.clone(),
+            pedal_regions: staff.pedal_regions.clone(),
+            grace_timings: staff.voices.iter().flat_map(Voice::grace_timings).collect(),
+            text_directions: staff.text_directions.clone(),
+            staff_lines: staff.staff_lines,
+            tablature: staff.tablature.clone(),
         }
     }
 }
@@ -49,8 +95,14 @@ impl From<&Staff> for StaffDto {
 pub struct InstrumentDto {
     pub id: String,
     pub name: String,
+    /// Abbreviated name for later systems (v22)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
     pub instrument_type: String,
     pub staves: Vec<StaffDto>,
+    /// MIDI program, channel, volume and pan (v21)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub midi: Option<MidiSettings>,
 }
 
 impl From<&Instrument> for InstrumentDto {
@@ -58,8 +110,10 @@ impl From<&Instrument> for InstrumentDto {
         Self {
             id: instrument.id.to_string(),
             name: instrument.name.clone(),
+            short_name: instrument.short_name.clone(),
             instrument_type: instrument.instrument_type.clone(),
             staves: instrument.staves.iter().map(StaffDto::from).collect(),
+            midi: instrument.midi,
         }
     }
 }
@@ -73,7 +127,22 @@ impl From<&Instrument> for InstrumentDto {
 /// v6: pickup_ticks added to ScoreDto (044-time-signatures)
 /// v7: volta_brackets added to ScoreDto (047-repeat-volta-playback)
 /// v8: octave_shift_regions added to ScoreDto (050-fix-layout-preloaded-scores)
-const SCORE_SCHEMA_VERSION: u32 = 8;
+/// v9: chord_symbols added to StaffDto
+/// v10: pedal_regions added to StaffDto
+/// v11: cross_staff added to notes
+/// v12: grace annotations added to notes, grace_timings added to StaffDto
+/// v13: arpeggio, gliss_next/gliss_wavy and tremolo added to notes
+/// v14: exact tempo (bpm_milli, beat_ticks, ramp) on tempo events, fermatas added to ScoreDto
+/// v15: printed tempo marks, text_directions added to StaffDto, rehearsal_marks added to ScoreDto
+/// v16: page_breaks added to ScoreDto
+/// v17: system_breaks added to ScoreDto
+/// v18: staff_lines added to StaffDto
+/// v19: tablature added to StaffDto, tab (string/fret) added to notes
+/// v20: part_groups added to ScoreDto
+/// v21: midi added to InstrumentDto
+/// v22: short_name added to InstrumentDto
+/// v23: measure_numbers added to ScoreDto
+const SCORE_SCHEMA_VERSION: u32 = 23;
 
 /// DTO for Score containing InstrumentDtos with schema versioning
 #[derive(Debug, Serialize, Deserialize)]
@@ -87,6 +156,21 @@ pub struct ScoreDto {
     /// v5: Added rest_events to Voice
     /// v6: Added pickup_ticks for anacrusis/pickup measure support
     /// v7: Added volta_brackets for volta bracket playback (Feature 047)
+    /// v9: Added chord_symbols to StaffDto
+    /// v10: Added pedal_regions to StaffDto
+    /// v11: Added cross_staff to notes (cross-staff notes stay in their voice)
+    /// v12: Added grace annotations to notes and grace_timings to StaffDto
+    /// v13: Added arpeggio, glissando and tremolo markings to notes
+    /// v14: Added exact/ramped tempo events and fermatas
+    /// v15: Added tempo marks, text directions and rehearsal marks
+    /// v16: Added page_breaks
+    /// v17: Added system_breaks
+    /// v18: Added staff line counts to StaffDto
+    /// v19: Added tablature tunings to StaffDto and string/fret to notes
+    /// v20: Added part_groups
+    /// v21: Added instrument midi settings
+    /// v22: Added instrument short names
+    /// v23: Added measure_numbers
     pub schema_version: u32,
 
     pub global_structural_events: Vec<GlobalStructuralEvent>,
@@ -106,6 +190,24 @@ pub struct ScoreDto {
     /// Octave-shift regions (8va/8vb brackets) per staff (Feature 050)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub octave_shift_regions: Vec<OctaveShiftRegion>,
+    /// Fermata holds for playback timing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fermatas: Vec<FermataHold>,
+    /// Rehearsal marks (v15)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rehearsal_marks: Vec<RehearsalMark>,
+    /// Measure indices that start a new page (v16)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub page_breaks: Vec<u32>,
+    /// Measure indices that start a new system (v17)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub system_breaks: Vec<u32>,
+    /// Brackets and braces joining adjacent instruments (v20)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub part_groups: Vec<PartGroup>,
+    /// Printed measure numbers by measure index, `None` = unnumbered (v23)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub measure_numbers: Vec<Option<String>>,
 }
 
 impl From<&Score> for ScoreDto {
@@ -120,6 +222,12 @@ impl From<&Score> for ScoreDto {
             pickup_ticks: score.pickup_ticks,
             measure_end_ticks: score.measure_end_ticks.clone(),
             octave_shift_regions: score.octave_shift_regions.clone(),
+            fermatas: score.fermatas.clone(),
+            rehearsal_marks: score.rehearsal_marks.clone(),
+            page_breaks: score.page_breaks.clone(),
+            system_breaks: score.system_breaks.clone(),
+            part_groups: score.part_groups.clone(),
+            measure_numbers: score.measure_numbers.clone(),
         }
     }
 }
@@ -4,6 +4,9 @@ pub mod persistence;
 // Shared DTOs for both API and WASM adapters
 pub mod dtos;
 
+// Score editing session, exposed to JavaScript by the WASM adapter
+pub mod session;
+
 // API adapter only for native backend (uses axum, tower, not available in WASM)
 #[cfg(not(target_arch = "wasm32"))]
 pub mod api;
@@ -0,0 +1,404 @@
+// Score editing session shared by the WASM adapter
+// Owns a Score, indexes it by id and keeps its layout and timing up to date
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::adapters::dtos::ScoreDto;
+use crate::domain::{
+    errors::DomainError,
+    events::{
+        clef::ClefEvent, key_signature::KeySignatureEvent, note::Note, rest::RestEvent,
+        tempo::TempoEvent, time_signature::TimeSignatureEvent,
+    },
+    ids::{InstrumentId, NoteId, StaffId, VoiceId},
+    instrument::Instrument,
+    score::Score,
+    staff::Staff,
+    tempo_map::TempoMap,
+    value_objects::{BPM, Clef, KeySignature, Tick},
+    voice::Voice,
+};
+use crate::layout::{GlobalLayout, IncrementalLayout, LayoutConfig, TickRange};
+
+/// What an edit changed, sent back instead of the whole score
+///
+/// Added entities are sent whole (with their nested staves and voices)
+/// together with the id of their parent.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScoreChange {
+    InstrumentAdded {
+        instrument: Instrument,
+    },
+    StaffAdded {
+        instrument_id: InstrumentId,
+        staff: Staff,
+    },
+    VoiceAdded {
+        staff_id: StaffId,
+        voice: Voice,
+    },
+    NoteAdded {
+        voice_id: VoiceId,
+        note: Note,
+    },
+    NoteRemoved {
+        voice_id: VoiceId,
+        note_id: NoteId,
+    },
+    /// Every voice's rests after `fill_rests`
+    RestsFilled {
+        voices: Vec<VoiceRests>,
+    },
+    TempoEventAdded {
+        event: TempoEvent,
+    },
+    TimeSignatureEventAdded {
+        event: TimeSignatureEvent,
+    },
+    ClefEventAdded {
+        staff_id: StaffId,
+        event: ClefEvent,
+    },
+    KeySignatureEventAdded {
+        staff_id: StaffId,
+        event: KeySignatureEvent,
+    },
+}
+
+/// The rests of one voice
+#[derive(Debug, Clone, Serialize)]
+pub struct VoiceRests {
+    pub voice_id: VoiceId,
+    pub rest_events: Vec<RestEvent>,
+}
+
+/// Positions of every instrument, staff, voice and note in the score
+///
+/// Instruments, staves and voices are only ever appended, so their
+/// positions stay valid; note positions are fixed up when a note is removed.
+#[derive(Debug, Default)]
+struct ScoreIndex {
+    instruments: HashMap<InstrumentId, usize>,
+    /// (instrument, staff)
+    staves: HashMap<StaffId, (usize, usize)>,
+    /// (instrument, staff, voice)
+    voices: HashMap<VoiceId, (usize, usize, usize)>,
+    /// (voice, position within the voice's notes)
+    notes: HashMap<NoteId, (VoiceId, usize)>,
+}
+
+impl ScoreIndex {
+    fn new(score: &Score) -> Self {
+        let mut index = Self::default();
+        for (i, instrument) in score.instruments.iter().enumerate() {
+            index.add_instrument(instrument, i);
+        }
+        index
+    }
+
+    fn add_instrument(&mut self, instrument: &Instrument, i: usize) {
+        self.instruments.insert(instrument.id, i);
+        for (s, staff) in instrument.staves.iter().enumerate() {
+            self.add_staff(staff, i, s);
+        }
+    }
+
+    fn add_staff(&mut self, staff: &Staff, i: usize, s: usize) {
+        self.staves.insert(staff.id, (i, s));
+        for (v, voice) in staff.voices.iter().enumerate() {
+            self.add_voice(voice, (i, s, v));
+        }
+    }
+
+    fn add_voice(&mut self, voice: &Voice, path: (usize, usize, usize)) {
+        self.voices.insert(voice.id, path);
+        for (n, note) in voice.interval_events.iter().enumerate() {
+            self.notes.insert(note.id, (voice.id, n));
+        }
+    }
+}
+
+/// A score being edited
+///
+/// Keeps the `Score` in memory between edits so that callers (the WASM
+/// `ScoreSession`) send and receive only what changes: each edit returns a
+/// `ScoreChange`, and `layout` re-lays out only the ticks edited since the
+/// previous call (see `IncrementalLayout`). The tempo map is kept until the
+/// tempo changes, for playback queries.
+pub struct ScoreSession {
+    score: Score,
+    index: ScoreIndex,
+    config: LayoutConfig,
+    layout: Option<IncrementalLayout>,
+    /// Ticks edited since the layout was last brought up to date
+    pending: Option<TickRange>,
+    tempo_map: Option<TempoMap>,
+}
+
+impl ScoreSession {
+    /// Start a session on an existing score
+    pub fn new(score: Score) -> Self {
+        Self {
+            index: ScoreIndex::new(&score),
+            score,
+            config: LayoutConfig::default(),
+            layout: None,
+            pending: None,
+            tempo_map: None,
+        }
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    pub fn instrument(&self, id: InstrumentId) -> Option<&Instrument> {
+        let &i = self.index.instruments.get(&id)?;
+        self.score.instruments.get(i)
+    }
+
+    pub fn staff(&self, id: StaffId) -> Option<&Staff> {
+        let &(i, s) = self.index.staves.get(&id)?;
+        self.score.instruments.get(i)?.staves.get(s)
+    }
+
+    pub fn voice(&self, id: VoiceId) -> Option<&Voice> {
+        let &(i, s, v) = self.index.voices.get(&id)?;
+        self.score.instruments.get(i)?.staves.get(s)?.voices.get(v)
+    }
+
+    pub fn note(&self, id: NoteId) -> Option<&Note> {
+        let &(voice_id, n) = self.index.notes.get(&id)?;
+        self.voice(voice_id)?.interval_events.get(n)
+    }
+
+    /// Add an instrument with one staff and one voice
+    pub fn add_instrument(&mut self, name: &str) -> ScoreChange {
+        let instrument = Instrument::new(name.to_string());
+        self.index
+            .add_instrument(&instrument, self.score.instruments.len());
+        self.score.add_instrument(instrument.clone());
+        self.touch_all();
+        ScoreChange::InstrumentAdded { instrument }
+    }
+
+    /// Add a staff with one voice to an instrument
+    pub fn add_staff(&mut self, instrument_id: InstrumentId) -> Result<ScoreChange, DomainError> {
+        let &i = self.index.instruments.get(&instrument_id).ok_or_else(|| {
+            DomainError::NotFound(format!("Instrument with id {} not found", instrument_id))
+        })?;
+        let staff = Staff::new();
+        let instrument = &mut self.score.instruments[i];
+        self.index.add_staff(&staff, i, instrument.staves.len());
+        instrument.add_staff(staff.clone());
+        self.touch_all();
+        Ok(ScoreChange::StaffAdded {
+            instrument_id,
+            staff,
+        })
+    }
+
+    /// Add an empty voice to a staff
+    pub fn add_voice(&mut self, staff_id: StaffId) -> Result<ScoreChange, DomainError> {
+        let (i, s) = self.staff_path(staff_id)?;
+        let voice = Voice::new();
+        let staff = &mut self.score.instruments[i].staves[s];
+        self.index.add_voice(&voice, (i, s, staff.voices.len()));
+        staff.add_voice(voice.clone());
+        self.touch_all();
+        Ok(ScoreChange::VoiceAdded { staff_id, voice })
+    }
+
+    /// Add a note to a voice, rejecting overlaps at the same pitch
+    pub fn add_note(&mut self, voice_id: VoiceId, note: Note) -> Result<ScoreChange, DomainError> {
+        if self.index.notes.contains_key(&note.id) {
+            return Err(DomainError::DuplicateError(format!(
+                "Note with id {} already exists",
+                note.id
+            )));
+        }
+        let voice = self.voice_mut(voice_id)?;
+        voice.add_note(note.clone())?;
+        let position = voice.interval_events.len() - 1;
+        self.index.notes.insert(note.id, (voice_id, position));
+        self.touch(note.start_tick.value(), note.end_tick().value());
+        Ok(ScoreChange::NoteAdded { voice_id, note })
+    }
+
+    /// Remove a note, and the ties and slurs of its voice that end on it
+    pub fn remove_note(&mut self, note_id: NoteId) -> Result<ScoreChange, DomainError> {
+        let (voice_id, position) =
+            self.index.notes.remove(&note_id).ok_or_else(|| {
+                DomainError::NotFound(format!("Note with id {} not found", note_id))
+            })?;
+        let voice = self.voice_mut(voice_id)?;
+        let note = voice.interval_events.remove(position);
+        for other in &mut voice.interval_events {
+            if other.tie_next == Some(note_id) {
+                other.tie_next = None;
+            }
+            if other.slur_next == Some(note_id) {
+                other.slur_next = None;
+            }
+        }
+        let moved: Vec<NoteId> = voice.interval_events[position..]
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        for (offset, id) in moved.into_iter().enumerate() {
+            self.index.notes.insert(id, (voice_id, position + offset));
+        }
+        self.touch(note.start_tick.value(), note.end_tick().value());
+        Ok(ScoreChange::NoteRemoved { voice_id, note_id })
+    }
+
+    /// Fill the gaps of every voice with rests (see `Score::fill_rests`)
+    pub fn fill_rests(&mut self) -> ScoreChange {
+        self.score.fill_rests();
+        self.touch_all();
+        let voices = self
+            .score
+            .instruments
+            .iter()
+            .flat_map(|i| &i.staves)
+            .flat_map(|s| &s.voices)
+            .map(|v| VoiceRests {
+                voice_id: v.id,
+                rest_events: v.rest_events.clone(),
+            })
+            .collect();
+        ScoreChange::RestsFilled { voices }
+    }
+
+    /// Add a tempo change, printed as a metronome mark
+    pub fn add_tempo_event(&mut self, tick: u32, bpm: u16) -> Result<ScoreChange, DomainError> {
+        let bpm = BPM::new(bpm).map_err(|e| DomainError::ValidationError(e.to_string()))?;
+        let event = TempoEvent::new(Tick::new(tick), bpm).with_mark(None, true);
+        self.score.add_tempo_event(event.clone())?;
+        self.tempo_map = None;
+        self.touch_all();
+        Ok(ScoreChange::TempoEventAdded { event })
+    }
+
+    pub fn add_time_signature_event(
+        &mut self,
+        tick: u32,
+        numerator: u8,
+        denominator: u8,
+    ) -> Result<ScoreChange, DomainError> {
+        let event = TimeSignatureEvent::new(Tick::new(tick), numerator, denominator);
+        self.score.add_time_signature_event(event.clone())?;
+        self.touch_all();
+        Ok(ScoreChange::TimeSignatureEventAdded { event })
+    }
+
+    pub fn add_clef_event(
+        &mut self,
+        staff_id: StaffId,
+        tick: u32,
+        clef: Clef,
+    ) -> Result<ScoreChange, DomainError> {
+        let (i, s) = self.staff_path(staff_id)?;
+        let event = ClefEvent::new(Tick::new(tick), clef);
+        self.score.instruments[i].staves[s].add_clef_event(event.clone())?;
+        self.touch_all();
+        Ok(ScoreChange::ClefEventAdded { staff_id, event })
+    }
+
+    /// Add a key signature change; `sharps` is negative for flats
+    pub fn add_key_signature_event(
+        &mut self,
+        staff_id: StaffId,
+        tick: u32,
+        sharps: i8,
+    ) -> Result<ScoreChange, DomainError> {
+        let (i, s) = self.staff_path(staff_id)?;
+        let key =
+            KeySignature::new(sharps).map_err(|e| DomainError::ValidationError(e.to_string()))?;
+        let event = KeySignatureEvent::new(Tick::new(tick), key);
+        self.score.instruments[i].staves[s].add_key_signature_event(event.clone())?;
+        self.touch_all();
+        Ok(ScoreChange::KeySignatureEventAdded { staff_id, event })
+    }
+
+    /// Layout of the score as edited so far
+    ///
+    /// The first call lays out the whole score; later calls re-lay out only
+    /// the ticks edited in between.
+    pub fn layout(&mut self) -> &GlobalLayout {
+        let pending = self.pending.take();
+        let score = &self.score;
+        let json = || serde_json::to_value(ScoreDto::from(score)).unwrap_or_default();
+        match (&mut self.layout, pending) {
+            (Some(layout), Some(changed)) => {
+                layout.update(&json(), changed);
+            }
+            (Some(_), None) => {}
+            (None, _) => self.layout = Some(IncrementalLayout::new(&json(), &self.config)),
+        }
+        self.layout
+            .as_ref()
+            .expect("layout computed above")
+            .layout()
+    }
+
+    /// Tick ↔ seconds conversion for the score as edited so far, built on
+    /// the first call after a tempo change
+    pub fn tempo_map(&mut self) -> &TempoMap {
+        let score = &self.score;
+        self.tempo_map.get_or_insert_with(|| score.tempo_map())
+    }
+
+    /// Change the layout configuration, e.g. after a viewport resize
+    pub fn set_layout_config(&mut self, config: &LayoutConfig) -> &GlobalLayout {
+        self.config = config.clone();
+        if let Some(layout) = &mut self.layout {
+            layout.set_config(config);
+        }
+        self.layout()
+    }
+
+    fn staff_path(&self, staff_id: StaffId) -> Result<(usize, usize), DomainError> {
+        self.index
+            .staves
+            .get(&staff_id)
+            .copied()
+            .ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", staff_id)))
+    }
+
+    fn voice_mut(&mut self, voice_id: VoiceId) -> Result<&mut Voice, DomainError> {
+        let &(i, s, v) = self.index.voices.get(&voice_id).ok_or_else(|| {
+            DomainError::NotFound(format!("Voice with id {} not found", voice_id))
+        })?;
+        Ok(&mut self.score.instruments[i].staves[s].voices[v])
+    }
+
+    /// Mark ticks as edited
+    fn touch(&mut self, start_tick: u32, end_tick: u32) {
+        let end_tick = end_tick.max(start_tick + 1);
+        self.pending = Some(match self.pending {
+            Some(p) => TickRange {
+                start_tick: p.start_tick.min(start_tick),
+                end_tick: p.end_tick.max(end_tick),
+            },
+            None => TickRange {
+                start_tick,
+                end_tick,
+            },
+        });
+    }
+
+    /// Mark a structural edit; the layout notices what changed
+    fn touch_all(&mut self) {
+        self.touch(0, u32::MAX);
+    }
+}
+
+impl Default for ScoreSession {
+    fn default() -> Self {
+        Self::new(Score::new())
+    }
+}
@@ -132,8 +132,8 @@ pub fn parse_musicxml(xml_content: &str) -> Result<JsValue, JsValue> {
 use super::error_handling::to_js_error;
 use crate::domain::{
     events::{
-        clef::ClefEvent, key_signature::KeySignatureEvent, note::Note, tempo::TempoEvent,
-        time_signature::TimeSignatureEvent,
+        chord_symbol::ChordSymbolEvent, clef::ClefEvent, key_signature::KeySignatureEvent,
+        note::Note, tempo::TempoEvent, time_signature::TimeSignatureEvent,
     },
     ids::{InstrumentId, StaffId, VoiceId},
     instrument::Instrument,
@@ -145,7 +145,7 @@ use crate::domain::{
 
 /// Helper function to parse key signature string to sharps/flats count
 /// Examples: "C" -> 0, "G" -> 1, "D" -> 2, "F" -> -1, "Bb" -> -2
-fn parse_key_signature(key: &str) -> Result<i8, JsValue> {
+pub(super) fn parse_key_signature(key: &str) -> Result<i8, JsValue> {
     match key {
         // Major keys with sharps
         "C" => Ok(0),
@@ -188,6 +188,20 @@ fn parse_key_signature(key: &str) -> Result<i8, JsValue> {
     }
 }
 
+/// Helper function to parse a clef name: treble, bass, alto or tenor
+pub(super) fn parse_clef(clef_type: &str) -> Result<Clef, JsValue> {
+    match clef_type.to_lowercase().as_str() {
+        "treble" => Ok(Clef::Treble),
+        "bass" => Ok(Clef::Bass),
+        "alto" => Ok(Clef::Alto),
+        "tenor" => Ok(Clef::Tenor),
+        _ => Err(JsValue::from_str(&format!(
+            "Invalid clef type: {}",
+            clef_type
+        ))),
+    }
+}
+
 /// Create a new empty score with default structural events
 ///
 /// # Arguments
@@ -343,7 +357,8 @@ pub fn add_tempo_event(score_js: JsValue, tick: u32, bpm: u16) -> Result<JsValue
 
     let bpm_value = BPM::new(bpm).map_err(|e| JsValue::from_str(e))?;
 
-    let tempo_event = TempoEvent::new(Tick::new(tick), bpm_value);
+    // Printed as a metronome mark (♩ = N)
+    let tempo_event = TempoEvent::new(Tick::new(tick), bpm_value).with_mark(None, true);
 
     score.add_tempo_event(tempo_event).map_err(to_js_error)?;
 
@@ -404,18 +419,7 @@ pub fn add_clef_event(
     let staff_id = StaffId::parse(staff_id)
         .map_err(|e| JsValue::from_str(&format!("Invalid staff ID: {}", e)))?;
 
-    let clef = match clef_type.to_lowercase().as_str() {
-        "treble" => Clef::Treble,
-        "bass" => Clef::Bass,
-        "alto" => Clef::Alto,
-        "tenor" => Clef::Tenor,
-        _ => {
-            return Err(JsValue::from_str(&format!(
-                "Invalid clef type: {}",
-                clef_type
-            )));
-        }
-    };
+    let clef = parse_clef(clef_type)?;
 
     let clef_event = ClefEvent::new(Tick::new(tick), clef);
 
@@ -484,3 +488,124 @@ pub fn add_key_signature_event(
     serde_wasm_bindgen::to_value(&score)
         .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
 }
+
+/// Format a chord symbol for display
+///
+/// Shares the engine's chord-symbol formatting with the frontend so that
+/// chord names shown outside the rendered score match the layout output.
+///
+/// # Arguments
+/// * `chord_js` - ChordSymbolEvent as JsValue
+///
+/// # Returns
+/// * JsValue representing ChordSymbolText (root, quality, superscript extension, bass)
+#[wasm_bindgen]
+pub fn format_chord_symbol(chord_js: JsValue) -> Result<JsValue, JsValue> {
+    let chord: ChordSymbolEvent = serde_wasm_bindgen::from_value(chord_js)
+        .map_err(|e| JsValue::from_str(&format!("Deserialization error: {}", e)))?;
+
+    serde_wasm_bindgen::to_value(&chord.format())
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+// ============================================================================
+// Tempo map: tick ↔ seconds conversion
+// ============================================================================
+
+/// The parts of a Score / ScoreDto that determine its timing
+#[derive(Debug, Deserialize)]
+struct ScoreTiming {
+    global_structural_events: Vec<crate::domain::events::global::GlobalStructuralEvent>,
+    #[serde(default)]
+    fermatas: Vec<crate::domain::events::tempo::FermataHold>,
+}
+
+fn tempo_map_from_js(score_js: JsValue) -> Result<crate::domain::tempo_map::TempoMap, JsValue> {
+    use crate::domain::events::global::GlobalStructuralEvent;
+
+    let timing: ScoreTiming = serde_wasm_bindgen::from_value(score_js)
+        .map_err(|e| JsValue::from_str(&format!("Deserialization error: {}", e)))?;
+    let tempo_events: Vec<TempoEvent> = timing
+        .global_structural_events
+        .into_iter()
+        .filter_map(|e| match e {
+            GlobalStructuralEvent::Tempo(te) => Some(te),
+            _ => None,
+        })
+        .collect();
+    Ok(crate::domain::tempo_map::TempoMap::new(
+        &tempo_events,
+        &timing.fermatas,
+    ))
+}
+
+/// Handle to the tempo map of one score
+///
+/// Build it once per score and keep it for playback queries: the free
+/// functions `ticks_to_seconds` / `seconds_to_ticks` read the whole score on
+/// every call. A `ScoreSession` keeps its own.
+#[wasm_bindgen(js_name = TempoMap)]
+pub struct TempoMapWasm {
+    inner: crate::domain::tempo_map::TempoMap,
+}
+
+#[wasm_bindgen(js_class = TempoMap)]
+impl TempoMapWasm {
+    /// Tempo map of a Score or ScoreDto
+    #[wasm_bindgen(constructor)]
+    pub fn new(score_js: JsValue) -> Result<TempoMapWasm, JsValue> {
+        Ok(Self {
+            inner: tempo_map_from_js(score_js)?,
+        })
+    }
+
+    /// Seconds from the start of the score for each tick
+    pub fn ticks_to_seconds(&self, ticks: Vec<u32>) -> Vec<f64> {
+        ticks
+            .iter()
+            .map(|&t| self.inner.tick_to_seconds(t))
+            .collect()
+    }
+
+    /// Fractional tick position for each time in seconds
+    pub fn seconds_to_ticks(&self, seconds: Vec<f64>) -> Vec<f64> {
+        seconds
+            .iter()
+            .map(|&s| self.inner.seconds_to_tick(s))
+            .collect()
+    }
+}
+
+/// Convert tick positions to seconds from the start of the score
+///
+/// Uses the score's tempo events (fractional BPM, beat units, accel./rit.
+/// ramps) and fermata holds, so playback, recording alignment and MIDI
+/// export share the engine's exact timing. Reads the whole score; for
+/// repeated queries keep a `TempoMap` (or use the `ScoreSession`).
+///
+/// # Arguments
+/// * `score_js` - Score or ScoreDto as JsValue
+/// * `ticks` - Tick positions (960 PPQ)
+///
+/// # Returns
+/// * Seconds for each tick, in the same order
+#[wasm_bindgen]
+pub fn ticks_to_seconds(score_js: JsValue, ticks: Vec<u32>) -> Result<Vec<f64>, JsValue> {
+    let map = tempo_map_from_js(score_js)?;
+    Ok(ticks.iter().map(|&t| map.tick_to_seconds(t)).collect())
+}
+
+/// Convert times in seconds to (fractional) tick positions; the inverse of
+/// [`ticks_to_seconds`]
+///
+/// # Arguments
+/// * `score_js` - Score or ScoreDto as JsValue
+/// * `seconds` - Times from the start of the score
+///
+/// # Returns
+/// * Fractional tick position for each time, in the same order
+#[wasm_bindgen]
+pub fn seconds_to_ticks(score_js: JsValue, seconds: Vec<f64>) -> Result<Vec<f64>, JsValue> {
+    let map = tempo_map_from_js(score_js)?;
+    Ok(seconds.iter().map(|&s| map.seconds_to_tick(s)).collect())
+}
@@ -3,5 +3,6 @@
 
 pub mod bindings;
 pub mod error_handling;
+pub mod session;
 
 pub use error_handling::{WasmError, error_to_js, import_error_to_js, to_js_error};
@@ -0,0 +1,187 @@
+// WASM Score Session
+// Keeps the Score in Rust memory between edits; see adapters::session
+
+use super::bindings::{parse_clef, parse_key_signature};
+use super::error_handling::{import_error_to_js, to_js_error};
+use crate::adapters::dtos::ScoreDto;
+use crate::adapters::session::{ScoreChange, ScoreSession};
+use crate::domain::{
+    events::note::Note,
+    ids::{InstrumentId, NoteId, StaffId, VoiceId},
+    importers::musicxml::{ImportContext, MusicXMLConverter, MusicXMLParser},
+    score::Score,
+};
+use crate::layout::{LayoutConfig, encode_layout};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// A score being edited from JavaScript
+///
+/// Unlike the stateless bindings, which send the whole score across the
+/// boundary on every call, the session owns the score: edits return a
+/// `ScoreChange` delta and `layout()` re-lays out only what was edited.
+#[wasm_bindgen(js_name = ScoreSession)]
+pub struct ScoreSessionWasm {
+    inner: ScoreSession,
+}
+
+#[wasm_bindgen(js_class = ScoreSession)]
+impl ScoreSessionWasm {
+    /// Start a session on a new empty score (120 BPM, 4/4)
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ScoreSessionWasm {
+        Self {
+            inner: ScoreSession::new(Score::new()),
+        }
+    }
+
+    /// Start a session on an existing Score, sent across once
+    pub fn from_score(score_js: JsValue) -> Result<ScoreSessionWasm, JsValue> {
+        let score: Score = serde_wasm_bindgen::from_value(score_js)
+            .map_err(|e| JsValue::from_str(&format!("Deserialization error: {}", e)))?;
+        Ok(Self {
+            inner: ScoreSession::new(score),
+        })
+    }
+
+    /// Start a session on an imported MusicXML document
+    pub fn from_musicxml(xml_content: &str) -> Result<ScoreSessionWasm, JsValue> {
+        let mut context = ImportContext::new();
+        let doc = MusicXMLParser::parse(xml_content, &mut context).map_err(import_error_to_js)?;
+        let score = MusicXMLConverter::convert(doc, &mut context).map_err(import_error_to_js)?;
+        Ok(Self {
+            inner: ScoreSession::new(score),
+        })
+    }
+
+    /// The whole score as ScoreDto, e.g. for saving
+    pub fn score(&self) -> Result<JsValue, JsValue> {
+        to_js(&ScoreDto::from(self.inner.score()))
+    }
+
+    /// Add an instrument with one staff and one voice
+    pub fn add_instrument(&mut self, name: &str) -> Result<JsValue, JsValue> {
+        change_to_js(Ok(self.inner.add_instrument(name)))
+    }
+
+    pub fn add_staff(&mut self, instrument_id: &str) -> Result<JsValue, JsValue> {
+        let instrument_id = InstrumentId::parse(instrument_id)
+            .map_err(|e| JsValue::from_str(&format!("Invalid instrument ID: {}", e)))?;
+        change_to_js(self.inner.add_staff(instrument_id))
+    }
+
+    pub fn add_voice(&mut self, staff_id: &str) -> Result<JsValue, JsValue> {
+        change_to_js(self.inner.add_voice(parse_staff_id(staff_id)?))
+    }
+
+    pub fn add_note(&mut self, voice_id: &str, note_js: JsValue) -> Result<JsValue, JsValue> {
+        let voice_id = VoiceId::parse(voice_id)
+            .map_err(|e| JsValue::from_str(&format!("Invalid voice ID: {}", e)))?;
+        let note: Note = serde_wasm_bindgen::from_value(note_js)
+            .map_err(|e| JsValue::from_str(&format!("Note deserialization error: {}", e)))?;
+        change_to_js(self.inner.add_note(voice_id, note))
+    }
+
+    pub fn remove_note(&mut self, note_id: &str) -> Result<JsValue, JsValue> {
+        let note_id = NoteId::parse(note_id)
+            .map_err(|e| JsValue::from_str(&format!("Invalid note ID: {}", e)))?;
+        change_to_js(self.inner.remove_note(note_id))
+    }
+
+    /// Fill every voice's gaps with rests; the change lists each voice's rests
+    pub fn fill_rests(&mut self) -> Result<JsValue, JsValue> {
+        change_to_js(Ok(self.inner.fill_rests()))
+    }
+
+    pub fn add_tempo_event(&mut self, tick: u32, bpm: u16) -> Result<JsValue, JsValue> {
+        change_to_js(self.inner.add_tempo_event(tick, bpm))
+    }
+
+    pub fn add_time_signature_event(
+        &mut self,
+        tick: u32,
+        numerator: u8,
+        denominator: u8,
+    ) -> Result<JsValue, JsValue> {
+        change_to_js(
+            self.inner
+                .add_time_signature_event(tick, numerator, denominator),
+        )
+    }
+
+    /// Add a clef change: "treble", "bass", "alto" or "tenor"
+    pub fn add_clef_event(
+        &mut self,
+        staff_id: &str,
+        tick: u32,
+        clef_type: &str,
+    ) -> Result<JsValue, JsValue> {
+        let staff_id = parse_staff_id(staff_id)?;
+        let clef = parse_clef(clef_type)?;
+        change_to_js(self.inner.add_clef_event(staff_id, tick, clef))
+    }
+
+    /// Add a key signature change, e.g. "G", "Bb", "F#m"
+    pub fn add_key_signature_event(
+        &mut self,
+        staff_id: &str,
+        tick: u32,
+        key: &str,
+    ) -> Result<JsValue, JsValue> {
+        let staff_id = parse_staff_id(staff_id)?;
+        let sharps = parse_key_signature(key)?;
+        change_to_js(self.inner.add_key_signature_event(staff_id, tick, sharps))
+    }
+
+    /// GlobalLayout of the score as edited so far
+    pub fn layout(&mut self) -> Result<JsValue, JsValue> {
+        to_js(self.inner.layout())
+    }
+
+    /// `layout()` in the binary encoding (`layout::binary`), as a Uint8Array
+    pub fn layout_binary(&mut self) -> Vec<u8> {
+        encode_layout(self.inner.layout())
+    }
+
+    /// Seconds from the start of the score for each tick (see
+    /// `ticks_to_seconds`), without sending the score across
+    pub fn ticks_to_seconds(&mut self, ticks: Vec<u32>) -> Vec<f64> {
+        let map = self.inner.tempo_map();
+        ticks.iter().map(|&t| map.tick_to_seconds(t)).collect()
+    }
+
+    /// Fractional tick position for each time in seconds; the inverse of
+    /// `ticks_to_seconds`
+    pub fn seconds_to_ticks(&mut self, seconds: Vec<f64>) -> Vec<f64> {
+        let map = self.inner.tempo_map();
+        seconds.iter().map(|&s| map.seconds_to_tick(s)).collect()
+    }
+
+    /// Re-layout with a new configuration (LayoutConfig as JSON string)
+    pub fn set_layout_config(&mut self, config_json: &str) -> Result<JsValue, JsValue> {
+        let config: LayoutConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse config JSON: {}", e)))?;
+        to_js(self.inner.set_layout_config(&config))
+    }
+}
+
+impl Default for ScoreSessionWasm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_staff_id(staff_id: &str) -> Result<StaffId, JsValue> {
+    StaffId::parse(staff_id).map_err(|e| JsValue::from_str(&format!("Invalid staff ID: {}", e)))
+}
+
+fn change_to_js(
+    change: Result<ScoreChange, crate::domain::errors::DomainError>,
+) -> Result<JsValue, JsValue> {
+    to_js(&change.map_err(to_js_error)?)
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(value)
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
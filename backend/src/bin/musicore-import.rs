@@ -3,6 +3,7 @@
 
 use clap::Parser;
 use musicore_backend::domain::importers::musicxml::MusicXMLImporter;
+use musicore_backend::domain::lint::{LintReport, LintSeverity, ScoreLinter};
 use musicore_backend::ports::importers::IMusicXMLImporter;
 use std::fs;
 use std::path::PathBuf;
@@ -28,6 +29,11 @@ struct Cli {
     #[arg(long)]
     validate_only: bool,
 
+    /// Check the imported score and print the findings (unless quiet);
+    /// exits with code 5 if any finding is an error
+    #[arg(long)]
+    lint: bool,
+
     /// Suppress all output except errors
     #[arg(short, long)]
     quiet: bool,
@@ -129,8 +135,22 @@ fn main() {
         }
     }
 
+    // Lint the imported score
+    let lint_failed = if cli.lint {
+        let report = ScoreLinter::new().lint(&result.score);
+        if !cli.quiet {
+            print_lint_report(&report);
+        }
+        report.has_errors()
+    } else {
+        false
+    };
+
     // Validate-only mode: exit without saving
     if cli.validate_only {
+        if lint_failed {
+            process::exit(5);
+        }
         if !cli.quiet {
             eprintln!("\n✓ Validation successful");
         }
@@ -172,6 +192,57 @@ fn main() {
             println!("{}", output_content);
         }
     }
+
+    if lint_failed {
+        process::exit(5);
+    }
+}
+
+/// Print lint findings, one per line, with their location
+fn print_lint_report(report: &LintReport) {
+    eprintln!("\nLint:");
+    if report.is_clean() {
+        eprintln!("  ✓ No findings");
+        return;
+    }
+    for finding in &report.findings {
+        let location = &finding.location;
+        let mut context_parts = Vec::new();
+        if let Some(measure) = location.measure {
+            context_parts.push(format!("measure {}", measure + 1));
+        }
+        if let Some(instrument) = location.instrument {
+            context_parts.push(format!("instrument {}", instrument + 1));
+        }
+        if let Some(staff) = location.staff {
+            context_parts.push(format!("staff {}", staff + 1));
+        }
+        if let Some(voice) = location.voice {
+            context_parts.push(format!("voice {}", voice + 1));
+        }
+        if let Some(tick) = location.tick {
+            context_parts.push(format!("tick {}", tick));
+        }
+
+        let severity_marker = match finding.severity {
+            LintSeverity::Info => "ℹ",
+            LintSeverity::Warning => "⚠",
+            LintSeverity::Error => "✗",
+        };
+        eprintln!(
+            "  {} {} [{}] {}",
+            severity_marker,
+            finding.rule.id(),
+            context_parts.join(", "),
+            finding.message
+        );
+    }
+    eprintln!(
+        "  {} error(s), {} warning(s), {} info",
+        report.count(LintSeverity::Error),
+        report.count(LintSeverity::Warning),
+        report.count(LintSeverity::Info)
+    );
 }
 
 /// Configure logging based on CLI flags
@@ -0,0 +1,168 @@
+// Score Render CLI Tool
+// Command-line tool for rendering MusicXML or MusiCore JSON scores to SVG
+
+use clap::Parser;
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::importers::musicxml::MusicXMLImporter;
+use musicore_backend::domain::score::Score;
+use musicore_backend::layout::render::{SvgOptions, render_svg};
+use musicore_backend::layout::{
+    EngravingStyle, LayoutConfig, LineBreaking, PageConfig, compute_layout,
+};
+use musicore_backend::ports::importers::IMusicXMLImporter;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// CLI arguments for score rendering
+#[derive(Parser, Debug)]
+#[command(
+    name = "musicore-render",
+    about = "Render scores (.xml, .mxl, .musicxml or MusiCore .json) to SVG",
+    version
+)]
+struct Cli {
+    /// Path to the score to render
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Music font family for SMuFL glyphs
+    #[arg(long, default_value = "Bravura")]
+    font_family: String,
+
+    /// URL of the music font, embedded as an @font-face rule
+    #[arg(long, value_name = "URL")]
+    font_url: Option<String>,
+
+    /// Maximum system width in logical units (ignored with --page-size)
+    #[arg(long)]
+    max_system_width: Option<f32>,
+
+    /// Lay out on pages: a4 or letter
+    #[arg(long, value_name = "SIZE")]
+    page_size: Option<String>,
+
+    /// Balance all systems with total-fit line breaking instead of greedy
+    #[arg(long)]
+    total_fit: bool,
+
+    /// Target measures per system (implies --total-fit)
+    #[arg(long, value_name = "N")]
+    measures_per_system: Option<u32>,
+
+    /// Leave the last system at its natural width (implies --total-fit)
+    #[arg(long)]
+    ragged_last: bool,
+
+    /// Engraving style preset: compact, default or spacious
+    #[arg(long, value_name = "PRESET")]
+    style: Option<String>,
+
+    /// Lay out the whole score as one horizontal line of natural width
+    #[arg(long, conflicts_with_all = ["page_size", "total_fit", "measures_per_system", "ragged_last"])]
+    single_line: bool,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if !cli.file.exists() {
+        eprintln!("Error: File not found: {}", cli.file.display());
+        process::exit(1);
+    }
+
+    let score_json = match load_score(&cli.file) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(2);
+        }
+    };
+
+    let mut config = LayoutConfig::default();
+    if let Some(width) = cli.max_system_width {
+        config.max_system_width = width;
+    }
+    config.page = match cli.page_size.as_deref() {
+        None => None,
+        Some("a4") => Some(PageConfig::default()),
+        Some("letter") => Some(PageConfig::letter()),
+        Some(other) => {
+            eprintln!("Error: Unsupported page size: {} (use a4 or letter)", other);
+            process::exit(1);
+        }
+    };
+    if cli.total_fit || cli.measures_per_system.is_some() || cli.ragged_last {
+        config.line_breaking = LineBreaking::TotalFit {
+            target_measures_per_system: cli.measures_per_system,
+            ragged_last_system: cli.ragged_last,
+        };
+    }
+    if let Some(name) = cli.style.as_deref() {
+        config.engraving = match EngravingStyle::preset(name) {
+            Some(style) => style,
+            None => {
+                eprintln!(
+                    "Error: Unsupported style: {} (use compact, default or spacious)",
+                    name
+                );
+                process::exit(1);
+            }
+        };
+    }
+    if cli.single_line {
+        config.line_breaking = LineBreaking::single_line();
+    }
+    let layout = compute_layout(&score_json, &config);
+
+    let options = SvgOptions {
+        font_family: cli.font_family,
+        font_url: cli.font_url,
+        ..SvgOptions::default()
+    };
+    let svg = render_svg(&layout, &options);
+
+    match &cli.output {
+        Some(path) => {
+            if let Err(e) = fs::write(path, svg) {
+                eprintln!("Error: Failed to write output file: {}", e);
+                process::exit(4);
+            }
+        }
+        None => print!("{}", svg),
+    }
+}
+
+/// Load a score as the JSON the layout engine consumes
+///
+/// MusicXML is imported; JSON is accepted either as a serialized `Score`
+/// (as written by `musicore-import`) or as an already converted DTO.
+fn load_score(path: &Path) -> Result<serde_json::Value, String> {
+    let is_json = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+    let score = if is_json {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let value: serde_json::Value =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid JSON: {}", e))?;
+        match serde_json::from_value::<Score>(value.clone()) {
+            Ok(score) => score,
+            Err(_) => return Ok(value),
+        }
+    } else {
+        MusicXMLImporter::new()
+            .import_file(path)
+            .map_err(|e| format!("Import failed: {}", e))?
+            .score
+    };
+
+    serde_json::to_value(ScoreDto::from(&score))
+        .map_err(|e| format!("Failed to serialize score: {}", e))
+}
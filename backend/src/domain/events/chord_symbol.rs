@@ -0,0 +1,333 @@
+use crate::domain::value_objects::{NoteSpelling, Tick};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Chord quality, mirroring the MusicXML `<kind>` vocabulary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChordKind {
+    Major,
+    Minor,
+    Augmented,
+    Diminished,
+    Dominant,
+    MajorSeventh,
+    MinorSeventh,
+    DiminishedSeventh,
+    AugmentedSeventh,
+    HalfDiminished,
+    MajorMinor,
+    MajorSixth,
+    MinorSixth,
+    DominantNinth,
+    MajorNinth,
+    MinorNinth,
+    Dominant11th,
+    Major11th,
+    Minor11th,
+    Dominant13th,
+    Major13th,
+    Minor13th,
+    SuspendedSecond,
+    SuspendedFourth,
+    Neapolitan,
+    Italian,
+    French,
+    German,
+    Pedal,
+    Power,
+    Tristan,
+    Other,
+    /// "N.C." — no chord
+    None,
+}
+
+impl ChordKind {
+    /// Default (quality, superscript extension) pair for this kind.
+    ///
+    /// The quality sits on the baseline after the root ("m", "dim", "+"),
+    /// the extension is raised ("7", "maj9", "sus4").
+    fn symbol_parts(self) -> (&'static str, &'static str) {
+        match self {
+            ChordKind::Major => ("", ""),
+            ChordKind::Minor => ("m", ""),
+            ChordKind::Augmented => ("+", ""),
+            ChordKind::Diminished => ("dim", ""),
+            ChordKind::Dominant => ("", "7"),
+            ChordKind::MajorSeventh => ("", "maj7"),
+            ChordKind::MinorSeventh => ("m", "7"),
+            ChordKind::DiminishedSeventh => ("dim", "7"),
+            ChordKind::AugmentedSeventh => ("+", "7"),
+            ChordKind::HalfDiminished => ("m", "7\u{266D}5"),
+            ChordKind::MajorMinor => ("m", "maj7"),
+            ChordKind::MajorSixth => ("", "6"),
+            ChordKind::MinorSixth => ("m", "6"),
+            ChordKind::DominantNinth => ("", "9"),
+            ChordKind::MajorNinth => ("", "maj9"),
+            ChordKind::MinorNinth => ("m", "9"),
+            ChordKind::Dominant11th => ("", "11"),
+            ChordKind::Major11th => ("", "maj11"),
+            ChordKind::Minor11th => ("m", "11"),
+            ChordKind::Dominant13th => ("", "13"),
+            ChordKind::Major13th => ("", "maj13"),
+            ChordKind::Minor13th => ("m", "13"),
+            ChordKind::SuspendedSecond => ("", "sus2"),
+            ChordKind::SuspendedFourth => ("", "sus4"),
+            ChordKind::Neapolitan => ("", "N6"),
+            ChordKind::Italian => ("", "It+6"),
+            ChordKind::French => ("", "Fr+6"),
+            ChordKind::German => ("", "Ger+6"),
+            ChordKind::Pedal => ("", "ped"),
+            ChordKind::Power => ("", "5"),
+            ChordKind::Tristan => ("", "Tristan"),
+            ChordKind::Other | ChordKind::None => ("", ""),
+        }
+    }
+}
+
+/// How a `<degree>` modifies the chord
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DegreeType {
+    Add,
+    Alter,
+    Subtract,
+}
+
+/// A chord degree alteration (e.g. ♭9 added, ♯5 altered, 3 omitted)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChordDegree {
+    /// Scale degree (e.g. 5, 9, 11, 13)
+    pub value: u8,
+    /// Chromatic alteration: -1 = flat, 0 = natural, 1 = sharp
+    pub alter: i8,
+    pub degree_type: DegreeType,
+}
+
+/// Chord symbol (lead-sheet harmony) anchored to a tick on a staff.
+///
+/// Imported from MusicXML `<harmony>`; formatted for display with
+/// [`ChordSymbolEvent::format`] so that the layout engine and the frontend
+/// render the same text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChordSymbolEvent {
+    pub tick: Tick,
+    /// Root note; `None` for "N.C." (no chord)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<NoteSpelling>,
+    pub kind: ChordKind,
+    /// Explicit display text from `<kind text="...">`, overriding the default suffix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind_text: Option<String>,
+    /// Bass note for slash chords (e.g. the G in C/G)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bass: Option<NoteSpelling>,
+    /// Added, altered or omitted degrees, in source order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degrees: Vec<ChordDegree>,
+}
+
+impl ChordSymbolEvent {
+    pub fn new(tick: Tick, root: Option<NoteSpelling>, kind: ChordKind) -> Self {
+        Self {
+            tick,
+            root,
+            kind,
+            kind_text: None,
+            bass: None,
+            degrees: Vec::new(),
+        }
+    }
+
+    /// Set the explicit kind display text (builder pattern)
+    pub fn with_kind_text(mut self, text: String) -> Self {
+        self.kind_text = Some(text);
+        self
+    }
+
+    /// Set the bass note of a slash chord (builder pattern)
+    pub fn with_bass(mut self, bass: NoteSpelling) -> Self {
+        self.bass = Some(bass);
+        self
+    }
+
+    /// Set degree alterations (builder pattern)
+    pub fn with_degrees(mut self, degrees: Vec<ChordDegree>) -> Self {
+        self.degrees = degrees;
+        self
+    }
+
+    /// Format this chord symbol into display segments.
+    ///
+    /// Accidentals are rendered as ♯/♭ (𝄪/𝄫 for doubles); the extension and
+    /// degree alterations are meant to be drawn as superscript.
+    pub fn format(&self) -> ChordSymbolText {
+        let Some(root) = self.root.filter(|_| self.kind != ChordKind::None) else {
+            return ChordSymbolText {
+                root: "N.C.".to_string(),
+                ..ChordSymbolText::default()
+            };
+        };
+
+        let (quality, mut extension) = match &self.kind_text {
+            Some(text) => split_kind_text(text),
+            None => {
+                let (q, e) = self.kind.symbol_parts();
+                (q.to_string(), e.to_string())
+            }
+        };
+
+        for degree in &self.degrees {
+            let accidental = accidental_str(degree.alter);
+            match degree.degree_type {
+                DegreeType::Add => {
+                    extension.push_str(&format!("add{}{}", accidental, degree.value))
+                }
+                DegreeType::Alter => extension.push_str(&format!("{}{}", accidental, degree.value)),
+                DegreeType::Subtract => extension.push_str(&format!("no{}", degree.value)),
+            }
+        }
+
+        ChordSymbolText {
+            root: spelling_str(root),
+            quality,
+            extension,
+            bass: self.bass.map(spelling_str),
+        }
+    }
+}
+
+/// Formatted chord symbol, split into the segments a renderer draws
+/// differently: root and quality on the baseline, extension raised, and an
+/// optional "/bass" suffix back on the baseline.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChordSymbolText {
+    /// Root with accidental glyph, e.g. "B♭" (or "N.C.")
+    pub root: String,
+    /// Baseline quality, e.g. "m", "dim", "+"
+    pub quality: String,
+    /// Superscript extension and alterations, e.g. "7♭9"
+    pub extension: String,
+    /// Bass note with accidental glyph, without the slash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bass: Option<String>,
+}
+
+impl fmt::Display for ChordSymbolText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.root, self.quality, self.extension)?;
+        if let Some(bass) = &self.bass {
+            write!(f, "/{}", bass)?;
+        }
+        Ok(())
+    }
+}
+
+fn accidental_str(alter: i8) -> &'static str {
+    match alter {
+        2 => "\u{1D12A}",
+        1 => "\u{266F}",
+        -1 => "\u{266D}",
+        -2 => "\u{1D12B}",
+        _ => "",
+    }
+}
+
+fn spelling_str(spelling: NoteSpelling) -> String {
+    format!(
+        "{}{}",
+        spelling.step.to_ascii_uppercase(),
+        accidental_str(spelling.alter)
+    )
+}
+
+/// Split a `<kind text>` override into baseline quality and superscript
+/// extension at the first digit, converting ASCII accidentals before digits
+/// ("7b9" → "7♭9").
+fn split_kind_text(text: &str) -> (String, String) {
+    let split_at = text
+        .char_indices()
+        .find(|(i, c)| {
+            c.is_ascii_digit()
+                || ((*c == 'b' || *c == '#')
+                    && text[i + 1..].starts_with(|n: char| n.is_ascii_digit()))
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let (quality, extension) = text.split_at(split_at);
+
+    let mut converted = String::with_capacity(extension.len());
+    let chars: Vec<char> = extension.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        let before_digit = chars.get(i + 1).is_some_and(|n| n.is_ascii_digit());
+        match c {
+            'b' if before_digit => converted.push('\u{266D}'),
+            '#' if before_digit => converted.push('\u{266F}'),
+            _ => converted.push(c),
+        }
+    }
+    (quality.to_string(), converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spelling(step: char, alter: i8) -> NoteSpelling {
+        NoteSpelling { step, alter }
+    }
+
+    #[test]
+    fn test_format_major_and_minor_seventh() {
+        let c = ChordSymbolEvent::new(Tick::new(0), Some(spelling('C', 0)), ChordKind::Major);
+        assert_eq!(c.format().to_string(), "C");
+
+        let bbm7 = ChordSymbolEvent::new(
+            Tick::new(0),
+            Some(spelling('B', -1)),
+            ChordKind::MinorSeventh,
+        );
+        let text = bbm7.format();
+        assert_eq!(text.root, "B\u{266D}");
+        assert_eq!(text.quality, "m");
+        assert_eq!(text.extension, "7");
+        assert_eq!(text.to_string(), "B♭m7");
+    }
+
+    #[test]
+    fn test_format_slash_chord_and_degrees() {
+        let chord =
+            ChordSymbolEvent::new(Tick::new(0), Some(spelling('F', 1)), ChordKind::Dominant)
+                .with_bass(spelling('A', 1))
+                .with_degrees(vec![
+                    ChordDegree {
+                        value: 9,
+                        alter: -1,
+                        degree_type: DegreeType::Alter,
+                    },
+                    ChordDegree {
+                        value: 3,
+                        alter: 0,
+                        degree_type: DegreeType::Subtract,
+                    },
+                ]);
+        let text = chord.format();
+        assert_eq!(text.extension, "7♭9no3");
+        assert_eq!(text.bass.as_deref(), Some("A♯"));
+        assert_eq!(text.to_string(), "F♯7♭9no3/A♯");
+    }
+
+    #[test]
+    fn test_format_kind_text_override() {
+        let chord =
+            ChordSymbolEvent::new(Tick::new(0), Some(spelling('D', 0)), ChordKind::Dominant)
+                .with_kind_text("m7b5".to_string());
+        let text = chord.format();
+        assert_eq!(text.quality, "m");
+        assert_eq!(text.extension, "7♭5");
+    }
+
+    #[test]
+    fn test_format_no_chord() {
+        let nc = ChordSymbolEvent::new(Tick::new(0), None, ChordKind::None);
+        assert_eq!(nc.format().to_string(), "N.C.");
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Which side of the staff a direction is printed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Placement {
+    #[default]
+    Above,
+    Below,
+}
+
+/// Typographic role of a text direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TextDirectionStyle {
+    /// Tempo indication without a metronome mark ("Allegro", "rit.") — bold
+    Tempo,
+    /// Expression or technique text ("dolce", "espress.", "pizz.") — italic
+    #[default]
+    Expression,
+}
+
+/// Free text direction (`<words>`) anchored to a staff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextDirection {
+    pub tick: u32,
+    pub text: String,
+    #[serde(default)]
+    pub placement: Placement,
+    #[serde(default)]
+    pub style: TextDirectionStyle,
+}
+
+impl TextDirection {
+    pub fn new(tick: u32, text: impl Into<String>) -> Self {
+        Self {
+            tick,
+            text: text.into(),
+            placement: Placement::Above,
+            style: TextDirectionStyle::Expression,
+        }
+    }
+}
+
+/// Frame drawn around a rehearsal mark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Enclosure {
+    #[default]
+    Rectangle,
+    Circle,
+    None,
+}
+
+/// Rehearsal mark ("A", "B", "12"), printed boxed above the system
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RehearsalMark {
+    pub tick: u32,
+    pub label: String,
+    #[serde(default)]
+    pub enclosure: Enclosure,
+}
+
+impl RehearsalMark {
+    pub fn new(tick: u32, label: impl Into<String>) -> Self {
+        Self {
+            tick,
+            label: label.into(),
+            enclosure: Enclosure::Rectangle,
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::domain::{events::note::Note, ids::NoteId};
+use serde::{Deserialize, Serialize};
+
+/// Grace note style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GraceKind {
+    /// Short "crushed" note, usually drawn with a slash through the stem
+    Acciaccatura,
+    /// Leaning note that takes a real share of the principal note's value
+    Appoggiatura,
+}
+
+/// Grace note details from MusicXML `<grace>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraceAnnotation {
+    pub kind: GraceKind,
+    /// Draw a slash through the stem (`slash="yes"`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub slash: bool,
+    /// Percentage of the previous note's duration taken by the grace notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steal_time_previous: Option<u8>,
+    /// Percentage of the principal (following) note's duration taken by the grace notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steal_time_following: Option<u8>,
+    /// Written note type (e.g. "eighth", "16th"); grace notes have no `<duration>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note_type: Option<String>,
+}
+
+/// Default sounding length of one acciaccatura (a 32nd note at 960 PPQ)
+const ACCIACCATURA_TICKS: u32 = 120;
+
+impl GraceAnnotation {
+    /// Grace annotation from the `<grace>` slash flag: slashed grace notes are
+    /// acciaccaturas, unslashed ones appoggiaturas.
+    pub fn new(slash: bool) -> Self {
+        Self {
+            kind: if slash {
+                GraceKind::Acciaccatura
+            } else {
+                GraceKind::Appoggiatura
+            },
+            slash,
+            steal_time_previous: None,
+            steal_time_following: None,
+            note_type: None,
+        }
+    }
+
+    /// Notated value in ticks (960 PPQ), defaulting to an eighth note
+    pub fn written_ticks(&self) -> u32 {
+        match self.note_type.as_deref() {
+            Some("whole") => 3840,
+            Some("half") => 1920,
+            Some("quarter") => 960,
+            Some("16th") => 240,
+            Some("32nd") => 120,
+            Some("64th") => 60,
+            _ => 480,
+        }
+    }
+}
+
+/// Sounding onset and duration of a note, as resolved for playback
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteTiming {
+    pub note_id: NoteId,
+    pub start_tick: u32,
+    pub duration_ticks: u32,
+}
+
+/// Resolve the real onset and duration of every grace note in a voice.
+///
+/// Grace notes are stored at a visual-only position (a few ticks each from
+/// the principal note's tick). Each run of grace notes takes its time from
+/// the previous note (`steal-time-previous`), from the principal note
+/// (`steal-time-following`), or by default from the principal note: a short
+/// fixed value per acciaccatura, or the written value for appoggiaturas,
+/// capped at half of the principal. The result also contains the adjusted
+/// timing of every regular note that loses time to a grace run.
+pub fn resolve_grace_timings(notes: &[Note]) -> Vec<NoteTiming> {
+    let mut graces: Vec<&Note> = notes.iter().filter(|n| n.is_grace).collect();
+    if graces.is_empty() {
+        return Vec::new();
+    }
+    graces.sort_by_key(|n| n.start_tick);
+    let mut regular: Vec<&Note> = notes.iter().filter(|n| !n.is_grace).collect();
+    regular.sort_by_key(|n| n.start_tick);
+
+    // Runs of consecutive grace notes (grace chords share an onset)
+    let mut runs: Vec<Vec<&Note>> = Vec::new();
+    for grace in graces {
+        match runs.last_mut() {
+            Some(run)
+                if run.last().is_some_and(|prev| {
+                    grace.start_tick == prev.start_tick || grace.start_tick == prev.end_tick()
+                }) =>
+            {
+                run.push(grace)
+            }
+            _ => runs.push(vec![grace]),
+        }
+    }
+
+    let mut timings = Vec::new();
+    for run in runs {
+        let anchor = run[0].start_tick.value();
+        let principal = regular.iter().find(|n| n.start_tick.value() >= anchor);
+        let previous = regular.iter().rev().find(|n| n.start_tick.value() < anchor);
+        let annotation = run.iter().find_map(|n| n.grace.as_ref());
+        let steal_previous = annotation.and_then(|a| a.steal_time_previous);
+        let steal_following = annotation.and_then(|a| a.steal_time_following);
+
+        let mut onsets: Vec<u32> = run.iter().map(|n| n.start_tick.value()).collect();
+        onsets.dedup();
+        let steps = onsets.len() as u32;
+
+        let (run_start, total, shortened) = match (steal_previous, previous, principal) {
+            (Some(percent), Some(prev), _) => {
+                let total = prev.duration_ticks * u32::from(percent.min(100)) / 100;
+                (anchor.saturating_sub(total), total, Some((*prev, false)))
+            }
+            (_, _, Some(principal)) if principal.start_tick.value() == anchor => {
+                let total = match steal_following {
+                    Some(percent) => principal.duration_ticks * u32::from(percent.min(100)) / 100,
+                    None => default_run_ticks(&run, steps).min(principal.duration_ticks / 2),
+                };
+                (anchor, total, Some((*principal, true)))
+            }
+            // No principal to steal from: sound the written values before the anchor
+            _ => {
+                let total = default_run_ticks(&run, steps);
+                (anchor.saturating_sub(total), total, None)
+            }
+        };
+        if total < steps {
+            continue;
+        }
+
+        let step = total / steps;
+        for grace in &run {
+            let k = onsets
+                .iter()
+                .position(|&t| t == grace.start_tick.value())
+                .unwrap_or(0) as u32;
+            let duration = if k + 1 == steps {
+                total - step * k
+            } else {
+                step
+            };
+            timings.push(NoteTiming {
+                note_id: grace.id,
+                start_tick: run_start + step * k,
+                duration_ticks: duration,
+            });
+        }
+
+        // The note the time was taken from starts later or ends earlier
+        if let Some((note, delayed)) = shortened
+            && total < note.duration_ticks
+        {
+            timings.push(NoteTiming {
+                note_id: note.id,
+                start_tick: if delayed {
+                    note.start_tick.value() + total
+                } else {
+                    note.start_tick.value()
+                },
+                duration_ticks: note.duration_ticks - total,
+            });
+        }
+    }
+
+    timings
+}
+
+/// Default length of a grace run when no steal-time is given
+fn default_run_ticks(run: &[&Note], steps: u32) -> u32 {
+    let kind = run
+        .iter()
+        .find_map(|n| n.grace.as_ref())
+        .map_or(GraceKind::Acciaccatura, |a| a.kind);
+    match kind {
+        GraceKind::Acciaccatura => steps * ACCIACCATURA_TICKS,
+        GraceKind::Appoggiatura => {
+            let mut onsets: Vec<(u32, u32)> = run
+                .iter()
+                .map(|n| {
+                    let written = n.grace.as_ref().map_or(480, GraceAnnotation::written_ticks);
+                    (n.start_tick.value(), written)
+                })
+                .collect();
+            onsets.dedup_by_key(|(tick, _)| *tick);
+            onsets.iter().map(|(_, written)| written).sum()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Pitch, Tick};
+
+    fn note(tick: u32, duration: u32) -> Note {
+        Note::new(Tick::new(tick), duration, Pitch::new(60).unwrap()).unwrap()
+    }
+
+    fn grace(tick: u32, annotation: GraceAnnotation) -> Note {
+        note(tick, 60).with_grace(annotation)
+    }
+
+    #[test]
+    fn test_acciaccatura_steals_from_principal_by_default() {
+        let notes = vec![
+            note(0, 960),
+            grace(960, GraceAnnotation::new(true)),
+            note(960, 960),
+        ];
+        let timings = resolve_grace_timings(&notes);
+
+        assert_eq!(timings.len(), 2);
+        assert_eq!(
+            (timings[0].start_tick, timings[0].duration_ticks),
+            (960, 120)
+        );
+        assert_eq!(timings[1].note_id, notes[2].id);
+        assert_eq!(
+            (timings[1].start_tick, timings[1].duration_ticks),
+            (1080, 840)
+        );
+    }
+
+    #[test]
+    fn test_steal_time_previous_sounds_before_the_beat() {
+        let mut annotation = GraceAnnotation::new(false);
+        annotation.steal_time_previous = Some(25);
+        let notes = vec![
+            note(0, 960),
+            grace(960, annotation.clone()),
+            grace(1020, annotation),
+            note(960, 960),
+        ];
+        let timings = resolve_grace_timings(&notes);
+
+        // 25% of the previous quarter, split over two grace notes
+        assert_eq!(
+            (timings[0].start_tick, timings[0].duration_ticks),
+            (720, 120)
+        );
+        assert_eq!(
+            (timings[1].start_tick, timings[1].duration_ticks),
+            (840, 120)
+        );
+        assert_eq!(timings[2].note_id, notes[0].id);
+        assert_eq!(timings[2].duration_ticks, 720);
+    }
+
+    #[test]
+    fn test_appoggiatura_takes_written_value_capped_at_half() {
+        let mut annotation = GraceAnnotation::new(false);
+        annotation.note_type = Some("quarter".to_string());
+        let notes = vec![grace(0, annotation), note(0, 960)];
+        let timings = resolve_grace_timings(&notes);
+
+        assert_eq!((timings[0].start_tick, timings[0].duration_ticks), (0, 480));
+        assert_eq!(
+            (timings[1].start_tick, timings[1].duration_ticks),
+            (480, 480)
+        );
+    }
+}
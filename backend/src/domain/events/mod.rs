@@ -1,7 +1,12 @@
+pub mod chord_symbol;
 pub mod clef;
+pub mod direction;
 pub mod global;
+pub mod grace;
 pub mod key_signature;
 pub mod note;
+pub mod ornament;
+pub mod pedal;
 pub mod rest;
 pub mod staff;
 pub mod tempo;
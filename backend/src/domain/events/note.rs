@@ -1,4 +1,6 @@
 use crate::domain::{
+    events::grace::GraceAnnotation,
+    events::ornament::{ArpeggioStyle, Tremolo},
     ids::NoteId,
     value_objects::{NoteSpelling, Pitch, Tick},
 };
@@ -40,6 +42,15 @@ pub struct FingeringAnnotation {
     pub above: bool,
 }
 
+/// Tablature position from MusicXML `<technical><string>` / `<fret>` elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TabPosition {
+    /// String number, 1 = highest-pitched string
+    pub string: u8,
+    /// Fret number, 0 = open string
+    pub fret: u8,
+}
+
 /// Note represents a musical note with timing and pitch
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Note {
@@ -84,6 +95,29 @@ pub struct Note {
     /// Fingering annotations from MusicXML `<technical><fingering>` elements
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fingering: Vec<FingeringAnnotation>,
+    /// Cross-staff note: 0-based index of the instrument staff this note is
+    /// drawn on when it differs from the staff of its voice (e.g. a treble
+    /// voice dipping into the bass staff). `None` = drawn on its own staff.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cross_staff: Option<u8>,
+    /// Grace note type, slash and steal-time (present only on grace notes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grace: Option<GraceAnnotation>,
+    /// Arpeggio sign on this chord note (`<arpeggiate>` / `<non-arpeggiate>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arpeggio: Option<ArpeggioStyle>,
+    /// If a glissando or slide starts on this note, the ID of the note where it ends.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gliss_next: Option<NoteId>,
+    /// Glissando drawn as a wavy line (straight line when false)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub gliss_wavy: bool,
+    /// Single- or two-note tremolo from `<ornaments><tremolo>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tremolo: Option<Tremolo>,
+    /// String and fret on a tablature staff. `None` = chosen at layout time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab: Option<TabPosition>,
 }
 
 fn is_zero_u8(v: &u8) -> bool {
@@ -113,6 +147,13 @@ impl Note {
             has_explicit_accidental: false,
             stem_down: None,
             fingering: Vec::new(),
+            cross_staff: None,
+            grace: None,
+            arpeggio: None,
+            gliss_next: None,
+            gliss_wavy: false,
+            tremolo: None,
+            tab: None,
         })
     }
 
@@ -170,6 +211,37 @@ impl Note {
         self
     }
 
+    /// Draw this note on another staff of the same instrument (builder pattern)
+    pub fn with_cross_staff(mut self, staff_index: u8) -> Self {
+        self.cross_staff = Some(staff_index);
+        self
+    }
+
+    /// Mark this note as a grace note with the given details (builder pattern)
+    pub fn with_grace(mut self, grace: GraceAnnotation) -> Self {
+        self.is_grace = true;
+        self.grace = Some(grace);
+        self
+    }
+
+    /// Set the arpeggio sign (builder pattern)
+    pub fn with_arpeggio(mut self, style: ArpeggioStyle) -> Self {
+        self.arpeggio = Some(style);
+        self
+    }
+
+    /// Set the tremolo marking (builder pattern)
+    pub fn with_tremolo(mut self, tremolo: Tremolo) -> Self {
+        self.tremolo = Some(tremolo);
+        self
+    }
+
+    /// Set the tablature string and fret (builder pattern)
+    pub fn with_tab(mut self, tab: TabPosition) -> Self {
+        self.tab = Some(tab);
+        self
+    }
+
     pub fn end_tick(&self) -> Tick {
         self.start_tick.add(self.duration_ticks)
     }
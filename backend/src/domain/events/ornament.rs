@@ -0,0 +1,263 @@
+use crate::domain::{
+    events::note::Note,
+    ids::NoteId,
+    value_objects::{Pitch, Tick},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Arpeggio sign on a chord (`<arpeggiate>` / `<non-arpeggiate>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ArpeggioStyle {
+    /// Wavy line, rolled from the bottom up
+    Wavy,
+    /// Wavy line with an upward arrow
+    Up,
+    /// Wavy line with a downward arrow, rolled from the top down
+    Down,
+    /// Square bracket: the chord must NOT be rolled
+    NonArpeggio,
+}
+
+/// Tremolo role of a note (`<ornaments><tremolo type="…">`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TremoloKind {
+    /// Repeated single note (strokes on the stem)
+    Single,
+    /// First note of a two-note (fingered) tremolo
+    Start,
+    /// Second note of a two-note tremolo
+    Stop,
+    /// Unmeasured (as fast as possible) single-note tremolo
+    Unmeasured,
+}
+
+/// Tremolo marking on a note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tremolo {
+    pub kind: TremoloKind,
+    /// Number of strokes (1 = eighths, 2 = sixteenths, 3 = thirty-seconds, …)
+    pub strokes: u8,
+}
+
+impl Tremolo {
+    /// Length of each repeated note in ticks (960 PPQ)
+    pub fn step_ticks(&self) -> u32 {
+        match self.kind {
+            TremoloKind::Unmeasured => 60,
+            _ => (960u32 >> self.strokes.clamp(1, 5)).max(30),
+        }
+    }
+}
+
+/// A note as it sounds in playback, after ornament expansion
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayedNote {
+    /// The written note this attack comes from
+    pub note_id: NoteId,
+    pub pitch: Pitch,
+    pub start_tick: u32,
+    pub duration_ticks: u32,
+}
+
+/// Delay between successive notes of a rolled chord, in ticks
+const ARPEGGIO_STEP_TICKS: u32 = 60;
+
+/// Expand tremolos and arpeggios of a voice into the notes actually played.
+///
+/// - Single-note tremolos repeat the note at the stroke value for its whole duration.
+/// - Two-note tremolos alternate the start and stop chords over their combined span.
+/// - Arpeggiated chords are rolled (bottom-up, or top-down for `Down`); every
+///   note still ends with the chord. `NonArpeggio` chords are played together.
+///
+/// All other notes are passed through unchanged. The result is sorted by start tick.
+pub fn expand_for_playback(notes: &[Note]) -> Vec<PlayedNote> {
+    let mut chords: BTreeMap<Tick, Vec<&Note>> = BTreeMap::new();
+    for note in notes {
+        chords.entry(note.start_tick).or_default().push(note);
+    }
+    let chords: Vec<Vec<&Note>> = chords.into_values().collect();
+
+    let mut played = Vec::new();
+    let mut consumed = vec![false; chords.len()];
+    for (index, chord) in chords.iter().enumerate() {
+        if consumed[index] {
+            continue;
+        }
+        let tremolo = chord.iter().find_map(|n| n.tremolo);
+        match tremolo {
+            Some(t) if t.kind == TremoloKind::Start => {
+                let partner = chords.iter().enumerate().skip(index + 1).find(|(_, c)| {
+                    c.iter()
+                        .any(|n| n.tremolo.is_some_and(|t| t.kind == TremoloKind::Stop))
+                });
+                match partner {
+                    Some((partner_index, partner)) => {
+                        consumed[partner_index] = true;
+                        played.extend(alternate(chord, partner, t.step_ticks()));
+                    }
+                    None => played.extend(chord.iter().map(|n| as_played(n))),
+                }
+            }
+            Some(t) if t.kind != TremoloKind::Stop => {
+                for note in chord {
+                    played.extend(repeat(note, t.step_ticks()));
+                }
+            }
+            _ => played.extend(roll(chord)),
+        }
+    }
+
+    played.sort_by_key(|p| (p.start_tick, p.pitch.value()));
+    played
+}
+
+fn as_played(note: &Note) -> PlayedNote {
+    PlayedNote {
+        note_id: note.id,
+        pitch: note.pitch,
+        start_tick: note.start_tick.value(),
+        duration_ticks: note.duration_ticks,
+    }
+}
+
+/// Repeat `note` at `step` ticks over its duration; the last repeat takes the remainder
+fn repeat(note: &Note, step: u32) -> Vec<PlayedNote> {
+    let start = note.start_tick.value();
+    let end = start + note.duration_ticks;
+    let mut played = Vec::new();
+    let mut tick = start;
+    while tick < end {
+        let duration = if end - tick < 2 * step {
+            end - tick
+        } else {
+            step
+        };
+        played.push(PlayedNote {
+            start_tick: tick,
+            duration_ticks: duration,
+            ..as_played(note)
+        });
+        tick += duration;
+    }
+    played
+}
+
+/// Alternate two chords at `step` ticks from the first chord's start to the
+/// second chord's end
+fn alternate(first: &[&Note], second: &[&Note], step: u32) -> Vec<PlayedNote> {
+    let start = first[0].start_tick.value();
+    let end = second
+        .iter()
+        .map(|n| n.end_tick().value())
+        .max()
+        .unwrap_or(start);
+    let mut played = Vec::new();
+    let mut tick = start;
+    let mut use_first = true;
+    while tick < end {
+        let duration = if end - tick < 2 * step {
+            end - tick
+        } else {
+            step
+        };
+        let chord = if use_first { first } else { second };
+        played.extend(chord.iter().map(|n| PlayedNote {
+            start_tick: tick,
+            duration_ticks: duration,
+            ..as_played(n)
+        }));
+        tick += duration;
+        use_first = !use_first;
+    }
+    played
+}
+
+/// Roll an arpeggiated chord; other chords are returned unchanged
+fn roll(chord: &[&Note]) -> Vec<PlayedNote> {
+    let style = chord.iter().find_map(|n| n.arpeggio);
+    let rolled = matches!(
+        style,
+        Some(ArpeggioStyle::Wavy | ArpeggioStyle::Up | ArpeggioStyle::Down)
+    );
+    if !rolled || chord.len() < 2 {
+        return chord.iter().map(|n| as_played(n)).collect();
+    }
+
+    let mut ordered: Vec<&Note> = chord.to_vec();
+    ordered.sort_by_key(|n| n.pitch.value());
+    if style == Some(ArpeggioStyle::Down) {
+        ordered.reverse();
+    }
+    // Keep the roll within the first half of the shortest note
+    let shortest = ordered.iter().map(|n| n.duration_ticks).min().unwrap_or(0);
+    let step = ARPEGGIO_STEP_TICKS.min(shortest / 2 / (ordered.len() as u32 - 1));
+
+    ordered
+        .iter()
+        .enumerate()
+        .map(|(k, note)| {
+            let delay = step * k as u32;
+            PlayedNote {
+                start_tick: note.start_tick.value() + delay,
+                duration_ticks: note.duration_ticks - delay,
+                ..as_played(note)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(tick: u32, duration: u32, pitch: u8) -> Note {
+        Note::new(Tick::new(tick), duration, Pitch::new(pitch).unwrap()).unwrap()
+    }
+
+    fn with_tremolo(note: Note, kind: TremoloKind, strokes: u8) -> Note {
+        note.with_tremolo(Tremolo { kind, strokes })
+    }
+
+    #[test]
+    fn test_single_tremolo_repeats_note() {
+        let notes = vec![with_tremolo(note(0, 960, 60), TremoloKind::Single, 2)];
+        let played = expand_for_playback(&notes);
+
+        let ticks: Vec<(u32, u32)> = played
+            .iter()
+            .map(|p| (p.start_tick, p.duration_ticks))
+            .collect();
+        assert_eq!(ticks, vec![(0, 240), (240, 240), (480, 240), (720, 240)]);
+        assert!(played.iter().all(|p| p.note_id == notes[0].id));
+    }
+
+    #[test]
+    fn test_two_note_tremolo_alternates() {
+        let notes = vec![
+            with_tremolo(note(0, 960, 60), TremoloKind::Start, 1),
+            with_tremolo(note(960, 960, 67), TremoloKind::Stop, 1),
+        ];
+        let played = expand_for_playback(&notes);
+
+        let pitches: Vec<u8> = played.iter().map(|p| p.pitch.value()).collect();
+        assert_eq!(pitches, vec![60, 67, 60, 67]);
+        assert_eq!(played[3].start_tick + played[3].duration_ticks, 1920);
+    }
+
+    #[test]
+    fn test_arpeggio_rolls_chord_and_non_arpeggio_does_not() {
+        let mut chord = vec![note(0, 960, 64), note(0, 960, 60), note(0, 960, 67)];
+        chord[0].arpeggio = Some(ArpeggioStyle::Down);
+        let played = expand_for_playback(&chord);
+        let rolled: Vec<(u8, u32, u32)> = played
+            .iter()
+            .map(|p| (p.pitch.value(), p.start_tick, p.duration_ticks))
+            .collect();
+        assert_eq!(rolled, vec![(67, 0, 960), (64, 60, 900), (60, 120, 840)]);
+
+        chord[0].arpeggio = Some(ArpeggioStyle::NonArpeggio);
+        let played = expand_for_playback(&chord);
+        assert!(played.iter().all(|p| p.start_tick == 0));
+    }
+}
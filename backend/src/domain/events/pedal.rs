@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// Piano pedal type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PedalKind {
+    /// Damper (right) pedal — "Ped." … "*"
+    Sustain,
+    /// Middle pedal — "Sost." … "*"
+    Sostenuto,
+    /// Soft (left) pedal — "una corda" … "tre corde"
+    UnaCorda,
+}
+
+/// A region during which a pedal is held down, anchored to a staff.
+///
+/// `change_ticks` are pedal changes inside the region: the pedal is released
+/// and immediately pressed again (drawn as a notch in bracket-line style).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PedalRegion {
+    pub kind: PedalKind,
+    /// Start tick (inclusive) — pedal pressed
+    pub start_tick: u32,
+    /// End tick (exclusive) — pedal released
+    pub end_tick: u32,
+    /// Ticks where the pedal is lifted and re-pressed, sorted ascending
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub change_ticks: Vec<u32>,
+    /// Draw as a bracket line (`line="yes"`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub line: bool,
+    /// Draw "Ped." / "*" signs (`sign="yes"`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sign: bool,
+}
+
+impl PedalRegion {
+    pub fn new(kind: PedalKind, start_tick: u32, end_tick: u32) -> Self {
+        Self {
+            kind,
+            start_tick,
+            end_tick,
+            change_ticks: Vec::new(),
+            line: false,
+            sign: true,
+        }
+    }
+
+    /// Contiguous spans during which the pedal is physically down,
+    /// i.e. the region split at each change tick. Used by playback.
+    pub fn press_spans(&self) -> Vec<(u32, u32)> {
+        let mut spans = Vec::with_capacity(self.change_ticks.len() + 1);
+        let mut start = self.start_tick;
+        for &change in &self.change_ticks {
+            if change > start && change < self.end_tick {
+                spans.push((start, change));
+                start = change;
+            }
+        }
+        spans.push((start, self.end_tick));
+        spans
+    }
+
+    /// Whether the pedal is down at `tick`
+    pub fn contains(&self, tick: u32) -> bool {
+        tick >= self.start_tick && tick < self.end_tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_press_spans_split_at_changes() {
+        let mut region = PedalRegion::new(PedalKind::Sustain, 0, 3840);
+        region.change_ticks = vec![960, 1920];
+        assert_eq!(
+            region.press_spans(),
+            vec![(0, 960), (960, 1920), (1920, 3840)]
+        );
+    }
+
+    #[test]
+    fn test_press_spans_ignore_out_of_range_changes() {
+        let mut region = PedalRegion::new(PedalKind::Sostenuto, 960, 1920);
+        region.change_ticks = vec![960, 4000];
+        assert_eq!(region.press_spans(), vec![(960, 1920)]);
+        assert!(region.contains(960));
+        assert!(!region.contains(1920));
+    }
+}
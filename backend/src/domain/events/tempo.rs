@@ -1,15 +1,129 @@
 use crate::domain::value_objects::{BPM, Tick};
 use serde::{Deserialize, Serialize};
 
+/// Ticks per quarter note, the default tempo beat unit
+const QUARTER_TICKS: u32 = 960;
+
+fn quarter_ticks() -> u32 {
+    QUARTER_TICKS
+}
+
+fn is_quarter(beat_ticks: &u32) -> bool {
+    *beat_ticks == QUARTER_TICKS
+}
+
 /// Tempo event defines BPM at a specific tick
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TempoEvent {
     pub tick: Tick,
     pub bpm: BPM,
+    /// Exact tempo in thousandths of a beat per minute (e.g. 92_500 = 92.5).
+    /// `bpm` holds the same tempo rounded and clamped to 20–400.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bpm_milli: Option<u32>,
+    /// Beat unit the tempo counts, in ticks (960 = quarter, 1440 = dotted quarter)
+    #[serde(default = "quarter_ticks", skip_serializing_if = "is_quarter")]
+    pub beat_ticks: u32,
+    /// Gradual tempo change (accelerando/ritardando) starting at this event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ramp: Option<TempoRamp>,
+    /// Printed tempo marking; unmarked events only affect playback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mark: Option<TempoMark>,
+}
+
+/// How a tempo event is printed: tempo text ("Allegro") and/or a metronome
+/// mark ("♩ = 120") in the event's beat unit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TempoMark {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub show_metronome: bool,
+}
+
+/// Linear tempo change from the event's tempo to `end_bpm_milli` at `end_tick`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TempoRamp {
+    pub end_tick: u32,
+    /// Tempo reached at `end_tick`, in thousandths of a beat (of the event's beat unit) per minute
+    pub end_bpm_milli: u32,
 }
 
 impl TempoEvent {
     pub fn new(tick: Tick, bpm: BPM) -> Self {
-        Self { tick, bpm }
+        Self {
+            tick,
+            bpm,
+            bpm_milli: None,
+            beat_ticks: QUARTER_TICKS,
+            ramp: None,
+            mark: None,
+        }
+    }
+
+    /// Tempo event from a fractional BPM counted in beats of `beat_ticks`
+    /// (e.g. dotted quarter = 72.5). `bpm` is rounded and clamped to its valid range.
+    /// `None` unless the tempo is finite and positive.
+    pub fn with_exact_bpm(tick: Tick, beats_per_minute: f64, beat_ticks: u32) -> Option<Self> {
+        if !beats_per_minute.is_finite() || beats_per_minute <= 0.0 {
+            return None;
+        }
+        let rounded = beats_per_minute.round().clamp(20.0, 400.0) as u16;
+        Some(Self {
+            tick,
+            bpm: BPM::new(rounded).ok()?,
+            bpm_milli: Some((beats_per_minute * 1000.0).round().max(1.0) as u32),
+            beat_ticks: beat_ticks.max(1),
+            ramp: None,
+            mark: None,
+        })
+    }
+
+    /// Mark this event to be printed with `text` and/or a metronome mark
+    pub fn with_mark(mut self, text: Option<String>, show_metronome: bool) -> Self {
+        self.mark = Some(TempoMark {
+            text,
+            show_metronome,
+        });
+        self
+    }
+
+    /// Exact tempo in beats (of `beat_ticks`) per minute
+    pub fn beats_per_minute(&self) -> f64 {
+        match self.bpm_milli {
+            Some(milli) => f64::from(milli) / 1000.0,
+            None => f64::from(self.bpm.value()),
+        }
+    }
+
+    /// Exact tempo in quarter notes per minute
+    pub fn quarter_bpm(&self) -> f64 {
+        self.beats_per_minute() * f64::from(self.beat_ticks) / f64::from(QUARTER_TICKS)
+    }
+}
+
+/// Fermata hold: the note or rest under the fermata is held longer than written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FermataHold {
+    /// Start tick of the held note or rest
+    pub tick: u32,
+    /// Written duration of the held note or rest, in ticks
+    pub duration_ticks: u32,
+    /// How much longer the hold sounds, in percent of the written duration
+    /// (100 = held for twice its value)
+    pub extra_percent: u32,
+}
+
+impl FermataHold {
+    /// Default hold: the written value is doubled
+    pub const DEFAULT_EXTRA_PERCENT: u32 = 100;
+
+    pub fn new(tick: u32, duration_ticks: u32) -> Self {
+        Self {
+            tick,
+            duration_ticks,
+            extra_percent: Self::DEFAULT_EXTRA_PERCENT,
+        }
     }
 }
@@ -1,18 +1,25 @@
 // MusicXML to Domain Converter - Feature 006-musicxml-import
 // Transforms MusicXML intermediate representation to domain entities
 
+use crate::domain::events::chord_symbol::ChordSymbolEvent;
 use crate::domain::events::clef::ClefEvent;
+use crate::domain::events::direction::{RehearsalMark, TextDirection};
 use crate::domain::events::global::GlobalStructuralEvent;
+use crate::domain::events::grace::GraceAnnotation;
 use crate::domain::events::key_signature::KeySignatureEvent;
 use crate::domain::events::note::Note;
+use crate::domain::events::pedal::{PedalKind, PedalRegion};
 use crate::domain::events::rest::RestEvent;
-use crate::domain::events::tempo::TempoEvent;
+use crate::domain::events::tempo::{FermataHold, TempoEvent, TempoRamp};
 use crate::domain::events::time_signature::TimeSignatureEvent;
-use crate::domain::instrument::Instrument;
+use crate::domain::instrument::{Instrument, MidiSettings};
+use crate::domain::instrument_catalog;
+use crate::domain::part_group::{GroupSymbol, PartGroup};
 use crate::domain::repeat::{RepeatBarline, RepeatBarlineType, VoltaBracket, VoltaEndType};
 use crate::domain::score::{OctaveShiftRegion, Score};
 use crate::domain::staff::Staff;
-use crate::domain::value_objects::{BPM, Tick};
+use crate::domain::tablature::Tablature;
+use crate::domain::value_objects::{BPM, Clef, Tick};
 use crate::domain::voice::Voice;
 
 use super::ImportContext;
@@ -22,8 +29,9 @@ use super::timing::Fraction;
 use super::types::BeamType;
 use super::types::EndingParseType;
 use super::types::{
-    MeasureData, MeasureElement, MusicXMLDocument, NoteData, OctaveShiftData, PartData, SlurInfo,
-    SlurType, TieType,
+    ClefData, GlissandoInfo, MeasureData, MeasureElement, MusicXMLDocument, NoteData,
+    OctaveShiftData, PartData, PartGroupData, SlurInfo, SlurType, TempoData, TempoWordsKind,
+    TieType,
 };
 use std::collections::{BTreeMap, HashMap};
 
@@ -261,7 +269,7 @@ impl MusicXMLConverter {
     /// # Returns
     /// Score with all instruments, staves, voices, and events
     pub fn convert(
-        doc: MusicXMLDocument,
+        mut doc: MusicXMLDocument,
         context: &mut ImportContext,
     ) -> Result<Score, ImportError> {
         // Create Score with defaults (120 BPM, 4/4 time signature)
@@ -351,6 +359,87 @@ impl MusicXMLConverter {
             })
             .unwrap_or_default();
 
+        // Tempo changes and fermatas from all parts (tempo is score-wide)
+        let (tempo_events, fermatas) = Self::collect_tempo_changes(
+            &doc.parts,
+            doc.default_tempo,
+            ticks_per_measure,
+            pickup_ticks,
+            &measure_end_ticks,
+        );
+        for event in tempo_events {
+            let existing = score
+                .global_structural_events
+                .iter_mut()
+                .find(|e| matches!(e, GlobalStructuralEvent::Tempo(te) if te.tick == event.tick));
+            match existing {
+                Some(existing) => *existing = GlobalStructuralEvent::Tempo(event),
+                None => score.add_tempo_event(event)?,
+            }
+        }
+        score.fermatas = fermatas;
+
+        // Rehearsal marks are score-wide: take them from the first part
+        if let Some(first_part) = doc.parts.first() {
+            let (_, mut rehearsal_marks) = Self::collect_direction_texts(
+                &first_part.measures,
+                ticks_per_measure,
+                pickup_ticks,
+                &measure_end_ticks,
+            );
+            rehearsal_marks.sort_by_key(|m| m.tick);
+            score.rehearsal_marks = rehearsal_marks;
+        }
+
+        // Page and system breaks are score-wide: take them from the first part
+        if let Some(first_part) = doc.parts.first() {
+            // A break before the first measure just starts the first page/system
+            let breaks = |is_break: fn(&MeasureData) -> bool| -> Vec<u32> {
+                first_part
+                    .measures
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, m)| *i > 0 && is_break(m))
+                    .map(|(i, _)| i as u32)
+                    .collect()
+            };
+            score.page_breaks = breaks(|m| m.new_page);
+            // A new page also starts a new system
+            score.system_breaks = breaks(|m| m.new_system || m.new_page);
+
+            // Printed measure numbers, kept only when they differ from the
+            // default numbering layout derives from the pickup. Measure "0"
+            // is the usual number of an unmarked pickup
+            let measure_numbers: Vec<Option<String>> = first_part
+                .measures
+                .iter()
+                .map(|m| {
+                    let numbered = !m.implicit && !m.number.is_empty() && m.number != "0";
+                    numbered.then(|| m.number.clone())
+                })
+                .collect();
+            let pickup = usize::from(pickup_ticks > 0);
+            let is_default = measure_numbers.iter().enumerate().all(|(i, number)| {
+                if i < pickup {
+                    number.is_none()
+                } else {
+                    number.as_deref() == Some((i + 1 - pickup).to_string().as_str())
+                }
+            });
+            if !is_default {
+                score.measure_numbers = measure_numbers;
+            }
+        }
+
+        // Part groups refer to parts by id; instruments follow part order
+        let part_indices: HashMap<String, usize> = doc
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.id.clone(), i))
+            .collect();
+        let part_groups = std::mem::take(&mut doc.part_groups);
+
         // Convert each part to an Instrument
         for part_data in doc.parts {
             let instrument = Self::convert_part(
@@ -363,6 +452,10 @@ impl MusicXMLConverter {
             score.add_instrument(instrument);
         }
 
+        for group in &part_groups {
+            Self::add_part_group(&mut score, group, &part_indices, context);
+        }
+
         score.repeat_barlines = repeat_barlines;
         score.volta_brackets = volta_brackets;
         score.octave_shift_regions = octave_shift_regions;
@@ -373,6 +466,41 @@ impl MusicXMLConverter {
         Ok(score)
     }
 
+    /// Adds a <part-group> to the score, spanning the instruments of its parts
+    fn add_part_group(
+        score: &mut Score,
+        group: &PartGroupData,
+        part_indices: &HashMap<String, usize>,
+        context: &mut ImportContext,
+    ) {
+        let indices: Vec<usize> = group
+            .part_ids
+            .iter()
+            .filter_map(|id| part_indices.get(id).copied())
+            .collect();
+        let (Some(&first), Some(&last)) = (indices.iter().min(), indices.iter().max()) else {
+            return;
+        };
+        let symbol = match group.symbol.as_deref() {
+            Some("bracket") => GroupSymbol::Bracket,
+            Some("brace") => GroupSymbol::Brace,
+            Some("square") => GroupSymbol::Square,
+            Some("line") => GroupSymbol::Line,
+            _ => GroupSymbol::None,
+        };
+        let mut part_group = PartGroup::new(first, last, symbol).with_barline(group.barline);
+        if let Some(name) = &group.name {
+            part_group = part_group.with_name(name.clone());
+        }
+        if let Err(e) = score.add_part_group(part_group) {
+            context.warn(
+                super::WarningSeverity::Warning,
+                super::WarningCategory::StructuralIssues,
+                format!("Skipping part group {}: {}", group.number, e),
+            );
+        }
+    }
+
     /// Detects if the first measure is a pickup/anacrusis by computing its
     /// actual tick duration and comparing to the expected ticks_per_measure.
     fn detect_pickup_ticks(parts: &[PartData], ticks_per_measure: u32) -> u32 {
@@ -416,7 +544,12 @@ impl MusicXMLConverter {
                     let _ = timing.advance_by_duration(*duration);
                     max_tick = max_tick.max(timing.current_tick);
                 }
-                MeasureElement::Attributes(_) | MeasureElement::OctaveShift(_) => {}
+                MeasureElement::Attributes(_)
+                | MeasureElement::OctaveShift(_)
+                | MeasureElement::Harmony(_)
+                | MeasureElement::Pedal(_)
+                | MeasureElement::Tempo(_)
+                | MeasureElement::DirectionText(_) => {}
             }
         }
 
@@ -478,7 +611,12 @@ impl MusicXMLConverter {
                         let _ = timing.advance_by_duration(*duration);
                         max_tick = max_tick.max(timing.current_tick);
                     }
-                    MeasureElement::Attributes(_) | MeasureElement::OctaveShift(_) => {}
+                    MeasureElement::Attributes(_)
+                    | MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
                 }
             }
 
@@ -582,6 +720,222 @@ impl MusicXMLConverter {
         result
     }
 
+    /// Collects tempo events and fermata holds from all parts.
+    ///
+    /// Metronome marks and `<sound tempo>` set a new tempo (a metronome mark
+    /// keeps its beat unit and fractional value). "rit."/"accel." words start
+    /// a linear ramp that ends at the next tempo instruction: at that tempo if
+    /// it continues the change, otherwise at the `<sound tempo>` given with
+    /// the words, or by default at 3/4 (rit.) or 4/3 (accel.) of the starting
+    /// tempo. "a tempo" restores the tempo in effect before the ramp.
+    /// Duplicate marks at one tick (e.g. repeated in every part) yield one event.
+    fn collect_tempo_changes(
+        parts: &[PartData],
+        default_tempo: f64,
+        ticks_per_measure: u32,
+        pickup_ticks: u32,
+        measure_end_ticks: &[u32],
+    ) -> (Vec<TempoEvent>, Vec<FermataHold>) {
+        let mut marks: Vec<(u32, &TempoData)> = Vec::new();
+        let mut fermatas: Vec<FermataHold> = Vec::new();
+
+        for part in parts {
+            let mut timing = TimingContext::new();
+            let mut note_start = 0u32;
+            for (i, measure) in part.measures.iter().enumerate() {
+                timing.current_tick =
+                    actual_measure_start(i, measure_end_ticks, pickup_ticks, ticks_per_measure);
+                if let Some(attrs) = &measure.attributes
+                    && let Some(divisions) = attrs.divisions
+                {
+                    timing.set_divisions(divisions);
+                }
+                let to_ticks = |duration: i32, divisions: i32| {
+                    Fraction::from_musicxml(duration, divisions)
+                        .to_ticks()
+                        .unwrap_or(0)
+                };
+
+                for element in &measure.elements {
+                    match element {
+                        MeasureElement::Note(note_data) => {
+                            if !note_data.is_chord {
+                                note_start = timing.current_tick;
+                                let _ = timing.advance_by_duration(note_data.duration);
+                            }
+                            let duration = to_ticks(note_data.duration, timing.divisions);
+                            if note_data.fermata && note_data.grace.is_none() && duration > 0 {
+                                fermatas.push(FermataHold::new(note_start, duration as u32));
+                            }
+                        }
+                        MeasureElement::Rest(rest_data) => {
+                            let start = timing.current_tick;
+                            let _ = timing.advance_by_duration(rest_data.duration);
+                            if rest_data.fermata && timing.current_tick > start {
+                                fermatas.push(FermataHold::new(start, timing.current_tick - start));
+                            }
+                        }
+                        MeasureElement::Backup(dur) => {
+                            let ticks = to_ticks(*dur, timing.divisions).max(0) as u32;
+                            timing.current_tick = timing.current_tick.saturating_sub(ticks);
+                        }
+                        MeasureElement::Forward(dur) => {
+                            let _ = timing.advance_by_duration(*dur);
+                        }
+                        MeasureElement::Attributes(attrs) => {
+                            if let Some(divisions) = attrs.divisions {
+                                timing.set_divisions(divisions);
+                            }
+                        }
+                        MeasureElement::Tempo(tempo) => {
+                            let offset = to_ticks(tempo.offset, timing.divisions);
+                            let tick = (timing.current_tick as i64 + offset as i64).max(0) as u32;
+                            marks.push((tick, tempo));
+                        }
+                        MeasureElement::OctaveShift(_)
+                        | MeasureElement::Harmony(_)
+                        | MeasureElement::Pedal(_)
+                        | MeasureElement::DirectionText(_) => {}
+                    }
+                }
+            }
+        }
+        marks.sort_by_key(|(tick, _)| *tick);
+        fermatas.sort_by_key(|f| f.tick);
+        fermatas.dedup_by(|later, earlier| {
+            later.tick == earlier.tick && {
+                earlier.duration_ticks = earlier.duration_ticks.max(later.duration_ticks);
+                true
+            }
+        });
+
+        // (beats per minute, beat unit in ticks)
+        let quarter = |bpm: f64, beat_ticks: u32| bpm * f64::from(beat_ticks) / 960.0;
+        // NaN, infinite and non-positive tempos are ignored
+        let playable = |bpm: &f64| bpm.is_finite() && *bpm > 0.0;
+        let initial = (
+            Some(default_tempo)
+                .filter(playable)
+                .unwrap_or(120.0)
+                .max(1.0),
+            960u32,
+        );
+        let mut current = initial;
+        let mut base = initial;
+        let mut events: Vec<TempoEvent> = Vec::new();
+        // (event index, slowing down, tempo given with the words)
+        type OpenRamp = (usize, bool, Option<(f64, u32)>);
+        let mut pending: Option<OpenRamp> = None;
+
+        // Close the open ramp at `tick`; returns the tempo it reaches
+        let close_ramp = |events: &mut Vec<TempoEvent>,
+                          pending: OpenRamp,
+                          tick: u32,
+                          arriving: Option<(f64, u32)>| {
+            let (index, slower, given) = pending;
+            let event = &mut events[index];
+            let start = event.quarter_bpm();
+            let continues = |q: f64| if slower { q < start } else { q > start };
+            let target = given
+                .map(|(bpm, beat)| quarter(bpm, beat))
+                .or(arriving
+                    .map(|(bpm, beat)| quarter(bpm, beat))
+                    .filter(|&q| continues(q)))
+                .unwrap_or(if slower {
+                    start * 0.75
+                } else {
+                    start * 4.0 / 3.0
+                });
+            if tick > event.tick.value() {
+                event.ramp = Some(TempoRamp {
+                    end_tick: tick,
+                    end_bpm_milli: (target * 960.0 / f64::from(event.beat_ticks) * 1000.0).round()
+                        as u32,
+                });
+            }
+            (target, 960u32)
+        };
+
+        for (tick, mark) in marks {
+            let explicit = mark
+                .metronome
+                .as_ref()
+                .map(|(unit, dots, per_minute)| {
+                    (
+                        *per_minute,
+                        ElementMapper::map_beat_unit(unit, *dots).unwrap_or(960),
+                    )
+                })
+                .or(mark.sound_tempo.map(|bpm| (bpm, 960)))
+                .filter(|(bpm, _)| playable(bpm));
+            let words = mark
+                .words
+                .as_deref()
+                .and_then(ElementMapper::map_tempo_words);
+
+            match words {
+                Some(TempoWordsKind::Ritardando | TempoWordsKind::Accelerando) => {
+                    if let Some(open) = pending.take() {
+                        current = close_ramp(&mut events, open, tick, None);
+                    }
+                    let Some(event) =
+                        TempoEvent::with_exact_bpm(Tick::new(tick), current.0, current.1)
+                    else {
+                        continue;
+                    };
+                    events.push(event);
+                    let slower = words == Some(TempoWordsKind::Ritardando);
+                    pending = Some((events.len() - 1, slower, explicit));
+                }
+                _ if explicit.is_some() || words == Some(TempoWordsKind::ATempo) => {
+                    let tempo = explicit.unwrap_or(base);
+                    if let Some(open) = pending.take() {
+                        close_ramp(&mut events, open, tick, Some(tempo));
+                    }
+                    let Some(mut event) =
+                        TempoEvent::with_exact_bpm(Tick::new(tick), tempo.0, tempo.1)
+                    else {
+                        continue;
+                    };
+                    // Words without a metronome mark are imported as a text direction
+                    if mark.metronome.is_some() {
+                        let text = mark.words.clone().filter(|w| !w.is_empty());
+                        event = event.with_mark(text, true);
+                    }
+                    events.push(event);
+                    current = tempo;
+                    base = tempo;
+                }
+                _ => {}
+            }
+        }
+        if let Some(open) = pending.take() {
+            let measure_count = parts.first().map_or(0, |p| p.measures.len());
+            let end = actual_measure_start(
+                measure_count,
+                measure_end_ticks,
+                pickup_ticks,
+                ticks_per_measure,
+            );
+            close_ramp(&mut events, open, end, None);
+        }
+
+        // One event per tick: the last instruction wins, keeping any printed mark
+        let mut deduped: Vec<TempoEvent> = Vec::new();
+        for mut event in events {
+            match deduped.last_mut() {
+                Some(last) if last.tick == event.tick => {
+                    if event.mark.is_none() {
+                        event.mark = last.mark.take();
+                    }
+                    *last = event;
+                }
+                _ => deduped.push(event),
+            }
+        }
+        (deduped, fermatas)
+    }
+
     /// Collects octave-shift regions (8va/8vb/15ma brackets) from measure data.
     fn collect_octave_shift_regions(
         measures: &[MeasureData],
@@ -654,6 +1008,10 @@ impl MusicXMLConverter {
                             open.insert(staff_index, (timing.current_tick, os.clone()));
                         }
                     }
+                    MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
                 }
             }
         }
@@ -661,6 +1019,304 @@ impl MusicXMLConverter {
         result
     }
 
+    /// Collects chord symbols (`<harmony>`) from a part's measures.
+    ///
+    /// Returns `(staff_index, event)` pairs; the staff index is 0-based.
+    fn collect_chord_symbols(
+        measures: &[MeasureData],
+        ticks_per_measure: u32,
+        pickup_ticks: u32,
+        measure_end_ticks: &[u32],
+    ) -> Vec<(usize, ChordSymbolEvent)> {
+        let mut result = Vec::new();
+        let mut timing = TimingContext::new();
+
+        for (i, measure) in measures.iter().enumerate() {
+            let measure_start =
+                actual_measure_start(i, measure_end_ticks, pickup_ticks, ticks_per_measure);
+            timing.current_tick = measure_start;
+
+            if let Some(attrs) = &measure.attributes
+                && let Some(divisions) = attrs.divisions
+            {
+                timing.set_divisions(divisions);
+            }
+
+            for element in &measure.elements {
+                match element {
+                    MeasureElement::Note(note_data) => {
+                        if !note_data.is_chord {
+                            let _ = timing.advance_by_duration(note_data.duration);
+                        }
+                    }
+                    MeasureElement::Rest(rest_data) => {
+                        let _ = timing.advance_by_duration(rest_data.duration);
+                    }
+                    MeasureElement::Backup(dur) => {
+                        if let Ok(ticks) = Fraction::from_musicxml(*dur, timing.divisions)
+                            .to_ticks()
+                            .map(|t| t as u32)
+                        {
+                            timing.current_tick = timing.current_tick.saturating_sub(ticks);
+                        }
+                    }
+                    MeasureElement::Forward(dur) => {
+                        let _ = timing.advance_by_duration(*dur);
+                    }
+                    MeasureElement::Attributes(attrs) => {
+                        if let Some(divisions) = attrs.divisions {
+                            timing.set_divisions(divisions);
+                        }
+                    }
+                    MeasureElement::OctaveShift(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
+                    MeasureElement::Harmony(harmony) => {
+                        let offset_ticks =
+                            Fraction::from_musicxml(harmony.offset, timing.divisions)
+                                .to_ticks()
+                                .unwrap_or(0);
+                        let tick = (timing.current_tick as i64 + offset_ticks as i64).max(0) as u32;
+                        let staff_index = harmony.staff.saturating_sub(1); // 1-indexed → 0-indexed
+                        result.push((staff_index, ElementMapper::map_harmony(harmony, tick)));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Collects printed direction text (`<words>` and `<rehearsal>`) from a
+    /// part's measures.
+    ///
+    /// Returns `(staff_index, direction)` pairs for words (the staff index is
+    /// 0-based) and the part's rehearsal marks.
+    fn collect_direction_texts(
+        measures: &[MeasureData],
+        ticks_per_measure: u32,
+        pickup_ticks: u32,
+        measure_end_ticks: &[u32],
+    ) -> (Vec<(usize, TextDirection)>, Vec<RehearsalMark>) {
+        let mut texts = Vec::new();
+        let mut rehearsals = Vec::new();
+        let mut timing = TimingContext::new();
+
+        for (i, measure) in measures.iter().enumerate() {
+            timing.current_tick =
+                actual_measure_start(i, measure_end_ticks, pickup_ticks, ticks_per_measure);
+            if let Some(attrs) = &measure.attributes
+                && let Some(divisions) = attrs.divisions
+            {
+                timing.set_divisions(divisions);
+            }
+
+            for element in &measure.elements {
+                match element {
+                    MeasureElement::Note(note_data) => {
+                        if !note_data.is_chord {
+                            let _ = timing.advance_by_duration(note_data.duration);
+                        }
+                    }
+                    MeasureElement::Rest(rest_data) => {
+                        let _ = timing.advance_by_duration(rest_data.duration);
+                    }
+                    MeasureElement::Backup(dur) => {
+                        if let Ok(ticks) = Fraction::from_musicxml(*dur, timing.divisions)
+                            .to_ticks()
+                            .map(|t| t as u32)
+                        {
+                            timing.current_tick = timing.current_tick.saturating_sub(ticks);
+                        }
+                    }
+                    MeasureElement::Forward(dur) => {
+                        let _ = timing.advance_by_duration(*dur);
+                    }
+                    MeasureElement::Attributes(attrs) => {
+                        if let Some(divisions) = attrs.divisions {
+                            timing.set_divisions(divisions);
+                        }
+                    }
+                    MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_) => {}
+                    MeasureElement::DirectionText(data) => {
+                        let offset_ticks = Fraction::from_musicxml(data.offset, timing.divisions)
+                            .to_ticks()
+                            .unwrap_or(0);
+                        let tick = (timing.current_tick as i64 + offset_ticks as i64).max(0) as u32;
+                        if data.rehearsal {
+                            rehearsals.push(RehearsalMark {
+                                tick,
+                                label: data.text.clone(),
+                                enclosure: ElementMapper::map_enclosure(data.enclosure.as_deref()),
+                            });
+                        } else {
+                            texts.push((
+                                data.staff.saturating_sub(1), // 1-indexed → 0-indexed
+                                TextDirection {
+                                    tick,
+                                    text: data.text.clone(),
+                                    placement: ElementMapper::map_placement(
+                                        data.placement.as_deref(),
+                                    ),
+                                    style: ElementMapper::map_text_style(&data.text),
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        (texts, rehearsals)
+    }
+
+    /// Collects pedal regions from `<pedal>` directions (and pedal `<sound>`
+    /// attributes) in a part's measures.
+    ///
+    /// `start` opens a region, `change` records a re-pedal inside the open
+    /// region (or opens one), `stop` closes it. A region still open at the
+    /// end of the part is closed at the final barline.
+    /// Returns `(staff_index, region)` pairs; the staff index is 0-based.
+    fn collect_pedal_regions(
+        measures: &[MeasureData],
+        ticks_per_measure: u32,
+        pickup_ticks: u32,
+        measure_end_ticks: &[u32],
+    ) -> Vec<(usize, PedalRegion)> {
+        let mut result: Vec<(usize, PedalRegion)> = Vec::new();
+        let mut open: HashMap<(usize, PedalKind), PedalRegion> = HashMap::new();
+        let mut timing = TimingContext::new();
+
+        let close = |result: &mut Vec<(usize, PedalRegion)>,
+                     staff_index: usize,
+                     mut region: PedalRegion,
+                     tick: u32| {
+            region.end_tick = tick;
+            region.change_ticks.retain(|&t| t < tick);
+            if region.end_tick > region.start_tick {
+                result.push((staff_index, region));
+            }
+        };
+
+        for (i, measure) in measures.iter().enumerate() {
+            let measure_start =
+                actual_measure_start(i, measure_end_ticks, pickup_ticks, ticks_per_measure);
+            timing.current_tick = measure_start;
+
+            if let Some(attrs) = &measure.attributes
+                && let Some(divisions) = attrs.divisions
+            {
+                timing.set_divisions(divisions);
+            }
+
+            for element in &measure.elements {
+                match element {
+                    MeasureElement::Note(note_data) => {
+                        if !note_data.is_chord {
+                            let _ = timing.advance_by_duration(note_data.duration);
+                        }
+                    }
+                    MeasureElement::Rest(rest_data) => {
+                        let _ = timing.advance_by_duration(rest_data.duration);
+                    }
+                    MeasureElement::Backup(dur) => {
+                        if let Ok(ticks) = Fraction::from_musicxml(*dur, timing.divisions)
+                            .to_ticks()
+                            .map(|t| t as u32)
+                        {
+                            timing.current_tick = timing.current_tick.saturating_sub(ticks);
+                        }
+                    }
+                    MeasureElement::Forward(dur) => {
+                        let _ = timing.advance_by_duration(*dur);
+                    }
+                    MeasureElement::Attributes(attrs) => {
+                        if let Some(divisions) = attrs.divisions {
+                            timing.set_divisions(divisions);
+                        }
+                    }
+                    MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
+                    MeasureElement::Pedal(pedal) => {
+                        let Some(mut kind) = ElementMapper::map_pedal_kind(&pedal.pedal_kind)
+                        else {
+                            continue;
+                        };
+                        let offset_ticks = Fraction::from_musicxml(pedal.offset, timing.divisions)
+                            .to_ticks()
+                            .unwrap_or(0);
+                        let tick = (timing.current_tick as i64 + offset_ticks as i64).max(0) as u32;
+                        let staff_index = pedal.staff.saturating_sub(1); // 1-indexed → 0-indexed
+
+                        // A plain "stop" also releases a sostenuto pedal when no sustain is down
+                        if pedal.pedal_type == "stop"
+                            && kind == PedalKind::Sustain
+                            && !open.contains_key(&(staff_index, PedalKind::Sustain))
+                            && open.contains_key(&(staff_index, PedalKind::Sostenuto))
+                        {
+                            kind = PedalKind::Sostenuto;
+                        }
+                        let key = (staff_index, kind);
+
+                        match pedal.pedal_type.as_str() {
+                            "start" | "sostenuto" => {
+                                if let Some(region) = open.remove(&key) {
+                                    close(&mut result, staff_index, region, tick);
+                                }
+                                let mut region = PedalRegion::new(kind, tick, tick);
+                                region.line = pedal.line;
+                                region.sign = pedal.sign;
+                                open.insert(key, region);
+                            }
+                            "change" => match open.get_mut(&key) {
+                                Some(region) => {
+                                    if tick > region.start_tick {
+                                        region.change_ticks.push(tick);
+                                    }
+                                }
+                                None => {
+                                    let mut region = PedalRegion::new(kind, tick, tick);
+                                    region.line = pedal.line;
+                                    region.sign = pedal.sign;
+                                    open.insert(key, region);
+                                }
+                            },
+                            "stop" => {
+                                if let Some(region) = open.remove(&key) {
+                                    close(&mut result, staff_index, region, tick);
+                                }
+                            }
+                            // "continue", "discontinue", "resume" only affect
+                            // how a line is drawn across breaks
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        let part_end = actual_measure_start(
+            measures.len(),
+            measure_end_ticks,
+            pickup_ticks,
+            ticks_per_measure,
+        );
+        let mut remaining: Vec<_> = open.into_iter().collect();
+        remaining.sort_by_key(|((staff_index, _), region)| (*staff_index, region.start_tick));
+        for ((staff_index, _), region) in remaining {
+            let end = part_end.max(region.start_tick);
+            close(&mut result, staff_index, region, end);
+        }
+
+        result
+    }
+
     /// Converts PartData to Instrument
     fn convert_part(
         part_data: PartData,
@@ -677,6 +1333,7 @@ impl MusicXMLConverter {
 
         // Create instrument with default staff, then clear
         let mut instrument = Instrument::new(name);
+        instrument.short_name = part_data.abbreviation.clone();
         instrument.staves.clear();
 
         // Check staff count and route accordingly
@@ -704,6 +1361,46 @@ impl MusicXMLConverter {
             }
         }
 
+        Self::apply_catalog_instrument(&mut instrument, &part_data)?;
+
+        // Attach chord symbols to their staff (out-of-range staff numbers fall back to the last staff)
+        let last_staff = instrument.staves.len().saturating_sub(1);
+        for (staff_index, event) in Self::collect_chord_symbols(
+            &part_data.measures,
+            ticks_per_measure,
+            pickup_ticks,
+            measure_end_ticks,
+        ) {
+            if let Some(staff) = instrument.staves.get_mut(staff_index.min(last_staff)) {
+                staff.add_chord_symbol(event);
+            }
+        }
+
+        // Attach pedal regions the same way
+        for (staff_index, region) in Self::collect_pedal_regions(
+            &part_data.measures,
+            ticks_per_measure,
+            pickup_ticks,
+            measure_end_ticks,
+        ) {
+            if let Some(staff) = instrument.staves.get_mut(staff_index.min(last_staff)) {
+                staff.add_pedal_region(region);
+            }
+        }
+
+        // And text directions
+        let (texts, _) = Self::collect_direction_texts(
+            &part_data.measures,
+            ticks_per_measure,
+            pickup_ticks,
+            measure_end_ticks,
+        );
+        for (staff_index, direction) in texts {
+            if let Some(staff) = instrument.staves.get_mut(staff_index.min(last_staff)) {
+                staff.add_text_direction(direction);
+            }
+        }
+
         Ok(instrument)
     }
 
@@ -732,7 +1429,7 @@ impl MusicXMLConverter {
                     if let Some(clef_data) =
                         attrs.clefs.iter().find(|c| c.staff_number == staff_num)
                     {
-                        let clef = ElementMapper::map_clef(&clef_data.sign, clef_data.line)?;
+                        let clef = Self::map_staff_clef(clef_data)?;
                         let clef_event = ClefEvent::new(Tick::new(0), clef);
                         staff.add_clef_event(clef_event)?;
                     }
@@ -747,6 +1444,8 @@ impl MusicXMLConverter {
                 }
             }
 
+            Self::apply_staff_details(&mut staff, &part_data.measures, staff_num, context);
+
             // Extract key signature changes from subsequent measures
             Self::add_key_changes_from_measures(
                 &mut staff,
@@ -766,7 +1465,8 @@ impl MusicXMLConverter {
                 measure_end_ticks,
             )?;
 
-            // Convert measures to notes grouped by MusicXML voice number, filtering by staff
+            // Convert measures to notes grouped by MusicXML voice number, filtering by
+            // the voice's home staff (cross-staff notes stay with their voice)
             let (notes_by_voice, rests) =
                 Self::collect_notes_for_staff(&part_data.measures, staff_num, context)?;
 
@@ -823,7 +1523,7 @@ impl MusicXMLConverter {
             if let Some(attrs) = &first_measure.attributes {
                 // Add clef from first measure
                 if let Some(clef_data) = attrs.clefs.first() {
-                    let clef = ElementMapper::map_clef(&clef_data.sign, clef_data.line)?;
+                    let clef = Self::map_staff_clef(clef_data)?;
                     let clef_event = ClefEvent::new(Tick::new(0), clef);
                     staff.add_clef_event(clef_event)?;
                 }
@@ -837,6 +1537,8 @@ impl MusicXMLConverter {
             }
         }
 
+        Self::apply_staff_details(&mut staff, &part_data.measures, 1, context);
+
         // Extract key signature changes from subsequent measures
         Self::add_key_changes_from_measures(
             &mut staff,
@@ -890,6 +1592,146 @@ impl MusicXMLConverter {
         Ok(staff)
     }
 
+    /// Recognise the part's instrument in the catalog, from its
+    /// `<instrument-sound>`, then its instrument and part names, then its
+    /// MIDI program. Sets the instrument type and MIDI settings and gives
+    /// staves without a written clef the instrument's default clef.
+    fn apply_catalog_instrument(
+        instrument: &mut Instrument,
+        part_data: &PartData,
+    ) -> Result<(), ImportError> {
+        let sound = part_data.instrument.as_ref();
+        let entry = sound
+            .and_then(|s| s.instrument_sound.as_deref())
+            .and_then(instrument_catalog::find_by_sound)
+            .or_else(|| {
+                sound
+                    .and_then(|s| s.instrument_name.as_deref())
+                    .and_then(instrument_catalog::match_name)
+            })
+            .or_else(|| instrument_catalog::match_name(&part_data.name))
+            .or_else(|| {
+                sound.and_then(|s| s.midi_program).and_then(|program| {
+                    instrument_catalog::find_by_program(program)
+                        .first()
+                        .copied()
+                })
+            });
+
+        if let Some(entry) = entry {
+            instrument.instrument_type = entry.id.to_string();
+            for (index, staff) in instrument.staves.iter_mut().enumerate() {
+                if staff.get_clef_at(Tick::new(0)).is_none() && staff.tablature.is_none() {
+                    let clef = entry.clefs[index.min(entry.clefs.len() - 1)];
+                    staff.add_clef_event(ClefEvent::new(Tick::new(0), clef))?;
+                }
+            }
+        }
+
+        let program = sound
+            .and_then(|s| s.midi_program)
+            .or(entry.map(|e| e.midi_program));
+        instrument.midi = program.filter(|p| (1..=128).contains(p)).map(|program| {
+            let mut midi = MidiSettings::new(program);
+            if let Some(sound) = sound {
+                if let Some(channel) = sound.midi_channel.filter(|c| (1..=16).contains(c)) {
+                    midi.channel = channel;
+                }
+                if let Some(volume) = sound.volume {
+                    midi.volume = volume.round().clamp(0.0, 100.0) as u8;
+                }
+                if let Some(pan) = sound.pan {
+                    midi.pan = pan.round().clamp(-90.0, 90.0) as i16;
+                }
+            }
+            midi
+        });
+        Ok(())
+    }
+
+    /// Map a `<clef>` to the staff's clef.
+    ///
+    /// Tablature staves (`<sign>TAB</sign>`) keep a treble clef for their
+    /// pitches; the TAB sign is drawn from the staff's tuning.
+    fn map_staff_clef(clef_data: &ClefData) -> Result<Clef, ImportError> {
+        if clef_data.sign == "TAB" {
+            return Ok(Clef::Treble);
+        }
+        Ok(ElementMapper::map_clef(&clef_data.sign, clef_data.line)?)
+    }
+
+    /// Set a staff's line count and tuning from the first `<staff-details>`
+    /// given for it.
+    ///
+    /// Counts outside 1..=MAX_STAFF_LINES keep the regular five lines. A
+    /// staff with `<staff-tuning>` elements or a TAB clef becomes a
+    /// tablature staff; without tunings it gets standard bass tuning on
+    /// four lines and standard guitar tuning otherwise.
+    fn apply_staff_details(
+        staff: &mut Staff,
+        measures: &[MeasureData],
+        staff_number: usize,
+        context: &mut ImportContext,
+    ) {
+        let all_attrs = || measures.iter().filter_map(|m| m.attributes.as_ref());
+        let details = || {
+            all_attrs()
+                .flat_map(|attrs| &attrs.staff_details)
+                .filter(|details| details.staff_number == staff_number)
+        };
+        let lines = details().find_map(|details| details.lines);
+        if let Some(lines) = lines
+            && staff.set_staff_lines(lines).is_err()
+        {
+            context.warn(
+                super::WarningSeverity::Warning,
+                super::WarningCategory::StructuralIssues,
+                format!(
+                    "Unsupported staff line count {} on staff {}, using 5 lines",
+                    lines, staff_number
+                ),
+            );
+        }
+
+        let tunings = details()
+            .map(|details| &details.tunings)
+            .find(|tunings| !tunings.is_empty());
+        let tab_clef = all_attrs()
+            .flat_map(|attrs| &attrs.clefs)
+            .any(|clef| clef.staff_number == staff_number && clef.sign == "TAB");
+        let tablature = match tunings {
+            Some(tunings) => {
+                // MusicXML numbers lines from the bottom; string 1 is the top line
+                let mut tunings = tunings.clone();
+                tunings.sort_by_key(|t| std::cmp::Reverse(t.line));
+                let strings: Result<Vec<_>, _> = tunings
+                    .iter()
+                    .map(|t| ElementMapper::map_pitch(t.step, t.octave, t.alter))
+                    .collect();
+                match strings.ok().map(Tablature::new) {
+                    Some(Ok(tablature)) => Some(tablature),
+                    _ => {
+                        context.warn(
+                            super::WarningSeverity::Warning,
+                            super::WarningCategory::StructuralIssues,
+                            format!(
+                                "Unsupported staff tuning on staff {}, using standard guitar tuning",
+                                staff_number
+                            ),
+                        );
+                        Some(Tablature::standard_guitar())
+                    }
+                }
+            }
+            None if tab_clef && lines == Some(4) => Some(Tablature::standard_bass()),
+            None if tab_clef => Some(Tablature::standard_guitar()),
+            None => None,
+        };
+        if let Some(tablature) = tablature {
+            staff.set_tablature(tablature);
+        }
+    }
+
     /// Extract key signature changes from all measures after the first.
     ///
     /// Iterates measures starting from index 1 and emits a KeySignatureEvent
@@ -993,13 +1835,17 @@ impl MusicXMLConverter {
                         };
                         if let Some(cd) = clef_data {
                             let tick = measure_start + offset;
-                            let clef = ElementMapper::map_clef(&cd.sign, cd.line)?;
+                            let clef = Self::map_staff_clef(cd)?;
                             let clef_event = ClefEvent::new(Tick::new(tick), clef);
                             // Silently ignores duplicates (e.g., tick-0 clef already added from initial attributes)
                             let _ = staff.add_clef_event(clef_event);
                         }
                     }
-                    MeasureElement::OctaveShift(_) => {}
+                    MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
                 }
             }
         }
@@ -1030,6 +1876,7 @@ impl MusicXMLConverter {
         let mut notes_by_voice: HashMap<usize, Vec<Note>> = HashMap::new();
         let mut tie_info_by_voice: HashMap<usize, Vec<Option<TieType>>> = HashMap::new();
         let mut slur_info_by_voice: HashMap<usize, Vec<Vec<SlurInfo>>> = HashMap::new();
+        let mut gliss_info_by_voice: HashMap<usize, Vec<Vec<GlissandoInfo>>> = HashMap::new();
         let mut rests = Vec::new();
         let mut timing_context = TimingContext::new();
 
@@ -1057,6 +1904,10 @@ impl MusicXMLConverter {
                                     .entry(voice)
                                     .or_default()
                                     .push(note_data.slurs.clone());
+                                gliss_info_by_voice
+                                    .entry(voice)
+                                    .or_default()
+                                    .push(note_data.glissandos.clone());
                                 notes_by_voice.entry(voice).or_default().push(note);
                             }
                             Err(e) => {
@@ -1107,7 +1958,12 @@ impl MusicXMLConverter {
                         // Move timing cursor forward
                         timing_context.advance_by_duration(*duration)?;
                     }
-                    MeasureElement::Attributes(_) | MeasureElement::OctaveShift(_) => {}
+                    MeasureElement::Attributes(_)
+                    | MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
                 }
             }
         }
@@ -1120,6 +1976,9 @@ impl MusicXMLConverter {
             if let Some(slur_infos) = slur_info_by_voice.get(voice_num) {
                 Self::resolve_slur_chains(notes, slur_infos);
             }
+            if let Some(gliss_infos) = gliss_info_by_voice.get(voice_num) {
+                Self::resolve_glissando_chains(notes, gliss_infos);
+            }
         }
 
         Ok((notes_by_voice, rests))
@@ -1165,7 +2024,12 @@ impl MusicXMLConverter {
                         // Move timing cursor forward
                         timing_context.advance_by_duration(*duration)?;
                     }
-                    MeasureElement::Attributes(_) | MeasureElement::OctaveShift(_) => {}
+                    MeasureElement::Attributes(_)
+                    | MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
                 }
             }
         }
@@ -1174,6 +2038,44 @@ impl MusicXMLConverter {
     }
 
     /// Collects notes filtered by staff number (for multi-staff instruments)
+    /// Determines the home staff of each voice within each `<backup>`-delimited
+    /// pass of a measure: the staff most of the voice's notes in that pass are
+    /// written on (ties go to the upper staff).
+    ///
+    /// MusicXML encodes cross-staff notes as `<staff>` changes inside a voice
+    /// without a `<backup>`; such notes stay in their voice (keeping beams,
+    /// ties and slurs intact) and are drawn on the other staff.
+    /// Keyed by (pass index within the measure, voice number).
+    fn compute_pass_home_staves(measure: &MeasureData) -> HashMap<(usize, usize), usize> {
+        let mut counts: HashMap<(usize, usize), BTreeMap<usize, usize>> = HashMap::new();
+        let mut pass = 0;
+        for element in &measure.elements {
+            match element {
+                MeasureElement::Note(note_data) => {
+                    *counts
+                        .entry((pass, note_data.voice))
+                        .or_default()
+                        .entry(note_data.staff)
+                        .or_insert(0) += 1;
+                }
+                MeasureElement::Backup(_) => pass += 1,
+                _ => {}
+            }
+        }
+        counts
+            .into_iter()
+            .filter_map(|(key, by_staff)| {
+                // max_by_key returns the last maximum; iterate in reverse so the
+                // lowest staff number wins ties
+                by_staff
+                    .iter()
+                    .rev()
+                    .max_by_key(|(_, count)| **count)
+                    .map(|(staff, _)| (key, *staff))
+            })
+            .collect()
+    }
+
     fn collect_notes_for_staff(
         measures: &[MeasureData],
         staff_num: usize,
@@ -1182,6 +2084,7 @@ impl MusicXMLConverter {
         let mut notes_by_voice: HashMap<usize, Vec<Note>> = HashMap::new();
         let mut tie_info_by_voice: HashMap<usize, Vec<Option<TieType>>> = HashMap::new();
         let mut slur_info_by_voice: HashMap<usize, Vec<Vec<SlurInfo>>> = HashMap::new();
+        let mut gliss_info_by_voice: HashMap<usize, Vec<Vec<GlissandoInfo>>> = HashMap::new();
         let mut rests = Vec::new();
         let mut timing_context = TimingContext::new();
 
@@ -1200,15 +2103,24 @@ impl MusicXMLConverter {
             // Process musical elements, filtering by staff number
             // Track maximum tick reached in this measure for staff timing
             let mut max_tick_in_measure = measure_start_tick;
+            let home_staves = Self::compute_pass_home_staves(measure);
+            let mut pass = 0;
 
             for element in &measure.elements {
                 match element {
                     MeasureElement::Note(note_data) => {
-                        // Only process notes for this staff
-                        if note_data.staff == staff_num {
+                        // Only process notes whose voice lives on this staff
+                        let home_staff = home_staves
+                            .get(&(pass, note_data.voice))
+                            .copied()
+                            .unwrap_or(note_data.staff);
+                        if home_staff == staff_num {
                             // Try to convert note, skip if invalid (e.g., zero duration)
                             match Self::convert_note(note_data, &mut timing_context) {
-                                Ok(note) => {
+                                Ok(mut note) => {
+                                    if note_data.staff != staff_num && note_data.staff >= 1 {
+                                        note = note.with_cross_staff((note_data.staff - 1) as u8);
+                                    }
                                     let voice = note_data.voice;
                                     tie_info_by_voice
                                         .entry(voice)
@@ -1218,6 +2130,10 @@ impl MusicXMLConverter {
                                         .entry(voice)
                                         .or_default()
                                         .push(note_data.slurs.clone());
+                                    gliss_info_by_voice
+                                        .entry(voice)
+                                        .or_default()
+                                        .push(note_data.glissandos.clone());
                                     notes_by_voice.entry(voice).or_default().push(note);
                                     // Track the maximum tick reached for this staff in this measure
                                     max_tick_in_measure =
@@ -1267,12 +2183,18 @@ impl MusicXMLConverter {
                     }
                     MeasureElement::Backup(_duration) => {
                         timing_context.current_tick = measure_start_tick;
+                        pass += 1;
                     }
                     MeasureElement::Forward(duration) => {
                         timing_context.advance_by_duration(*duration)?;
                         max_tick_in_measure = max_tick_in_measure.max(timing_context.current_tick);
                     }
-                    MeasureElement::Attributes(_) | MeasureElement::OctaveShift(_) => {}
+                    MeasureElement::Attributes(_)
+                    | MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
                 }
             }
 
@@ -1290,6 +2212,9 @@ impl MusicXMLConverter {
             if let Some(slur_infos) = slur_info_by_voice.get(voice_num) {
                 Self::resolve_slur_chains(notes, slur_infos);
             }
+            if let Some(gliss_infos) = gliss_info_by_voice.get(voice_num) {
+                Self::resolve_glissando_chains(notes, gliss_infos);
+            }
         }
 
         Ok((notes_by_voice, rests))
@@ -1356,7 +2281,12 @@ impl MusicXMLConverter {
                         timing_context.advance_by_duration(*duration)?;
                         max_tick_in_measure = max_tick_in_measure.max(timing_context.current_tick);
                     }
-                    MeasureElement::Attributes(_) | MeasureElement::OctaveShift(_) => {}
+                    MeasureElement::Attributes(_)
+                    | MeasureElement::OctaveShift(_)
+                    | MeasureElement::Harmony(_)
+                    | MeasureElement::Pedal(_)
+                    | MeasureElement::Tempo(_)
+                    | MeasureElement::DirectionText(_) => {}
                 }
             }
 
@@ -1464,6 +2394,29 @@ impl MusicXMLConverter {
         }
     }
 
+    /// Resolves glissando/slide lines within a voice's note list.
+    ///
+    /// For each note with a glissando start, finds the next note with a
+    /// matching stop (same number) and sets gliss_next on the start note.
+    fn resolve_glissando_chains(notes: &mut [Note], gliss_infos: &[Vec<GlissandoInfo>]) {
+        if notes.len() != gliss_infos.len() {
+            return;
+        }
+        for i in 0..notes.len() {
+            for gliss in gliss_infos[i].iter().filter(|g| g.start) {
+                let end = (i + 1..notes.len()).find(|&j| {
+                    gliss_infos[j]
+                        .iter()
+                        .any(|g| !g.start && g.number == gliss.number)
+                });
+                if let Some(j) = end {
+                    notes[i].gliss_next = Some(notes[j].id);
+                    notes[i].gliss_wavy = gliss.wavy;
+                }
+            }
+        }
+    }
+
     /// Converts NoteData to Note
     fn convert_note(
         note_data: &NoteData,
@@ -1483,19 +2436,25 @@ impl MusicXMLConverter {
         // Grace notes have no rhythmic duration — assign a small visual duration
         // and place them at the current tick, advancing forward. The total grace
         // advance is compensated at the measure boundary to prevent cascading shifts.
+        // Sounding timing is resolved from the grace annotation (see `resolve_grace_timings`).
         if note_data.is_grace {
             let grace_visual_duration: u32 = 60; // 1/16 of a quarter (960 PPQ)
             timing_context.grace_count += 1;
             let tick = timing_context.current_tick();
             timing_context.current_tick += grace_visual_duration;
             timing_context.grace_tick_advance += grace_visual_duration;
-            let mut note =
+            let note =
                 Note::new(tick, grace_visual_duration, pitch).map_err(|e: &'static str| {
                     ImportError::ValidationError {
                         errors: vec![e.to_string()],
                     }
                 })?;
-            note.is_grace = true;
+            let mut grace = note_data
+                .grace
+                .clone()
+                .unwrap_or_else(|| GraceAnnotation::new(false));
+            grace.note_type = note_data.note_type.clone();
+            let note = note.with_grace(grace);
 
             let spelling = crate::domain::value_objects::NoteSpelling {
                 step: pitch_data.step,
@@ -1634,6 +2593,21 @@ impl MusicXMLConverter {
         } else {
             note
         };
+        let note = if let Some(style) = note_data.arpeggio {
+            note.with_arpeggio(style)
+        } else {
+            note
+        };
+        let note = if let Some(tremolo) = note_data.tremolo {
+            note.with_tremolo(tremolo)
+        } else {
+            note
+        };
+        let note = if let Some(tab) = note_data.tab {
+            note.with_tab(tab)
+        } else {
+            note
+        };
 
         Ok(note)
     }
@@ -1658,11 +2632,14 @@ mod tests {
             name: "Piano".to_string(),
             measures: Vec::new(),
             staff_count: 1,
+            instrument: None,
+            abbreviation: None,
         };
 
         // Create measure with attributes
         let measure = MeasureData {
-            number: 1,
+            number: "1".to_string(),
+            implicit: false,
             attributes: Some(AttributesData {
                 divisions: Some(480),
                 key: Some(KeyData {
@@ -1678,6 +2655,7 @@ mod tests {
                     line: 2,
                     staff_number: 1,
                 }],
+                staff_details: vec![],
                 tempo: Some(120.0),
             }),
             elements: vec![MeasureElement::Note(NoteData {
@@ -1698,16 +2676,24 @@ mod tests {
                 tie_placement: None,
                 slurs: Vec::new(),
                 is_grace: false,
+                grace: None,
+                arpeggio: None,
+                glissandos: Vec::new(),
+                tremolo: None,
+                fermata: false,
                 has_explicit_accidental: false,
                 is_measure_rest: false,
                 stem_down: None,
                 fingering: Vec::new(),
+                tab: None,
             })],
             start_repeat: false,
             end_repeat: false,
             endings: vec![],
             sound_tempo: None,
             metronome_tempo: None,
+            new_page: false,
+            new_system: false,
         };
 
         part.measures.push(measure);
@@ -1777,10 +2763,16 @@ mod tests {
             tie_placement: None,
             slurs: Vec::new(),
             is_grace: false,
+            grace: None,
+            arpeggio: None,
+            glissandos: Vec::new(),
+            tremolo: None,
+            fermata: false,
             has_explicit_accidental: false,
             is_measure_rest: false,
             stem_down: None,
             fingering: Vec::new(),
+            tab: None,
         };
 
         let result = MusicXMLConverter::convert_note(&note_data, &mut timing_ctx);
@@ -1803,12 +2795,14 @@ mod tests {
     fn test_convert_voice_with_multiple_notes() {
         // T048: Convert multiple notes to Voice
         let measures = vec![MeasureData {
-            number: 1,
+            number: "1".to_string(),
+            implicit: false,
             attributes: Some(AttributesData {
                 divisions: Some(480),
                 key: None,
                 time: None,
                 clefs: vec![],
+                staff_details: vec![],
                 tempo: None,
             }),
             elements: vec![
@@ -1830,10 +2824,16 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 }),
                 MeasureElement::Note(NoteData {
                     pitch: Some(PitchData {
@@ -1853,10 +2853,16 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 }),
             ],
             start_repeat: false,
@@ -1864,6 +2870,8 @@ mod tests {
             endings: vec![],
             sound_tempo: None,
             metronome_tempo: None,
+            new_page: false,
+            new_system: false,
         }];
 
         let result = MusicXMLConverter::convert_voice(&measures);
@@ -1889,12 +2897,14 @@ mod tests {
     fn test_convert_chord_notes() {
         // Test chord notes start at the same tick
         let measures = vec![MeasureData {
-            number: 1,
+            number: "1".to_string(),
+            implicit: false,
             attributes: Some(AttributesData {
                 divisions: Some(480),
                 key: None,
                 time: None,
                 clefs: vec![],
+                staff_details: vec![],
                 tempo: None,
             }),
             elements: vec![
@@ -1917,10 +2927,16 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 }),
                 // Second note of chord: F#5 (should start at same tick)
                 MeasureElement::Note(NoteData {
@@ -1941,10 +2957,16 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 }),
                 // Third note: C#5 (sequential, after the chord)
                 MeasureElement::Note(NoteData {
@@ -1965,10 +2987,16 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 }),
             ],
             start_repeat: false,
@@ -1976,6 +3004,8 @@ mod tests {
             endings: vec![],
             sound_tempo: None,
             metronome_tempo: None,
+            new_page: false,
+            new_system: false,
         }];
 
         let result = MusicXMLConverter::convert_voice(&measures);
@@ -2039,8 +3069,11 @@ mod tests {
             id: "P1".to_string(),
             name: "Piano".to_string(),
             staff_count: 1,
+            instrument: None,
+            abbreviation: None,
             measures: vec![MeasureData {
-                number: 1,
+                number: "1".to_string(),
+                implicit: false,
                 attributes: Some(AttributesData {
                     divisions: Some(480),
                     key: None,
@@ -2053,6 +3086,7 @@ mod tests {
                         line: 2,
                         staff_number: 1,
                     }],
+                    staff_details: vec![],
                     tempo: None,
                 }),
                 elements: vec![MeasureElement::Note(NoteData {
@@ -2073,16 +3107,24 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 })],
                 start_repeat: false,
                 end_repeat: false,
                 endings: vec![],
                 sound_tempo: None,
                 metronome_tempo: None,
+                new_page: false,
+                new_system: false,
             }],
         };
         doc.parts.push(part);
@@ -2106,8 +3148,11 @@ mod tests {
             id: "P1".to_string(),
             name: "Piano".to_string(),
             staff_count: 1,
+            instrument: None,
+            abbreviation: None,
             measures: vec![MeasureData {
-                number: 1,
+                number: "1".to_string(),
+                implicit: false,
                 attributes: Some(AttributesData {
                     divisions: Some(480),
                     key: None,
@@ -2120,6 +3165,7 @@ mod tests {
                         line: 2,
                         staff_number: 1,
                     }],
+                    staff_details: vec![],
                     tempo: None,
                 }),
                 elements: vec![MeasureElement::Note(NoteData {
@@ -2140,16 +3186,24 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 })],
                 start_repeat: false,
                 end_repeat: false,
                 endings: vec![],
                 sound_tempo: None,
                 metronome_tempo: None,
+                new_page: false,
+                new_system: false,
             }],
         };
         doc.parts.push(part);
@@ -2173,8 +3227,11 @@ mod tests {
             id: "P1".to_string(),
             name: "Piano".to_string(),
             staff_count: 1,
+            instrument: None,
+            abbreviation: None,
             measures: vec![MeasureData {
-                number: 1,
+                number: "1".to_string(),
+                implicit: false,
                 attributes: Some(AttributesData {
                     divisions: Some(480),
                     key: None,
@@ -2187,6 +3244,7 @@ mod tests {
                         line: 2,
                         staff_number: 1,
                     }],
+                    staff_details: vec![],
                     tempo: None,
                 }),
                 elements: vec![MeasureElement::Note(NoteData {
@@ -2207,16 +3265,24 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 })],
                 start_repeat: false,
                 end_repeat: false,
                 endings: vec![],
                 sound_tempo: None,
                 metronome_tempo: None,
+                new_page: false,
+                new_system: false,
             }],
         };
         doc.parts.push(part);
@@ -2240,8 +3306,11 @@ mod tests {
             id: "P1".to_string(),
             name: "Piano".to_string(),
             staff_count: 1,
+            instrument: None,
+            abbreviation: None,
             measures: vec![MeasureData {
-                number: 1,
+                number: "1".to_string(),
+                implicit: false,
                 attributes: Some(AttributesData {
                     divisions: Some(480),
                     key: None,
@@ -2251,6 +3320,7 @@ mod tests {
                         line: 2,
                         staff_number: 1,
                     }],
+                    staff_details: vec![],
                     tempo: None,
                 }),
                 elements: vec![MeasureElement::Note(NoteData {
@@ -2271,16 +3341,24 @@ mod tests {
                     tie_placement: None,
                     slurs: Vec::new(),
                     is_grace: false,
+                    grace: None,
+                    arpeggio: None,
+                    glissandos: Vec::new(),
+                    tremolo: None,
+                    fermata: false,
                     has_explicit_accidental: false,
                     is_measure_rest: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    tab: None,
                 })],
                 start_repeat: false,
                 end_repeat: false,
                 endings: vec![],
                 sound_tempo: None,
                 metronome_tempo: None,
+                new_page: false,
+                new_system: false,
             }],
         };
         doc.parts.push(part);
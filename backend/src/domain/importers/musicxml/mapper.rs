@@ -2,7 +2,12 @@
 // Maps MusicXML elements to domain types (clefs, pitches, key signatures)
 
 use super::errors::MappingError;
-use crate::domain::value_objects::{Clef, KeySignature, Pitch};
+use super::types::{HarmonyData, TempoWordsKind};
+use crate::domain::events::chord_symbol::{ChordDegree, ChordKind, ChordSymbolEvent, DegreeType};
+use crate::domain::events::direction::{Enclosure, Placement, TextDirectionStyle};
+use crate::domain::events::pedal::PedalKind;
+use crate::domain::instrument_catalog;
+use crate::domain::value_objects::{Clef, KeySignature, NoteSpelling, Pitch, Tick};
 
 /// Maps MusicXML elements to domain value objects
 pub struct ElementMapper;
@@ -112,24 +117,178 @@ impl ElementMapper {
     /// # Returns
     /// Clef (defaults to Treble if unrecognized)
     pub fn infer_clef_from_instrument(name: &str) -> Clef {
-        let name_lower = name.to_lowercase();
+        // Default to treble for instruments the catalog does not know
+        instrument_catalog::match_name(name).map_or(Clef::Treble, |instrument| instrument.clefs[0])
+    }
 
-        if name_lower.contains("bass")
-            || name_lower.contains("cello")
-            || name_lower.contains("trombone")
-            || name_lower.contains("contrabass")
-        {
-            Clef::Bass
-        } else if name_lower.contains("viola") {
-            Clef::Alto
-        } else if name_lower.contains("tenor")
-            && (name_lower.contains("trombone") || name_lower.contains("voice"))
+    /// Maps MusicXML `<kind>` text to ChordKind
+    ///
+    /// Unknown values map to `ChordKind::Other` so the `text` attribute
+    /// can still be displayed.
+    pub fn map_chord_kind(kind: &str) -> ChordKind {
+        match kind {
+            "major" | "" => ChordKind::Major,
+            "minor" => ChordKind::Minor,
+            "augmented" => ChordKind::Augmented,
+            "diminished" => ChordKind::Diminished,
+            "dominant" => ChordKind::Dominant,
+            "major-seventh" => ChordKind::MajorSeventh,
+            "minor-seventh" => ChordKind::MinorSeventh,
+            "diminished-seventh" => ChordKind::DiminishedSeventh,
+            "augmented-seventh" => ChordKind::AugmentedSeventh,
+            "half-diminished" => ChordKind::HalfDiminished,
+            "major-minor" => ChordKind::MajorMinor,
+            "major-sixth" => ChordKind::MajorSixth,
+            "minor-sixth" => ChordKind::MinorSixth,
+            "dominant-ninth" => ChordKind::DominantNinth,
+            "major-ninth" => ChordKind::MajorNinth,
+            "minor-ninth" => ChordKind::MinorNinth,
+            "dominant-11th" => ChordKind::Dominant11th,
+            "major-11th" => ChordKind::Major11th,
+            "minor-11th" => ChordKind::Minor11th,
+            "dominant-13th" => ChordKind::Dominant13th,
+            "major-13th" => ChordKind::Major13th,
+            "minor-13th" => ChordKind::Minor13th,
+            "suspended-second" => ChordKind::SuspendedSecond,
+            "suspended-fourth" => ChordKind::SuspendedFourth,
+            "Neapolitan" => ChordKind::Neapolitan,
+            "Italian" => ChordKind::Italian,
+            "French" => ChordKind::French,
+            "German" => ChordKind::German,
+            "pedal" => ChordKind::Pedal,
+            "power" => ChordKind::Power,
+            "Tristan" => ChordKind::Tristan,
+            "none" => ChordKind::None,
+            _ => ChordKind::Other,
+        }
+    }
+
+    /// Maps a pedal kind ("sustain", "sostenuto", "una-corda") to PedalKind
+    pub fn map_pedal_kind(kind: &str) -> Option<PedalKind> {
+        match kind {
+            "sustain" => Some(PedalKind::Sustain),
+            "sostenuto" => Some(PedalKind::Sostenuto),
+            "una-corda" => Some(PedalKind::UnaCorda),
+            _ => None,
+        }
+    }
+
+    /// Maps a metronome beat unit ("quarter", "eighth", …) and its dots to ticks (960 PPQ)
+    pub fn map_beat_unit(unit: &str, dots: u8) -> Option<u32> {
+        let base: u32 = match unit {
+            "breve" => 7680,
+            "whole" => 3840,
+            "half" => 1920,
+            "quarter" => 960,
+            "eighth" => 480,
+            "16th" => 240,
+            "32nd" => 120,
+            _ => return None,
+        };
+        // Each dot adds half of the previous value
+        Some((0..=u32::from(dots.min(3))).map(|d| base >> d).sum())
+    }
+
+    /// Recognizes gradual tempo changes ("rit.", "poco accel.") and tempo
+    /// restorations ("a tempo", "Tempo I") in direction words
+    pub fn map_tempo_words(words: &str) -> Option<TempoWordsKind> {
+        let lower = words.trim().to_lowercase();
+        let text = ["poco ", "molto ", "più ", "piu ", "sempre "]
+            .iter()
+            .fold(lower.as_str(), |t, qualifier| {
+                t.strip_prefix(qualifier).unwrap_or(t)
+            });
+        if text.starts_with("a tempo") || text == "tempo i" || text.starts_with("tempo primo") {
+            return Some(TempoWordsKind::ATempo);
+        }
+        // Whole words or their abbreviations only: "strings" and "ritmico" are
+        // not tempo changes
+        let word = text.split_whitespace().next()?;
+        let abbreviated = word.ends_with('.');
+        match word.trim_end_matches('.') {
+            "rit" | "ritard" | "ritardando" | "riten" | "ritenuto" | "rall" | "rallent"
+            | "rallentando" | "allarg" | "allargando" => Some(TempoWordsKind::Ritardando),
+            "accel" | "accelerando" | "stringendo" => Some(TempoWordsKind::Accelerando),
+            "string" if abbreviated => Some(TempoWordsKind::Accelerando),
+            _ => None,
+        }
+    }
+
+    /// Maps a `<rehearsal enclosure="…">` value; rehearsal marks are boxed by default
+    pub fn map_enclosure(enclosure: Option<&str>) -> Enclosure {
+        match enclosure {
+            Some("circle" | "oval") => Enclosure::Circle,
+            Some("none") => Enclosure::None,
+            _ => Enclosure::Rectangle,
+        }
+    }
+
+    /// Maps a direction `placement` attribute; directions default to above the staff
+    pub fn map_placement(placement: Option<&str>) -> Placement {
+        match placement {
+            Some("below") => Placement::Below,
+            _ => Placement::Above,
+        }
+    }
+
+    /// Tempo words ("Allegro", "rit.", "a tempo") are printed bold; other
+    /// direction words ("dolce", "pizz.") are expression text
+    pub fn map_text_style(words: &str) -> TextDirectionStyle {
+        // Prefixes of common Italian, French and German tempo terms
+        const TEMPO_TERMS: [&str; 16] = [
+            "grave", "larg", "lent", "adagi", "andant", "moderat", "allegr", "vivac", "prest",
+            "tempo", "maestoso", "rubato", "vif", "modéré", "langsam", "schnell",
+        ];
+        let lower = words.trim().to_lowercase();
+        let first_word = lower.split_whitespace().next().unwrap_or("");
+        if Self::map_tempo_words(words).is_some()
+            || TEMPO_TERMS.iter().any(|term| first_word.starts_with(term))
+            || lower.starts_with("con moto")
         {
-            Clef::Tenor
+            TextDirectionStyle::Tempo
         } else {
-            // Default to treble for violin, flute, trumpet, piano (RH), voice (soprano/alto)
-            Clef::Treble
+            TextDirectionStyle::Expression
+        }
+    }
+
+    /// Maps a parsed `<harmony>` element to a ChordSymbolEvent at `tick`
+    pub fn map_harmony(harmony: &HarmonyData, tick: u32) -> ChordSymbolEvent {
+        let kind = Self::map_chord_kind(&harmony.kind);
+        let root = harmony.root_step.map(|step| NoteSpelling {
+            step: step.to_ascii_uppercase(),
+            alter: harmony.root_alter,
+        });
+        let mut event = ChordSymbolEvent::new(Tick::new(tick), root, kind);
+        if let Some(text) = &harmony.kind_text {
+            event = event.with_kind_text(text.clone());
+        }
+        if let Some(step) = harmony.bass_step {
+            event = event.with_bass(NoteSpelling {
+                step: step.to_ascii_uppercase(),
+                alter: harmony.bass_alter,
+            });
         }
+        let degrees: Vec<ChordDegree> = harmony
+            .degrees
+            .iter()
+            .filter_map(|d| {
+                let degree_type = match d.degree_type.as_str() {
+                    "add" => DegreeType::Add,
+                    "alter" => DegreeType::Alter,
+                    "subtract" => DegreeType::Subtract,
+                    _ => return None,
+                };
+                Some(ChordDegree {
+                    value: d.value,
+                    alter: d.alter,
+                    degree_type,
+                })
+            })
+            .collect();
+        if !degrees.is_empty() {
+            event = event.with_degrees(degrees);
+        }
+        event
     }
 }
 
@@ -175,6 +334,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_map_beat_unit_and_tempo_words() {
+        assert_eq!(ElementMapper::map_beat_unit("quarter", 1), Some(1440));
+        assert_eq!(ElementMapper::map_beat_unit("half", 0), Some(1920));
+        assert_eq!(ElementMapper::map_beat_unit("maxima", 0), None);
+
+        assert_eq!(
+            ElementMapper::map_tempo_words("rit."),
+            Some(TempoWordsKind::Ritardando)
+        );
+        assert_eq!(
+            ElementMapper::map_tempo_words("poco rall."),
+            Some(TempoWordsKind::Ritardando)
+        );
+        assert_eq!(
+            ElementMapper::map_tempo_words("accel."),
+            Some(TempoWordsKind::Accelerando)
+        );
+        assert_eq!(
+            ElementMapper::map_tempo_words("a tempo"),
+            Some(TempoWordsKind::ATempo)
+        );
+        assert_eq!(ElementMapper::map_tempo_words("dolce"), None);
+    }
+
+    #[test]
+    fn test_map_tempo_words_matches_whole_words() {
+        assert_eq!(
+            ElementMapper::map_tempo_words("ritard."),
+            Some(TempoWordsKind::Ritardando)
+        );
+        assert_eq!(
+            ElementMapper::map_tempo_words("molto stringendo"),
+            Some(TempoWordsKind::Accelerando)
+        );
+        assert_eq!(ElementMapper::map_tempo_words("strings only"), None);
+        assert_eq!(ElementMapper::map_tempo_words("ritmico"), None);
+        assert_eq!(ElementMapper::map_tempo_words("rallying"), None);
+        assert_eq!(ElementMapper::map_tempo_words("accelerate"), None);
+    }
+
     // T023: Test pitch mapping for middle C (MIDI 60)
     #[test]
     fn test_map_pitch_middle_c() {
@@ -285,6 +485,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_map_chord_kind() {
+        assert_eq!(
+            ElementMapper::map_chord_kind("minor-seventh"),
+            ChordKind::MinorSeventh
+        );
+        assert_eq!(
+            ElementMapper::map_chord_kind("dominant-13th"),
+            ChordKind::Dominant13th
+        );
+        assert_eq!(ElementMapper::map_chord_kind("none"), ChordKind::None);
+        assert_eq!(ElementMapper::map_chord_kind("bogus"), ChordKind::Other);
+    }
+
+    #[test]
+    fn test_map_harmony_slash_chord() {
+        let harmony = HarmonyData {
+            root_step: Some('E'),
+            root_alter: -1,
+            kind: "major".to_string(),
+            bass_step: Some('G'),
+            staff: 1,
+            ..HarmonyData::default()
+        };
+        let event = ElementMapper::map_harmony(&harmony, 960);
+        assert_eq!(event.tick, Tick::new(960));
+        assert_eq!(event.format().to_string(), "E♭/G");
+    }
+
     #[test]
     fn test_infer_clef_unknown() {
         assert_eq!(
@@ -2,12 +2,14 @@
 // Parses MusicXML documents using quick-xml streaming parser
 
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use std::io::BufRead;
 
 use super::ImportContext;
 use super::errors::ImportError;
 use super::types::*;
+use crate::domain::events::grace::GraceAnnotation;
+use crate::domain::events::ornament::{ArpeggioStyle, Tremolo, TremoloKind};
 
 // Parser-private intermediate result type for parse_barline_content (Feature 047)
 
@@ -100,7 +102,10 @@ impl MusicXMLParser {
                         }
 
                         if let Some(part_id) = current_part_id.clone() {
-                            let part_data = Self::parse_part(reader, &part_id, &doc.part_names)?;
+                            let mut part_data =
+                                Self::parse_part(reader, &part_id, &doc.part_names)?;
+                            part_data.instrument = doc.part_instruments.get(&part_id).cloned();
+                            part_data.abbreviation = doc.part_abbreviations.get(&part_id).cloned();
 
                             // Set doc.default_tempo from the first tempo found.
                             // Prefer <metronome><per-minute> over <sound tempo> since
@@ -145,9 +150,14 @@ impl MusicXMLParser {
     /// Structure:
     /// ```xml
     /// <part-list>
+    ///   <part-group type="start" number="1">
+    ///     <group-symbol>bracket</group-symbol>
+    ///   </part-group>
     ///   <score-part id="P1">
     ///     <part-name>Piano</part-name>
+    ///     <part-abbreviation>Pno.</part-abbreviation>
     ///   </score-part>
+    ///   <part-group type="stop" number="1"/>
     /// </part-list>
     /// ```
     fn parse_part_list<B: BufRead>(
@@ -157,6 +167,13 @@ impl MusicXMLParser {
         let mut buf = Vec::new();
         let mut current_part_id: Option<String> = None;
         let mut current_part_name = String::new();
+        let mut current_abbreviation = String::new();
+        // <part-name-display>/<part-abbreviation-display> replace the plain names
+        let mut name_display: Option<String> = None;
+        let mut abbreviation_display: Option<String> = None;
+        let mut current_instrument = PartInstrumentData::default();
+        // Indices into doc.part_groups of the groups not yet stopped
+        let mut open_groups: Vec<usize> = Vec::new();
 
         loop {
             match reader.read_event_into(&mut buf) {
@@ -170,6 +187,20 @@ impl MusicXMLParser {
                             }
                         }
                         current_part_name.clear();
+                        current_abbreviation.clear();
+                        name_display = None;
+                        abbreviation_display = None;
+                        current_instrument = PartInstrumentData::default();
+                        if let Some(part_id) = &current_part_id {
+                            for &group in &open_groups {
+                                doc.part_groups[group].part_ids.push(part_id.clone());
+                            }
+                        }
+                    }
+                    b"part-group" => {
+                        let (group_type, number) = Self::part_group_attributes(&e);
+                        let group = Self::parse_part_group(reader, number)?;
+                        Self::apply_part_group(doc, &mut open_groups, &group_type, group);
                     }
                     b"part-name" => {
                         // Read part name text content
@@ -177,14 +208,61 @@ impl MusicXMLParser {
                             current_part_name = String::from_utf8_lossy(e.as_ref()).to_string();
                         }
                     }
-                    _ => {}
+                    b"part-abbreviation" => {
+                        current_abbreviation = read_text(reader, &mut buf);
+                    }
+                    b"part-name-display" => {
+                        name_display =
+                            Some(Self::parse_name_display(reader, b"part-name-display")?);
+                    }
+                    b"part-abbreviation-display" => {
+                        abbreviation_display = Some(Self::parse_name_display(
+                            reader,
+                            b"part-abbreviation-display",
+                        )?);
+                    }
+                    element => {
+                        let element = element.to_vec();
+                        Self::parse_part_instrument_element(
+                            reader,
+                            &mut buf,
+                            &element,
+                            &mut current_instrument,
+                        );
+                    }
                 },
+                Ok(Event::Empty(e)) if e.name().as_ref() == b"part-group" => {
+                    let (group_type, number) = Self::part_group_attributes(&e);
+                    let group = PartGroupData {
+                        number,
+                        name: None,
+                        symbol: None,
+                        barline: false,
+                        part_ids: Vec::new(),
+                    };
+                    Self::apply_part_group(doc, &mut open_groups, &group_type, group);
+                }
                 Ok(Event::End(e)) => match e.name().as_ref() {
                     b"score-part" => {
                         // Store part name mapping when closing score-part element
                         if let Some(ref part_id) = current_part_id {
-                            doc.part_names
-                                .insert(part_id.clone(), current_part_name.clone());
+                            let name = name_display
+                                .take()
+                                .filter(|name| !name.is_empty())
+                                .unwrap_or_else(|| current_part_name.clone());
+                            doc.part_names.insert(part_id.clone(), name);
+                            let abbreviation = abbreviation_display
+                                .take()
+                                .unwrap_or_else(|| std::mem::take(&mut current_abbreviation));
+                            if !abbreviation.is_empty() {
+                                doc.part_abbreviations.insert(part_id.clone(), abbreviation);
+                            }
+                            if current_instrument != PartInstrumentData::default() {
+                                doc.part_instruments.insert(
+                                    part_id.clone(),
+                                    std::mem::take(&mut current_instrument),
+                                );
+                            }
                         }
                         current_part_id = None;
                         current_part_name.clear();
@@ -214,6 +292,209 @@ impl MusicXMLParser {
         Ok(())
     }
 
+    /// Reads a <part-name-display> or <part-abbreviation-display> up to its
+    /// end tag, joining its texts and accidentals into one name
+    ///
+    /// Structure:
+    /// ```xml
+    /// <part-name-display>
+    ///   <display-text>Clarinet in B</display-text>
+    ///   <accidental-text>flat</accidental-text>
+    /// </part-name-display>
+    /// ```
+    fn parse_name_display<B: BufRead>(
+        reader: &mut Reader<B>,
+        end: &[u8],
+    ) -> Result<String, ImportError> {
+        let mut buf = Vec::new();
+        let mut name = String::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => match e.name().as_ref() {
+                    b"display-text" => {
+                        // Spaces around the text are significant here
+                        if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                            name.push_str(&text.unescape().unwrap_or_default());
+                        }
+                    }
+                    b"accidental-text" => {
+                        name.push_str(match read_text(reader, &mut buf).as_str() {
+                            "sharp" => "♯",
+                            "flat" => "♭",
+                            "natural" => "♮",
+                            "double-sharp" | "sharp-sharp" => "𝄪",
+                            "flat-flat" => "𝄫",
+                            _ => "",
+                        });
+                    }
+                    _ => {}
+                },
+                Ok(Event::End(e)) if e.name().as_ref() == end => break,
+                Ok(Event::Eof) => {
+                    return Err(ImportError::InvalidStructure {
+                        reason: "Unexpected EOF in part name display".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Err(ImportError::ParseError {
+                        line: reader.buffer_position(),
+                        column: 0,
+                        message: format!("XML parse error in part name display: {}", e),
+                    });
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(name.trim().to_string())
+    }
+
+    /// Reads one child of <score-instrument> or <midi-instrument>
+    ///
+    /// Structure:
+    /// ```xml
+    /// <score-instrument id="P1-I1">
+    ///   <instrument-name>Violin</instrument-name>
+    ///   <instrument-sound>strings.violin</instrument-sound>
+    /// </score-instrument>
+    /// <midi-instrument id="P1-I1">
+    ///   <midi-channel>1</midi-channel>
+    ///   <midi-program>41</midi-program>
+    ///   <volume>80</volume>
+    ///   <pan>-45</pan>
+    /// </midi-instrument>
+    /// ```
+    fn parse_part_instrument_element<B: BufRead>(
+        reader: &mut Reader<B>,
+        buf: &mut Vec<u8>,
+        element: &[u8],
+        instrument: &mut PartInstrumentData,
+    ) {
+        let text = match element {
+            b"instrument-name" | b"instrument-sound" | b"midi-channel" | b"midi-program"
+            | b"volume" | b"pan" => read_text(reader, buf),
+            _ => return,
+        };
+        if text.is_empty() {
+            return;
+        }
+        // Parts with several instruments (e.g. drum kits) keep the first
+        match element {
+            b"instrument-name" => {
+                instrument.instrument_name.get_or_insert(text);
+            }
+            b"instrument-sound" => {
+                instrument.instrument_sound.get_or_insert(text);
+            }
+            b"midi-channel" => {
+                instrument.midi_channel = instrument.midi_channel.or(text.parse().ok());
+            }
+            b"midi-program" => {
+                instrument.midi_program = instrument.midi_program.or(text.parse().ok());
+            }
+            b"volume" => instrument.volume = instrument.volume.or(text.parse().ok()),
+            _ => instrument.pan = instrument.pan.or(text.parse().ok()),
+        }
+    }
+
+    /// Reads the `type` and `number` attributes of a <part-group>
+    fn part_group_attributes(e: &BytesStart) -> (String, String) {
+        let mut group_type = String::new();
+        let mut number = "1".to_string();
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"type" => group_type = String::from_utf8_lossy(&attr.value).to_string(),
+                b"number" => number = String::from_utf8_lossy(&attr.value).to_string(),
+                _ => {}
+            }
+        }
+        (group_type, number)
+    }
+
+    /// Parses the children of a <part-group> element
+    ///
+    /// Structure:
+    /// ```xml
+    /// <part-group type="start" number="1">
+    ///   <group-name>Strings</group-name>
+    ///   <group-symbol>bracket</group-symbol>
+    ///   <group-barline>yes</group-barline>
+    /// </part-group>
+    /// ```
+    fn parse_part_group<B: BufRead>(
+        reader: &mut Reader<B>,
+        number: String,
+    ) -> Result<PartGroupData, ImportError> {
+        let mut buf = Vec::new();
+        let mut group = PartGroupData {
+            number,
+            name: None,
+            symbol: None,
+            barline: false,
+            part_ids: Vec::new(),
+        };
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => {
+                    let element = e.name().as_ref().to_vec();
+                    let text = match reader.read_event_into(&mut buf) {
+                        Ok(Event::Text(t)) => t.unescape().unwrap_or_default().trim().to_string(),
+                        _ => String::new(),
+                    };
+                    match element.as_slice() {
+                        b"group-name" if !text.is_empty() => group.name = Some(text),
+                        b"group-symbol" => group.symbol = Some(text),
+                        b"group-barline" => group.barline = text == "yes",
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(e)) if e.name().as_ref() == b"part-group" => break,
+                Ok(Event::Eof) => {
+                    return Err(ImportError::InvalidStructure {
+                        reason: "Unexpected EOF in part-group".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Err(ImportError::ParseError {
+                        line: reader.buffer_position(),
+                        column: 0,
+                        message: format!("XML parse error in part-group: {}", e),
+                    });
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(group)
+    }
+
+    /// Opens a part group on `type="start"` and closes the open group with
+    /// the same number on `type="stop"`
+    fn apply_part_group(
+        doc: &mut MusicXMLDocument,
+        open_groups: &mut Vec<usize>,
+        group_type: &str,
+        group: PartGroupData,
+    ) {
+        match group_type {
+            "start" => {
+                open_groups.push(doc.part_groups.len());
+                doc.part_groups.push(group);
+            }
+            "stop" => {
+                if let Some(pos) = open_groups
+                    .iter()
+                    .rposition(|&i| doc.part_groups[i].number == group.number)
+                {
+                    open_groups.remove(pos);
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Feature 022: Parses <work> element to extract work-title
     ///
     /// Structure:
@@ -230,13 +511,11 @@ impl MusicXMLParser {
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    if e.name().as_ref() == b"work-title" {
-                        if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
-                            let title = text.unescape().unwrap_or_default().trim().to_string();
-                            if !title.is_empty() {
-                                doc.work_title = Some(title);
-                            }
+                Ok(Event::Start(e)) if e.name().as_ref() == b"work-title" => {
+                    if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                        let title = text.unescape().unwrap_or_default().trim().to_string();
+                        if !title.is_empty() {
+                            doc.work_title = Some(title);
                         }
                     }
                 }
@@ -275,22 +554,17 @@ impl MusicXMLParser {
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    if e.name().as_ref() == b"creator" {
-                        let mut is_composer = false;
-                        for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"type" && attr.value.as_ref() == b"composer" {
-                                is_composer = true;
-                            }
+                Ok(Event::Start(e)) if e.name().as_ref() == b"creator" => {
+                    let mut is_composer = false;
+                    for attr in e.attributes().flatten() {
+                        if attr.key.as_ref() == b"type" && attr.value.as_ref() == b"composer" {
+                            is_composer = true;
                         }
-                        if is_composer {
-                            if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
-                                let composer =
-                                    text.unescape().unwrap_or_default().trim().to_string();
-                                if !composer.is_empty() {
-                                    doc.composer = Some(composer);
-                                }
-                            }
+                    }
+                    if is_composer && let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                        let composer = text.unescape().unwrap_or_default().trim().to_string();
+                        if !composer.is_empty() {
+                            doc.composer = Some(composer);
                         }
                     }
                 }
@@ -335,26 +609,33 @@ impl MusicXMLParser {
             }),
             measures: Vec::new(),
             staff_count: 1, // Will be updated after parsing
+            instrument: None,
+            abbreviation: None,
         };
 
         let mut buf = Vec::new();
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    if e.name().as_ref() == b"measure" {
-                        // Extract measure number from attributes
-                        let mut measure_number = 1;
-                        for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"number" {
-                                let num_str = String::from_utf8_lossy(&attr.value);
-                                measure_number = num_str.parse().unwrap_or(1);
+                Ok(Event::Start(e)) if e.name().as_ref() == b"measure" => {
+                    // Printed measure number ("12", "12a") and whether the
+                    // measure is left out of the numbering
+                    let mut measure_number = String::new();
+                    let mut implicit = false;
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"number" => {
+                                measure_number =
+                                    String::from_utf8_lossy(&attr.value).trim().to_string();
                             }
+                            b"implicit" => implicit = attr.value.as_ref() == b"yes",
+                            _ => {}
                         }
-
-                        let measure = Self::parse_measure(reader, measure_number)?;
-                        part.measures.push(measure);
                     }
+
+                    let mut measure = Self::parse_measure(reader, measure_number)?;
+                    measure.implicit = implicit;
+                    part.measures.push(measure);
                 }
                 Ok(Event::End(e)) if e.name().as_ref() == b"part" => {
                     break;
@@ -381,15 +662,11 @@ impl MusicXMLParser {
         for measure in &part.measures {
             for element in &measure.elements {
                 match element {
-                    MeasureElement::Note(note_data) => {
-                        if note_data.staff > max_staff {
-                            max_staff = note_data.staff;
-                        }
+                    MeasureElement::Note(note_data) if note_data.staff > max_staff => {
+                        max_staff = note_data.staff;
                     }
-                    MeasureElement::Rest(rest_data) => {
-                        if rest_data.staff > max_staff {
-                            max_staff = rest_data.staff;
-                        }
+                    MeasureElement::Rest(rest_data) if rest_data.staff > max_staff => {
+                        max_staff = rest_data.staff;
                     }
                     _ => {}
                 }
@@ -412,10 +689,11 @@ impl MusicXMLParser {
     /// ```
     fn parse_measure<B: BufRead>(
         reader: &mut Reader<B>,
-        measure_number: i32,
+        measure_number: String,
     ) -> Result<MeasureData, ImportError> {
         let mut measure = MeasureData {
             number: measure_number,
+            implicit: false,
             attributes: None,
             elements: Vec::new(),
             start_repeat: false,
@@ -423,6 +701,8 @@ impl MusicXMLParser {
             endings: Vec::new(),
             sound_tempo: None,
             metronome_tempo: None,
+            new_page: false,
+            new_system: false,
         };
 
         let mut buf = Vec::new();
@@ -455,6 +735,7 @@ impl MusicXMLParser {
                                 staff: note.staff,
                                 note_type: note.note_type.clone(),
                                 is_measure_rest: note.is_measure_rest,
+                                fermata: note.fermata,
                             };
                             measure.elements.push(MeasureElement::Rest(rest));
                         }
@@ -489,12 +770,29 @@ impl MusicXMLParser {
                         }
                     }
                     b"direction" => {
-                        // Parse <direction> for octave-shift elements
-                        Self::parse_direction(reader, &mut measure)?;
+                        let placement = e
+                            .attributes()
+                            .flatten()
+                            .find(|a| a.key.as_ref() == b"placement")
+                            .map(|a| String::from_utf8_lossy(&a.value).to_string());
+                        Self::parse_direction(reader, &mut measure, placement)?;
+                    }
+                    b"harmony" => {
+                        let printed = !e.attributes().flatten().any(|a| {
+                            a.key.as_ref() == b"print-object" && a.value.as_ref() == b"no"
+                        });
+                        let harmony = Self::parse_harmony(reader)?;
+                        if printed {
+                            measure.elements.push(MeasureElement::Harmony(harmony));
+                        }
                     }
                     b"metronome" => {
                         in_metronome = true;
                     }
+                    // Layout hints; children (<system-layout>, ...) are skipped
+                    b"print" => {
+                        Self::parse_print(&e, &mut measure);
+                    }
                     b"per-minute" if in_metronome => {
                         if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
                             if let Ok(val) =
@@ -506,15 +804,20 @@ impl MusicXMLParser {
                     }
                     _ => {}
                 },
-                Ok(Event::Empty(e)) => {
-                    if e.name().as_ref() == b"sound" {
-                        // Extract tempo from <sound tempo="..."/> at measure level
-                        for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"tempo" {
-                                if let Ok(tempo_str) = std::str::from_utf8(&attr.value) {
-                                    if let Ok(tempo) = tempo_str.parse::<f64>() {
-                                        measure.sound_tempo = Some(tempo);
-                                    }
+                Ok(Event::Empty(e)) if e.name().as_ref() == b"print" => {
+                    Self::parse_print(&e, &mut measure);
+                }
+                Ok(Event::Empty(e)) if e.name().as_ref() == b"sound" => {
+                    // Extract tempo from <sound tempo="..."/> at measure level
+                    for attr in e.attributes().flatten() {
+                        if attr.key.as_ref() == b"tempo" {
+                            if let Ok(tempo_str) = std::str::from_utf8(&attr.value) {
+                                if let Ok(tempo) = tempo_str.parse::<f64>() {
+                                    measure.sound_tempo = Some(tempo);
+                                    measure.elements.push(MeasureElement::Tempo(TempoData {
+                                        sound_tempo: Some(tempo),
+                                        ..TempoData::default()
+                                    }));
                                 }
                             }
                         }
@@ -547,15 +850,42 @@ impl MusicXMLParser {
         Ok(measure)
     }
 
-    /// Parses a `<direction>` element, looking for `<octave-shift>` children.
+    /// Reads page and system break hints from `<print new-page="yes">`
+    /// and `<print new-system="yes">`
+    fn parse_print(e: &BytesStart, measure: &mut MeasureData) {
+        for attr in e.attributes().flatten() {
+            let yes = attr.value.as_ref() == b"yes";
+            match attr.key.as_ref() {
+                b"new-page" => measure.new_page |= yes,
+                b"new-system" => measure.new_system |= yes,
+                _ => {}
+            }
+        }
+    }
+
+    /// Parses a `<direction>` element, looking for `<octave-shift>`,
+    /// `<pedal>` and tempo (`<metronome>`, `<sound tempo>`, `<words>`) children.
+    ///
+    /// Pedal attributes on `<sound>` (`damper-pedal`, `sostenuto-pedal`,
+    /// `soft-pedal`) are used when the direction has no `<pedal>` element;
+    /// they produce playback-only regions (no sign, no line).
     fn parse_direction<B: BufRead>(
         reader: &mut Reader<B>,
         measure: &mut MeasureData,
+        placement: Option<String>,
     ) -> Result<(), ImportError> {
         let mut buf = Vec::new();
         let mut staff: usize = 1;
+        let mut offset: i32 = 0;
         let mut octave_shift: Option<OctaveShiftData> = None;
+        let mut pedals: Vec<PedalData> = Vec::new();
+        let mut sound_pedals: Vec<PedalData> = Vec::new();
         let mut in_metronome = false;
+        let mut tempo = TempoData::default();
+        let mut beat_unit: Option<String> = None;
+        let mut beat_unit_dots: u8 = 0;
+        let mut per_minute: Option<f64> = None;
+        let mut texts: Vec<DirectionTextData> = Vec::new();
 
         loop {
             match reader.read_event_into(&mut buf) {
@@ -569,6 +899,12 @@ impl MusicXMLParser {
                             }
                         }
                     }
+                    b"offset" => {
+                        offset = read_text(reader, &mut buf).parse().unwrap_or(0);
+                    }
+                    b"pedal" => {
+                        pedals.extend(Self::parse_pedal_attributes(&e));
+                    }
                     b"metronome" => {
                         in_metronome = true;
                     }
@@ -578,12 +914,46 @@ impl MusicXMLParser {
                                 text.unescape().unwrap_or_default().trim().parse::<f64>()
                             {
                                 measure.metronome_tempo = Some(val);
+                                per_minute = Some(val);
                             }
                         }
                     }
+                    b"beat-unit" if in_metronome => {
+                        beat_unit = Some(read_text(reader, &mut buf));
+                    }
+                    b"words" => {
+                        let text = read_text(reader, &mut buf);
+                        let words = tempo.words.get_or_insert_with(String::new);
+                        if !words.is_empty() {
+                            words.push(' ');
+                        }
+                        words.push_str(text.trim());
+                    }
+                    b"rehearsal" => {
+                        let enclosure = e
+                            .attributes()
+                            .flatten()
+                            .find(|a| a.key.as_ref() == b"enclosure")
+                            .map(|a| String::from_utf8_lossy(&a.value).to_string());
+                        let text = read_text(reader, &mut buf);
+                        if !text.is_empty() {
+                            texts.push(DirectionTextData {
+                                text,
+                                rehearsal: true,
+                                enclosure,
+                                ..Default::default()
+                            });
+                        }
+                    }
                     _ => {}
                 },
                 Ok(Event::Empty(e)) => match e.name().as_ref() {
+                    b"beat-unit-dot" if in_metronome => {
+                        beat_unit_dots += 1;
+                    }
+                    b"pedal" => {
+                        pedals.extend(Self::parse_pedal_attributes(&e));
+                    }
                     b"octave-shift" => {
                         let mut shift_type = String::new();
                         let mut size: u8 = 8;
@@ -610,21 +980,47 @@ impl MusicXMLParser {
                     }
                     b"sound" => {
                         for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"tempo" {
-                                if let Ok(tempo_str) = std::str::from_utf8(&attr.value) {
-                                    if let Ok(tempo) = tempo_str.parse::<f64>() {
-                                        measure.sound_tempo = Some(tempo);
+                            let pedal_kind = match attr.key.as_ref() {
+                                b"tempo" => {
+                                    if let Ok(tempo_str) = std::str::from_utf8(&attr.value) {
+                                        if let Ok(value) = tempo_str.parse::<f64>() {
+                                            measure.sound_tempo = Some(value);
+                                            tempo.sound_tempo = Some(value);
+                                        }
                                     }
+                                    continue;
                                 }
-                            }
+                                b"damper-pedal" => "sustain",
+                                b"sostenuto-pedal" => "sostenuto",
+                                b"soft-pedal" => "una-corda",
+                                _ => continue,
+                            };
+                            // "yes"/"no" or a 0–100 depression value
+                            let down = match attr.value.as_ref() {
+                                b"yes" => true,
+                                b"no" => false,
+                                value => std::str::from_utf8(value)
+                                    .ok()
+                                    .and_then(|v| v.trim().parse::<f64>().ok())
+                                    .is_some_and(|v| v > 0.0),
+                            };
+                            sound_pedals.push(PedalData {
+                                pedal_type: if down { "start" } else { "stop" }.to_string(),
+                                pedal_kind: pedal_kind.to_string(),
+                                line: false,
+                                sign: false,
+                                offset: 0,
+                                staff: 1,
+                            });
                         }
                     }
                     _ => {}
                 },
-                Ok(Event::End(e)) => {
-                    if e.name().as_ref() == b"direction" {
-                        break;
-                    }
+                Ok(Event::End(e)) if e.name().as_ref() == b"metronome" => {
+                    in_metronome = false;
+                }
+                Ok(Event::End(e)) if e.name().as_ref() == b"direction" => {
+                    break;
                 }
                 Ok(Event::Eof) => break,
                 Err(_) => break,
@@ -633,14 +1029,176 @@ impl MusicXMLParser {
             buf.clear();
         }
 
+        if let Some(per_minute) = per_minute {
+            tempo.metronome = Some((
+                beat_unit.unwrap_or_else(|| "quarter".to_string()),
+                beat_unit_dots,
+                per_minute,
+            ));
+        }
+        // Words printed with a metronome mark belong to the tempo mark
+        // ("Allegro ♩ = 120"); otherwise they are a text direction of their own
+        if tempo.metronome.is_none()
+            && let Some(words) = tempo.words.as_ref().filter(|w| !w.is_empty())
+        {
+            texts.push(DirectionTextData {
+                text: words.clone(),
+                ..Default::default()
+            });
+        }
+        for mut text in texts {
+            text.placement = placement.clone();
+            text.offset = offset;
+            text.staff = staff;
+            measure.elements.push(MeasureElement::DirectionText(text));
+        }
+        if tempo.metronome.is_some() || tempo.sound_tempo.is_some() || tempo.words.is_some() {
+            tempo.offset = offset;
+            measure.elements.push(MeasureElement::Tempo(tempo));
+        }
+
         if let Some(mut os) = octave_shift {
             os.staff = staff;
             measure.elements.push(MeasureElement::OctaveShift(os));
         }
 
+        // An explicit <pedal> already describes the damper/sostenuto pedal;
+        // only keep sound-only pedals of a kind the direction does not mark.
+        sound_pedals.retain(|sp| !pedals.iter().any(|p| p.pedal_kind == sp.pedal_kind));
+        for mut pedal in pedals.into_iter().chain(sound_pedals) {
+            pedal.staff = staff;
+            pedal.offset = offset;
+            measure.elements.push(MeasureElement::Pedal(pedal));
+        }
+
         Ok(())
     }
 
+    /// Reads the attributes of a `<pedal>` element.
+    ///
+    /// Returns `None` when the element has no `type`. `sign` defaults to the
+    /// opposite of `line`, matching the MusicXML default.
+    fn parse_pedal_attributes(e: &BytesStart) -> Option<PedalData> {
+        let mut pedal_type = None;
+        let mut line = false;
+        let mut sign = None;
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"type" => pedal_type = Some(String::from_utf8_lossy(&attr.value).to_string()),
+                b"line" => line = attr.value.as_ref() == b"yes",
+                b"sign" => sign = Some(attr.value.as_ref() == b"yes"),
+                _ => {}
+            }
+        }
+        let pedal_type = pedal_type?;
+        let pedal_kind = if pedal_type == "sostenuto" {
+            "sostenuto"
+        } else {
+            "sustain"
+        };
+        Some(PedalData {
+            pedal_type,
+            pedal_kind: pedal_kind.to_string(),
+            line,
+            sign: sign.unwrap_or(!line),
+            offset: 0,
+            staff: 1,
+        })
+    }
+
+    /// Parses a `<harmony>` element (chord symbol)
+    ///
+    /// Structure:
+    /// ```xml
+    /// <harmony>
+    ///   <root><root-step>B</root-step><root-alter>-1</root-alter></root>
+    ///   <kind text="m7">minor-seventh</kind>
+    ///   <bass><bass-step>F</bass-step></bass>
+    ///   <degree><degree-value>9</degree-value><degree-alter>0</degree-alter><degree-type>add</degree-type></degree>
+    ///   <offset>2</offset>
+    ///   <staff>1</staff>
+    /// </harmony>
+    /// ```
+    fn parse_harmony<B: BufRead>(reader: &mut Reader<B>) -> Result<HarmonyData, ImportError> {
+        let mut harmony = HarmonyData {
+            staff: 1,
+            ..HarmonyData::default()
+        };
+        let mut buf = Vec::new();
+        let mut degree: Option<HarmonyDegreeData> = None;
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => match e.name().as_ref() {
+                    b"root-step" => {
+                        harmony.root_step = read_text(reader, &mut buf).chars().next();
+                    }
+                    b"root-alter" => harmony.root_alter = parse_alter(&read_text(reader, &mut buf)),
+                    b"kind" => {
+                        for attr in e.attributes().flatten() {
+                            if attr.key.as_ref() == b"text" {
+                                harmony.kind_text =
+                                    Some(String::from_utf8_lossy(&attr.value).to_string());
+                            }
+                        }
+                        harmony.kind = read_text(reader, &mut buf);
+                    }
+                    b"bass-step" => {
+                        harmony.bass_step = read_text(reader, &mut buf).chars().next();
+                    }
+                    b"bass-alter" => harmony.bass_alter = parse_alter(&read_text(reader, &mut buf)),
+                    b"offset" => harmony.offset = read_text(reader, &mut buf).parse().unwrap_or(0),
+                    b"staff" => harmony.staff = read_text(reader, &mut buf).parse().unwrap_or(1),
+                    b"degree" => degree = Some(HarmonyDegreeData::default()),
+                    b"degree-value" => {
+                        let value = read_text(reader, &mut buf).parse().unwrap_or(0);
+                        if let Some(d) = degree.as_mut() {
+                            d.value = value;
+                        }
+                    }
+                    b"degree-alter" => {
+                        let alter = parse_alter(&read_text(reader, &mut buf));
+                        if let Some(d) = degree.as_mut() {
+                            d.alter = alter;
+                        }
+                    }
+                    b"degree-type" => {
+                        let degree_type = read_text(reader, &mut buf);
+                        if let Some(d) = degree.as_mut() {
+                            d.degree_type = degree_type;
+                        }
+                    }
+                    _ => {}
+                },
+                Ok(Event::End(e)) => match e.name().as_ref() {
+                    b"degree" => {
+                        if let Some(d) = degree.take() {
+                            harmony.degrees.push(d);
+                        }
+                    }
+                    b"harmony" => break,
+                    _ => {}
+                },
+                Ok(Event::Eof) => {
+                    return Err(ImportError::InvalidStructure {
+                        reason: "Unexpected EOF in harmony".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Err(ImportError::ParseError {
+                        line: reader.buffer_position(),
+                        column: 0,
+                        message: format!("XML parse error in harmony: {}", e),
+                    });
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(harmony)
+    }
+
     /// Parses children of a `<barline>` element to detect `<repeat>` and `<ending>` markers.
     ///
     /// Returns a `ParsedBarlineResult` with repeat flags and optional ending data.
@@ -780,6 +1338,19 @@ impl MusicXMLParser {
                         clef.staff_number = staff_number;
                         attributes.clefs.push(clef);
                     }
+                    b"staff-details" => {
+                        // <staff-details number="2"> applies to the second staff
+                        let mut staff_number = 1;
+                        for attr in e.attributes().flatten() {
+                            if attr.key.as_ref() == b"number" {
+                                let number_str = String::from_utf8_lossy(&attr.value);
+                                staff_number = number_str.parse().unwrap_or(1);
+                            }
+                        }
+                        let mut details = Self::parse_staff_details(reader)?;
+                        details.staff_number = staff_number;
+                        attributes.staff_details.push(details);
+                    }
                     b"sound" => {
                         // Extract tempo from <sound tempo="120"/>
                         for attr in e.attributes().flatten() {
@@ -941,6 +1512,112 @@ impl MusicXMLParser {
         Ok(clef)
     }
 
+    /// Parses <staff-details> element
+    fn parse_staff_details<B: BufRead>(
+        reader: &mut Reader<B>,
+    ) -> Result<StaffDetailsData, ImportError> {
+        let mut details = StaffDetailsData {
+            staff_number: 1,
+            lines: None,
+            tunings: Vec::new(),
+        };
+
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) if e.name().as_ref() == b"staff-lines" => {
+                    if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                        let value = text.unescape().unwrap_or_default();
+                        details.lines = value.trim().parse().ok();
+                    }
+                }
+                Ok(Event::Start(e)) if e.name().as_ref() == b"staff-tuning" => {
+                    let mut line = None;
+                    for attr in e.attributes().flatten() {
+                        if attr.key.as_ref() == b"line" {
+                            line = String::from_utf8_lossy(&attr.value).parse().ok();
+                        }
+                    }
+                    let tuning = Self::parse_staff_tuning(reader)?;
+                    if let (Some(line), Some(tuning)) = (line, tuning) {
+                        details.tunings.push(StaffTuningData { line, ..tuning });
+                    }
+                }
+                Ok(Event::End(e)) if e.name().as_ref() == b"staff-details" => break,
+                Ok(Event::Eof) => {
+                    return Err(ImportError::InvalidStructure {
+                        reason: "Unexpected EOF in staff-details".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Err(ImportError::ParseError {
+                        line: reader.buffer_position(),
+                        column: 0,
+                        message: format!("XML parse error in staff-details: {}", e),
+                    });
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(details)
+    }
+
+    /// Parses <staff-tuning> element
+    ///
+    /// Returns `None` without a <tuning-step> and <tuning-octave>; the
+    /// caller fills in the line from the element's attribute.
+    fn parse_staff_tuning<B: BufRead>(
+        reader: &mut Reader<B>,
+    ) -> Result<Option<StaffTuningData>, ImportError> {
+        let mut step = None;
+        let mut alter = 0;
+        let mut octave = None;
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => {
+                    let name = e.name().as_ref().to_vec();
+                    if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                        let value = text.unescape().unwrap_or_default();
+                        let value = value.trim();
+                        match name.as_slice() {
+                            b"tuning-step" => step = value.chars().next(),
+                            b"tuning-alter" => alter = value.parse().unwrap_or(0),
+                            b"tuning-octave" => octave = value.parse().ok(),
+                            _ => {}
+                        }
+                    }
+                }
+                Ok(Event::End(e)) if e.name().as_ref() == b"staff-tuning" => break,
+                Ok(Event::Eof) => {
+                    return Err(ImportError::InvalidStructure {
+                        reason: "Unexpected EOF in staff-tuning".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Err(ImportError::ParseError {
+                        line: reader.buffer_position(),
+                        column: 0,
+                        message: format!("XML parse error in staff-tuning: {}", e),
+                    });
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(step.zip(octave).map(|(step, octave)| StaffTuningData {
+            line: 0,
+            step,
+            alter,
+            octave,
+        }))
+    }
+
     /// Parses <note> element
     ///
     /// Structure:
@@ -967,10 +1644,16 @@ impl MusicXMLParser {
             tie_placement: None,
             slurs: Vec::new(),
             is_grace: false,
+            grace: None,
+            arpeggio: None,
+            glissandos: Vec::new(),
+            tremolo: None,
+            fermata: false,
             has_explicit_accidental: false,
             is_measure_rest: false,
             stem_down: None,
             fingering: Vec::new(),
+            tab: None,
         };
 
         let mut buf = Vec::new();
@@ -1021,6 +1704,7 @@ impl MusicXMLParser {
                     }
                     b"grace" => {
                         note.is_grace = true;
+                        note.grace = Some(Self::parse_grace_attributes(&e));
                     }
                     b"accidental" => {
                         // <accidental>flat|sharp|natural|...</accidental>
@@ -1143,6 +1827,9 @@ impl MusicXMLParser {
                     b"technical" => {
                         Self::parse_technical(reader, note)?;
                     }
+                    b"fermata" => {
+                        note.fermata = true;
+                    }
                     b"staccato" => {
                         // <staccato/> can also appear directly under <notations>
                         note.staccato = true;
@@ -1216,6 +1903,74 @@ impl MusicXMLParser {
                             });
                         }
                     }
+                    b"arpeggiate" => {
+                        // <arpeggiate direction="up|down"/> — rolled chord
+                        let mut style = ArpeggioStyle::Wavy;
+                        for attr in e.attributes().flatten() {
+                            if attr.key.as_ref() == b"direction" {
+                                match attr.value.as_ref() {
+                                    b"up" => style = ArpeggioStyle::Up,
+                                    b"down" => style = ArpeggioStyle::Down,
+                                    _ => {}
+                                }
+                            }
+                        }
+                        note.arpeggio = Some(style);
+                    }
+                    b"non-arpeggiate" => {
+                        note.arpeggio = Some(ArpeggioStyle::NonArpeggio);
+                    }
+                    b"glissando" | b"slide" => {
+                        // <glissando type="start|stop" number="N" line-type="wavy|solid"/>
+                        // Glissandos default to wavy lines, slides to solid ones.
+                        let mut start = None;
+                        let mut number: u8 = 1;
+                        let mut wavy = e.name().as_ref() == b"glissando";
+                        for attr in e.attributes().flatten() {
+                            match attr.key.as_ref() {
+                                b"type" => start = Some(attr.value.as_ref() == b"start"),
+                                b"number" => {
+                                    number = std::str::from_utf8(&attr.value)
+                                        .unwrap_or("1")
+                                        .parse()
+                                        .unwrap_or(1);
+                                }
+                                b"line-type" => wavy = attr.value.as_ref() == b"wavy",
+                                _ => {}
+                            }
+                        }
+                        if let Some(start) = start {
+                            note.glissandos.push(GlissandoInfo {
+                                start,
+                                number,
+                                wavy,
+                            });
+                        }
+                    }
+                    b"tremolo" => {
+                        // <ornaments><tremolo type="single|start|stop|unmeasured">3</tremolo>
+                        let mut kind = TremoloKind::Single;
+                        for attr in e.attributes().flatten() {
+                            if attr.key.as_ref() == b"type" {
+                                kind = match attr.value.as_ref() {
+                                    b"start" => TremoloKind::Start,
+                                    b"stop" => TremoloKind::Stop,
+                                    b"unmeasured" => TremoloKind::Unmeasured,
+                                    _ => TremoloKind::Single,
+                                };
+                            }
+                        }
+                        let mut strokes = 3;
+                        if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                            strokes = text
+                                .unescape()
+                                .unwrap_or_default()
+                                .trim()
+                                .parse()
+                                .unwrap_or(3);
+                        }
+                        note.tremolo = Some(Tremolo { kind, strokes });
+                    }
                     _ => {}
                 },
                 Ok(Event::End(e)) if e.name().as_ref() == b"notations" => break,
@@ -1227,43 +1982,55 @@ impl MusicXMLParser {
         Ok(())
     }
 
-    /// Parses `<technical>` element for fingering annotations.
+    /// Parses `<technical>` element for fingering annotations and
+    /// tablature string/fret.
     fn parse_technical<B: BufRead>(
         reader: &mut Reader<B>,
         note: &mut NoteData,
     ) -> Result<(), ImportError> {
         let mut buf = Vec::new();
+        let mut string: Option<u8> = None;
+        let mut fret: Option<u8> = None;
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    if e.name().as_ref() == b"fingering" {
-                        // Read placement attribute
-                        let mut placement_above: Option<bool> = None;
-                        for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"placement" {
-                                match attr.value.as_ref() {
-                                    b"above" => placement_above = Some(true),
-                                    b"below" => placement_above = Some(false),
-                                    _ => {}
-                                }
+                Ok(Event::Start(e)) if matches!(e.name().as_ref(), b"string" | b"fret") => {
+                    let is_string = e.name().as_ref() == b"string";
+                    if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                        let value = text.unescape().unwrap_or_default().trim().parse().ok();
+                        if is_string {
+                            string = value;
+                        } else {
+                            fret = value;
+                        }
+                    }
+                }
+                Ok(Event::Start(e)) if e.name().as_ref() == b"fingering" => {
+                    // Read placement attribute
+                    let mut placement_above: Option<bool> = None;
+                    for attr in e.attributes().flatten() {
+                        if attr.key.as_ref() == b"placement" {
+                            match attr.value.as_ref() {
+                                b"above" => placement_above = Some(true),
+                                b"below" => placement_above = Some(false),
+                                _ => {}
                             }
                         }
-                        // Read text content (the digit)
-                        if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
-                            let value = text.unescape().unwrap_or_default();
-                            let above = placement_above.unwrap_or(note.staff <= 1);
-                            // Handle both single-digit ("3") and multi-line
-                            // ("1\n3\n5") fingering text from MusicXML editors.
-                            for token in value.split_whitespace() {
-                                if let Ok(digit) = token.parse::<u8>() {
-                                    if digit > 0 {
-                                        note.fingering.push(
-                                            crate::domain::events::note::FingeringAnnotation {
-                                                digit,
-                                                above,
-                                            },
-                                        );
-                                    }
+                    }
+                    // Read text content (the digit)
+                    if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                        let value = text.unescape().unwrap_or_default();
+                        let above = placement_above.unwrap_or(note.staff <= 1);
+                        // Handle both single-digit ("3") and multi-line
+                        // ("1\n3\n5") fingering text from MusicXML editors.
+                        for token in value.split_whitespace() {
+                            if let Ok(digit) = token.parse::<u8>() {
+                                if digit > 0 {
+                                    note.fingering.push(
+                                        crate::domain::events::note::FingeringAnnotation {
+                                            digit,
+                                            above,
+                                        },
+                                    );
                                 }
                             }
                         }
@@ -1275,6 +2042,11 @@ impl MusicXMLParser {
             }
             buf.clear();
         }
+        if let (Some(string), Some(fret)) = (string, fret)
+            && string > 0
+        {
+            note.tab = Some(crate::domain::events::note::TabPosition { string, fret });
+        }
         Ok(())
     }
 
@@ -1286,10 +2058,8 @@ impl MusicXMLParser {
         let mut buf = Vec::new();
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
-                    if e.name().as_ref() == b"staccato" {
-                        note.staccato = true;
-                    }
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.name().as_ref() == b"staccato" => {
+                    note.staccato = true;
                 }
                 Ok(Event::End(e)) if e.name().as_ref() == b"articulations" => break,
                 Ok(Event::Eof) => break,
@@ -1300,6 +2070,35 @@ impl MusicXMLParser {
         Ok(())
     }
 
+    /// Parses the attributes of a `<grace>` element
+    ///
+    /// `slash="yes"` marks an acciaccatura; `steal-time-previous` and
+    /// `steal-time-following` are percentages (decimals are rounded).
+    fn parse_grace_attributes(e: &BytesStart) -> GraceAnnotation {
+        let mut slash = false;
+        let mut steal_previous = None;
+        let mut steal_following = None;
+        let percent = |value: &[u8]| {
+            String::from_utf8_lossy(value)
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .map(|p| p.clamp(0.0, 100.0).round() as u8)
+        };
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"slash" => slash = attr.value.as_ref() == b"yes",
+                b"steal-time-previous" => steal_previous = percent(&attr.value),
+                b"steal-time-following" => steal_following = percent(&attr.value),
+                _ => {}
+            }
+        }
+        let mut grace = GraceAnnotation::new(slash);
+        grace.steal_time_previous = steal_previous;
+        grace.steal_time_following = steal_following;
+        grace
+    }
+
     /// Parses <pitch> element
     fn parse_pitch<B: BufRead>(reader: &mut Reader<B>) -> Result<PitchData, ImportError> {
         let mut pitch = PitchData {
@@ -1358,12 +2157,10 @@ impl MusicXMLParser {
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    if e.name().as_ref() == b"duration" {
-                        if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
-                            let value = text.unescape().unwrap_or_default();
-                            duration = Some(value.parse().unwrap_or(0));
-                        }
+                Ok(Event::Start(e)) if e.name().as_ref() == b"duration" => {
+                    if let Ok(Event::Text(text)) = reader.read_event_into(&mut buf) {
+                        let value = text.unescape().unwrap_or_default();
+                        duration = Some(value.parse().unwrap_or(0));
                     }
                 }
                 Ok(Event::End(e))
@@ -1386,3 +2183,19 @@ impl MusicXMLParser {
         Ok(duration)
     }
 }
+
+/// Reads the text content following a start tag (empty if none).
+fn read_text<B: BufRead>(reader: &mut Reader<B>, buf: &mut Vec<u8>) -> String {
+    match reader.read_event_into(buf) {
+        Ok(Event::Text(text)) => text.unescape().unwrap_or_default().trim().to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Parses a `<root-alter>`/`<bass-alter>`/`<degree-alter>` value, rounding
+/// microtonal values (e.g. "-0.5") to the nearest semitone.
+fn parse_alter(text: &str) -> i8 {
+    text.parse::<f64>()
+        .map(|v| v.round().clamp(-2.0, 2.0) as i8)
+        .unwrap_or(0)
+}
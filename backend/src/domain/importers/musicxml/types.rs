@@ -51,6 +51,12 @@ pub struct MusicXMLDocument {
     /// Populated during part-list parsing (T092 - User Story 3)
     pub part_names: HashMap<String, String>,
 
+    /// Mapping of part ID to its <part-abbreviation> (e.g., "P1" -> "Vln.")
+    pub part_abbreviations: HashMap<String, String>,
+
+    /// Mapping of part ID to its instrument sound and MIDI settings
+    pub part_instruments: HashMap<String, PartInstrumentData>,
+
     /// Feature 022: Title from <work>/<work-title> element
     pub work_title: Option<String>,
 
@@ -59,6 +65,10 @@ pub struct MusicXMLDocument {
 
     /// Feature 022: Composer from <identification>/<creator type="composer">
     pub composer: Option<String>,
+
+    /// Part groups from <part-group> elements in the part-list, in the
+    /// order they start
+    pub part_groups: Vec<PartGroupData>,
 }
 
 /// Metadata from <encoding> element
@@ -83,6 +93,46 @@ pub struct PartData {
 
     /// Number of staves (1 for single staff, 2 for grand staff, etc.)
     pub staff_count: usize,
+
+    /// Sound and MIDI settings from the part-list's <score-instrument> and
+    /// <midi-instrument>
+    pub instrument: Option<PartInstrumentData>,
+
+    /// Abbreviated name for systems after the first (e.g., "Vln.")
+    pub abbreviation: Option<String>,
+}
+
+/// The first <score-instrument> of a score-part and its <midi-instrument>
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartInstrumentData {
+    /// <instrument-name>, e.g. "Violin"
+    pub instrument_name: Option<String>,
+    /// <instrument-sound>, e.g. "strings.violin"
+    pub instrument_sound: Option<String>,
+    /// <midi-channel> (1-16)
+    pub midi_channel: Option<u8>,
+    /// <midi-program> (1-128)
+    pub midi_program: Option<u8>,
+    /// <volume> (0-100)
+    pub volume: Option<f64>,
+    /// <pan> in degrees (-180 to 180)
+    pub pan: Option<f64>,
+}
+
+/// A <part-group> from the part-list, spanning the score-parts between its
+/// start and stop elements
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartGroupData {
+    /// Group number attribute, pairing start and stop
+    pub number: String,
+    /// <group-name>
+    pub name: Option<String>,
+    /// <group-symbol>: "bracket", "brace", "square", "line" or "none"
+    pub symbol: Option<String>,
+    /// <group-barline>yes</group-barline>
+    pub barline: bool,
+    /// Ids of the score-parts inside the group, in part-list order
+    pub part_ids: Vec<String>,
 }
 
 // Intermediate types for <ending> element parsing (Feature 047)
@@ -107,8 +157,12 @@ pub struct RawEndingData {
 /// Represents a <measure> element
 #[derive(Debug, Clone)]
 pub struct MeasureData {
-    /// Measure number (1-indexed)
-    pub number: i32,
+    /// Printed measure number as written (`number` attribute, e.g. "12a")
+    pub number: String,
+
+    /// True for measures left out of the numbering (`implicit="yes"`),
+    /// such as pickups and the second half of a split measure
+    pub implicit: bool,
 
     /// Timing context from <attributes> element
     pub attributes: Option<AttributesData>,
@@ -130,6 +184,12 @@ pub struct MeasureData {
 
     /// Tempo from <metronome><per-minute>...</per-minute></metronome> (Feature 001-score-tempo)
     pub metronome_tempo: Option<f64>,
+
+    /// True if the measure starts a new page (`<print new-page="yes">`)
+    pub new_page: bool,
+
+    /// True if the measure starts a new system (`<print new-system="yes">`)
+    pub new_system: bool,
 }
 
 /// Timing and notation attributes from <attributes> element
@@ -147,6 +207,9 @@ pub struct AttributesData {
     /// Clefs for each staff (indexed by staff number - 1)
     pub clefs: Vec<ClefData>,
 
+    /// Staff details (line count) per staff
+    pub staff_details: Vec<StaffDetailsData>,
+
     /// Tempo marking (BPM)
     pub tempo: Option<f64>,
 }
@@ -184,6 +247,35 @@ pub struct ClefData {
     pub line: i32,
 }
 
+/// Staff details from <staff-details> element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaffDetailsData {
+    /// Staff number (1-indexed, 1 = first staff)
+    pub staff_number: usize,
+
+    /// Number of staff lines from <staff-lines>, if given
+    pub lines: Option<u8>,
+
+    /// Open-string tunings from <staff-tuning> (tablature staves)
+    pub tunings: Vec<StaffTuningData>,
+}
+
+/// Open-string tuning from <staff-tuning line="..."> element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaffTuningData {
+    /// Staff line of the string, 1 = bottom line (lowest string)
+    pub line: u8,
+
+    /// Step from <tuning-step>: 'C', 'D', 'E', 'F', 'G', 'A', 'B'
+    pub step: char,
+
+    /// Alteration from <tuning-alter>
+    pub alter: i32,
+
+    /// Octave from <tuning-octave>
+    pub octave: i32,
+}
+
 /// Octave-shift (8va/8vb/15ma) direction from `<direction><direction-type><octave-shift>`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OctaveShiftData {
@@ -195,15 +287,110 @@ pub struct OctaveShiftData {
     pub staff: usize,
 }
 
+/// Pedal marking from `<direction>`: `<pedal>` or pedal attributes of `<sound>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PedalData {
+    /// "start", "stop", "change", "continue", "sostenuto", ...
+    pub pedal_type: String,
+    /// "sustain", "sostenuto" or "una-corda"
+    pub pedal_kind: String,
+    /// `line="yes"`: bracket-line style
+    pub line: bool,
+    /// `sign="yes"`: "Ped." / "*" signs
+    pub sign: bool,
+    /// Direction `<offset>` in divisions
+    pub offset: i32,
+    /// Staff number (1-indexed)
+    pub staff: usize,
+}
+
+/// Tempo information from a `<direction>` (or a measure-level `<sound>`)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TempoData {
+    /// `<metronome>`: (beat unit, beat-unit dots, per-minute)
+    pub metronome: Option<(String, u8, f64)>,
+    /// `<sound tempo="…">`, always in quarter notes per minute
+    pub sound_tempo: Option<f64>,
+    /// `<words>` text ("rit.", "accel.", "a tempo", …)
+    pub words: Option<String>,
+    /// Direction `<offset>` in divisions
+    pub offset: i32,
+}
+
+/// Printed text from a `<direction>`: `<words>` or `<rehearsal>`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DirectionTextData {
+    pub text: String,
+    /// true for `<rehearsal>`
+    pub rehearsal: bool,
+    /// `enclosure` attribute ("rectangle", "circle", "none", …)
+    pub enclosure: Option<String>,
+    /// Direction `placement` attribute ("above"/"below")
+    pub placement: Option<String>,
+    /// Direction `<offset>` in divisions
+    pub offset: i32,
+    /// Staff number (1-indexed)
+    pub staff: usize,
+}
+
+/// Gradual or restoring tempo instruction recognized in direction words
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TempoWordsKind {
+    /// "rit.", "rall.", "ritard." …: slowing down
+    Ritardando,
+    /// "accel.", "stringendo" …: speeding up
+    Accelerando,
+    /// "a tempo", "tempo I", "tempo primo": back to the tempo before the change
+    ATempo,
+}
+
+/// Chord symbol from `<harmony>`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HarmonyData {
+    /// `<root-step>`; `None` for "N.C." (`<kind>none</kind>` without root)
+    pub root_step: Option<char>,
+    /// `<root-alter>`
+    pub root_alter: i8,
+    /// `<kind>` text content, e.g. "major", "minor-seventh", "dominant"
+    pub kind: String,
+    /// `<kind text="...">` display override
+    pub kind_text: Option<String>,
+    /// `<bass-step>` for slash chords
+    pub bass_step: Option<char>,
+    /// `<bass-alter>`
+    pub bass_alter: i8,
+    /// `<degree>` modifications
+    pub degrees: Vec<HarmonyDegreeData>,
+    /// `<offset>` in divisions relative to the current position
+    pub offset: i32,
+    /// Staff number (1-indexed)
+    pub staff: usize,
+}
+
+/// `<degree>` child of `<harmony>`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HarmonyDegreeData {
+    /// `<degree-value>`
+    pub value: u8,
+    /// `<degree-alter>`
+    pub alter: i8,
+    /// `<degree-type>`: "add", "alter" or "subtract"
+    pub degree_type: String,
+}
+
 /// Element within a measure (note, rest, or other)
 #[derive(Debug, Clone)]
 pub enum MeasureElement {
     Note(NoteData),
     Rest(RestData),
-    Backup(i32),                  // Move timing backward by N duration units
-    Forward(i32),                 // Move timing forward by N duration units (rest in voice)
-    Attributes(AttributesData),   // Mid-measure attribute changes (clef, key, etc.)
-    OctaveShift(OctaveShiftData), // Octave transposition bracket start/stop
+    Backup(i32),                      // Move timing backward by N duration units
+    Forward(i32),                     // Move timing forward by N duration units (rest in voice)
+    Attributes(AttributesData),       // Mid-measure attribute changes (clef, key, etc.)
+    OctaveShift(OctaveShiftData),     // Octave transposition bracket start/stop
+    Harmony(HarmonyData),             // Chord symbol at the current position
+    Pedal(PedalData),                 // Piano pedal start/change/stop
+    Tempo(TempoData),                 // Metronome mark, <sound tempo>, rit./accel./a tempo
+    DirectionText(DirectionTextData), // <words> and <rehearsal> text
 }
 
 /// The role this note plays in a tie relationship.
@@ -235,6 +422,17 @@ pub struct SlurInfo {
     pub placement: Option<SlurPlacement>,
 }
 
+/// Glissando or slide start/stop info from <notations><glissando> / <slide>.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlissandoInfo {
+    /// "start" (true) or "stop" (false)
+    pub start: bool,
+    /// Glissando number (1-indexed, for overlapping lines)
+    pub number: u8,
+    /// Wavy line (`line-type="wavy"`, the `<glissando>` default)
+    pub wavy: bool,
+}
+
 /// Visual arc placement for a slur, sourced from <notations><slur placement="..."/>.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SlurPlacement {
@@ -291,6 +489,21 @@ pub struct NoteData {
     /// Grace note (no rhythmic duration, ornamental)
     pub is_grace: bool,
 
+    /// `<grace>` attributes: slash and steal-time percentages (grace notes only)
+    pub grace: Option<crate::domain::events::grace::GraceAnnotation>,
+
+    /// Arpeggio sign from `<notations><arpeggiate>` / `<non-arpeggiate>`
+    pub arpeggio: Option<crate::domain::events::ornament::ArpeggioStyle>,
+
+    /// Glissando/slide start/stop annotations from `<notations>`
+    pub glissandos: Vec<GlissandoInfo>,
+
+    /// Tremolo from `<notations><ornaments><tremolo>`
+    pub tremolo: Option<crate::domain::events::ornament::Tremolo>,
+
+    /// `<notations><fermata>`: the note or rest is held longer than written
+    pub fermata: bool,
+
     /// Explicit accidental from MusicXML `<accidental>` element (courtesy/editorial)
     pub has_explicit_accidental: bool,
 
@@ -303,6 +516,9 @@ pub struct NoteData {
 
     /// Fingering annotations from `<notations><technical><fingering>` elements
     pub fingering: Vec<crate::domain::events::note::FingeringAnnotation>,
+
+    /// String and fret from `<notations><technical><string>` / `<fret>`
+    pub tab: Option<crate::domain::events::note::TabPosition>,
 }
 
 /// Pitch from <pitch> element
@@ -335,6 +551,9 @@ pub struct RestData {
 
     /// `<rest measure="yes"/>` — a rest that fills the entire measure.
     pub is_measure_rest: bool,
+
+    /// `<notations><fermata>` on the rest
+    pub fermata: bool,
 }
 
 impl Default for MusicXMLDocument {
@@ -345,9 +564,12 @@ impl Default for MusicXMLDocument {
             parts: Vec::new(),
             default_tempo: 120.0,
             part_names: HashMap::new(),
+            part_abbreviations: HashMap::new(),
+            part_instruments: HashMap::new(),
             work_title: None,
             movement_title: None,
             composer: None,
+            part_groups: Vec::new(),
         }
     }
 }
@@ -1,7 +1,10 @@
 use crate::domain::{
     errors::DomainError,
+    events::{clef::ClefEvent, staff::StaffStructuralEvent},
     ids::{InstrumentId, StaffId},
+    instrument_catalog::{self, CatalogInstrument},
     staff::Staff,
+    value_objects::Tick,
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,23 +13,91 @@ use serde::{Deserialize, Serialize};
 pub struct Instrument {
     pub id: InstrumentId,
     pub name: String,
-    /// Type of instrument for playback (e.g., "piano", "guitar")
-    /// Feature 003: Music Playback - MVP always "piano"
+    /// Abbreviated name printed on systems after the first, e.g. "Vln. I"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    /// Type of instrument for playback (e.g., "piano", "guitar"): an
+    /// `instrument_catalog` id, "piano" when the instrument is not recognised
     pub instrument_type: String,
     pub staves: Vec<Staff>,
+    /// MIDI playback settings imported from `<midi-instrument>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub midi: Option<MidiSettings>,
+}
+
+/// MIDI playback settings of an instrument
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MidiSettings {
+    /// General MIDI program (1-128)
+    pub program: u8,
+    /// MIDI channel (1-16)
+    pub channel: u8,
+    /// Volume in percent (0-100)
+    pub volume: u8,
+    /// Pan in degrees, -90 (left) to 90 (right)
+    pub pan: i16,
+}
+
+impl MidiSettings {
+    /// Default settings for a General MIDI program
+    pub fn new(program: u8) -> Self {
+        Self {
+            program,
+            channel: 1,
+            volume: 80,
+            pan: 0,
+        }
+    }
 }
 
 impl Instrument {
-    /// Create a new instrument with one default staff
+    /// Create a new instrument with one default staff; the instrument type
+    /// is recognised from the name, defaulting to piano
     pub fn new(name: String) -> Self {
+        let instrument_type = instrument_catalog::match_name(&name)
+            .map_or("piano", |instrument| instrument.id)
+            .to_string();
         Self {
             id: InstrumentId::new(),
             name,
-            instrument_type: "piano".to_string(), // Default to piano for MVP
+            short_name: None,
+            instrument_type,
             staves: vec![Staff::new()],
+            midi: None,
         }
     }
 
+    /// Create an instrument from a catalog entry with its default staves
+    /// and clefs
+    pub fn from_catalog(instrument: &CatalogInstrument) -> Self {
+        let staves = instrument
+            .clefs
+            .iter()
+            .map(|&clef| {
+                let mut staff = Staff::new();
+                for event in &mut staff.staff_structural_events {
+                    if let StaffStructuralEvent::Clef(clef_event) = event {
+                        *clef_event = ClefEvent::new(Tick::new(0), clef);
+                    }
+                }
+                staff
+            })
+            .collect();
+        Self {
+            id: InstrumentId::new(),
+            name: instrument.name.to_string(),
+            short_name: Some(instrument.short_name.to_string()),
+            instrument_type: instrument.id.to_string(),
+            staves,
+            midi: Some(MidiSettings::new(instrument.midi_program)),
+        }
+    }
+
+    /// The catalog entry for this instrument's type
+    pub fn catalog_entry(&self) -> Option<&'static CatalogInstrument> {
+        instrument_catalog::find(&self.instrument_type)
+    }
+
     /// Add an additional staff to the instrument
     pub fn add_staff(&mut self, staff: Staff) {
         self.staves.push(staff);
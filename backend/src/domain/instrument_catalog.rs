@@ -0,0 +1,272 @@
+// Built-in instrument catalog - names, ranges, clefs, transpositions and GM programs
+
+use crate::domain::value_objects::Clef;
+use serde::Serialize;
+
+/// Instrument family, in score order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum InstrumentFamily {
+    Woodwind,
+    Brass,
+    Percussion,
+    Keyboard,
+    PluckedStrings,
+    Voice,
+    Strings,
+}
+
+/// Inclusive range of sounding pitches (MIDI note numbers)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PitchRange {
+    pub low: u8,
+    pub high: u8,
+}
+
+impl PitchRange {
+    pub fn contains(&self, pitch: u8) -> bool {
+        (self.low..=self.high).contains(&pitch)
+    }
+}
+
+/// An instrument of the built-in catalog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CatalogInstrument {
+    /// Stable identifier, stored as `Instrument::instrument_type`
+    pub id: &'static str,
+    /// Display name, e.g. "Violin"
+    pub name: &'static str,
+    /// Abbreviated name for systems after the first, e.g. "Vln."
+    pub short_name: &'static str,
+    pub family: InstrumentFamily,
+    /// Every pitch the instrument can sound
+    pub range: PitchRange,
+    /// Pitches playable without strain by an average player
+    pub comfortable_range: PitchRange,
+    /// Default clef of each staff, top to bottom
+    pub clefs: &'static [Clef],
+    /// Semitones from written to sounding pitch (-2 for a B♭ clarinet)
+    pub transposition: i8,
+    /// General MIDI program (1-128)
+    pub midi_program: u8,
+    /// MusicXML `<instrument-sound>` id, e.g. "strings.violin"
+    pub sound: &'static str,
+    /// Lowercase words recognising the instrument in part names
+    #[serde(skip)]
+    pub keywords: &'static [&'static str],
+}
+
+impl CatalogInstrument {
+    /// Number of staves the instrument is written on
+    pub fn staff_count(&self) -> usize {
+        self.clefs.len()
+    }
+
+    /// Sounding pitch of a written pitch
+    pub fn sounding_pitch(&self, written: u8) -> Option<u8> {
+        u8::try_from(i16::from(written) + i16::from(self.transposition)).ok()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn entry(
+    id: &'static str,
+    name: &'static str,
+    short_name: &'static str,
+    family: InstrumentFamily,
+    range: (u8, u8),
+    comfortable_range: (u8, u8),
+    clefs: &'static [Clef],
+    transposition: i8,
+    midi_program: u8,
+    sound: &'static str,
+    keywords: &'static [&'static str],
+) -> CatalogInstrument {
+    CatalogInstrument {
+        id,
+        name,
+        short_name,
+        family,
+        range: PitchRange {
+            low: range.0,
+            high: range.1,
+        },
+        comfortable_range: PitchRange {
+            low: comfortable_range.0,
+            high: comfortable_range.1,
+        },
+        clefs,
+        transposition,
+        midi_program,
+        sound,
+        keywords,
+    }
+}
+
+use Clef::{Alto, Bass, Treble};
+use InstrumentFamily::*;
+
+#[rustfmt::skip]
+static CATALOG: &[CatalogInstrument] = &[
+    // Woodwinds
+    entry("piccolo", "Piccolo", "Picc.", Woodwind, (74, 108), (74, 103), &[Treble], 12, 73,
+        "wind.flutes.flute.piccolo", &["piccolo", "ottavino"]),
+    entry("flute", "Flute", "Fl.", Woodwind, (60, 96), (60, 93), &[Treble], 0, 74,
+        "wind.flutes.flute", &["flute", "flauto", "flöte", "flûte"]),
+    entry("oboe", "Oboe", "Ob.", Woodwind, (58, 91), (60, 86), &[Treble], 0, 69,
+        "wind.reed.oboe", &["oboe", "hautbois"]),
+    entry("english-horn", "English Horn", "E.H.", Woodwind, (52, 81), (52, 77), &[Treble], -7, 70,
+        "wind.reed.english-horn", &["english horn", "cor anglais", "corno inglese"]),
+    entry("clarinet", "Clarinet in B♭", "Cl.", Woodwind, (50, 91), (52, 84), &[Treble], -2, 72,
+        "wind.reed.clarinet.b-flat", &["clarinet", "clarinetto", "klarinette"]),
+    entry("bass-clarinet", "Bass Clarinet", "B. Cl.", Woodwind, (34, 70), (36, 67), &[Treble], -14, 72,
+        "wind.reed.clarinet.bass", &["bass clarinet"]),
+    entry("bassoon", "Bassoon", "Bsn.", Woodwind, (34, 75), (34, 70), &[Bass], 0, 71,
+        "wind.reed.bassoon", &["bassoon", "fagotto", "fagott", "basson"]),
+    entry("alto-saxophone", "Alto Saxophone", "A. Sax.", Woodwind, (49, 81), (49, 77), &[Treble], -9, 66,
+        "wind.reed.saxophone.alto", &["alto saxophone", "alto sax"]),
+    entry("tenor-saxophone", "Tenor Saxophone", "T. Sax.", Woodwind, (44, 76), (44, 72), &[Treble], -14, 67,
+        "wind.reed.saxophone.tenor", &["tenor saxophone", "tenor sax"]),
+    // Brass
+    entry("horn", "Horn in F", "Hn.", Brass, (34, 77), (41, 72), &[Treble], -7, 61,
+        "brass.french-horn", &["horn", "french horn", "corno", "cor"]),
+    entry("trumpet", "Trumpet in B♭", "Tpt.", Brass, (52, 82), (55, 79), &[Treble], -2, 57,
+        "brass.trumpet.b-flat", &["trumpet", "tromba", "trompete"]),
+    entry("trombone", "Trombone", "Tbn.", Brass, (40, 72), (40, 69), &[Bass], 0, 58,
+        "brass.trombone", &["trombone", "tenor trombone", "posaune"]),
+    entry("tuba", "Tuba", "Tba.", Brass, (28, 65), (29, 58), &[Bass], 0, 59,
+        "brass.tuba", &["tuba"]),
+    // Percussion
+    entry("timpani", "Timpani", "Timp.", Percussion, (40, 57), (41, 55), &[Bass], 0, 48,
+        "drum.timpani", &["timpani", "timpano", "pauken", "kettledrums"]),
+    entry("xylophone", "Xylophone", "Xyl.", Percussion, (65, 108), (65, 108), &[Treble], 12, 14,
+        "pitched-percussion.xylophone", &["xylophone"]),
+    entry("vibraphone", "Vibraphone", "Vib.", Percussion, (53, 89), (53, 89), &[Treble], 0, 12,
+        "pitched-percussion.vibraphone", &["vibraphone", "vibes"]),
+    entry("marimba", "Marimba", "Mar.", Percussion, (45, 96), (45, 96), &[Treble, Bass], 0, 13,
+        "pitched-percussion.marimba", &["marimba"]),
+    // Keyboards
+    entry("piano", "Piano", "Pno.", Keyboard, (21, 108), (28, 103), &[Treble, Bass], 0, 1,
+        "keyboard.piano", &["piano", "pianoforte", "klavier"]),
+    entry("harpsichord", "Harpsichord", "Hpschd.", Keyboard, (29, 89), (29, 89), &[Treble, Bass], 0, 7,
+        "keyboard.harpsichord", &["harpsichord", "cembalo", "clavecin"]),
+    entry("organ", "Organ", "Org.", Keyboard, (36, 96), (36, 91), &[Treble, Bass, Bass], 0, 20,
+        "keyboard.organ.pipe", &["organ", "organo", "orgel"]),
+    entry("celesta", "Celesta", "Cel.", Keyboard, (72, 108), (72, 108), &[Treble, Bass], 12, 9,
+        "pitched-percussion.celesta", &["celesta", "celeste"]),
+    // Plucked strings
+    entry("harp", "Harp", "Hp.", PluckedStrings, (24, 103), (24, 96), &[Treble, Bass], 0, 47,
+        "pluck.harp", &["harp", "arpa", "harfe"]),
+    entry("guitar", "Guitar", "Gtr.", PluckedStrings, (40, 88), (40, 76), &[Treble], -12, 25,
+        "pluck.guitar", &["guitar", "classical guitar", "acoustic guitar", "chitarra", "gitarre"]),
+    entry("electric-guitar", "Electric Guitar", "E. Gtr.", PluckedStrings, (40, 88), (40, 84), &[Treble], -12, 28,
+        "pluck.guitar.electric", &["electric guitar"]),
+    entry("bass-guitar", "Bass Guitar", "B. Gtr.", PluckedStrings, (28, 67), (28, 60), &[Bass], -12, 34,
+        "pluck.bass.electric", &["bass guitar", "electric bass"]),
+    // Voices
+    entry("soprano", "Soprano", "S.", Voice, (59, 84), (62, 79), &[Treble], 0, 53,
+        "voice.soprano", &["soprano", "sopran"]),
+    entry("alto", "Alto", "A.", Voice, (53, 77), (55, 74), &[Treble], 0, 53,
+        "voice.alto", &["alto", "contralto", "mezzo", "mezzo soprano"]),
+    entry("tenor", "Tenor", "T.", Voice, (47, 72), (48, 69), &[Treble], -12, 53,
+        "voice.tenor", &["tenor", "tenore"]),
+    entry("baritone", "Baritone", "Bar.", Voice, (43, 67), (45, 64), &[Bass], 0, 53,
+        "voice.baritone", &["baritone", "bariton"]),
+    entry("bass-voice", "Bass", "B.", Voice, (40, 64), (41, 62), &[Bass], 0, 53,
+        "voice.bass", &["bass", "basso", "bass voice"]),
+    // Strings
+    entry("violin", "Violin", "Vln.", Strings, (55, 103), (55, 93), &[Treble], 0, 41,
+        "strings.violin", &["violin", "violino", "violine", "fiddle"]),
+    entry("viola", "Viola", "Vla.", Strings, (48, 91), (48, 81), &[Alto], 0, 42,
+        "strings.viola", &["viola", "bratsche"]),
+    entry("cello", "Violoncello", "Vc.", Strings, (36, 84), (36, 76), &[Bass], 0, 43,
+        "strings.cello", &["cello", "violoncello", "celli"]),
+    entry("contrabass", "Contrabass", "Cb.", Strings, (28, 67), (28, 60), &[Bass], -12, 44,
+        "strings.contrabass", &["contrabass", "double bass", "string bass", "kontrabass", "contrabbasso"]),
+];
+
+/// All instruments of the catalog, grouped by family in score order
+pub fn catalog() -> &'static [CatalogInstrument] {
+    CATALOG
+}
+
+/// Look up an instrument by its catalog id
+pub fn find(id: &str) -> Option<&'static CatalogInstrument> {
+    CATALOG.iter().find(|i| i.id == id)
+}
+
+/// Look up an instrument by MusicXML `<instrument-sound>` id
+pub fn find_by_sound(sound: &str) -> Option<&'static CatalogInstrument> {
+    CATALOG.iter().find(|i| i.sound == sound)
+}
+
+/// Instruments using a General MIDI program (1-128)
+pub fn find_by_program(program: u8) -> Vec<&'static CatalogInstrument> {
+    CATALOG
+        .iter()
+        .filter(|i| i.midi_program == program)
+        .collect()
+}
+
+/// Recognise an instrument from a part or instrument name.
+///
+/// Matches whole words, ignoring case, punctuation and numbering
+/// ("Violin I", "2. Violins"); the longest matching keyword wins, so
+/// "Bass Clarinet" is a bass clarinet rather than a bass voice.
+pub fn match_name(name: &str) -> Option<&'static CatalogInstrument> {
+    let words: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphabetic() { c } else { ' ' })
+        .collect();
+    let words = format!(
+        " {} ",
+        words.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    CATALOG
+        .iter()
+        .flat_map(|instrument| instrument.keywords.iter().map(move |kw| (instrument, kw)))
+        .filter(|(_, kw)| words.contains(&format!(" {kw} ")) || words.contains(&format!(" {kw}s ")))
+        .max_by_key(|(_, kw)| kw.len())
+        .map(|(instrument, _)| instrument)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_entries_are_consistent() {
+        for instrument in catalog() {
+            assert!(find(instrument.id).is_some_and(|i| std::ptr::eq(i, instrument)));
+            assert!(instrument.staff_count() >= 1, "{}", instrument.id);
+            assert!(
+                (1..=128).contains(&instrument.midi_program),
+                "{}",
+                instrument.id
+            );
+            assert!(instrument.range.low <= instrument.comfortable_range.low);
+            assert!(instrument.comfortable_range.high <= instrument.range.high);
+        }
+    }
+
+    #[test]
+    fn test_match_name() {
+        let id = |name| match_name(name).map(|i| i.id);
+        assert_eq!(id("Violin I"), Some("violin"));
+        assert_eq!(id("2. Violins"), Some("violin"));
+        assert_eq!(id("Violoncello"), Some("cello"));
+        assert_eq!(id("Bass Clarinet in Bb"), Some("bass-clarinet"));
+        assert_eq!(id("Double Bass"), Some("contrabass"));
+        assert_eq!(id("Bass"), Some("bass-voice"));
+        assert_eq!(id("Alto Sax"), Some("alto-saxophone"));
+        assert_eq!(id("Theremin"), None);
+    }
+
+    #[test]
+    fn test_transposition() {
+        let clarinet = find_by_sound("wind.reed.clarinet.b-flat").unwrap();
+        assert_eq!(clarinet.sounding_pitch(62), Some(60));
+        assert_eq!(find("guitar").unwrap().sounding_pitch(64), Some(52));
+        assert_eq!(find_by_program(41)[0].id, "violin");
+    }
+}
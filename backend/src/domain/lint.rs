@@ -0,0 +1,540 @@
+// Score validation - structured findings for imported and API-built scores
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::domain::{
+    events::{global::GlobalStructuralEvent, note::Note, staff::StaffStructuralEvent},
+    ids::NoteId,
+    instrument::Instrument,
+    score::Score,
+    voice::Voice,
+};
+use serde::{Deserialize, Serialize};
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// Unusual but possibly intended
+    Info,
+    /// Likely a mistake; the score still renders and plays
+    Warning,
+    /// Inconsistent data that renders or plays wrongly
+    Error,
+}
+
+/// The check that produced a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    /// A voice leaves part of a measure it plays in unaccounted for
+    UnderfilledMeasure,
+    /// A note or rest runs past the end of its measure
+    OverfilledMeasure,
+    /// A note the instrument cannot sound
+    NoteOutOfRange,
+    /// `tie_next` names a note that does not exist
+    DanglingTie,
+    /// `slur_next` names a note that does not exist
+    DanglingSlur,
+    /// A tie joins notes of different pitch
+    TiePitchMismatch,
+    /// A rest overlaps a note or another rest of the same voice
+    OverlappingEvents,
+    /// A time or key signature away from a barline
+    StructuralEventOffBarline,
+    /// Events stored out of tick order
+    UnsortedEvents,
+}
+
+impl LintRule {
+    /// Stable identifier, e.g. "underfilled_measure"
+    pub fn id(&self) -> &'static str {
+        match self {
+            LintRule::UnderfilledMeasure => "underfilled_measure",
+            LintRule::OverfilledMeasure => "overfilled_measure",
+            LintRule::NoteOutOfRange => "note_out_of_range",
+            LintRule::DanglingTie => "dangling_tie",
+            LintRule::DanglingSlur => "dangling_slur",
+            LintRule::TiePitchMismatch => "tie_pitch_mismatch",
+            LintRule::OverlappingEvents => "overlapping_events",
+            LintRule::StructuralEventOffBarline => "structural_event_off_barline",
+            LintRule::UnsortedEvents => "unsorted_events",
+        }
+    }
+
+    /// Severity of the rule's findings
+    pub fn severity(&self) -> LintSeverity {
+        match self {
+            LintRule::OverfilledMeasure
+            | LintRule::DanglingTie
+            | LintRule::DanglingSlur
+            | LintRule::TiePitchMismatch
+            | LintRule::OverlappingEvents => LintSeverity::Error,
+            LintRule::UnderfilledMeasure
+            | LintRule::NoteOutOfRange
+            | LintRule::StructuralEventOffBarline
+            | LintRule::UnsortedEvents => LintSeverity::Warning,
+        }
+    }
+}
+
+/// Where a finding is, as 0-based indices into the score; fields that do
+/// not apply are `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instrument: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staff: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub measure: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick: Option<u32>,
+}
+
+/// One problem found in a score
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub severity: LintSeverity,
+    pub rule: LintRule,
+    /// Human-readable description
+    pub message: String,
+    pub location: LintLocation,
+}
+
+/// All findings for a score, in score order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// True when nothing was found
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// True when any finding is an error
+    pub fn has_errors(&self) -> bool {
+        self.count(LintSeverity::Error) > 0
+    }
+
+    /// Number of findings of a severity
+    pub fn count(&self, severity: LintSeverity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    /// Findings of one rule
+    pub fn by_rule(&self, rule: LintRule) -> impl Iterator<Item = &LintFinding> {
+        self.findings.iter().filter(move |f| f.rule == rule)
+    }
+}
+
+/// Checks a score for inconsistencies the editing operations and the
+/// importer do not prevent
+///
+/// ```ignore
+/// let report = ScoreLinter::new().lint(&score);
+/// for finding in report.by_rule(LintRule::DanglingTie) { ... }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScoreLinter;
+
+impl ScoreLinter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Run every rule over the score
+    pub fn lint(&self, score: &Score) -> LintReport {
+        let measures = score.measure_ranges(score.end_tick());
+        let mut lint = Lint {
+            measures: &measures,
+            findings: Vec::new(),
+        };
+
+        lint.global_events(score);
+        let notes = note_index(score);
+        for (i, instrument) in score.instruments.iter().enumerate() {
+            lint.instrument(i, instrument, &notes);
+        }
+
+        LintReport {
+            findings: lint.findings,
+        }
+    }
+}
+
+/// Every note of the score by id, with its instrument, staff and voice
+fn note_index(score: &Score) -> HashMap<NoteId, &Note> {
+    score
+        .instruments
+        .iter()
+        .flat_map(|i| &i.staves)
+        .flat_map(|s| &s.voices)
+        .flat_map(|v| &v.interval_events)
+        .map(|n| (n.id, n))
+        .collect()
+}
+
+/// Findings collected while walking one score
+struct Lint<'a> {
+    measures: &'a [Range<u32>],
+    findings: Vec<LintFinding>,
+}
+
+impl Lint<'_> {
+    fn report(&mut self, rule: LintRule, location: LintLocation, message: String) {
+        self.findings.push(LintFinding {
+            severity: rule.severity(),
+            rule,
+            message,
+            location,
+        });
+    }
+
+    /// Index of the measure containing `tick`
+    fn measure_at(&self, tick: u32) -> Option<usize> {
+        let index = self.measures.partition_point(|m| m.end <= tick);
+        (index < self.measures.len()).then_some(index)
+    }
+
+    fn on_barline(&self, tick: u32) -> bool {
+        tick == 0
+            || self
+                .measures
+                .binary_search_by_key(&tick, |m| m.start)
+                .is_ok()
+    }
+
+    fn global_events(&mut self, score: &Score) {
+        let ticks: Vec<u32> = score
+            .global_structural_events
+            .iter()
+            .map(|e| match e {
+                GlobalStructuralEvent::Tempo(t) => t.tick.value(),
+                GlobalStructuralEvent::TimeSignature(t) => t.tick.value(),
+            })
+            .collect();
+        self.sorted(&ticks, LintLocation::default(), "Global structural events");
+
+        for event in &score.global_structural_events {
+            if let GlobalStructuralEvent::TimeSignature(ts) = event {
+                let tick = ts.tick.value();
+                if !self.on_barline(tick) {
+                    self.report(
+                        LintRule::StructuralEventOffBarline,
+                        LintLocation {
+                            measure: self.measure_at(tick),
+                            tick: Some(tick),
+                            ..LintLocation::default()
+                        },
+                        format!(
+                            "Time signature {}/{} at tick {} is not on a barline",
+                            ts.numerator, ts.denominator, tick
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn instrument(
+        &mut self,
+        instrument_index: usize,
+        instrument: &Instrument,
+        notes: &HashMap<NoteId, &Note>,
+    ) {
+        let catalog = instrument.catalog_entry();
+        for (staff_index, staff) in instrument.staves.iter().enumerate() {
+            let staff_location = LintLocation {
+                instrument: Some(instrument_index),
+                staff: Some(staff_index),
+                ..LintLocation::default()
+            };
+
+            let ticks: Vec<u32> = staff
+                .staff_structural_events
+                .iter()
+                .map(|e| match e {
+                    StaffStructuralEvent::Clef(c) => c.tick.value(),
+                    StaffStructuralEvent::KeySignature(k) => k.tick.value(),
+                })
+                .collect();
+            self.sorted(&ticks, staff_location, "Staff structural events");
+
+            // Clef changes inside a measure are ordinary; key changes are not
+            for event in &staff.staff_structural_events {
+                if let StaffStructuralEvent::KeySignature(key) = event {
+                    let tick = key.tick.value();
+                    if !self.on_barline(tick) {
+                        self.report(
+                            LintRule::StructuralEventOffBarline,
+                            LintLocation {
+                                measure: self.measure_at(tick),
+                                tick: Some(tick),
+                                ..staff_location
+                            },
+                            format!("Key signature at tick {} is not on a barline", tick),
+                        );
+                    }
+                }
+            }
+
+            for (voice_index, voice) in staff.voices.iter().enumerate() {
+                let location = LintLocation {
+                    voice: Some(voice_index),
+                    ..staff_location
+                };
+                self.voice_order(voice, location);
+                self.measure_fill(voice, location);
+                self.rest_overlaps(voice, location);
+                for note in &voice.interval_events {
+                    let location = LintLocation {
+                        measure: self.measure_at(note.start_tick.value()),
+                        tick: Some(note.start_tick.value()),
+                        ..location
+                    };
+                    self.links(note, notes, location);
+                    if let Some(entry) = catalog {
+                        let written = note.pitch.value();
+                        let in_range = entry
+                            .sounding_pitch(written)
+                            .is_some_and(|p| entry.range.contains(p));
+                        if !in_range {
+                            self.report(
+                                LintRule::NoteOutOfRange,
+                                location,
+                                format!(
+                                    "Pitch {} is outside the range of the {}",
+                                    written,
+                                    entry.name.to_lowercase()
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn sorted(&mut self, ticks: &[u32], location: LintLocation, what: &str) {
+        if let Some(pair) = ticks.windows(2).find(|w| w[1] < w[0]) {
+            self.report(
+                LintRule::UnsortedEvents,
+                LintLocation {
+                    tick: Some(pair[1]),
+                    ..location
+                },
+                format!(
+                    "{} are not in tick order: tick {} follows tick {}",
+                    what, pair[1], pair[0]
+                ),
+            );
+        }
+    }
+
+    /// Grace notes are left out: they sit at notional ticks around their
+    /// principal note
+    fn voice_order(&mut self, voice: &Voice, location: LintLocation) {
+        let notes: Vec<u32> = voice
+            .interval_events
+            .iter()
+            .filter(|n| !n.is_grace)
+            .map(|n| n.start_tick.value())
+            .collect();
+        self.sorted(&notes, location, "Notes");
+        let rests: Vec<u32> = voice
+            .rest_events
+            .iter()
+            .map(|r| r.start_tick.value())
+            .collect();
+        self.sorted(&rests, location, "Rests");
+    }
+
+    /// Under- and over-filled measures, judged from the notes and rests
+    /// (grace notes take no time) that start in each measure
+    fn measure_fill(&mut self, voice: &Voice, location: LintLocation) {
+        let mut spans: Vec<Range<u32>> = voice
+            .interval_events
+            .iter()
+            .filter(|n| !n.is_grace)
+            .map(|n| n.start_tick.value()..n.end_tick().value())
+            .chain(
+                voice
+                    .rest_events
+                    .iter()
+                    .map(|r| r.start_tick.value()..r.start_tick.value() + r.duration_ticks),
+            )
+            .collect();
+        spans.sort_by_key(|s| (s.start, s.end));
+
+        // One walk over the sorted spans: `first..last` are the spans
+        // starting in the current measure, `reach` the furthest end of those
+        // starting before it
+        let mut first = 0;
+        let mut last = 0;
+        let mut reach = 0;
+        for (index, measure) in self.measures.iter().enumerate() {
+            while first < spans.len() && spans[first].start < measure.start {
+                reach = reach.max(spans[first].end);
+                first += 1;
+            }
+            last = last.max(first);
+            while last < spans.len() && spans[last].start < measure.end {
+                last += 1;
+            }
+            let in_measure = &spans[first..last];
+            if in_measure.is_empty() {
+                continue;
+            }
+            let location = LintLocation {
+                measure: Some(index),
+                ..location
+            };
+
+            if let Some(over) = in_measure.iter().find(|s| s.end > measure.end) {
+                self.report(
+                    LintRule::OverfilledMeasure,
+                    LintLocation {
+                        tick: Some(over.start),
+                        ..location
+                    },
+                    format!(
+                        "Event at tick {} ends at tick {}, after the measure ends at {}",
+                        over.start, over.end, measure.end
+                    ),
+                );
+            }
+
+            // First gap left by the events starting in this measure (events
+            // tied over from the previous measure count as well)
+            let mut covered = measure.start.max(reach);
+            for span in in_measure {
+                if span.start > covered || covered >= measure.end {
+                    break;
+                }
+                covered = covered.max(span.end);
+            }
+            if covered < measure.end {
+                let gap_end = in_measure
+                    .iter()
+                    .map(|s| s.start)
+                    .find(|&start| start > covered)
+                    .unwrap_or(measure.end);
+                self.report(
+                    LintRule::UnderfilledMeasure,
+                    LintLocation {
+                        tick: Some(covered),
+                        ..location
+                    },
+                    format!("Nothing is written between tick {covered} and tick {gap_end}"),
+                );
+            }
+        }
+    }
+
+    /// Rests must not overlap notes or other rests of their voice. The rests
+    /// are walked in time order against the notes sorted by start, so each
+    /// voice takes one pass.
+    fn rest_overlaps(&mut self, voice: &Voice, location: LintLocation) {
+        let mut notes: Vec<Range<u32>> = voice
+            .interval_events
+            .iter()
+            .filter(|n| !n.is_grace)
+            .map(|n| n.start_tick.value()..n.end_tick().value())
+            .collect();
+        notes.sort_by_key(|n| n.start);
+        let mut rests: Vec<Range<u32>> = voice
+            .rest_events
+            .iter()
+            .map(|r| r.start_tick.value()..r.start_tick.value() + r.duration_ticks)
+            .collect();
+        rests.sort_by_key(|r| r.start);
+
+        // Notes before `next` start before the current rest; `longest` is
+        // the one of them that ends last
+        let mut next = 0;
+        let mut longest: Option<&Range<u32>> = None;
+        for (i, rest) in rests.iter().enumerate() {
+            while next < notes.len() && notes[next].start < rest.start {
+                if !longest.is_some_and(|l| l.end >= notes[next].end) {
+                    longest = Some(&notes[next]);
+                }
+                next += 1;
+            }
+            let note = longest
+                .filter(|l| l.end > rest.start)
+                .or_else(|| notes.get(next).filter(|n| n.start < rest.end));
+            // A later rest overlaps this one only if the next one does
+            let other_rest = rests.get(i + 1).filter(|r| r.start < rest.end);
+            let location = LintLocation {
+                measure: self.measure_at(rest.start),
+                tick: Some(rest.start),
+                ..location
+            };
+            if let Some(note) = note {
+                self.report(
+                    LintRule::OverlappingEvents,
+                    location,
+                    format!(
+                        "Rest at tick {} overlaps a note at tick {}",
+                        rest.start, note.start
+                    ),
+                );
+            }
+            if let Some(other) = other_rest {
+                self.report(
+                    LintRule::OverlappingEvents,
+                    location,
+                    format!(
+                        "Rest at tick {} overlaps another rest at tick {}",
+                        rest.start, other.start
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Ties and slurs must point at notes that exist; ties join equal pitches
+    fn links(&mut self, note: &Note, notes: &HashMap<NoteId, &Note>, location: LintLocation) {
+        let tick = note.start_tick.value();
+        if let Some(next) = note.tie_next {
+            match notes.get(&next) {
+                None => self.report(
+                    LintRule::DanglingTie,
+                    location,
+                    format!("Tie from the note at tick {} leads to no note", tick),
+                ),
+                Some(target) if target.pitch != note.pitch => self.report(
+                    LintRule::TiePitchMismatch,
+                    location,
+                    format!(
+                        "Tie joins pitch {} at tick {} to pitch {} at tick {}",
+                        note.pitch.value(),
+                        tick,
+                        target.pitch.value(),
+                        target.start_tick.value()
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+        if let Some(next) = note.slur_next {
+            if !notes.contains_key(&next) {
+                self.report(
+                    LintRule::DanglingSlur,
+                    location,
+                    format!("Slur from the note at tick {} leads to no note", tick),
+                );
+            }
+        }
+    }
+}
@@ -9,8 +9,26 @@ pub mod voice;
 // Score module
 pub mod score;
 
+// Built-in instrument catalog (ranges, clefs, transpositions, GM programs)
+pub mod instrument_catalog;
+
+// Score validation (structured lint findings)
+pub mod lint;
+
+// Part groups (brackets and braces across instruments)
+pub mod part_group;
+
+// Rest filling for voices built note by note
+pub mod rest_filling;
+
 // Repeat barlines module (Feature 041)
 pub mod repeat;
 
+// Tablature tunings and fret assignment
+pub mod tablature;
+
+// Tick ↔ seconds conversion
+pub mod tempo_map;
+
 // Importers module (Feature 006)
 pub mod importers;
@@ -0,0 +1,79 @@
+// Part group domain types - brackets and braces joining instruments
+
+use serde::{Deserialize, Serialize};
+
+/// Symbol drawn at the left of a part group's staves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroupSymbol {
+    /// Thick bracket with curved ends (choir, string section)
+    Bracket,
+    /// Curly brace (piano or harp written as separate parts)
+    Brace,
+    /// Thin bracket with square ends (sub-groups such as divisi strings)
+    Square,
+    /// Plain vertical line
+    Line,
+    /// No symbol; the group only joins barlines
+    None,
+}
+
+/// A group of adjacent instruments joined by a bracket and, optionally,
+/// by barlines running through all of their staves
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartGroup {
+    /// First instrument of the group (index into `Score::instruments`)
+    pub first_instrument: usize,
+    /// Last instrument of the group (inclusive)
+    pub last_instrument: usize,
+    pub symbol: GroupSymbol,
+    /// Barlines run unbroken from the group's top staff to its bottom staff
+    #[serde(default)]
+    pub barline: bool,
+    /// Group name, e.g. "Strings"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl PartGroup {
+    /// Create a group of instruments `first..=last` without joined barlines
+    pub fn new(first_instrument: usize, last_instrument: usize, symbol: GroupSymbol) -> Self {
+        Self {
+            first_instrument,
+            last_instrument,
+            symbol,
+            barline: false,
+            name: None,
+        }
+    }
+
+    /// Join barlines through the group's staves
+    pub fn with_barline(mut self, barline: bool) -> Self {
+        self.barline = barline;
+        self
+    }
+
+    /// Name the group
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Whether the group includes the instrument at `index`
+    pub fn contains(&self, index: usize) -> bool {
+        (self.first_instrument..=self.last_instrument).contains(&index)
+    }
+
+    /// Whether `other` lies entirely within this group
+    pub fn encloses(&self, other: &PartGroup) -> bool {
+        self.first_instrument <= other.first_instrument
+            && other.last_instrument <= self.last_instrument
+    }
+
+    /// Whether the two groups share instruments without one enclosing the
+    /// other; such groups cannot be drawn as nested brackets
+    pub fn crosses(&self, other: &PartGroup) -> bool {
+        let overlap = self.first_instrument <= other.last_instrument
+            && other.first_instrument <= self.last_instrument;
+        overlap && !self.encloses(other) && !other.encloses(self)
+    }
+}
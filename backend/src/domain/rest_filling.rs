@@ -0,0 +1,290 @@
+//! Rest filling: completes voices with rests wherever they are silent
+//!
+//! Rests are only read from MusicXML; scores built note by note have gaps
+//! instead. Filling splits each gap at the barlines, then into rests that
+//! follow the beats of the active time signature: a rest never starts off
+//! its own grid and sub-beat rests stay within their beat. A rest spanning
+//! several beats only occurs in meters whose measure it divides: no half
+//! rest fills two beats of 3/4 or four eighths of 5/8. Simple meters use
+//! undotted rests; compound meters add dotted ones (an empty 6/8 beat is a
+//! dotted quarter rest), and there no rest spans more than one beat.
+//! An empty measure of a staff's first voice becomes a single
+//! whole-measure rest; other voices are only completed in measures where
+//! they have notes.
+
+use std::ops::Range;
+
+use crate::domain::{events::rest::RestEvent, score::Score, value_objects::Tick, voice::Voice};
+
+/// Ticks of a whole note at 960 PPQ
+const WHOLE_TICKS: u32 = 3840;
+
+/// Undotted rest values from longest to shortest, with their MusicXML types
+const REST_VALUES: [(u32, &str); 7] = [
+    (3840, "whole"),
+    (1920, "half"),
+    (960, "quarter"),
+    (480, "eighth"),
+    (240, "16th"),
+    (120, "32nd"),
+    (60, "64th"),
+];
+
+/// A measure and the time signature it is in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeasureMeter {
+    pub range: Range<u32>,
+    pub numerator: u8,
+    pub denominator: u8,
+    /// Metrical position of the measure's first tick; non-zero for a pickup,
+    /// whose beats are counted back from its end
+    pub offset: u32,
+}
+
+impl MeasureMeter {
+    /// Length of a complete measure in this time signature
+    fn full_ticks(&self) -> u32 {
+        WHOLE_TICKS * u32::from(self.numerator) / u32::from(self.denominator.max(1))
+    }
+
+    /// 6/8, 9/8, 12/8 and the like: beats of three beat units
+    fn is_compound(&self) -> bool {
+        self.numerator > 3 && self.numerator % 3 == 0
+    }
+
+    fn beat_ticks(&self) -> u32 {
+        let unit = WHOLE_TICKS / u32::from(self.denominator.max(1));
+        if self.is_compound() { 3 * unit } else { unit }
+    }
+
+    /// Rest values gaps in this measure split into, longest first, as
+    /// `(duration, note type, grid)`: a rest starts on a multiple of its
+    /// grid within the beat. Compound meters add the dotted values up to a
+    /// beat, which start where their undotted double could.
+    fn rest_values(&self) -> Vec<(u32, &'static str, u32)> {
+        let mut values: Vec<_> = REST_VALUES
+            .iter()
+            .map(|&(duration, note_type)| (duration, note_type, duration))
+            .collect();
+        if self.is_compound() {
+            values.extend(
+                REST_VALUES
+                    .iter()
+                    .map(|&(duration, note_type)| (duration * 3 / 2, note_type, duration * 2))
+                    .filter(|&(dotted, _, _)| dotted <= self.beat_ticks()),
+            );
+            values.sort_by_key(|&(duration, _, _)| std::cmp::Reverse(duration));
+        }
+        values
+    }
+}
+
+/// Measures of `score` up to its last note or rest, with their meters
+pub fn measure_meters(score: &Score) -> Vec<MeasureMeter> {
+    score
+        .measure_ranges(score.end_tick())
+        .into_iter()
+        .enumerate()
+        .map(|(index, range)| {
+            let (numerator, denominator) = score
+                .get_time_signature_at(Tick::new(range.start))
+                .map_or((4, 4), |ts| (ts.numerator, ts.denominator));
+            let mut measure = MeasureMeter {
+                range,
+                numerator,
+                denominator,
+                offset: 0,
+            };
+            let len = measure.range.end - measure.range.start;
+            if index == 0 && len < measure.full_ticks() {
+                measure.offset = measure.full_ticks() - len;
+            }
+            measure
+        })
+        .collect()
+}
+
+/// Rests filling `gap`, which lies within `measure`, as
+/// `(start tick, duration, note type)`; a dotted rest has the type of its
+/// undotted value
+///
+/// A stretch that no rest value fits (e.g. left over by a tuplet) becomes
+/// one rest up to the next beat, without a note type.
+pub fn split_gap(measure: &MeasureMeter, gap: Range<u32>) -> Vec<(u32, u32, Option<&'static str>)> {
+    let beat = measure.beat_ticks();
+    let values = measure.rest_values();
+    let mut rests = Vec::new();
+    let mut start = gap.start;
+    while start < gap.end {
+        let remaining = gap.end - start;
+        let position = start - measure.range.start + measure.offset;
+        let in_beat = position % beat;
+        let fits = |duration: u32, grid: u32| {
+            if duration <= beat {
+                in_beat % grid == 0 && in_beat + duration <= beat
+            } else {
+                !measure.is_compound()
+                    && duration % beat == 0
+                    && measure.full_ticks() % duration == 0
+                    && position % duration == 0
+            }
+        };
+        let rest = values
+            .iter()
+            .find(|&&(duration, _, grid)| duration <= remaining && fits(duration, grid))
+            .map(|&(duration, note_type, _)| (duration, Some(note_type)))
+            .unwrap_or(((beat - in_beat).min(remaining), None));
+        rests.push((start, rest.0, rest.1));
+        start += rest.0;
+    }
+    rests
+}
+
+/// Fill the gaps of `voice` within `measures` with rests
+///
+/// Rests overlapping the voice's notes are stale (left over from before an
+/// edit) and are removed first. With `whole_measures`, measures where the
+/// voice is silent get a whole-measure rest; otherwise they stay empty.
+/// `voice_number` and `staff_number` are 1-indexed; a voice that already
+/// has rests keeps their voice number. Returns the number of rests added.
+pub fn fill_voice(
+    voice: &mut Voice,
+    measures: &[MeasureMeter],
+    staff_number: usize,
+    voice_number: usize,
+    whole_measures: bool,
+) -> usize {
+    let notes: Vec<Range<u32>> = voice
+        .interval_events
+        .iter()
+        .filter(|n| !n.is_grace)
+        .map(|n| n.start_tick.value()..n.end_tick().value())
+        .collect();
+    let voice_number = voice.rest_events.first().map_or(voice_number, |r| r.voice);
+    let overlaps = |a: &Range<u32>, b: &Range<u32>| a.start < b.end && b.start < a.end;
+    voice.rest_events.retain(|r| {
+        let range = r.start_tick.value()..r.start_tick.value() + r.duration_ticks;
+        !notes.iter().any(|n| overlaps(n, &range))
+    });
+
+    let mut occupied: Vec<Range<u32>> = notes
+        .into_iter()
+        .chain(
+            voice
+                .rest_events
+                .iter()
+                .map(|r| r.start_tick.value()..r.start_tick.value() + r.duration_ticks),
+        )
+        .collect();
+    occupied.sort_by_key(|r| r.start);
+
+    let mut added = Vec::new();
+    for measure in measures {
+        let range = &measure.range;
+        let within: Vec<&Range<u32>> = occupied.iter().filter(|o| overlaps(o, range)).collect();
+        if within.is_empty() {
+            if whole_measures {
+                added.push(RestEvent::new_measure_rest(
+                    Tick::new(range.start),
+                    range.end - range.start,
+                    Some("whole".to_string()),
+                    voice_number,
+                    staff_number,
+                ));
+            }
+            continue;
+        }
+
+        let mut gaps = Vec::new();
+        let mut cursor = range.start;
+        for o in within {
+            if o.start > cursor {
+                gaps.push(cursor..o.start);
+            }
+            cursor = cursor.max(o.end);
+        }
+        if cursor < range.end {
+            gaps.push(cursor..range.end);
+        }
+        for gap in gaps {
+            for (start, duration, note_type) in split_gap(measure, gap) {
+                added.push(RestEvent::new(
+                    Tick::new(start),
+                    duration,
+                    note_type.map(str::to_string),
+                    voice_number,
+                    staff_number,
+                ));
+            }
+        }
+    }
+
+    let count = added.len();
+    voice.rest_events.extend(added);
+    voice.rest_events.sort_by_key(|r| r.start_tick);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(numerator: u8, denominator: u8) -> MeasureMeter {
+        let mut measure = MeasureMeter {
+            range: 0..0,
+            numerator,
+            denominator,
+            offset: 0,
+        };
+        measure.range = 0..measure.full_ticks();
+        measure
+    }
+
+    fn durations(measure: &MeasureMeter, gap: Range<u32>) -> Vec<u32> {
+        split_gap(measure, gap).into_iter().map(|r| r.1).collect()
+    }
+
+    #[test]
+    fn test_simple_meter_rests_follow_beats() {
+        let common = measure(4, 4);
+        // No dotted half after beat 1, no half across beat 3
+        assert_eq!(durations(&common, 960..3840), vec![960, 1920]);
+        assert_eq!(durations(&common, 0..2880), vec![1920, 960]);
+        assert_eq!(durations(&common, 480..1920), vec![480, 960]);
+        assert_eq!(durations(&measure(3, 4), 960..2880), vec![960, 960]);
+        assert_eq!(durations(&measure(3, 4), 0..1920), vec![960, 960]);
+    }
+
+    #[test]
+    fn test_irregular_meter_rests_stay_within_beats() {
+        // No half rest over four of the five eighths, no quarter across 2+3
+        let five_eight = measure(5, 8);
+        assert_eq!(durations(&five_eight, 0..1920), vec![480; 4]);
+        assert_eq!(durations(&five_eight, 480..2400), vec![480; 4]);
+        assert_eq!(durations(&measure(7, 8), 960..3360), vec![480; 5]);
+        assert_eq!(durations(&five_eight, 240..480), vec![240]);
+    }
+
+    #[test]
+    fn test_compound_meter_rests_stay_within_beats() {
+        let six_eight = measure(6, 8);
+        // A dotted quarter per empty beat
+        assert_eq!(
+            split_gap(&six_eight, 0..2880),
+            vec![(0, 1440, Some("quarter")), (1440, 1440, Some("quarter"))]
+        );
+        assert_eq!(durations(&six_eight, 480..1440), vec![480, 480]);
+        assert_eq!(durations(&six_eight, 960..2880), vec![480, 1440]);
+        // Dotted values within the beat start where their double could
+        assert_eq!(durations(&six_eight, 0..720), vec![720]);
+        assert_eq!(durations(&six_eight, 720..1440), vec![240, 480]);
+        // No dotted half across the beats of 12/8
+        assert_eq!(durations(&measure(12, 8), 0..2880), vec![1440, 1440]);
+    }
+
+    #[test]
+    fn test_off_grid_gap_rests_up_to_the_beat() {
+        // After a triplet eighth
+        assert_eq!(split_gap(&measure(4, 4), 320..960), vec![(320, 640, None)]);
+    }
+}
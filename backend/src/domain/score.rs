@@ -1,14 +1,21 @@
 use crate::domain::{
     errors::DomainError,
     events::{
-        global::GlobalStructuralEvent, tempo::TempoEvent, time_signature::TimeSignatureEvent,
+        direction::RehearsalMark,
+        global::GlobalStructuralEvent,
+        tempo::{FermataHold, TempoEvent},
+        time_signature::TimeSignatureEvent,
     },
     ids::ScoreId,
     instrument::Instrument,
+    part_group::PartGroup,
     repeat::{RepeatBarline, VoltaBracket},
+    rest_filling,
+    tempo_map::TempoMap,
     value_objects::{BPM, Tick},
 };
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
 
 /// Octave-shift region (8va, 8vb, 15ma, etc.) for display transposition
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -46,6 +53,26 @@ pub struct Score {
     /// Octave-shift regions (8va/8vb brackets) per staff
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub octave_shift_regions: Vec<OctaveShiftRegion>,
+    /// Fermata holds (notes or rests held longer than written), for playback timing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fermatas: Vec<FermataHold>,
+    /// Rehearsal marks, sorted by tick
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rehearsal_marks: Vec<RehearsalMark>,
+    /// Measure indices (0-based) that start a new page in paginated layout
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub page_breaks: Vec<u32>,
+    /// Measure indices (0-based) that start a new system; includes page breaks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub system_breaks: Vec<u32>,
+    /// Brackets and braces joining adjacent instruments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub part_groups: Vec<PartGroup>,
+    /// Printed number of each measure by index ("12", "12a"); `None` for
+    /// measures left out of the numbering. Empty = numbered from 1, after
+    /// any pickup measure
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub measure_numbers: Vec<Option<String>>,
 }
 
 impl Score {
@@ -60,6 +87,12 @@ impl Score {
             pickup_ticks: 0,
             measure_end_ticks: Vec::new(),
             octave_shift_regions: Vec::new(),
+            fermatas: Vec::new(),
+            rehearsal_marks: Vec::new(),
+            page_breaks: Vec::new(),
+            system_breaks: Vec::new(),
+            part_groups: Vec::new(),
+            measure_numbers: Vec::new(),
         };
 
         // Add default tempo (120 BPM) at tick 0
@@ -123,6 +156,32 @@ impl Score {
         self.instruments.push(instrument);
     }
 
+    /// Add a part group, checking that it spans existing instruments and
+    /// nests with the groups already present
+    pub fn add_part_group(&mut self, group: PartGroup) -> Result<(), DomainError> {
+        if group.first_instrument > group.last_instrument
+            || group.last_instrument >= self.instruments.len()
+        {
+            return Err(DomainError::ValidationError(format!(
+                "Part group spans instruments {}..={} but the score has {}",
+                group.first_instrument,
+                group.last_instrument,
+                self.instruments.len()
+            )));
+        }
+        if let Some(other) = self.part_groups.iter().find(|g| g.crosses(&group)) {
+            return Err(DomainError::ConstraintViolation(format!(
+                "Part group {}..={} overlaps group {}..={} without nesting",
+                group.first_instrument,
+                group.last_instrument,
+                other.first_instrument,
+                other.last_instrument
+            )));
+        }
+        self.part_groups.push(group);
+        Ok(())
+    }
+
     /// Remove a tempo event at a specific tick
     pub fn remove_tempo_event(&mut self, tick: Tick) -> Result<(), DomainError> {
         if tick == Tick::new(0) {
@@ -196,6 +255,26 @@ impl Score {
             .max_by_key(|te| te.tick)
     }
 
+    /// All tempo events, in tick order
+    pub fn tempo_events(&self) -> Vec<&TempoEvent> {
+        let mut events: Vec<&TempoEvent> = self
+            .global_structural_events
+            .iter()
+            .filter_map(|e| match e {
+                GlobalStructuralEvent::Tempo(te) => Some(te),
+                _ => None,
+            })
+            .collect();
+        events.sort_by_key(|te| te.tick);
+        events
+    }
+
+    /// Tick ↔ seconds conversion for this score's tempo events, ramps and fermatas
+    pub fn tempo_map(&self) -> TempoMap {
+        let events: Vec<TempoEvent> = self.tempo_events().into_iter().cloned().collect();
+        TempoMap::new(&events, &self.fermatas)
+    }
+
     /// Get the active time signature at a specific tick
     pub fn get_time_signature_at(&self, tick: Tick) -> Option<&TimeSignatureEvent> {
         self.global_structural_events
@@ -206,6 +285,72 @@ impl Score {
             })
             .max_by_key(|te| te.tick)
     }
+
+    /// Tick after the last note or rest of any voice
+    pub fn end_tick(&self) -> u32 {
+        self.instruments
+            .iter()
+            .flat_map(|i| &i.staves)
+            .flat_map(|s| &s.voices)
+            .flat_map(|v| {
+                let notes = v.interval_events.iter().map(|n| n.end_tick().value());
+                let rests = v
+                    .rest_events
+                    .iter()
+                    .map(|r| r.start_tick.value() + r.duration_ticks);
+                notes.chain(rests)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Tick range of each measure, covering at least `0..end_tick`
+    ///
+    /// Follows `measure_end_ticks` where the score has them; further measures
+    /// last as long as the time signature active at their start, after the
+    /// pickup measure (if any).
+    pub fn measure_ranges(&self, end_tick: u32) -> Vec<Range<u32>> {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while start < end_tick || ranges.len() < self.measure_end_ticks.len() {
+            let index = ranges.len();
+            let end = if let Some(&end) = self.measure_end_ticks.get(index) {
+                end
+            } else if index == 0 && self.pickup_ticks > 0 {
+                self.pickup_ticks
+            } else {
+                let (numerator, denominator) = self
+                    .get_time_signature_at(Tick::new(start))
+                    .map_or((4, 4), |ts| (ts.numerator, ts.denominator));
+                start + 3840 * u32::from(numerator) / u32::from(denominator.max(1))
+            };
+            if end <= start {
+                break;
+            }
+            ranges.push(start..end);
+            start = end;
+        }
+        ranges
+    }
+
+    /// Fill every voice's gaps with rests split along the beats
+    ///
+    /// Every staff's first voice is completed up to the end of the score,
+    /// with whole-measure rests for empty measures; other voices only in
+    /// measures where they have notes. Stale rests under notes are removed.
+    /// Returns the number of rests added.
+    pub fn fill_rests(&mut self) -> usize {
+        let measures = rest_filling::measure_meters(self);
+        let mut added = 0;
+        for instrument in &mut self.instruments {
+            for (s, staff) in instrument.staves.iter_mut().enumerate() {
+                for (v, voice) in staff.voices.iter_mut().enumerate() {
+                    added += rest_filling::fill_voice(voice, &measures, s + 1, v + 1, v == 0);
+                }
+            }
+        }
+        added
+    }
 }
 
 impl Default for Score {
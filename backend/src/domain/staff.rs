@@ -1,18 +1,59 @@
 use crate::domain::{
     errors::DomainError,
-    events::{clef::ClefEvent, key_signature::KeySignatureEvent, staff::StaffStructuralEvent},
+    events::{
+        chord_symbol::ChordSymbolEvent,
+        clef::ClefEvent,
+        direction::TextDirection,
+        key_signature::KeySignatureEvent,
+        pedal::{PedalKind, PedalRegion},
+        staff::StaffStructuralEvent,
+    },
     ids::{StaffId, VoiceId},
+    tablature::Tablature,
     value_objects::{Clef, KeySignature, Tick},
     voice::Voice,
 };
 use serde::{Deserialize, Serialize};
 
+/// Line count of a regular staff
+pub const DEFAULT_STAFF_LINES: u8 = 5;
+
+/// Most lines a staff can have (8-string guitar tablature)
+pub const MAX_STAFF_LINES: u8 = 8;
+
+fn default_staff_lines() -> u8 {
+    DEFAULT_STAFF_LINES
+}
+
+fn is_default_staff_lines(lines: &u8) -> bool {
+    *lines == DEFAULT_STAFF_LINES
+}
+
 /// Staff contains voices and staff-scoped structural events
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Staff {
     pub id: StaffId,
     pub staff_structural_events: Vec<StaffStructuralEvent>,
     pub voices: Vec<Voice>,
+    /// Chord symbols (lead-sheet harmony) anchored above this staff, sorted by tick
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chord_symbols: Vec<ChordSymbolEvent>,
+    /// Piano pedal regions (sustain, sostenuto, una corda), sorted by start tick
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pedal_regions: Vec<PedalRegion>,
+    /// Text directions ("dolce", "pizz.") anchored to this staff, sorted by tick
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub text_directions: Vec<TextDirection>,
+    /// Number of staff lines: 5 for regular staves, 1 for rhythm and
+    /// percussion staves, 6 for guitar tablature
+    #[serde(
+        default = "default_staff_lines",
+        skip_serializing_if = "is_default_staff_lines"
+    )]
+    pub staff_lines: u8,
+    /// String tuning of a tablature staff; `None` for staff notation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tablature: Option<Tablature>,
 }
 
 impl Staff {
@@ -22,6 +63,11 @@ impl Staff {
             id: StaffId::new(),
             staff_structural_events: Vec::new(),
             voices: Vec::new(),
+            chord_symbols: Vec::new(),
+            pedal_regions: Vec::new(),
+            text_directions: Vec::new(),
+            staff_lines: DEFAULT_STAFF_LINES,
+            tablature: None,
         };
 
         // Add default clef (Treble) at tick 0
@@ -42,6 +88,24 @@ impl Staff {
         staff
     }
 
+    /// Set the number of staff lines (1 to `MAX_STAFF_LINES`)
+    pub fn set_staff_lines(&mut self, lines: u8) -> Result<(), DomainError> {
+        if !(1..=MAX_STAFF_LINES).contains(&lines) {
+            return Err(DomainError::ValidationError(format!(
+                "Staff must have 1 to {} lines, got {}",
+                MAX_STAFF_LINES, lines
+            )));
+        }
+        self.staff_lines = lines;
+        Ok(())
+    }
+
+    /// Make this a tablature staff with one line per string
+    pub fn set_tablature(&mut self, tablature: Tablature) {
+        self.staff_lines = tablature.string_count();
+        self.tablature = Some(tablature);
+    }
+
     /// Add a clef event with duplicate tick validation
     pub fn add_clef_event(&mut self, event: ClefEvent) -> Result<(), DomainError> {
         // Check for duplicate clef event at the same tick
@@ -80,6 +144,43 @@ impl Staff {
         Ok(())
     }
 
+    /// Add a chord symbol, keeping chord symbols sorted by tick.
+    /// A chord symbol already at the same tick is replaced.
+    pub fn add_chord_symbol(&mut self, event: ChordSymbolEvent) {
+        match self
+            .chord_symbols
+            .binary_search_by_key(&event.tick, |cs| cs.tick)
+        {
+            Ok(idx) => self.chord_symbols[idx] = event,
+            Err(idx) => self.chord_symbols.insert(idx, event),
+        }
+    }
+
+    /// Add a pedal region, keeping regions sorted by start tick
+    pub fn add_pedal_region(&mut self, region: PedalRegion) {
+        let idx = self
+            .pedal_regions
+            .partition_point(|r| r.start_tick <= region.start_tick);
+        self.pedal_regions.insert(idx, region);
+    }
+
+    /// Add a text direction, keeping directions sorted by tick
+    /// (directions at the same tick keep their insertion order)
+    pub fn add_text_direction(&mut self, direction: TextDirection) {
+        let idx = self
+            .text_directions
+            .partition_point(|d| d.tick <= direction.tick);
+        self.text_directions.insert(idx, direction);
+    }
+
+    /// Whether a pedal of the given kind is held down at `tick`.
+    /// A pedal change at `tick` counts as down (it is re-pressed immediately).
+    pub fn is_pedal_down_at(&self, kind: PedalKind, tick: Tick) -> bool {
+        self.pedal_regions
+            .iter()
+            .any(|r| r.kind == kind && r.contains(tick.value()))
+    }
+
     /// Add an additional voice to the staff
     pub fn add_voice(&mut self, voice: Voice) {
         self.voices.push(voice);
@@ -0,0 +1,298 @@
+// Tablature domain types - string tunings and automatic fret assignment
+
+use crate::domain::{
+    errors::DomainError, events::note::TabPosition, staff::MAX_STAFF_LINES, value_objects::Pitch,
+};
+use serde::{Deserialize, Serialize};
+
+/// Highest fret used when choosing positions
+pub const MAX_FRET: u8 = 24;
+/// Widest fret span (lowest to highest fretted note) playable without a stretch
+const HAND_SPAN: u8 = 3;
+/// Cost of leaving a note without a position (no free string can play it)
+const UNPLAYABLE_COST: f32 = 1000.0;
+/// Cost per fret of a stretch beyond `HAND_SPAN`
+const STRETCH_COST: f32 = 50.0;
+
+/// What is known of a note's position before `Tablature::assign_positions`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabRequest {
+    /// Any free string that can play the note
+    Free,
+    /// Written in the score
+    Written(TabPosition),
+    /// Continues a tie from note `note` of the earlier chord `chord`, on the
+    /// same string and fret
+    Tied { chord: usize, note: usize },
+}
+
+/// Open-string tuning of a tablature staff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tablature {
+    /// Open-string pitches, string 1 (highest-pitched, top line) first
+    pub strings: Vec<Pitch>,
+}
+
+impl Tablature {
+    /// Create a tuning from open-string pitches, string 1 first
+    pub fn new(strings: Vec<Pitch>) -> Result<Self, DomainError> {
+        if strings.is_empty() || strings.len() > MAX_STAFF_LINES as usize {
+            return Err(DomainError::ValidationError(format!(
+                "Tablature must have 1 to {} strings, got {}",
+                MAX_STAFF_LINES,
+                strings.len()
+            )));
+        }
+        Ok(Self { strings })
+    }
+
+    /// Six-string guitar in standard tuning (E4 B3 G3 D3 A2 E2)
+    pub fn standard_guitar() -> Self {
+        Self::from_midi(&[64, 59, 55, 50, 45, 40])
+    }
+
+    /// Four-string bass in standard tuning (G2 D2 A1 E1)
+    pub fn standard_bass() -> Self {
+        Self::from_midi(&[43, 38, 33, 28])
+    }
+
+    fn from_midi(pitches: &[u8]) -> Self {
+        Self {
+            strings: pitches.iter().map(|&p| Pitch::new(p).unwrap()).collect(),
+        }
+    }
+
+    /// Number of strings (and staff lines)
+    pub fn string_count(&self) -> u8 {
+        self.strings.len() as u8
+    }
+
+    /// Fret that plays `pitch` on `string` (1-based), if within `MAX_FRET`
+    pub fn fret_for(&self, string: u8, pitch: u8) -> Option<u8> {
+        let open = self
+            .strings
+            .get(usize::from(string).checked_sub(1)?)?
+            .value();
+        pitch.checked_sub(open).filter(|&fret| fret <= MAX_FRET)
+    }
+
+    /// Choose string and fret for every note of a passage.
+    ///
+    /// `chords` holds the pitches sounding together, in time order, each with
+    /// what is known of its position. Written positions are kept and tie
+    /// continuations take the position assigned to the note they continue;
+    /// the other notes of each chord go to distinct free strings so that the
+    /// fretted notes fit under the hand and lie close to the previous chord's
+    /// hand position, preferring open strings and low positions. A note no
+    /// free string can play gets `None`.
+    pub fn assign_positions(
+        &self,
+        chords: &[Vec<(u8, TabRequest)>],
+    ) -> Vec<Vec<Option<TabPosition>>> {
+        let mut hand: Option<f32> = None;
+        let mut assigned: Vec<Vec<Option<TabPosition>>> = Vec::with_capacity(chords.len());
+        for chord in chords {
+            let fixed: Vec<(u8, Option<TabPosition>)> = chord
+                .iter()
+                .map(|&(pitch, request)| {
+                    let position = match request {
+                        TabRequest::Free => None,
+                        TabRequest::Written(position) => Some(position),
+                        TabRequest::Tied { chord, note } => assigned
+                            .get(chord)
+                            .and_then(|positions| positions.get(note).copied().flatten()),
+                    };
+                    (pitch, position)
+                })
+                .collect();
+            let positions = self.assign_chord(&fixed, hand);
+            let fretted: Vec<f32> = positions
+                .iter()
+                .flatten()
+                .filter(|p| p.fret > 0)
+                .map(|p| f32::from(p.fret))
+                .collect();
+            if !fretted.is_empty() {
+                hand = Some(fretted.iter().sum::<f32>() / fretted.len() as f32);
+            }
+            assigned.push(positions);
+        }
+        assigned
+    }
+
+    fn assign_chord(
+        &self,
+        chord: &[(u8, Option<TabPosition>)],
+        hand: Option<f32>,
+    ) -> Vec<Option<TabPosition>> {
+        let mut current: Vec<Option<TabPosition>> = chord.iter().map(|(_, tab)| *tab).collect();
+        let open: Vec<usize> = (0..chord.len()).filter(|&i| chord[i].1.is_none()).collect();
+        if open.is_empty() {
+            return current;
+        }
+        let mut search = ChordSearch {
+            tablature: self,
+            pitches: chord.iter().map(|(pitch, _)| *pitch).collect(),
+            open,
+            hand,
+            best: None,
+        };
+        search.run(0, &mut current, 0.0);
+        search.best.map(|(_, best)| best).unwrap_or(current)
+    }
+}
+
+/// Branch-and-bound search for the cheapest positions of one chord
+struct ChordSearch<'a> {
+    tablature: &'a Tablature,
+    pitches: Vec<u8>,
+    /// Indices of the notes without a written position
+    open: Vec<usize>,
+    hand: Option<f32>,
+    best: Option<(f32, Vec<Option<TabPosition>>)>,
+}
+
+impl ChordSearch<'_> {
+    fn run(&mut self, depth: usize, current: &mut Vec<Option<TabPosition>>, cost: f32) {
+        if self.best.as_ref().is_some_and(|(best, _)| cost >= *best) {
+            return;
+        }
+        if depth == self.open.len() {
+            let total = cost + self.span_cost(current);
+            if !self.best.as_ref().is_some_and(|(best, _)| *best <= total) {
+                self.best = Some((total, current.clone()));
+            }
+            return;
+        }
+
+        let note = self.open[depth];
+        let pitch = self.pitches[note];
+        let mut playable = false;
+        for string in 1..=self.tablature.string_count() {
+            if current.iter().flatten().any(|p| p.string == string) {
+                continue;
+            }
+            let Some(fret) = self.tablature.fret_for(string, pitch) else {
+                continue;
+            };
+            playable = true;
+            current[note] = Some(TabPosition { string, fret });
+            self.run(depth + 1, current, cost + self.fret_cost(fret));
+            current[note] = None;
+        }
+        if !playable {
+            self.run(depth + 1, current, cost + UNPLAYABLE_COST);
+        }
+    }
+
+    /// Distance of a fretted note from the hand; open strings are free
+    fn fret_cost(&self, fret: u8) -> f32 {
+        if fret == 0 {
+            return 0.0;
+        }
+        let fret = f32::from(fret);
+        match self.hand {
+            Some(hand) => (fret - hand).abs(),
+            // No hand position yet: prefer low positions
+            None => fret * 0.5,
+        }
+    }
+
+    /// Penalty for fretted notes spread wider than the hand
+    fn span_cost(&self, positions: &[Option<TabPosition>]) -> f32 {
+        let frets = positions
+            .iter()
+            .flatten()
+            .map(|p| p.fret)
+            .filter(|&f| f > 0);
+        let (Some(low), Some(high)) = (frets.clone().min(), frets.max()) else {
+            return 0.0;
+        };
+        f32::from((high - low).saturating_sub(HAND_SPAN)) * STRETCH_COST
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(string: u8, fret: u8) -> Option<TabPosition> {
+        Some(TabPosition { string, fret })
+    }
+
+    #[test]
+    fn test_fret_for() {
+        let guitar = Tablature::standard_guitar();
+        assert_eq!(guitar.fret_for(1, 64), Some(0));
+        assert_eq!(guitar.fret_for(6, 43), Some(3));
+        assert_eq!(guitar.fret_for(6, 39), None); // below the open string
+        assert_eq!(guitar.fret_for(1, 64 + MAX_FRET + 1), None);
+        assert_eq!(guitar.fret_for(7, 64), None);
+    }
+
+    #[test]
+    fn test_new_rejects_string_counts() {
+        assert!(Tablature::new(vec![]).is_err());
+        assert!(Tablature::new(vec![Pitch::new(40).unwrap(); 9]).is_err());
+        assert_eq!(
+            Tablature::new(vec![Pitch::new(40).unwrap(); 7])
+                .unwrap()
+                .string_count(),
+            7
+        );
+    }
+
+    #[test]
+    fn test_assign_open_chord() {
+        // C major in first position: C3 E3 G3 C4 E4
+        let chord = [48, 52, 55, 60, 64].map(|pitch| (pitch, TabRequest::Free));
+        let positions = Tablature::standard_guitar().assign_positions(&[chord.to_vec()]);
+        assert_eq!(
+            positions[0],
+            vec![tab(5, 3), tab(4, 2), tab(3, 0), tab(2, 1), tab(1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_assign_keeps_written_positions_and_follows_hand() {
+        let guitar = Tablature::standard_guitar();
+        // B4 written at the 12th fret; G4 is then played up there too,
+        // rather than at the 3rd fret of string 1
+        let written = TabRequest::Written(TabPosition {
+            string: 2,
+            fret: 12,
+        });
+        let chords = vec![vec![(71, written)], vec![(67, TabRequest::Free)]];
+        let positions = guitar.assign_positions(&chords);
+        assert_eq!(positions[0], vec![tab(2, 12)]);
+        assert_eq!(positions[1], vec![tab(3, 12)]);
+
+        let alone = guitar.assign_positions(&[vec![(67, TabRequest::Free)]]);
+        assert_eq!(alone[0], vec![tab(1, 3)]);
+    }
+
+    #[test]
+    fn test_assign_unplayable_note() {
+        // Two notes only the low E string can play
+        let chord = vec![(40, TabRequest::Free), (41, TabRequest::Free)];
+        let positions = Tablature::standard_guitar().assign_positions(&[chord]);
+        assert_eq!(positions[0].iter().flatten().count(), 1);
+    }
+
+    #[test]
+    fn test_assign_tie_chain_holds_its_string() {
+        // A4 (string 1, 5th fret) tied on twice; the B4 sounding with the
+        // end of the chain moves off string 1 instead of sharing it
+        let chords = vec![
+            vec![(69, TabRequest::Free)],
+            vec![(69, TabRequest::Tied { chord: 0, note: 0 })],
+            vec![
+                (69, TabRequest::Tied { chord: 1, note: 0 }),
+                (71, TabRequest::Free),
+            ],
+        ];
+        let positions = Tablature::standard_guitar().assign_positions(&chords);
+        assert_eq!(positions[1], vec![tab(1, 5)]);
+        assert_eq!(positions[2], vec![tab(1, 5), tab(2, 12)]);
+    }
+}
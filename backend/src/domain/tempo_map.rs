@@ -0,0 +1,283 @@
+// Tempo map domain service - converts between score ticks and seconds
+
+use crate::domain::events::tempo::{FermataHold, TempoEvent};
+
+/// Ticks per quarter note
+const PPQ: f64 = 960.0;
+/// Tempo used before the first tempo event, in quarter notes per minute
+const DEFAULT_QPM: f64 = 120.0;
+/// Relative tempo difference below which a segment is treated as constant
+const CONSTANT_EPSILON: f64 = 1e-9;
+
+/// A stretch of the score with a constant or linearly changing tempo
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    start_tick: u32,
+    /// Exclusive end; `u32::MAX` for the final, open-ended segment
+    end_tick: u32,
+    /// Tempo at `start_tick` and (approaching) `end_tick`, in quarter notes per minute
+    start_qpm: f64,
+    end_qpm: f64,
+    /// Time stretch from fermata holds (1.0 = as written)
+    stretch: f64,
+    /// Seconds elapsed at `start_tick`
+    start_seconds: f64,
+}
+
+impl Segment {
+    /// Tempo slope in quarter notes per minute per tick (0 for constant segments)
+    fn slope(&self) -> f64 {
+        if self.end_tick == u32::MAX
+            || (self.end_qpm - self.start_qpm).abs() < CONSTANT_EPSILON * self.start_qpm
+        {
+            0.0
+        } else {
+            (self.end_qpm - self.start_qpm) / f64::from(self.end_tick - self.start_tick)
+        }
+    }
+
+    fn qpm_at(&self, tick: f64) -> f64 {
+        self.start_qpm + self.slope() * (tick - f64::from(self.start_tick))
+    }
+
+    /// Seconds from `start_tick` to `tick` (fractional ticks allowed)
+    fn seconds_to(&self, tick: f64) -> f64 {
+        let ticks = tick - f64::from(self.start_tick);
+        let k = self.slope();
+        let seconds = if k == 0.0 {
+            ticks / PPQ * 60.0 / self.start_qpm
+        } else {
+            // ∫ 60 / (PPQ · (q0 + k·t)) dt = 60 / (PPQ · k) · ln(q(t) / q0)
+            60.0 / (PPQ * k) * (self.qpm_at(tick) / self.start_qpm).ln()
+        };
+        seconds * self.stretch
+    }
+
+    /// Fractional tick reached `seconds` after `start_tick`
+    fn tick_after(&self, seconds: f64) -> f64 {
+        let seconds = seconds / self.stretch;
+        let k = self.slope();
+        let ticks = if k == 0.0 {
+            seconds * PPQ * self.start_qpm / 60.0
+        } else {
+            let qpm = self.start_qpm * (seconds * PPQ * k / 60.0).exp();
+            (qpm - self.start_qpm) / k
+        };
+        f64::from(self.start_tick) + ticks
+    }
+}
+
+/// Exact tick ↔ seconds conversion for a score's tempo.
+///
+/// Built from all tempo events (fractional BPM in any beat unit), linear
+/// accelerando/ritardando ramps, and fermata holds. The score is split into
+/// segments of constant or linearly changing tempo with precomputed start
+/// times, so both directions of the conversion are a binary search plus a
+/// closed-form integral: O(log n).
+///
+/// Fermata holds stretch time over the held note or rest, so the mapping
+/// stays continuous and strictly increasing in both directions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TempoMap {
+    segments: Vec<Segment>,
+}
+
+impl TempoMap {
+    /// Build the tempo map from tempo events (in any order) and fermata holds.
+    ///
+    /// Before the first event the first event's tempo applies (120 quarter
+    /// BPM if there are no events). A ramp changes the tempo linearly until
+    /// its end tick (or the next tempo event, if earlier); the reached tempo
+    /// then holds until the next event.
+    pub fn new(tempo_events: &[TempoEvent], fermatas: &[FermataHold]) -> Self {
+        let mut events: Vec<&TempoEvent> = tempo_events.iter().collect();
+        events.sort_by_key(|e| e.tick);
+
+        let mut breakpoints: Vec<u32> = vec![0];
+        for event in &events {
+            breakpoints.push(event.tick.value());
+            if let Some(ramp) = &event.ramp {
+                breakpoints.push(ramp.end_tick);
+            }
+        }
+        for hold in fermatas.iter().filter(|h| h.duration_ticks > 0) {
+            breakpoints.push(hold.tick);
+            breakpoints.push(hold.tick.saturating_add(hold.duration_ticks));
+        }
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        // Tempo of `event` (the one active at a segment start), evaluated at `tick`
+        let qpm_of = |event: Option<&TempoEvent>, tick: u32| -> f64 {
+            let Some(event) = event else {
+                return events.first().map_or(DEFAULT_QPM, |e| e.quarter_bpm());
+            };
+            let qpm = event.quarter_bpm();
+            match &event.ramp {
+                Some(ramp) if ramp.end_tick > event.tick.value() => {
+                    let end_qpm =
+                        f64::from(ramp.end_bpm_milli) / 1000.0 * f64::from(event.beat_ticks) / PPQ;
+                    let span = f64::from(ramp.end_tick - event.tick.value());
+                    let progress =
+                        (f64::from(tick.min(ramp.end_tick) - event.tick.value()) / span).min(1.0);
+                    qpm + (end_qpm - qpm) * progress
+                }
+                _ => qpm,
+            }
+        };
+
+        let mut holds: Vec<&FermataHold> =
+            fermatas.iter().filter(|h| h.duration_ticks > 0).collect();
+        holds.sort_by_key(|h| h.tick);
+
+        // One pass over the breakpoints, with cursors into the sorted events
+        // and holds
+        let mut segments: Vec<Segment> = Vec::with_capacity(breakpoints.len());
+        let mut next_event = 0;
+        let mut next_hold = 0;
+        let mut active_holds: Vec<&FermataHold> = Vec::new();
+        for (i, &start) in breakpoints.iter().enumerate() {
+            let end = breakpoints.get(i + 1).copied().unwrap_or(u32::MAX);
+            while next_event < events.len() && events[next_event].tick.value() <= start {
+                next_event += 1;
+            }
+            let event = next_event.checked_sub(1).map(|e| events[e]);
+            let start_qpm = qpm_of(event, start).max(1e-3);
+            let end_qpm = if end == u32::MAX {
+                start_qpm
+            } else {
+                qpm_of(event, end).max(1e-3)
+            };
+            while next_hold < holds.len() && holds[next_hold].tick <= start {
+                active_holds.push(holds[next_hold]);
+                next_hold += 1;
+            }
+            active_holds.retain(|h| start < h.tick.saturating_add(h.duration_ticks));
+            // Overlapping holds (e.g. the same fermata on several staves) count once
+            let extra = active_holds
+                .iter()
+                .map(|h| h.extra_percent)
+                .max()
+                .unwrap_or(0);
+            let mut segment = Segment {
+                start_tick: start,
+                end_tick: end,
+                start_qpm,
+                end_qpm,
+                stretch: 1.0 + f64::from(extra) / 100.0,
+                start_seconds: 0.0,
+            };
+            if let Some(previous) = segments.last() {
+                segment.start_seconds =
+                    previous.start_seconds + previous.seconds_to(f64::from(start));
+            }
+            segments.push(segment);
+        }
+
+        Self { segments }
+    }
+
+    /// Segment containing `tick`
+    fn segment_at_tick(&self, tick: f64) -> &Segment {
+        let index = self
+            .segments
+            .partition_point(|s| f64::from(s.start_tick) <= tick);
+        &self.segments[index.saturating_sub(1)]
+    }
+
+    /// Seconds from the start of the score to `tick`
+    pub fn tick_to_seconds(&self, tick: u32) -> f64 {
+        self.fractional_tick_to_seconds(f64::from(tick))
+    }
+
+    /// Seconds from the start of the score to a fractional tick position
+    pub fn fractional_tick_to_seconds(&self, tick: f64) -> f64 {
+        let tick = tick.max(0.0);
+        let segment = self.segment_at_tick(tick);
+        segment.start_seconds + segment.seconds_to(tick)
+    }
+
+    /// Score position (fractional tick) reached after `seconds`; the exact
+    /// inverse of [`Self::fractional_tick_to_seconds`]
+    pub fn seconds_to_tick(&self, seconds: f64) -> f64 {
+        let seconds = seconds.max(0.0);
+        let index = self
+            .segments
+            .partition_point(|s| s.start_seconds <= seconds);
+        let segment = &self.segments[index.saturating_sub(1)];
+        segment.tick_after(seconds - segment.start_seconds)
+    }
+
+    /// Sounding tempo at `tick` in quarter notes per minute, including ramps
+    /// and fermata holds
+    pub fn quarter_bpm_at(&self, tick: u32) -> f64 {
+        let segment = self.segment_at_tick(f64::from(tick));
+        segment.qpm_at(f64::from(tick)) / segment.stretch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::tempo::TempoRamp;
+    use crate::domain::value_objects::{BPM, Tick};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_constant_and_stepwise_tempo() {
+        let events = vec![
+            TempoEvent::new(Tick::new(0), BPM::new(120).unwrap()),
+            // Dotted quarter = 40 → 60 quarters per minute
+            TempoEvent::with_exact_bpm(Tick::new(3840), 40.0, 1440).unwrap(),
+        ];
+        let map = TempoMap::new(&events, &[]);
+
+        assert_close(map.tick_to_seconds(960), 0.5);
+        assert_close(map.tick_to_seconds(3840), 2.0);
+        assert_close(map.tick_to_seconds(4800), 3.0);
+        assert_close(map.seconds_to_tick(2.5), 4320.0);
+        assert_close(map.quarter_bpm_at(4000), 60.0);
+    }
+
+    #[test]
+    fn test_linear_ritardando_round_trips() {
+        let mut rit = TempoEvent::new(Tick::new(0), BPM::new(120).unwrap());
+        rit.ramp = Some(TempoRamp {
+            end_tick: 3840,
+            end_bpm_milli: 60_000,
+        });
+        let map = TempoMap::new(&[rit], &[]);
+
+        // ∫ 60/(960·q(t)) dt with q falling linearly from 120 to 60 over 3840 ticks
+        let expected = 60.0 / (960.0 * (-60.0 / 3840.0)) * (60.0f64 / 120.0).ln();
+        assert_close(map.tick_to_seconds(3840), expected);
+        // The reached tempo holds after the ramp
+        assert_close(map.tick_to_seconds(4800) - map.tick_to_seconds(3840), 1.0);
+        for tick in [0, 100, 1234, 3839, 3840, 9000] {
+            assert_close(
+                map.seconds_to_tick(map.tick_to_seconds(tick)),
+                f64::from(tick),
+            );
+        }
+        assert_close(map.quarter_bpm_at(1920), 90.0);
+    }
+
+    #[test]
+    fn test_fermata_hold_stretches_time() {
+        let events = vec![TempoEvent::new(Tick::new(0), BPM::new(60).unwrap())];
+        let holds = vec![FermataHold::new(960, 960), FermataHold::new(960, 960)];
+        let map = TempoMap::new(&events, &holds);
+
+        assert_close(map.tick_to_seconds(960), 1.0);
+        // Held for twice its value, counted once
+        assert_close(map.tick_to_seconds(1920), 3.0);
+        assert_close(map.tick_to_seconds(2880), 4.0);
+        assert_close(map.seconds_to_tick(2.0), 1440.0);
+    }
+}
@@ -1,5 +1,10 @@
 use crate::domain::{
-    errors::DomainError, events::note::Note, events::rest::RestEvent, ids::VoiceId,
+    errors::DomainError,
+    events::grace::{NoteTiming, resolve_grace_timings},
+    events::note::Note,
+    events::ornament::{PlayedNote, expand_for_playback},
+    events::rest::RestEvent,
+    ids::VoiceId,
 };
 use serde::{Deserialize, Serialize};
 
@@ -8,7 +13,8 @@ use serde::{Deserialize, Serialize};
 pub struct Voice {
     pub id: VoiceId,
     pub interval_events: Vec<Note>,
-    /// Rest events parsed from MusicXML, ordered by start_tick.
+    /// Rest events parsed from MusicXML or added by `Score::fill_rests`,
+    /// ordered by start_tick.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rest_events: Vec<RestEvent>,
 }
@@ -52,6 +58,18 @@ impl Voice {
             .iter()
             .any(|existing| existing.pitch == note.pitch && existing.overlaps_with(note))
     }
+
+    /// Sounding timing of this voice's grace notes, and of the regular notes
+    /// they take time from (see [`resolve_grace_timings`])
+    pub fn grace_timings(&self) -> Vec<NoteTiming> {
+        resolve_grace_timings(&self.interval_events)
+    }
+
+    /// This voice's notes as played, with tremolos and arpeggios expanded
+    /// (see [`expand_for_playback`])
+    pub fn playback_notes(&self) -> Vec<PlayedNote> {
+        expand_for_playback(&self.interval_events)
+    }
 }
 
 impl Default for Voice {
@@ -7,6 +7,7 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::layout::beams;
+use crate::layout::cross_staff::CrossStaffContext;
 use crate::layout::extraction::{NoteEvent, StaffData};
 use crate::layout::positioner;
 use crate::layout::stems;
@@ -16,6 +17,7 @@ use crate::layout::types::{LedgerLine, TickRange};
 type NoteData = (u8, u32, u32, Option<(char, i8)>, bool, u8, bool);
 
 /// Result of annotation rendering for a single staff.
+#[derive(Default)]
 pub(crate) struct AnnotationResult {
     pub ledger_lines: Vec<LedgerLine>,
     pub notation_dots: Vec<types::NotationDot>,
@@ -38,6 +40,8 @@ pub(crate) fn render_annotations(
     units_per_space: f32,
     note_positions: &HashMap<u32, f32>,
     measure_starts: &[u32],
+    cross_staff: &CrossStaffContext,
+    staff_index: usize,
 ) -> AnnotationResult {
     let ledger_lines = render_ledger_lines(
         staff_data,
@@ -45,6 +49,8 @@ pub(crate) fn render_annotations(
         staff_vertical_offset,
         units_per_space,
         note_positions,
+        cross_staff,
+        staff_index,
     );
 
     let staff_middle_y = staff_vertical_offset + 1.5 * units_per_space;
@@ -54,9 +60,12 @@ pub(crate) fn render_annotations(
         tick_range,
         staff_vertical_offset,
         staff_middle_y,
+        unified_left_margin,
         units_per_space,
         note_positions,
         measure_starts,
+        cross_staff,
+        staff_index,
     );
 
     let (tie_arcs, slur_arcs) = render_ties_and_slurs(
@@ -69,6 +78,8 @@ pub(crate) fn render_annotations(
         unified_left_margin,
         units_per_space,
         note_positions,
+        cross_staff,
+        staff_index,
     );
 
     let fingering_glyphs = render_fingering_glyphs(
@@ -77,6 +88,8 @@ pub(crate) fn render_annotations(
         staff_vertical_offset,
         units_per_space,
         note_positions,
+        cross_staff,
+        staff_index,
     );
 
     AnnotationResult {
@@ -88,32 +101,59 @@ pub(crate) fn render_annotations(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_ledger_lines(
     staff_data: &StaffData,
     tick_range: &TickRange,
     staff_vertical_offset: f32,
     units_per_space: f32,
     note_positions: &HashMap<u32, f32>,
+    cross_staff: &CrossStaffContext,
+    staff_index: usize,
 ) -> Vec<LedgerLine> {
     let mut ledger_lines = Vec::new();
+    let as_note_data = |note: &NoteEvent| -> NoteData {
+        (
+            note.pitch,
+            note.start_tick,
+            note.duration_ticks,
+            note.spelling,
+            note.staccato,
+            note.dot_count,
+            note.has_explicit_accidental,
+        )
+    };
     for voice in &staff_data.voices {
+        let in_range = |note: &&NoteEvent| {
+            note.start_tick >= tick_range.start_tick && note.start_tick < tick_range.end_tick
+        };
+
+        // Cross-staff notes get ledger lines relative to the staff they are drawn on
+        for note in voice.notes.iter().filter(in_range) {
+            let target = cross_staff.display_staff(staff_index, note);
+            if target == staff_index {
+                continue;
+            }
+            if let (Some(target_staff), Some(target_offset)) =
+                (cross_staff.staff(target), cross_staff.staff_offset(target))
+            {
+                ledger_lines.extend(positioner::position_ledger_lines(
+                    &[as_note_data(note)],
+                    &[*note_positions.get(&note.start_tick).unwrap_or(&0.0)],
+                    &[target_staff.get_clef_at_tick(note.start_tick)],
+                    units_per_space,
+                    target_offset,
+                    target_staff.line_count,
+                ));
+            }
+        }
+
         let notes_in_range: Vec<NoteData> = voice
             .notes
             .iter()
-            .filter(|note| {
-                note.start_tick >= tick_range.start_tick && note.start_tick < tick_range.end_tick
-            })
-            .map(|note| {
-                (
-                    note.pitch,
-                    note.start_tick,
-                    note.duration_ticks,
-                    note.spelling,
-                    note.staccato,
-                    note.dot_count,
-                    note.has_explicit_accidental,
-                )
-            })
+            .filter(in_range)
+            .filter(|note| cross_staff.display_staff(staff_index, note) == staff_index)
+            .map(as_note_data)
             .collect();
         let offsets: Vec<f32> = notes_in_range
             .iter()
@@ -129,6 +169,7 @@ fn render_ledger_lines(
             &ledger_clefs,
             units_per_space,
             staff_vertical_offset,
+            staff_data.line_count,
         ));
     }
     ledger_lines
@@ -140,12 +181,21 @@ fn render_notation_dots(
     tick_range: &TickRange,
     staff_vertical_offset: f32,
     staff_middle_y: f32,
+    unified_left_margin: f32,
     units_per_space: f32,
     note_positions: &HashMap<u32, f32>,
     measure_starts: &[u32],
+    cross_staff: &CrossStaffContext,
+    staff_index: usize,
 ) -> Vec<types::NotationDot> {
     let mut notation_dots = Vec::new();
     let dot_radius = 0.18 * units_per_space;
+    // Top line of the staff a note is drawn on (differs for cross-staff notes)
+    let display_offset = |note: &NoteEvent| {
+        cross_staff
+            .staff_offset(cross_staff.display_staff(staff_index, note))
+            .unwrap_or(staff_vertical_offset)
+    };
     let num_voices = staff_data.voices.len();
     for (dot_voice_idx, voice) in staff_data.voices.iter().enumerate() {
         // Multi-voice stem rule for dot placement — only force when another
@@ -187,11 +237,12 @@ fn render_notation_dots(
                     .filter(|n| n.duration_ticks <= 480 || !n.beam_info.is_empty())
                     .map(|n| {
                         let clef = staff_data.get_clef_at_tick(n.start_tick);
-                        let y = positioner::pitch_to_y_with_spelling(
+                        let y = positioner::pitch_to_staff_y(
                             n.pitch,
                             clef,
                             units_per_space,
                             n.spelling,
+                            staff_data.line_count,
                         ) + staff_vertical_offset;
                         let beam_types: Vec<String> =
                             n.beam_info.iter().map(|(_, bt)| bt.clone()).collect();
@@ -249,12 +300,14 @@ fn render_notation_dots(
             let mut note_ys: Vec<(f32, &NoteEvent)> = group
                 .iter()
                 .map(|n| {
-                    let y = positioner::pitch_to_y_with_spelling(
+                    let y = positioner::pitch_to_staff_y(
                         n.pitch,
                         clef,
                         units_per_space,
                         n.spelling,
-                    ) + staff_vertical_offset;
+                        staff_data.line_count,
+                    ) + staff_vertical_offset
+                        + cross_staff.shift(staff_index, n);
                     (y, *n)
                 })
                 .collect();
@@ -315,8 +368,7 @@ fn render_notation_dots(
                 if note.dot_count > 0 {
                     let note_x = *note_positions.get(&note.start_tick).unwrap_or(&0.0);
                     let visual_y = y_raw + 0.5 * units_per_space;
-                    let dot_y =
-                        shift_dot_to_space(visual_y, staff_vertical_offset, units_per_space);
+                    let dot_y = shift_dot_to_space(visual_y, display_offset(note), units_per_space);
                     chord_dot_entries.push((note_x + dot_x_extra, dot_y, note.dot_count));
                 }
             }
@@ -369,7 +421,7 @@ fn render_notation_dots(
                     visual_y + staccato_offset
                 };
                 let staccato_y =
-                    shift_dot_to_space(staccato_y, staff_vertical_offset, units_per_space);
+                    shift_dot_to_space(staccato_y, display_offset(anchor_note), units_per_space);
                 notation_dots.push(types::NotationDot {
                     x: note_x,
                     y: staccato_y,
@@ -378,6 +430,45 @@ fn render_notation_dots(
             }
         }
     }
+
+    // Augmentation dots of dotted rests, in the space above the rest's origin
+    let multi_voice = num_voices > 1;
+    for rest in staff_data
+        .voices
+        .iter()
+        .flat_map(|v| &v.rests)
+        .filter(|r| r.start_tick >= tick_range.start_tick && r.start_tick < tick_range.end_tick)
+    {
+        let dot_count = positioner::rest_dot_count(rest.note_type.as_deref(), rest.duration_ticks);
+        let is_full = rest.is_measure_rest
+            || positioner::is_full_measure_rest(
+                rest.duration_ticks,
+                staff_data.time_numerator,
+                staff_data.time_denominator,
+            );
+        if dot_count == 0 || is_full {
+            continue;
+        }
+        let rest_x = positioner::beat_rest_x(rest.start_tick, note_positions, unified_left_margin);
+        let rest_y = positioner::rest_y(
+            rest.duration_ticks,
+            rest.voice,
+            multi_voice,
+            units_per_space,
+        ) + staff_vertical_offset;
+        let dot_y = shift_dot_to_space(
+            rest_y - 0.5 * units_per_space,
+            staff_vertical_offset,
+            units_per_space,
+        );
+        for d in 0..dot_count {
+            notation_dots.push(types::NotationDot {
+                x: rest_x + (0.9 + 0.6 * f32::from(d)) * units_per_space,
+                y: dot_y,
+                radius: dot_radius,
+            });
+        }
+    }
     notation_dots
 }
 
@@ -396,6 +487,8 @@ fn render_fingering_glyphs(
     staff_vertical_offset: f32,
     units_per_space: f32,
     note_positions: &HashMap<u32, f32>,
+    cross_staff: &CrossStaffContext,
+    staff_index: usize,
 ) -> Vec<types::FingeringGlyph> {
     let mut glyphs = Vec::new();
     for voice in &staff_data.voices {
@@ -416,12 +509,14 @@ fn render_fingering_glyphs(
             }
             let note_x = *note_positions.get(&note.start_tick).unwrap_or(&0.0);
             let clef = staff_data.get_clef_at_tick(note.start_tick);
-            let notehead_y = positioner::pitch_to_y_with_spelling(
+            let notehead_y = positioner::pitch_to_staff_y(
                 note.pitch,
                 clef,
                 units_per_space,
                 note.spelling,
-            ) + staff_vertical_offset;
+                staff_data.line_count,
+            ) + staff_vertical_offset
+                + cross_staff.shift(staff_index, note);
             let visual_y = notehead_y + 0.5 * units_per_space;
 
             let is_chord = tick_note_count.get(&note.start_tick).copied().unwrap_or(1) > 1;
@@ -484,6 +579,8 @@ fn render_ties_and_slurs(
     unified_left_margin: f32,
     units_per_space: f32,
     note_positions: &HashMap<u32, f32>,
+    cross_staff: &CrossStaffContext,
+    staff_index: usize,
 ) -> (Vec<types::TieArc>, Vec<types::TieArc>) {
     let mut tie_arcs: Vec<types::TieArc> = Vec::new();
     let mut slur_arcs: Vec<types::TieArc> = Vec::new();
@@ -547,9 +644,14 @@ fn render_ties_and_slurs(
                 continue;
             }
             let clef = staff_data.get_clef_at_tick(n.start_tick);
-            let y_raw =
-                positioner::pitch_to_y_with_spelling(n.pitch, clef, units_per_space, n.spelling)
-                    + staff_vertical_offset;
+            let y_raw = positioner::pitch_to_staff_y(
+                n.pitch,
+                clef,
+                units_per_space,
+                n.spelling,
+                staff_data.line_count,
+            ) + staff_vertical_offset
+                + cross_staff.shift(staff_index, n);
             let visual_y = y_raw + 0.5 * units_per_space;
             let note_x = if n.is_grace {
                 *grace_x_by_tick.get(&n.start_tick).unwrap_or(&0.0)
@@ -595,8 +697,13 @@ fn render_ties_and_slurs(
             }
         }
         let clef = staff_data.get_clef_at_tick(start_tick);
-        let y_raw = positioner::pitch_to_y_with_spelling(pitch, clef, units_per_space, spelling)
-            + staff_vertical_offset;
+        let y_raw = positioner::pitch_to_staff_y(
+            pitch,
+            clef,
+            units_per_space,
+            spelling,
+            staff_data.line_count,
+        ) + staff_vertical_offset;
         y_raw <= staff_middle_y
     };
 
@@ -781,11 +888,12 @@ fn render_ties_and_slurs(
                                 .iter()
                                 .filter(|cn| cn.start_tick == n.start_tick)
                                 .map(|cn| {
-                                    positioner::pitch_to_y_with_spelling(
+                                    positioner::pitch_to_staff_y(
                                         cn.pitch,
                                         clef,
                                         units_per_space,
                                         cn.spelling,
+                                        staff_data.line_count,
                                     ) + staff_vertical_offset
                                 })
                                 .collect();
@@ -863,7 +971,7 @@ fn render_ties_and_slurs(
 
                             // Collect intermediate notes for clearance checks.
                             let clearance = notehead_half_h + 4.0;
-                            let intermediates: Vec<(f32, f32)> = note_lookup
+                            let mut intermediates: Vec<(f32, f32)> = note_lookup
                                 .values()
                                 .filter(|&&(nx, _ny, _np, nt)| {
                                     nt > start_tick
@@ -873,6 +981,10 @@ fn render_ties_and_slurs(
                                 })
                                 .map(|&(nx, ny, _np, _nt)| (nx, ny + y_edge))
                                 .collect();
+                            // Left to right, so the endpoint adjustments below
+                            // don't depend on hash map order
+                            intermediates
+                                .sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
 
                             // Phase 1: compute baseline arc height and push
                             // control-point Y so the arc clears every notehead.
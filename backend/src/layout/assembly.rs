@@ -1,31 +1,71 @@
 //! Staff lines and system assembly
 //!
-//! Creates the five staff lines for each staff, renders measure number
+//! Creates the staff lines for each staff, renders measure number
 //! annotations and volta brackets, and expands system bounding boxes
 //! to accommodate stems, beams, and other overhanging elements.
 
-use crate::layout::types::StaffLine;
+use crate::layout::positioner;
+use crate::layout::types::{BoundingBox, StaffLine, System};
 
 /// Create staff lines for a single staff
+///
+/// Lines are one staff space apart, placed by `positioner::staff_line_span`
+/// relative to the staff's vertical offset (the top line of a five-line
+/// staff).
 pub(crate) fn create_staff_lines(
     staff_vertical_offset: f32,
     system_width: f32,
     units_per_space: f32,
-) -> [StaffLine; 5] {
-    // Create 5 evenly spaced lines (1 staff space apart)
-    // A notehead fills exactly this gap, matching standard engraving
-    let mut lines = Vec::new();
-    for line_index in 0..5 {
-        let y_position = staff_vertical_offset + (line_index as f32 * units_per_space);
-        lines.push(StaffLine {
-            y_position,
+    line_count: u8,
+) -> Vec<StaffLine> {
+    // A notehead fills exactly the gap between two lines, matching standard engraving
+    let (top_line, _) = positioner::staff_line_span(line_count);
+    (0..line_count.max(1))
+        .map(|line_index| StaffLine {
+            y_position: staff_vertical_offset + (top_line + line_index as f32) * units_per_space,
             start_x: 0.0,
             end_x: system_width,
-        });
-    }
+        })
+        .collect()
+}
+
+/// Set each measure's bounding box to its barlines and the system's height,
+/// grown over the glyphs centred in the measure
+pub(crate) fn compute_measure_bounding_boxes(system: &mut System) {
+    let top = system.bounding_box.y;
+    let bottom = top + system.bounding_box.height;
+    let glyphs: Vec<&BoundingBox> = system
+        .staff_groups
+        .iter()
+        .flat_map(|g| &g.staves)
+        .flat_map(|s| {
+            s.glyph_runs
+                .iter()
+                .flat_map(|r| &r.glyphs)
+                .chain(&s.structural_glyphs)
+        })
+        .map(|g| &g.bounding_box)
+        .collect();
 
-    // Convert Vec to array (guaranteed to have exactly 5 elements)
-    lines.try_into().unwrap()
+    for measure in &mut system.measure_layouts {
+        let (mut left, mut right) = (measure.x_start, measure.x_end);
+        let (mut min_y, mut max_y) = (top, bottom);
+        for b in glyphs.iter().filter(|b| {
+            let center = b.x + b.width / 2.0;
+            center >= measure.x_start && center < measure.x_end
+        }) {
+            left = left.min(b.x);
+            right = right.max(b.x + b.width);
+            min_y = min_y.min(b.y);
+            max_y = max_y.max(b.y + b.height);
+        }
+        measure.bounding_box = BoundingBox {
+            x: left,
+            y: min_y,
+            width: right - left,
+            height: max_y - min_y,
+        };
+    }
 }
 
 #[cfg(test)]
@@ -38,7 +78,7 @@ mod tests {
         let units_per_space = 20.0;
         let system_width = 1200.0;
 
-        let lines = create_staff_lines(0.0, system_width, units_per_space);
+        let lines = create_staff_lines(0.0, system_width, units_per_space, 5);
 
         // Verify exactly 5 lines
         assert_eq!(lines.len(), 5, "Should have exactly 5 staff lines");
@@ -68,13 +108,13 @@ mod tests {
         let system_width = 1200.0;
 
         // First staff at vertical offset 0
-        let staff_0 = create_staff_lines(0.0, system_width, units_per_space);
+        let staff_0 = create_staff_lines(0.0, system_width, units_per_space, 5);
         assert_eq!(staff_0[0].y_position, 0.0);
         assert_eq!(staff_0[4].y_position, 80.0);
 
         // Second staff - offset by 20 staff spaces (400 units)
         let expected_offset = 20.0 * units_per_space; // 400 units
-        let staff_1 = create_staff_lines(expected_offset, system_width, units_per_space);
+        let staff_1 = create_staff_lines(expected_offset, system_width, units_per_space, 5);
         assert_eq!(staff_1[0].y_position, expected_offset);
         assert_eq!(staff_1[4].y_position, expected_offset + 80.0);
     }
@@ -85,15 +125,32 @@ mod tests {
         let system_width = 1200.0;
 
         // Test with different scale (units_per_space = 10)
-        let lines_scale_10 = create_staff_lines(0.0, system_width, 10.0);
+        let lines_scale_10 = create_staff_lines(0.0, system_width, 10.0, 5);
         assert_eq!(lines_scale_10[0].y_position, 0.0);
         assert_eq!(lines_scale_10[1].y_position, 10.0); // 1 * 10
         assert_eq!(lines_scale_10[4].y_position, 40.0); // 4 * 10
 
         // Test with different scale (units_per_space = 25)
-        let lines_scale_25 = create_staff_lines(0.0, system_width, 25.0);
+        let lines_scale_25 = create_staff_lines(0.0, system_width, 25.0, 5);
         assert_eq!(lines_scale_25[0].y_position, 0.0);
         assert_eq!(lines_scale_25[1].y_position, 25.0); // 1 * 25
         assert_eq!(lines_scale_25[4].y_position, 100.0); // 4 * 25
     }
+
+    #[test]
+    fn test_create_staff_lines_other_line_counts() {
+        let ys = |count: u8| -> Vec<f32> {
+            create_staff_lines(100.0, 1200.0, 20.0, count)
+                .iter()
+                .map(|l| l.y_position)
+                .collect()
+        };
+
+        // One line on the five-line staff's middle line
+        assert_eq!(ys(1), vec![140.0]);
+        // Three lines on the middle three
+        assert_eq!(ys(3), vec![120.0, 140.0, 160.0]);
+        // Six lines add one above
+        assert_eq!(ys(6), vec![80.0, 100.0, 120.0, 140.0, 160.0, 180.0]);
+    }
 }
@@ -7,10 +7,44 @@
 use std::collections::HashMap;
 
 use crate::layout::breaker;
+use crate::layout::positioner;
 use crate::layout::types::{
-    BarLine, BarLineSegment, BarLineType, RepeatDotPosition, StaffGroup, TickRange,
+    BarLine, BarLineSegment, BarLineType, RepeatDotPosition, Staff, StaffGroup, TickRange,
 };
 
+/// Top and bottom y of the barlines of a staff with `line_count` lines
+///
+/// Barlines join the top and bottom staff lines; on a one-line staff they
+/// reach one staff space above and below the line.
+pub(crate) fn barline_extent(
+    staff_vertical_offset: f32,
+    line_count: u8,
+    units_per_space: f32,
+) -> (f32, f32) {
+    let (top_line, bottom_line) = positioner::staff_line_span(line_count);
+    let overhang = if line_count == 1 { 1.0 } else { 0.0 };
+    (
+        staff_vertical_offset + (top_line - overhang) * units_per_space,
+        staff_vertical_offset + (bottom_line + overhang) * units_per_space,
+    )
+}
+
+/// Vertical offset of a laid-out staff: the top line of a five-line staff,
+/// which its lines are placed around
+pub(crate) fn staff_offset(staff: &Staff, units_per_space: f32) -> f32 {
+    let (top_line, _) = positioner::staff_line_span(staff.staff_lines.len() as u8);
+    staff.top_line_y() - top_line * units_per_space
+}
+
+/// `barline_extent` of a laid-out staff
+pub(crate) fn staff_barline_extent(staff: &Staff, units_per_space: f32) -> (f32, f32) {
+    barline_extent(
+        staff_offset(staff, units_per_space),
+        staff.staff_lines.len() as u8,
+        units_per_space,
+    )
+}
+
 /// Create bar lines for a single staff at measure boundaries
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn create_bar_lines(
@@ -20,14 +54,14 @@ pub(crate) fn create_bar_lines(
     left_margin: f32,
     _system_width: f32,
     units_per_space: f32,
+    line_count: u8,
     note_positions: &HashMap<u32, f32>,
     measure_x_bounds: &HashMap<u32, (f32, f32)>,
 ) -> Vec<BarLine> {
     let mut bar_lines = Vec::new();
 
     // Y positions for top and bottom staff lines
-    let y_start = staff_vertical_offset; // Top line (line 0)
-    let y_end = staff_vertical_offset + (4.0 * units_per_space); // Bottom line (line 4)
+    let (y_start, y_end) = barline_extent(staff_vertical_offset, line_count, units_per_space);
 
     // Find measures that overlap with this system's tick range
     let measures_in_system: Vec<&breaker::MeasureInfo> = measure_infos
@@ -106,7 +140,8 @@ pub(crate) fn create_bar_lines(
 
         // Create bar line segments with explicit geometry (Principle VI: Layout Engine Authority)
         let segments = create_bar_line_segments(barline_x, y_start, y_end, &bar_type);
-        let dots = compute_repeat_dots(barline_x, y_start, units_per_space, &bar_type);
+        let dots =
+            compute_repeat_dots(barline_x, staff_vertical_offset, units_per_space, &bar_type);
 
         bar_lines.push(BarLine {
             segments,
@@ -236,6 +271,8 @@ pub(crate) fn create_bar_line_segments(
 }
 
 /// Compute repeat dot positions for repeat barline types (Principle VI: Layout Engine Authority)
+///
+/// `y_start` is the staff's vertical offset (top line of a five-line staff).
 pub(crate) fn compute_repeat_dots(
     x_position: f32,
     y_start: f32,
@@ -352,22 +389,22 @@ pub(crate) fn render_system_barlines(
         }
     };
     for staff_group in staff_groups.iter_mut() {
-        let top_y = staff_group.staves.first().unwrap().staff_lines[0].y_position;
-        let bottom_y = staff_group.staves.last().unwrap().staff_lines[4].y_position;
+        let (top_y, _) = staff_barline_extent(staff_group.staves.first().unwrap(), units_per_space);
+        let (_, bottom_y) =
+            staff_barline_extent(staff_group.staves.last().unwrap(), units_per_space);
         let segments = create_bar_line_segments(content_width, top_y, bottom_y, &end_bar_type);
-        let dots = compute_repeat_dots(content_width, top_y, units_per_space, &end_bar_type);
-        let mut all_dots = dots;
-        if staff_group.staves.len() >= 2 {
-            for staff in &staff_group.staves[1..] {
-                let staff_top = staff.staff_lines[0].y_position;
-                all_dots.extend(compute_repeat_dots(
+        let all_dots = staff_group
+            .staves
+            .iter()
+            .flat_map(|staff| {
+                compute_repeat_dots(
                     content_width,
-                    staff_top,
+                    staff_offset(staff, units_per_space),
                     units_per_space,
                     &end_bar_type,
-                ));
-            }
-        }
+                )
+            })
+            .collect();
         let end_barline = BarLine {
             segments,
             bar_type: end_bar_type,
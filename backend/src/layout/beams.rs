@@ -278,6 +278,16 @@ pub fn group_beamable_by_time_signature(
 /// - Clamp to ±0.5 staff spaces (±10 logical units) per note
 /// - Distribute slope evenly across horizontal span
 pub fn compute_beam_slope(notes: &[BeamableNote], units_per_space: f32) -> f32 {
+    compute_beam_slope_clamped(notes, units_per_space, Beam::MAX_SLOPE)
+}
+
+/// Compute beam slope clamped to `max_slope` staff spaces per note
+/// (`EngravingStyle::max_beam_slope`)
+pub fn compute_beam_slope_clamped(
+    notes: &[BeamableNote],
+    units_per_space: f32,
+    max_slope: f32,
+) -> f32 {
     if notes.len() < 2 {
         return 0.0;
     }
@@ -295,8 +305,8 @@ pub fn compute_beam_slope(notes: &[BeamableNote], units_per_space: f32) -> f32 {
 
     let natural_slope = dy / dx;
 
-    // Clamp slope to ±0.5 staff spaces per note
-    let max_slope_units = Beam::MAX_SLOPE * units_per_space; // 0.5 * 20 = 10 units
+    // Clamp slope to ±max_slope staff spaces per note
+    let max_slope_units = max_slope * units_per_space; // 0.5 * 20 = 10 units by default
     let max_slope_per_unit = max_slope_units / dx.abs();
 
     natural_slope.clamp(-max_slope_per_unit, max_slope_per_unit)
@@ -0,0 +1,482 @@
+//! Compact binary encoding of a GlobalLayout
+//!
+//! The JSON shape of a layout repeats a `SourceReference`, a `Color` and
+//! several rounded floats for every glyph, and converting it to JavaScript
+//! builds one object per glyph. This encoding moves the glyphs of every
+//! staff (glyph runs and structural glyphs, the bulk of any layout) into
+//! flat little-endian columns that JavaScript can view as typed arrays
+//! without copying, and keeps the rest of the layout as JSON.
+//!
+//! # Format (version 1)
+//!
+//! All integers and floats are little-endian; every section starts at a
+//! multiple of 4 bytes, so each column can be viewed as a `Uint32Array` or
+//! `Float32Array` directly on the buffer.
+//!
+//! | Section  | Contents |
+//! |----------|----------|
+//! | header   | magic `b"MLAY"`, version `u16`, reserved `u16` (0) |
+//! | strings  | count `u32` = S, blob length `u32` = B, offsets `u32[S + 1]`, UTF-8 blob `u8[B]`, padding |
+//! | glyphs   | count `u32` = G, then 13 columns of G values (below) |
+//! | runs     | count `u32` = R, then 6 columns of R values (below) |
+//! | staves   | count `u32` = N, then 4 columns of N values (below) |
+//! | skeleton | length `u32` = L, UTF-8 JSON `u8[L]`, padding |
+//!
+//! String `i` is `blob[offsets[i]..offsets[i + 1]]`. Codepoints, font
+//! families and instrument ids are stored once and referenced by index.
+//!
+//! Glyph columns, in order: `x`, `y`, `bbox_x`, `bbox_y`, `bbox_width`,
+//! `bbox_height` (`f32`); `codepoint`, `instrument_id` (string index),
+//! `staff_index`, `voice_index`, `event_index` (`u32`); `font_size`,
+//! `opacity` (`f32`, NaN = not set).
+//!
+//! Run columns: `glyph_start`, `glyph_count` (`u32`, into the glyph
+//! table); `font_family` (string index); `font_size` (`f32`); `color`
+//! (`u32`, `r | g << 8 | b << 16 | a << 24`); `opacity` (`f32`).
+//!
+//! Staff columns: `run_start`, `run_count` (`u32`, into the run table);
+//! `structural_start`, `structural_count` (`u32`, into the glyph table).
+//! Staves are listed in document order: systems, then staff groups, then
+//! staves.
+//!
+//! The skeleton is the `GlobalLayout` JSON with every staff's
+//! `glyph_runs` and `structural_glyphs` left empty; a decoder parses it and
+//! fills the staves back in from the tables.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::layout::types::{
+    BoundingBox, Color, GlobalLayout, Glyph, GlyphRun, Point, SourceReference, Staff,
+};
+
+/// Leading bytes of every encoded layout
+pub const LAYOUT_MAGIC: [u8; 4] = *b"MLAY";
+
+/// Format version written by `encode_layout`
+pub const LAYOUT_FORMAT_VERSION: u16 = 1;
+
+/// Error decoding a binary layout
+#[derive(Error, Debug)]
+pub enum LayoutDecodeError {
+    /// Buffer does not start with `LAYOUT_MAGIC`
+    #[error("Not a binary layout (bad magic)")]
+    BadMagic,
+
+    /// Buffer was written by an unknown format version
+    #[error("Unsupported binary layout version: {0}")]
+    UnsupportedVersion(u16),
+
+    /// Buffer ends inside a section
+    #[error("Binary layout truncated at byte {offset}")]
+    Truncated { offset: usize },
+
+    /// Index into the string, glyph or run table out of range
+    #[error("Invalid {table} index: {index}")]
+    InvalidIndex { table: &'static str, index: u32 },
+
+    /// String table entry is not UTF-8
+    #[error("Invalid UTF-8 in string table")]
+    InvalidString,
+
+    /// Skeleton JSON does not parse as a GlobalLayout
+    #[error("Invalid layout skeleton: {0}")]
+    Skeleton(#[from] serde_json::Error),
+
+    /// Staff table and skeleton disagree on the number of staves
+    #[error("Staff table has {table} staves, skeleton has {skeleton}")]
+    StaffCountMismatch { table: usize, skeleton: usize },
+}
+
+/// Encode a layout in the binary format described in the module docs
+pub fn encode_layout(layout: &GlobalLayout) -> Vec<u8> {
+    let mut strings = StringTable::default();
+    let mut glyphs: Vec<&Glyph> = Vec::new();
+    let mut runs: Vec<(u32, u32, &GlyphRun)> = Vec::new();
+    let mut staves: Vec<[u32; 4]> = Vec::new();
+
+    for staff in staves_of(layout) {
+        let run_start = runs.len() as u32;
+        for run in &staff.glyph_runs {
+            runs.push((glyphs.len() as u32, run.glyphs.len() as u32, run));
+            glyphs.extend(&run.glyphs);
+        }
+        let structural_start = glyphs.len() as u32;
+        glyphs.extend(&staff.structural_glyphs);
+        staves.push([
+            run_start,
+            staff.glyph_runs.len() as u32,
+            structural_start,
+            staff.structural_glyphs.len() as u32,
+        ]);
+    }
+
+    let codepoints: Vec<u32> = glyphs
+        .iter()
+        .map(|g| strings.intern(&g.codepoint))
+        .collect();
+    let instruments: Vec<u32> = glyphs
+        .iter()
+        .map(|g| strings.intern(&g.source_reference.instrument_id))
+        .collect();
+    let families: Vec<u32> = runs
+        .iter()
+        .map(|(_, _, run)| strings.intern(&run.font_family))
+        .collect();
+
+    let mut skeleton = layout.clone();
+    for system in &mut skeleton.systems {
+        for staff in system.staff_groups.iter_mut().flat_map(|g| &mut g.staves) {
+            staff.glyph_runs.clear();
+            staff.structural_glyphs.clear();
+        }
+    }
+    let skeleton = serde_json::to_vec(&skeleton).unwrap_or_default();
+
+    let mut w = Writer::default();
+    w.bytes(&LAYOUT_MAGIC);
+    w.u16(LAYOUT_FORMAT_VERSION);
+    w.u16(0);
+
+    // Strings
+    w.u32(strings.strings.len() as u32);
+    let blob_len: usize = strings.strings.iter().map(|s| s.len()).sum();
+    w.u32(blob_len as u32);
+    let mut offset = 0;
+    w.u32(0);
+    for s in &strings.strings {
+        offset += s.len() as u32;
+        w.u32(offset);
+    }
+    for s in &strings.strings {
+        w.bytes(s.as_bytes());
+    }
+    w.pad();
+
+    // Glyphs
+    w.u32(glyphs.len() as u32);
+    w.f32s(glyphs.iter().map(|g| g.position.x));
+    w.f32s(glyphs.iter().map(|g| g.position.y));
+    w.f32s(glyphs.iter().map(|g| g.bounding_box.x));
+    w.f32s(glyphs.iter().map(|g| g.bounding_box.y));
+    w.f32s(glyphs.iter().map(|g| g.bounding_box.width));
+    w.f32s(glyphs.iter().map(|g| g.bounding_box.height));
+    w.u32s(codepoints);
+    w.u32s(instruments);
+    w.u32s(glyphs.iter().map(|g| g.source_reference.staff_index as u32));
+    w.u32s(glyphs.iter().map(|g| g.source_reference.voice_index as u32));
+    w.u32s(glyphs.iter().map(|g| g.source_reference.event_index as u32));
+    w.f32s(glyphs.iter().map(|g| g.font_size.unwrap_or(f32::NAN)));
+    w.f32s(glyphs.iter().map(|g| g.opacity.unwrap_or(f32::NAN)));
+
+    // Runs
+    w.u32(runs.len() as u32);
+    w.u32s(runs.iter().map(|&(start, _, _)| start));
+    w.u32s(runs.iter().map(|&(_, count, _)| count));
+    w.u32s(families);
+    w.f32s(runs.iter().map(|(_, _, run)| run.font_size));
+    w.u32s(runs.iter().map(|(_, _, run)| pack_color(run.color)));
+    w.f32s(runs.iter().map(|(_, _, run)| run.opacity));
+
+    // Staves
+    w.u32(staves.len() as u32);
+    for column in 0..4 {
+        w.u32s(staves.iter().map(|s| s[column]));
+    }
+
+    // Skeleton
+    w.u32(skeleton.len() as u32);
+    w.bytes(&skeleton);
+    w.pad();
+
+    w.buf
+}
+
+/// Decode a layout written by `encode_layout`
+pub fn decode_layout(bytes: &[u8]) -> Result<GlobalLayout, LayoutDecodeError> {
+    let mut r = Reader { bytes, pos: 0 };
+    if r.bytes(4)? != LAYOUT_MAGIC {
+        return Err(LayoutDecodeError::BadMagic);
+    }
+    let version = r.u16()?;
+    if version != LAYOUT_FORMAT_VERSION {
+        return Err(LayoutDecodeError::UnsupportedVersion(version));
+    }
+    r.u16()?;
+
+    // Strings
+    let count = r.u32()? as usize;
+    let blob_len = r.u32()? as usize;
+    let offsets = r.u32s(count + 1)?;
+    let blob = r.bytes(blob_len)?;
+    r.pad();
+    let strings = offsets
+        .windows(2)
+        .map(|w| {
+            let (start, end) = (w[0] as usize, w[1] as usize);
+            let bytes = blob
+                .get(start..end)
+                .ok_or(LayoutDecodeError::InvalidIndex {
+                    table: "string",
+                    index: w[1],
+                })?;
+            std::str::from_utf8(bytes).map_err(|_| LayoutDecodeError::InvalidString)
+        })
+        .collect::<Result<Vec<&str>, _>>()?;
+    let string = |index: u32| {
+        strings
+            .get(index as usize)
+            .map(|s| s.to_string())
+            .ok_or(LayoutDecodeError::InvalidIndex {
+                table: "string",
+                index,
+            })
+    };
+
+    // Glyphs
+    let count = r.u32()? as usize;
+    let (x, y) = (r.f32s(count)?, r.f32s(count)?);
+    let (bx, by, bw, bh) = (
+        r.f32s(count)?,
+        r.f32s(count)?,
+        r.f32s(count)?,
+        r.f32s(count)?,
+    );
+    let (codepoint, instrument) = (r.u32s(count)?, r.u32s(count)?);
+    let (staff_index, voice_index, event_index) = (r.u32s(count)?, r.u32s(count)?, r.u32s(count)?);
+    let (font_size, opacity) = (r.f32s(count)?, r.f32s(count)?);
+    let glyphs = (0..count)
+        .map(|i| {
+            Ok(Glyph {
+                position: Point { x: x[i], y: y[i] },
+                bounding_box: BoundingBox {
+                    x: bx[i],
+                    y: by[i],
+                    width: bw[i],
+                    height: bh[i],
+                },
+                codepoint: string(codepoint[i])?,
+                source_reference: SourceReference {
+                    instrument_id: string(instrument[i])?,
+                    staff_index: staff_index[i] as usize,
+                    voice_index: voice_index[i] as usize,
+                    event_index: event_index[i] as usize,
+                },
+                font_size: (!font_size[i].is_nan()).then_some(font_size[i]),
+                opacity: (!opacity[i].is_nan()).then_some(opacity[i]),
+            })
+        })
+        .collect::<Result<Vec<Glyph>, LayoutDecodeError>>()?;
+    let glyph_slice = |start: u32, count: u32| {
+        glyphs
+            .get(start as usize..(start as usize).saturating_add(count as usize))
+            .ok_or(LayoutDecodeError::InvalidIndex {
+                table: "glyph",
+                index: start.saturating_add(count),
+            })
+    };
+
+    // Runs
+    let count = r.u32()? as usize;
+    let (glyph_start, glyph_count, family) = (r.u32s(count)?, r.u32s(count)?, r.u32s(count)?);
+    let run_font_size = r.f32s(count)?;
+    let color = r.u32s(count)?;
+    let run_opacity = r.f32s(count)?;
+    let runs = (0..count)
+        .map(|i| {
+            Ok(GlyphRun {
+                glyphs: glyph_slice(glyph_start[i], glyph_count[i])?.to_vec(),
+                font_family: string(family[i])?,
+                font_size: run_font_size[i],
+                color: unpack_color(color[i]),
+                opacity: run_opacity[i],
+            })
+        })
+        .collect::<Result<Vec<GlyphRun>, LayoutDecodeError>>()?;
+
+    // Staves
+    let count = r.u32()? as usize;
+    let (run_start, run_count) = (r.u32s(count)?, r.u32s(count)?);
+    let (structural_start, structural_count) = (r.u32s(count)?, r.u32s(count)?);
+
+    // Skeleton
+    let len = r.u32()? as usize;
+    let mut layout: GlobalLayout = serde_json::from_slice(r.bytes(len)?)?;
+
+    let skeleton_staves = staves_of(&layout).count();
+    if skeleton_staves != count {
+        return Err(LayoutDecodeError::StaffCountMismatch {
+            table: count,
+            skeleton: skeleton_staves,
+        });
+    }
+    let staves = layout
+        .systems
+        .iter_mut()
+        .flat_map(|s| &mut s.staff_groups)
+        .flat_map(|g| &mut g.staves);
+    for (i, staff) in staves.enumerate() {
+        let (start, end) = (
+            run_start[i] as usize,
+            (run_start[i] as usize).saturating_add(run_count[i] as usize),
+        );
+        staff.glyph_runs = runs
+            .get(start..end)
+            .ok_or(LayoutDecodeError::InvalidIndex {
+                table: "run",
+                index: run_start[i].saturating_add(run_count[i]),
+            })?
+            .to_vec();
+        staff.structural_glyphs = glyph_slice(structural_start[i], structural_count[i])?.to_vec();
+    }
+    Ok(layout)
+}
+
+fn staves_of(layout: &GlobalLayout) -> impl Iterator<Item = &Staff> {
+    layout
+        .systems
+        .iter()
+        .flat_map(|s| &s.staff_groups)
+        .flat_map(|g| &g.staves)
+}
+
+fn pack_color(c: Color) -> u32 {
+    u32::from_le_bytes([c.r, c.g, c.b, c.a])
+}
+
+fn unpack_color(packed: u32) -> Color {
+    let [r, g, b, a] = packed.to_le_bytes();
+    Color { r, g, b, a }
+}
+
+#[derive(Default)]
+struct StringTable {
+    strings: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl StringTable {
+    fn intern(&mut self, s: &str) -> u32 {
+        if let Some(&index) = self.indices.get(s) {
+            return index;
+        }
+        let index = self.strings.len() as u32;
+        self.strings.push(s.to_string());
+        self.indices.insert(s.to_string(), index);
+        index
+    }
+}
+
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u32s(&mut self, values: impl IntoIterator<Item = u32>) {
+        for value in values {
+            self.u32(value);
+        }
+    }
+
+    fn f32s(&mut self, values: impl IntoIterator<Item = f32>) {
+        for value in values {
+            self.bytes(&value.to_le_bytes());
+        }
+    }
+
+    /// Zero-fill up to the next multiple of 4 bytes
+    fn pad(&mut self) {
+        while self.buf.len() % 4 != 0 {
+            self.buf.push(0);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], LayoutDecodeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(LayoutDecodeError::Truncated { offset: self.pos })?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, LayoutDecodeError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, LayoutDecodeError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u32s(&mut self, count: usize) -> Result<Vec<u32>, LayoutDecodeError> {
+        let bytes = self.bytes(
+            count
+                .checked_mul(4)
+                .ok_or(LayoutDecodeError::Truncated { offset: self.pos })?,
+        )?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+
+    fn f32s(&mut self, count: usize) -> Result<Vec<f32>, LayoutDecodeError> {
+        Ok(self.u32s(count)?.into_iter().map(f32::from_bits).collect())
+    }
+
+    /// Skip to the next multiple of 4 bytes
+    fn pad(&mut self) {
+        self.pos = self.pos.next_multiple_of(4).min(self.bytes.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings_are_interned() {
+        let mut table = StringTable::default();
+        assert_eq!(table.intern("\u{E0A4}"), 0);
+        assert_eq!(table.intern("Bravura"), 1);
+        assert_eq!(table.intern("\u{E0A4}"), 0);
+        assert_eq!(table.strings.len(), 2);
+    }
+
+    #[test]
+    fn test_color_packing() {
+        let color = Color {
+            r: 1,
+            g: 2,
+            b: 3,
+            a: 255,
+        };
+        assert_eq!(pack_color(color), 0xFF03_0201);
+        assert_eq!(unpack_color(pack_color(color)), color);
+    }
+}
@@ -1,10 +1,19 @@
 //! System breaking algorithm
 //!
-//! Implements greedy measure-by-measure breaking at measure boundaries.
+//! Breaks at measure boundaries, either greedily measure by measure or with
+//! a total-fit search that balances all systems of the piece together.
 
-use crate::layout::types::{BoundingBox, System, TickRange};
+use crate::layout::types::{BoundingBox, LineBreaking, System, TickRange};
+
+/// Total fit: how far below its natural width a system may be compressed
+const MAX_COMPRESSION: f32 = 0.1;
+/// Total fit: compression looks worse than the same amount of stretch
+const COMPRESSION_WEIGHT: f32 = 4.0;
+/// Total fit: cost of each measure more or fewer than the target per system
+const TARGET_WEIGHT: f32 = 2.0;
 
 /// Represents a measure with computed width and tick span
+#[derive(Debug, Clone, PartialEq)]
 pub struct MeasureInfo {
     /// Width of measure in logical units
     pub width: f32,
@@ -36,6 +45,18 @@ pub fn break_into_systems(
     max_width: f32,
     system_height: f32,
     system_spacing: f32,
+) -> Vec<System> {
+    break_into_systems_with_breaks(measures, max_width, system_height, system_spacing, &[])
+}
+
+/// Break measures into systems, always starting a new system at the
+/// measures listed in `forced_breaks` (0-based measure indices, e.g. page breaks)
+pub fn break_into_systems_with_breaks(
+    measures: &[MeasureInfo],
+    max_width: f32,
+    system_height: f32,
+    system_spacing: f32,
+    forced_breaks: &[usize],
 ) -> Vec<System> {
     if measures.is_empty() {
         return vec![];
@@ -45,10 +66,10 @@ pub fn break_into_systems(
     let mut current_system_measures = Vec::new();
     let mut current_width = 0.0;
 
-    for measure in measures {
+    for (index, measure) in measures.iter().enumerate() {
         // Check if adding this measure would exceed max_width
-        let would_exceed =
-            !current_system_measures.is_empty() && current_width + measure.width > max_width;
+        let would_exceed = !current_system_measures.is_empty()
+            && (current_width + measure.width > max_width || forced_breaks.contains(&index));
 
         if would_exceed {
             // Finish current system — justify to max_width so all systems are equal width
@@ -98,6 +119,172 @@ pub fn break_into_systems(
     systems
 }
 
+/// Break measures into systems with the configured strategy
+///
+/// Systems are `max_width` wide except a ragged last system, which keeps
+/// its natural width. A single line holds every measure at natural width,
+/// whatever `max_width` and `forced_breaks` say.
+pub fn break_systems(
+    measures: &[MeasureInfo],
+    max_width: f32,
+    system_height: f32,
+    system_spacing: f32,
+    forced_breaks: &[usize],
+    strategy: LineBreaking,
+) -> Vec<System> {
+    match strategy {
+        LineBreaking::Greedy => break_into_systems_with_breaks(
+            measures,
+            max_width,
+            system_height,
+            system_spacing,
+            forced_breaks,
+        ),
+        LineBreaking::TotalFit {
+            target_measures_per_system,
+            ragged_last_system,
+        } => break_into_systems_total_fit(
+            measures,
+            max_width,
+            system_height,
+            system_spacing,
+            forced_breaks,
+            target_measures_per_system,
+            ragged_last_system,
+        ),
+        LineBreaking::SingleLine { .. } => {
+            if measures.is_empty() {
+                return vec![];
+            }
+            let all: Vec<&MeasureInfo> = measures.iter().collect();
+            let natural = measures.iter().map(|m| m.width).sum();
+            vec![create_system(
+                0,
+                &all,
+                natural,
+                system_height,
+                system_spacing,
+            )]
+        }
+    }
+}
+
+/// Break measures into systems minimizing the total badness of all systems
+///
+/// Each system's badness is its squared stretch (or, weighted higher, its
+/// squared compression) relative to `max_width`, plus a penalty for every
+/// measure away from `target_measures_per_system`. Systems may be compressed
+/// by up to 10% below their natural width. Dynamic programming over break
+/// positions finds the global optimum in O(measures × measures per system).
+///
+/// A new system always starts at the measures in `forced_breaks`. With
+/// `ragged_last_system`, the last system costs nothing while it fits and
+/// keeps its natural width.
+pub fn break_into_systems_total_fit(
+    measures: &[MeasureInfo],
+    max_width: f32,
+    system_height: f32,
+    system_spacing: f32,
+    forced_breaks: &[usize],
+    target_measures_per_system: Option<u32>,
+    ragged_last_system: bool,
+) -> Vec<System> {
+    let n = measures.len();
+    if n == 0 {
+        return vec![];
+    }
+
+    // best[j] = (cost, start of last system) for measures[..j]
+    let mut best: Vec<(f32, usize)> = vec![(f32::INFINITY, 0); n + 1];
+    best[0] = (0.0, 0);
+    for end in 1..=n {
+        let is_last = end == n;
+        let mut natural = 0.0;
+        for start in (0..end).rev() {
+            natural += measures[start].width;
+            let count = end - start;
+            // Longer systems only get wider; a single measure always fits
+            if count > 1 && natural > max_width * (1.0 + MAX_COMPRESSION) {
+                break;
+            }
+            if best[start].0.is_finite() {
+                let cost = best[start].0
+                    + system_cost(
+                        natural,
+                        count,
+                        max_width,
+                        target_measures_per_system,
+                        is_last && ragged_last_system,
+                    );
+                if cost < best[end].0 {
+                    best[end] = (cost, start);
+                }
+            }
+            // A system cannot reach back across a forced break
+            if forced_breaks.contains(&start) {
+                break;
+            }
+        }
+    }
+
+    // Walk back from the end to recover the break positions
+    let mut ranges = Vec::new();
+    let mut end = n;
+    while end > 0 {
+        let start = best[end].1;
+        ranges.push(start..end);
+        end = start;
+    }
+    ranges.reverse();
+
+    ranges
+        .iter()
+        .enumerate()
+        .map(|(index, range)| {
+            let system_measures: Vec<&MeasureInfo> = measures[range.clone()].iter().collect();
+            let natural: f32 = system_measures.iter().map(|m| m.width).sum();
+            let ragged = ragged_last_system && range.end == n && natural <= max_width;
+            create_system(
+                index,
+                &system_measures,
+                if ragged { natural } else { max_width },
+                system_height,
+                system_spacing,
+            )
+        })
+        .collect()
+}
+
+/// Badness of one system of `count` measures with the given natural width
+fn system_cost(
+    natural: f32,
+    count: usize,
+    max_width: f32,
+    target: Option<u32>,
+    ragged: bool,
+) -> f32 {
+    let fit = if natural <= max_width {
+        if ragged {
+            0.0
+        } else {
+            let stretch = max_width / natural.max(1.0) - 1.0;
+            stretch * stretch
+        }
+    } else {
+        let compression = 1.0 - max_width / natural;
+        COMPRESSION_WEIGHT * compression * compression
+    };
+    let off_target = match target {
+        // A ragged last system may hold fewer measures without penalty
+        Some(target) if !(ragged && count < target as usize) => {
+            let diff = count as f32 - target as f32;
+            TARGET_WEIGHT * diff * diff
+        }
+        _ => 0.0,
+    };
+    fit + off_target
+}
+
 /// Helper function to create a System from accumulated measures
 fn create_system(
     index: usize,
@@ -127,7 +314,13 @@ fn create_system(
             end_tick,
         },
         measure_number: None, // Will be computed during layout computation
+        measure_number_labels: vec![], // Will be populated during layout computation
         volta_bracket_layouts: vec![], // Will be populated during layout computation
         ottava_bracket_layouts: vec![], // Will be populated during layout computation
+        direction_layouts: vec![], // Will be populated during layout computation
+        measure_layouts: vec![], // Will be populated during layout computation
+        tick_positions: vec![], // Will be populated during layout computation
+        group_brackets: vec![], // Will be populated during layout computation
+        indent: 0.0,          // Will be computed during layout computation
     }
 }
@@ -0,0 +1,193 @@
+//! Chord symbol rendering
+//!
+//! Positions formatted chord symbols (lead-sheet harmony) above a staff.
+//! Text comes from `ChordSymbolEvent::format`, the same formatter exposed
+//! to the frontend through WASM, so both render identical chord names.
+
+use std::collections::HashMap;
+
+use crate::layout::extraction::StaffData;
+use crate::layout::note_layout;
+use crate::layout::types::{BoundingBox, ChordSymbolLayout, ChordSymbolRun, TickRange};
+
+/// Chord symbol font size in staff spaces
+const FONT_SIZE_SPACES: f32 = 1.8;
+/// Superscript size relative to the main font size
+const SUPERSCRIPT_SCALE: f32 = 0.7;
+/// Superscript baseline raise relative to the main font size
+const SUPERSCRIPT_RAISE: f32 = 0.4;
+/// Approximate advance width per character relative to font size
+const CHAR_WIDTH_FACTOR: f32 = 0.6;
+/// Clearance between the highest note/stem and the chord baseline, in staff spaces
+const CLEARANCE_SPACES: f32 = 1.5;
+/// Minimum horizontal gap between consecutive chord symbols, in staff spaces
+const MIN_GAP_SPACES: f32 = 0.5;
+
+/// Render chord symbols for a single staff within a system.
+///
+/// All chord symbols in the system share one baseline placed above the
+/// highest note or stem (`staff_min_y`, relative to the staff top line), and
+/// symbols that would overlap are pushed right.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_chord_symbols(
+    staff_data: &StaffData,
+    tick_range: &TickRange,
+    staff_vertical_offset: f32,
+    staff_min_y: f32,
+    units_per_space: f32,
+    note_positions: &HashMap<u32, f32>,
+    measure_x_bounds: &HashMap<u32, (f32, f32)>,
+    unified_left_margin: f32,
+) -> Vec<ChordSymbolLayout> {
+    let font_size = FONT_SIZE_SPACES * units_per_space;
+    let baseline =
+        staff_vertical_offset + staff_min_y.min(0.0) - CLEARANCE_SPACES * units_per_space;
+
+    let mut result = Vec::new();
+    let mut min_x = f32::NEG_INFINITY;
+
+    for chord in &staff_data.chord_symbols {
+        let tick = chord.tick.value();
+        if tick < tick_range.start_tick || tick >= tick_range.end_tick {
+            continue;
+        }
+
+        let x = note_layout::tick_to_x(tick, note_positions, measure_x_bounds, unified_left_margin)
+            .max(min_x);
+        let text = chord.format();
+
+        let mut runs = Vec::new();
+        let mut cursor = x;
+        let mut push_run = |s: &str, superscript: bool, runs: &mut Vec<ChordSymbolRun>| {
+            if s.is_empty() {
+                return;
+            }
+            let size = if superscript {
+                font_size * SUPERSCRIPT_SCALE
+            } else {
+                font_size
+            };
+            let y = if superscript {
+                baseline - font_size * SUPERSCRIPT_RAISE
+            } else {
+                baseline
+            };
+            runs.push(ChordSymbolRun {
+                text: s.to_string(),
+                x: cursor,
+                y,
+                font_size: size,
+                superscript,
+            });
+            cursor += s.chars().count() as f32 * size * CHAR_WIDTH_FACTOR;
+        };
+
+        push_run(&format!("{}{}", text.root, text.quality), false, &mut runs);
+        push_run(&text.extension, true, &mut runs);
+        if let Some(bass) = &text.bass {
+            push_run(&format!("/{}", bass), false, &mut runs);
+        }
+
+        let top = if text.extension.is_empty() {
+            baseline - font_size
+        } else {
+            baseline - font_size * (SUPERSCRIPT_RAISE + SUPERSCRIPT_SCALE)
+        };
+        let bounding_box = BoundingBox {
+            x,
+            y: top,
+            width: cursor - x,
+            height: baseline + 0.25 * font_size - top,
+        };
+
+        min_x = cursor + MIN_GAP_SPACES * units_per_space;
+        result.push(ChordSymbolLayout {
+            tick,
+            text: text.to_string(),
+            runs,
+            bounding_box,
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::compute_layout;
+    use crate::layout::types::LayoutConfig;
+    use serde_json::json;
+
+    fn lead_sheet() -> serde_json::Value {
+        json!({
+            "instruments": [{
+                "id": "lead",
+                "name": "Lead",
+                "staves": [{
+                    "clef": "Treble",
+                    "voices": [{
+                        "notes": [
+                            {"tick": 0, "duration": 1920, "pitch": 72},
+                            {"tick": 1920, "duration": 1920, "pitch": 71},
+                            {"tick": 3840, "duration": 3840, "pitch": 72}
+                        ]
+                    }],
+                    "chord_symbols": [
+                        {"tick": 0, "root": {"step": "C", "alter": 0}, "kind": "MajorSeventh"},
+                        {"tick": 1920, "root": {"step": "B", "alter": -1}, "kind": "Dominant",
+                         "bass": {"step": "D", "alter": 0}},
+                        {"tick": 3840, "kind": "None"}
+                    ]
+                }]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_chord_symbols_positioned_above_staff() {
+        let layout = compute_layout(&lead_sheet(), &LayoutConfig::default());
+        let staff = &layout.systems[0].staff_groups[0].staves[0];
+        let top_line = staff.top_line_y();
+
+        assert_eq!(staff.chord_symbols.len(), 3);
+        let texts: Vec<&str> = staff
+            .chord_symbols
+            .iter()
+            .map(|c| c.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["Cmaj7", "B♭7/D", "N.C."]);
+
+        for chord in &staff.chord_symbols {
+            assert!(
+                chord.bounding_box.y + chord.bounding_box.height < top_line,
+                "chord {} must sit above the staff",
+                chord.text
+            );
+        }
+        // Shared baseline for all non-superscript runs
+        let baselines: Vec<f32> = staff
+            .chord_symbols
+            .iter()
+            .flat_map(|c| c.runs.iter())
+            .filter(|r| !r.superscript)
+            .map(|r| r.y)
+            .collect();
+        assert!(baselines.windows(2).all(|w| (w[0] - w[1]).abs() < 0.01));
+    }
+
+    #[test]
+    fn test_chord_symbol_extension_is_superscript() {
+        let layout = compute_layout(&lead_sheet(), &LayoutConfig::default());
+        let staff = &layout.systems[0].staff_groups[0].staves[0];
+        let bb7 = &staff.chord_symbols[1];
+
+        assert_eq!(bb7.runs.len(), 3);
+        assert_eq!(bb7.runs[0].text, "B♭");
+        assert!(bb7.runs[1].superscript);
+        assert_eq!(bb7.runs[1].text, "7");
+        assert!(bb7.runs[1].y < bb7.runs[0].y);
+        assert!(bb7.runs[1].font_size < bb7.runs[0].font_size);
+        assert_eq!(bb7.runs[2].text, "/D");
+        assert!(bb7.runs[2].x > bb7.runs[1].x);
+    }
+}
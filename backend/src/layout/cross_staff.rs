@@ -0,0 +1,125 @@
+//! Cross-staff note placement
+//!
+//! A cross-staff note belongs to a voice on one staff (its home staff) but is
+//! drawn on another staff of the same instrument. Layout keeps such notes in
+//! their home voice so beams, ties and slurs stay intact, and moves them
+//! vertically onto the target staff: the notehead y is computed with the
+//! target staff's clef and vertical offset instead of the home staff's.
+
+use std::collections::HashSet;
+
+use crate::layout::extraction::{NoteEvent, StaffData};
+use crate::layout::positioner;
+use crate::layout::types::{Glyph, TickRange, TieArc};
+
+/// Vertical placement of every staff of one instrument within a system.
+pub(crate) struct CrossStaffContext<'a> {
+    staves: &'a [StaffData],
+    /// Vertical offset (top line y) of each staff in this system
+    offsets: Vec<f32>,
+    units_per_space: f32,
+}
+
+impl<'a> CrossStaffContext<'a> {
+    pub(crate) fn new(staves: &'a [StaffData], offsets: Vec<f32>, units_per_space: f32) -> Self {
+        Self {
+            staves,
+            offsets,
+            units_per_space,
+        }
+    }
+
+    /// Staff the note is drawn on: its cross staff if valid, else `home_staff`.
+    pub(crate) fn display_staff(&self, home_staff: usize, note: &NoteEvent) -> usize {
+        match note.cross_staff {
+            Some(target) if target != home_staff && target < self.offsets.len() => target,
+            _ => home_staff,
+        }
+    }
+
+    /// Vertical shift that moves `note`, positioned as if on `home_staff`,
+    /// onto the staff it is drawn on. Zero for regular notes.
+    pub(crate) fn shift(&self, home_staff: usize, note: &NoteEvent) -> f32 {
+        let target = self.display_staff(home_staff, note);
+        if target == home_staff || home_staff >= self.offsets.len() {
+            return 0.0;
+        }
+        let y_on = |staff: usize| {
+            let clef = self.staves[staff].get_clef_at_tick(note.start_tick);
+            positioner::pitch_to_staff_y(
+                note.pitch,
+                clef,
+                self.units_per_space,
+                note.spelling,
+                self.staves[staff].line_count,
+            ) + self.offsets[staff]
+        };
+        y_on(target) - y_on(home_staff)
+    }
+
+    /// Vertical offset (top line y) of a staff in this system
+    pub(crate) fn staff_offset(&self, staff: usize) -> Option<f32> {
+        self.offsets.get(staff).copied()
+    }
+
+    /// The staff data of a staff of this instrument
+    pub(crate) fn staff(&self, staff: usize) -> Option<&'a StaffData> {
+        self.staves.get(staff)
+    }
+
+    /// Elements of `home_staff` in `tick_range` that are drawn on or
+    /// towards another staff
+    pub(crate) fn cross_staff_elements(
+        &self,
+        home_staff: usize,
+        tick_range: &TickRange,
+    ) -> CrossStaffElements {
+        let mut elements = CrossStaffElements::default();
+        let Some(staff) = self.staff(home_staff) else {
+            return elements;
+        };
+        for (voice_index, voice) in staff.voices.iter().enumerate() {
+            let in_range = voice.notes.iter().filter(|n| {
+                n.start_tick >= tick_range.start_tick && n.start_tick < tick_range.end_tick
+            });
+            for (event_index, note) in in_range.enumerate() {
+                if self.display_staff(home_staff, note) != home_staff {
+                    elements.voices.insert(voice_index);
+                    elements.notes.insert((voice_index, event_index));
+                    elements.note_ids.insert(note.note_id.clone());
+                }
+            }
+        }
+        elements
+    }
+}
+
+/// Cross-staff notes of one staff with everything attached to them
+///
+/// Their noteheads sit on the other staff, and the stems and beams of their
+/// voice may span the gap between the staves; both follow the staves when
+/// they move apart, so vertical spacing leaves them out.
+#[derive(Debug, Default)]
+pub(crate) struct CrossStaffElements {
+    /// Voices with at least one cross-staff note
+    voices: HashSet<usize>,
+    /// (voice index, event index within the system) of cross-staff notes
+    notes: HashSet<(usize, usize)>,
+    note_ids: HashSet<String>,
+}
+
+impl CrossStaffElements {
+    /// A glyph of a cross-staff note, or a stem or beam of a voice with one
+    pub(crate) fn contains_glyph(&self, glyph: &Glyph) -> bool {
+        let source = &glyph.source_reference;
+        let stem_or_beam = glyph.codepoint == "\u{0000}" || glyph.codepoint == "\u{0001}";
+        self.notes
+            .contains(&(source.voice_index, source.event_index))
+            || (stem_or_beam && self.voices.contains(&source.voice_index))
+    }
+
+    /// A tie or slur to or from a cross-staff note
+    pub(crate) fn contains_arc(&self, arc: &TieArc) -> bool {
+        self.note_ids.contains(&arc.note_id_start) || self.note_ids.contains(&arc.note_id_end)
+    }
+}
@@ -0,0 +1,474 @@
+//! Tempo marks, rehearsal marks and text directions
+//!
+//! Directions are placed per system once all staff content and the volta
+//! and ottava brackets are positioned. Each one starts just clear of the
+//! music under it and is pushed outward past any bracket or earlier
+//! direction it would overlap.
+
+use std::collections::HashMap;
+
+use crate::domain::events::direction::{
+    Enclosure, Placement, RehearsalMark, TextDirection, TextDirectionStyle,
+};
+use crate::domain::events::tempo::TempoEvent;
+use crate::layout::extraction::InstrumentData;
+use crate::layout::note_layout;
+use crate::layout::stems::Stem;
+use crate::layout::types::{
+    BoundingBox, DirectionKind, DirectionLayout, EnclosureLayout, MetronomeLayout, Staff, System,
+};
+
+/// Tempo text font size in staff spaces
+const TEMPO_FONT_SPACES: f32 = 1.8;
+/// Expression text font size in staff spaces
+const EXPRESSION_FONT_SPACES: f32 = 1.6;
+/// Rehearsal mark font size in staff spaces
+const REHEARSAL_FONT_SPACES: f32 = 2.0;
+/// Approximate advance width per character relative to font size
+const CHAR_WIDTH_FACTOR: f32 = 0.6;
+/// Text ascent and descent relative to font size
+const ASCENT: f32 = 0.75;
+const DESCENT: f32 = 0.25;
+/// Space between rehearsal text and its frame, in staff spaces
+const ENCLOSURE_PADDING_SPACES: f32 = 0.4;
+/// Clearance between the music and a direction, in staff spaces
+const CLEARANCE_SPACES: f32 = 1.0;
+/// Gap kept from brackets and other directions, in staff spaces
+const GAP_SPACES: f32 = 0.5;
+/// Metronome note size relative to the notation font (one em = 4 staff spaces)
+const METRONOME_GLYPH_SCALE: f32 = 0.55;
+/// Metronome note height and advance widths relative to its font size
+const METRONOME_NOTE_HEIGHT: f32 = 0.7;
+const METRONOME_NOTE_WIDTH: f32 = 0.32;
+const METRONOME_DOT_WIDTH: f32 = 0.18;
+/// SMuFL metAugmentationDot
+const METRONOME_DOT: char = '\u{ECB7}';
+/// Volta bracket extent around its line: label above, hooks below
+/// (as drawn by the renderer)
+const VOLTA_LABEL_HEIGHT: f32 = 32.0;
+const VOLTA_HOOK_LENGTH: f32 = 15.0;
+/// Ottava bracket extent: label on the outer side, hooks toward the staff
+const OTTAVA_LABEL_HEIGHT: f32 = 28.0;
+const OTTAVA_HOOK_LENGTH: f32 = 12.0;
+
+/// Lay out the tempo marks, rehearsal marks and text directions of one system.
+///
+/// Rehearsal and tempo marks sit above the top staff (rehearsal marks
+/// first, so a tempo mark at the same tick stacks above the box); text
+/// directions sit above or below their own staff.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_directions(
+    system: &System,
+    instruments: &[InstrumentData],
+    tempo_marks: &[TempoEvent],
+    rehearsal_marks: &[RehearsalMark],
+    note_positions: &HashMap<u32, f32>,
+    measure_x_bounds: &HashMap<u32, (f32, f32)>,
+    unified_left_margin: f32,
+    units_per_space: f32,
+) -> Vec<DirectionLayout> {
+    let tick_range = system.tick_range;
+    let in_system = |tick: u32| tick >= tick_range.start_tick && tick < tick_range.end_tick;
+    // Left edge of the notehead at `tick`
+    let note_x = |tick: u32| {
+        note_layout::tick_to_x(tick, note_positions, measure_x_bounds, unified_left_margin)
+            - Stem::NOTEHEAD_WIDTH / 2.0
+    };
+
+    let mut obstacles = bracket_boxes(system);
+    let mut result = Vec::new();
+
+    if let Some(top_staff) = system.staff_groups.first().and_then(|g| g.staves.first()) {
+        for mark in rehearsal_marks.iter().filter(|m| in_system(m.tick)) {
+            // Aligned with the barline when the mark starts a measure
+            let x = measure_x_bounds
+                .get(&mark.tick)
+                .map_or_else(|| note_x(mark.tick), |(start, _)| *start);
+            let direction = rehearsal_layout(mark, x, units_per_space);
+            result.push(place(
+                direction,
+                top_staff,
+                Placement::Above,
+                &mut obstacles,
+                units_per_space,
+            ));
+        }
+        for event in tempo_marks.iter().filter(|e| in_system(e.tick.value())) {
+            let Some(direction) = tempo_layout(event, note_x(event.tick.value()), units_per_space)
+            else {
+                continue;
+            };
+            result.push(place(
+                direction,
+                top_staff,
+                Placement::Above,
+                &mut obstacles,
+                units_per_space,
+            ));
+        }
+    }
+
+    for (group_index, (instrument, group)) in
+        instruments.iter().zip(&system.staff_groups).enumerate()
+    {
+        for (staff_index, (staff_data, staff)) in
+            instrument.staves.iter().zip(&group.staves).enumerate()
+        {
+            for text in staff_data
+                .text_directions
+                .iter()
+                .filter(|d| in_system(d.tick))
+            {
+                let mut direction = text_layout(text, note_x(text.tick), units_per_space);
+                direction.staff_group_index = group_index;
+                direction.staff_index = staff_index;
+                result.push(place(
+                    direction,
+                    staff,
+                    text.placement,
+                    &mut obstacles,
+                    units_per_space,
+                ));
+            }
+        }
+    }
+
+    result
+}
+
+/// Boxes covered by the system's volta and ottava brackets, labels and hooks included
+fn bracket_boxes(system: &System) -> Vec<BoundingBox> {
+    let span = |x_start: f32, x_end: f32, top: f32, bottom: f32| BoundingBox {
+        x: x_start,
+        y: top,
+        width: x_end - x_start,
+        height: bottom - top,
+    };
+    let voltas = system.volta_bracket_layouts.iter().map(|v| {
+        span(
+            v.x_start,
+            v.x_end,
+            v.y - VOLTA_LABEL_HEIGHT,
+            v.y + VOLTA_HOOK_LENGTH,
+        )
+    });
+    let ottavas = system.ottava_bracket_layouts.iter().map(|o| {
+        if o.above {
+            span(
+                o.x_start,
+                o.x_end,
+                o.y - OTTAVA_LABEL_HEIGHT,
+                o.y + OTTAVA_HOOK_LENGTH,
+            )
+        } else {
+            span(
+                o.x_start,
+                o.x_end,
+                o.y - OTTAVA_HOOK_LENGTH,
+                o.y + OTTAVA_LABEL_HEIGHT,
+            )
+        }
+    });
+    voltas.chain(ottavas).collect()
+}
+
+/// Move `direction` just clear of the staff content under it, then outward
+/// past every obstacle it overlaps, and record it as a new obstacle.
+fn place(
+    mut direction: DirectionLayout,
+    staff: &Staff,
+    placement: Placement,
+    obstacles: &mut Vec<BoundingBox>,
+    units_per_space: f32,
+) -> DirectionLayout {
+    let bbox = direction.bounding_box;
+    let (top, bottom) = staff_extent(staff, bbox.x, bbox.x + bbox.width);
+    let clearance = CLEARANCE_SPACES * units_per_space;
+    shift(
+        &mut direction,
+        match placement {
+            Placement::Above => top - clearance - (bbox.y + bbox.height),
+            Placement::Below => bottom + clearance - bbox.y,
+        },
+    );
+
+    // Every push moves the direction fully past one obstacle, always in the
+    // same direction, so each obstacle is hit at most once.
+    let gap = GAP_SPACES * units_per_space;
+    while let Some(hit) = obstacles
+        .iter()
+        .find(|o| o.intersects(&direction.bounding_box))
+        .copied()
+    {
+        let bbox = direction.bounding_box;
+        shift(
+            &mut direction,
+            match placement {
+                Placement::Above => hit.y - gap - (bbox.y + bbox.height),
+                Placement::Below => hit.y + hit.height + gap - bbox.y,
+            },
+        );
+    }
+
+    obstacles.push(direction.bounding_box);
+    direction
+}
+
+fn shift(direction: &mut DirectionLayout, dy: f32) {
+    direction.y += dy;
+    direction.bounding_box.y += dy;
+    if let Some(enclosure) = &mut direction.enclosure {
+        enclosure.bounding_box.y += dy;
+    }
+}
+
+/// Topmost and bottommost drawn y of a staff between `x0` and `x1`, staff
+/// lines included
+fn staff_extent(staff: &Staff, x0: f32, x1: f32) -> (f32, f32) {
+    let mut top = staff.top_line_y();
+    let mut bottom = staff.bottom_line_y();
+    let mut include = |left: f32, right: f32, y0: f32, y1: f32| {
+        if right >= x0 && left <= x1 {
+            top = top.min(y0.min(y1));
+            bottom = bottom.max(y0.max(y1));
+        }
+    };
+
+    for glyph in staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| run.glyphs.iter())
+        .chain(&staff.structural_glyphs)
+    {
+        // Stems and beams store their far end in the bounding box
+        let (p, b) = (glyph.position, glyph.bounding_box);
+        include(
+            p.x.min(b.x),
+            (p.x + b.width.max(0.0)).max(b.x + b.width),
+            p.y.min(b.y),
+            p.y.max(b.y + b.height),
+        );
+    }
+    for ledger in &staff.ledger_lines {
+        include(
+            ledger.start_x,
+            ledger.end_x,
+            ledger.y_position,
+            ledger.y_position,
+        );
+    }
+    for dot in &staff.notation_dots {
+        include(dot.x, dot.x, dot.y - dot.radius, dot.y + dot.radius);
+    }
+    for arc in staff.tie_arcs.iter().chain(&staff.slur_arcs) {
+        let points = [arc.start, arc.cp1, arc.cp2, arc.end];
+        let (mut left, mut right) = (f32::INFINITY, f32::NEG_INFINITY);
+        let (mut y0, mut y1) = (f32::INFINITY, f32::NEG_INFINITY);
+        for p in points {
+            left = left.min(p.x);
+            right = right.max(p.x);
+            y0 = y0.min(p.y);
+            y1 = y1.max(p.y);
+        }
+        include(left, right, y0, y1);
+    }
+    for chord in &staff.chord_symbols {
+        let b = chord.bounding_box;
+        include(b.x, b.x + b.width, b.y, b.y + b.height);
+    }
+    for mark in &staff.pedal_marks {
+        for glyph in &mark.glyphs {
+            let b = glyph.bounding_box;
+            include(b.x, b.x + b.width, b.y, b.y + b.height);
+        }
+        for text in &mark.texts {
+            let width = text.text.chars().count() as f32 * CHAR_WIDTH_FACTOR * text.font_size;
+            include(
+                text.x,
+                text.x + width,
+                text.y - ASCENT * text.font_size,
+                text.y,
+            );
+        }
+        if let Some(line) = &mark.line {
+            for p in &line.points {
+                include(p.x, p.x, p.y, p.y);
+            }
+        }
+    }
+
+    (top, bottom)
+}
+
+fn text_width(text: &str, font_size: f32) -> f32 {
+    text.chars().count() as f32 * CHAR_WIDTH_FACTOR * font_size
+}
+
+/// Direction with its baseline at y = 0 and a single-line text box
+fn direction_at(
+    kind: DirectionKind,
+    tick: u32,
+    text: &str,
+    x: f32,
+    font_size: f32,
+) -> DirectionLayout {
+    DirectionLayout {
+        kind,
+        tick,
+        text: text.to_string(),
+        x,
+        y: 0.0,
+        font_size,
+        bold: kind != DirectionKind::Expression,
+        italic: kind == DirectionKind::Expression,
+        metronome: None,
+        enclosure: None,
+        bounding_box: BoundingBox {
+            x,
+            y: -ASCENT * font_size,
+            width: text_width(text, font_size),
+            height: (ASCENT + DESCENT) * font_size,
+        },
+        staff_group_index: 0,
+        staff_index: 0,
+    }
+}
+
+/// Boxed (or circled) rehearsal mark with its frame's left edge at `x`
+fn rehearsal_layout(mark: &RehearsalMark, x: f32, units_per_space: f32) -> DirectionLayout {
+    let font_size = REHEARSAL_FONT_SPACES * units_per_space;
+    let padding = ENCLOSURE_PADDING_SPACES * units_per_space;
+    let mut direction = direction_at(
+        DirectionKind::Rehearsal,
+        mark.tick,
+        &mark.label,
+        x + padding,
+        font_size,
+    );
+    let text_box = direction.bounding_box;
+    let mut frame = BoundingBox {
+        x,
+        y: text_box.y - padding,
+        width: text_box.width + 2.0 * padding,
+        height: text_box.height + 2.0 * padding,
+    };
+    if mark.enclosure == Enclosure::Circle {
+        // Square around the text, so the circle clears its corners
+        let side = frame.width.max(frame.height);
+        frame.y -= (side - frame.height) / 2.0;
+        direction.x += (side - frame.width) / 2.0;
+        frame.width = side;
+        frame.height = side;
+    }
+    direction.bounding_box = frame;
+    if mark.enclosure != Enclosure::None {
+        direction.enclosure = Some(EnclosureLayout {
+            shape: mark.enclosure,
+            bounding_box: frame,
+        });
+    }
+    direction
+}
+
+/// Tempo text and/or metronome mark starting at `x`; `None` for events
+/// that are not printed
+fn tempo_layout(event: &TempoEvent, x: f32, units_per_space: f32) -> Option<DirectionLayout> {
+    let mark = event.mark.as_ref()?;
+    let font_size = TEMPO_FONT_SPACES * units_per_space;
+    let text = mark.text.as_deref().unwrap_or("");
+    let mut direction = direction_at(DirectionKind::Tempo, event.tick.value(), text, x, font_size);
+    if !mark.show_metronome {
+        return (!text.is_empty()).then_some(direction);
+    }
+
+    // Beat units without a metronome note are shown as quarters
+    let (glyph, bpm) = match metronome_glyph(event.beat_ticks) {
+        Some(glyph) => (glyph, event.beats_per_minute()),
+        None => (metronome_glyph(960)?, event.quarter_bpm()),
+    };
+    let glyph_font_size = METRONOME_GLYPH_SCALE * 4.0 * units_per_space;
+    let space = if text.is_empty() {
+        0.0
+    } else {
+        0.5 * font_size
+    };
+    let glyph_x = x + text_width(text, font_size) + space;
+    let dots = glyph.chars().filter(|&c| c == METRONOME_DOT).count() as f32;
+    let glyph_width = (METRONOME_NOTE_WIDTH + dots * METRONOME_DOT_WIDTH) * glyph_font_size;
+    let equals = format!("= {}", format_bpm(bpm));
+    let text_x = glyph_x + glyph_width + 0.25 * font_size;
+
+    let bbox = &mut direction.bounding_box;
+    bbox.width = text_x + text_width(&equals, font_size) - x;
+    let top = (-ASCENT * font_size).min(-METRONOME_NOTE_HEIGHT * glyph_font_size);
+    bbox.height += bbox.y - top;
+    bbox.y = top;
+    direction.metronome = Some(MetronomeLayout {
+        glyph,
+        glyph_x,
+        glyph_font_size,
+        text: equals,
+        text_x,
+    });
+    Some(direction)
+}
+
+/// SMuFL metronome note (with augmentation dots) for a beat unit in ticks
+fn metronome_glyph(beat_ticks: u32) -> Option<String> {
+    const NOTES: [(u32, char); 6] = [
+        (3840, '\u{ECA2}'), // metNoteWhole
+        (1920, '\u{ECA3}'), // metNoteHalfUp
+        (960, '\u{ECA5}'),  // metNoteQuarterUp
+        (480, '\u{ECA7}'),  // metNote8thUp
+        (240, '\u{ECA9}'),  // metNote16thUp
+        (120, '\u{ECAB}'),  // metNote32ndUp
+    ];
+    NOTES.iter().find_map(|&(base, note)| {
+        (0..=2usize)
+            .find(|&dots| (0..=dots).map(|d| base >> d).sum::<u32>() == beat_ticks)
+            .map(|dots| {
+                std::iter::once(note)
+                    .chain(std::iter::repeat(METRONOME_DOT).take(dots))
+                    .collect()
+            })
+    })
+}
+
+/// "120", or one decimal for fractional tempos ("72.5")
+fn format_bpm(bpm: f64) -> String {
+    if (bpm - bpm.round()).abs() < 0.05 {
+        format!("{}", bpm.round() as i64)
+    } else {
+        format!("{bpm:.1}")
+    }
+}
+
+/// Staff text: bold for tempo words ("Allegro", "rit."), italic otherwise
+fn text_layout(text: &TextDirection, x: f32, units_per_space: f32) -> DirectionLayout {
+    let (kind, font_spaces) = match text.style {
+        TextDirectionStyle::Tempo => (DirectionKind::Tempo, TEMPO_FONT_SPACES),
+        TextDirectionStyle::Expression => (DirectionKind::Expression, EXPRESSION_FONT_SPACES),
+    };
+    direction_at(
+        kind,
+        text.tick,
+        &text.text,
+        x,
+        font_spaces * units_per_space,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metronome_glyphs_for_dotted_beats() {
+        assert_eq!(metronome_glyph(960).as_deref(), Some("\u{ECA5}"));
+        assert_eq!(metronome_glyph(1440).as_deref(), Some("\u{ECA5}\u{ECB7}"));
+        assert_eq!(metronome_glyph(2880).as_deref(), Some("\u{ECA3}\u{ECB7}"));
+        assert_eq!(metronome_glyph(1000), None);
+        assert_eq!(format_bpm(120.0), "120");
+        assert_eq!(format_bpm(72.5), "72.5");
+    }
+}
@@ -4,6 +4,11 @@
 //! Contains tick-to-measure conversion helpers, instrument/staff/voice
 //! data structures, and the primary extraction functions.
 
+use crate::domain::events::note::TabPosition;
+use crate::domain::staff::{DEFAULT_STAFF_LINES, MAX_STAFF_LINES};
+use crate::domain::tablature::Tablature;
+use crate::layout::tablature::{self, TAB_CLEF};
+
 /// Compute the start tick of a measure, accounting for pickup/anacrusis.
 pub(crate) fn measure_start_tick(
     measure_index: usize,
@@ -107,6 +112,8 @@ pub(crate) fn actual_tick_to_measure(
 pub(crate) struct InstrumentData {
     pub(crate) id: String,
     pub(crate) name: String,
+    /// Abbreviated name for later systems, if the score has one
+    pub(crate) short_name: Option<String>,
     pub(crate) staves: Vec<StaffData>,
 }
 
@@ -125,6 +132,16 @@ pub(crate) struct StaffData {
     /// Octave shift regions for this staff: (start_tick, end_tick, display_shift).
     /// display_shift: -8 = 8va (display one octave lower), +8 = 8vb (display one octave higher).
     pub(crate) octave_shift_regions: Vec<(u32, u32, i8)>,
+    /// Chord symbols anchored to this staff, sorted by tick
+    pub(crate) chord_symbols: Vec<crate::domain::events::chord_symbol::ChordSymbolEvent>,
+    /// Pedal regions anchored to this staff, sorted by start tick
+    pub(crate) pedal_regions: Vec<crate::domain::events::pedal::PedalRegion>,
+    /// Text directions anchored to this staff, sorted by tick
+    pub(crate) text_directions: Vec<crate::domain::events::direction::TextDirection>,
+    /// Number of staff lines (5 unless the score says otherwise)
+    pub(crate) line_count: u8,
+    /// String tuning of a tablature staff
+    pub(crate) tablature: Option<Tablature>,
 }
 
 impl StaffData {
@@ -256,6 +273,21 @@ pub(crate) struct NoteEvent {
     pub(crate) stem_down: Option<bool>,
     /// Fingering annotations from MusicXML `<technical><fingering>` elements
     pub(crate) fingering: Vec<crate::domain::events::note::FingeringAnnotation>,
+    /// Cross-staff note: index of the instrument staff it is drawn on
+    pub(crate) cross_staff: Option<usize>,
+    /// Grace note type, slash and written value (grace notes only)
+    pub(crate) grace: Option<crate::domain::events::grace::GraceAnnotation>,
+    /// Arpeggio sign on this chord note
+    pub(crate) arpeggio: Option<crate::domain::events::ornament::ArpeggioStyle>,
+    /// If a glissando starts on this note, the ID of the note where it ends
+    pub(crate) gliss_next: Option<String>,
+    /// Glissando drawn as a wavy line
+    pub(crate) gliss_wavy: bool,
+    /// Single- or two-note tremolo
+    pub(crate) tremolo: Option<crate::domain::events::ornament::Tremolo>,
+    /// String and fret on a tablature staff (filled in by
+    /// `tablature::assign_tab_positions` where the score has none)
+    pub(crate) tab: Option<TabPosition>,
 }
 
 pub(crate) fn extract_measures(
@@ -500,6 +532,55 @@ pub(crate) fn extract_measures(
     }
 }
 
+/// Extract printed tempo marks (tempo events with a `mark`), sorted by tick
+pub(crate) fn extract_tempo_marks(
+    score: &serde_json::Value,
+) -> Vec<crate::domain::events::tempo::TempoEvent> {
+    let mut marks: Vec<crate::domain::events::tempo::TempoEvent> =
+        score["global_structural_events"]
+            .as_array()
+            .map(|events| {
+                events
+                    .iter()
+                    .filter_map(|e| serde_json::from_value(e["Tempo"].clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+    marks.retain(|e: &crate::domain::events::tempo::TempoEvent| e.mark.is_some());
+    marks.sort_by_key(|e| e.tick);
+    marks
+}
+
+/// Extract rehearsal marks, sorted by tick
+pub(crate) fn extract_rehearsal_marks(
+    score: &serde_json::Value,
+) -> Vec<crate::domain::events::direction::RehearsalMark> {
+    let mut marks: Vec<crate::domain::events::direction::RehearsalMark> = score["rehearsal_marks"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    marks.sort_by_key(|m| m.tick);
+    marks
+}
+
+/// Extract part groups (brackets and braces across instruments)
+pub(crate) fn extract_part_groups(
+    score: &serde_json::Value,
+) -> Vec<crate::domain::part_group::PartGroup> {
+    score["part_groups"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Extract instruments from CompiledScore JSON
 pub(crate) fn extract_instruments(
     score: &serde_json::Value,
@@ -515,6 +596,10 @@ pub(crate) fn extract_instruments(
                 .as_str()
                 .unwrap_or("Instrument")
                 .to_string();
+            let short_name = instrument["short_name"]
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(str::to_string);
             let mut staves = Vec::new();
 
             if let Some(staves_array) = instrument["staves"].as_array() {
@@ -645,6 +730,26 @@ pub(crate) fn extract_instruments(
                                                     .collect()
                                             })
                                             .unwrap_or_default(),
+                                        cross_staff: note_item["cross_staff"]
+                                            .as_u64()
+                                            .map(|s| s as usize),
+                                        grace: serde_json::from_value(note_item["grace"].clone())
+                                            .ok(),
+                                        arpeggio: serde_json::from_value(
+                                            note_item["arpeggio"].clone(),
+                                        )
+                                        .ok(),
+                                        gliss_next: note_item["gliss_next"]
+                                            .as_str()
+                                            .map(|s| s.to_string()),
+                                        gliss_wavy: note_item["gliss_wavy"]
+                                            .as_bool()
+                                            .unwrap_or(false),
+                                        tremolo: serde_json::from_value(
+                                            note_item["tremolo"].clone(),
+                                        )
+                                        .ok(),
+                                        tab: serde_json::from_value(note_item["tab"].clone()).ok(),
                                     });
                                 }
                             }
@@ -745,7 +850,62 @@ pub(crate) fn extract_instruments(
                     clef_events.sort_by_key(|(t, _)| *t);
                     clef_events.dedup_by_key(|(t, _)| *t);
 
-                    staves.push(StaffData {
+                    let mut chord_symbols: Vec<
+                        crate::domain::events::chord_symbol::ChordSymbolEvent,
+                    > = staff["chord_symbols"]
+                        .as_array()
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    chord_symbols.sort_by_key(|cs| cs.tick);
+
+                    let mut pedal_regions: Vec<crate::domain::events::pedal::PedalRegion> =
+                        staff["pedal_regions"]
+                            .as_array()
+                            .map(|arr| {
+                                arr.iter()
+                                    .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                    pedal_regions.sort_by_key(|r| r.start_tick);
+
+                    let mut text_directions: Vec<crate::domain::events::direction::TextDirection> =
+                        staff["text_directions"]
+                            .as_array()
+                            .map(|arr| {
+                                arr.iter()
+                                    .filter_map(|v| serde_json::from_value(v.clone()).ok())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                    text_directions.sort_by_key(|d| d.tick);
+
+                    let line_count = staff["staff_lines"]
+                        .as_u64()
+                        .map_or(DEFAULT_STAFF_LINES, |n| {
+                            n.clamp(1, MAX_STAFF_LINES as u64) as u8
+                        });
+                    let tablature: Option<Tablature> =
+                        serde_json::from_value(staff["tablature"].clone()).ok();
+                    // One-line and tablature staves show no key signature;
+                    // tablature staves show a TAB sign instead of a clef
+                    let (key_sharps, key_signature_events) =
+                        if line_count == 1 || tablature.is_some() {
+                            (0, Vec::new())
+                        } else {
+                            (key_sharps, key_signature_events)
+                        };
+                    let (clef, clef_events) = if tablature.is_some() {
+                        (TAB_CLEF.to_string(), Vec::new())
+                    } else {
+                        (clef, clef_events)
+                    };
+
+                    let mut staff_data = StaffData {
                         voices,
                         clef,
                         time_numerator,
@@ -754,11 +914,23 @@ pub(crate) fn extract_instruments(
                         key_signature_events,
                         clef_events,
                         octave_shift_regions: Vec::new(),
-                    });
+                        chord_symbols,
+                        pedal_regions,
+                        text_directions,
+                        line_count,
+                        tablature,
+                    };
+                    tablature::assign_tab_positions(&mut staff_data);
+                    staves.push(staff_data);
                 }
             }
 
-            instruments.push(InstrumentData { id, name, staves });
+            instruments.push(InstrumentData {
+                id,
+                name,
+                short_name,
+                staves,
+            });
         }
     }
 
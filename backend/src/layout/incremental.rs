@@ -0,0 +1,320 @@
+//! Incremental re-layout
+//!
+//! Keeps the previous layout of a score between edits. When notes change in a
+//! tick range, only the measures in that range are measured again and only
+//! the systems whose content or breaks changed are laid out again: systems
+//! before the edit and after the breaks re-synchronise are reused as they
+//! are. The result is byte-identical to a full `compute_layout`.
+
+use std::collections::HashMap;
+
+use crate::layout::breaker::MeasureInfo;
+use crate::layout::types::{GlobalLayout, LayoutConfig, System, TickRange};
+use crate::layout::{
+    ScoreContext, SystemSpacing, break_score, effective_config, layout_system, prepare_score,
+    stack_systems,
+};
+
+/// Voice keys holding notes and rests; every other part of the score is
+/// compared as a whole to decide whether incremental layout is possible
+const NOTE_KEYS: [&str; 3] = ["interval_events", "notes", "rest_events"];
+
+/// Work done by the last layout or update
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpdateStats {
+    /// True when the whole score was laid out again
+    pub full: bool,
+    /// Measures whose width was computed (the rest came from the cache)
+    pub measures_measured: usize,
+    /// Systems laid out
+    pub systems_laid_out: usize,
+    /// Systems reused from the previous layout
+    pub systems_reused: usize,
+}
+
+/// A laid-out system before it is moved into place
+struct CachedSystem {
+    /// Laid out with its top at y = 0
+    system: System,
+    /// Nominal advance and skyline for stacking
+    spacing: SystemSpacing,
+    /// The measures it holds
+    measures: Vec<MeasureInfo>,
+}
+
+/// Layout of one score that is updated in place as the score is edited
+///
+/// ```ignore
+/// let mut layout = IncrementalLayout::new(&score_json, &LayoutConfig::default());
+/// // ... change notes between ticks 7680 and 11520 ...
+/// let updated = layout.update(&edited_json, TickRange { start_tick: 7680, end_tick: 11520 });
+/// ```
+pub struct IncrementalLayout {
+    config: LayoutConfig,
+    score: serde_json::Value,
+    context: ScoreContext,
+    systems: Vec<CachedSystem>,
+    layout: GlobalLayout,
+    stats: UpdateStats,
+}
+
+impl IncrementalLayout {
+    /// Lay out a score from scratch
+    pub fn new(score: &serde_json::Value, config: &LayoutConfig) -> Self {
+        let effective = effective_config(config);
+        let context = prepare_score(score, &effective, |_, _, _| None);
+        let mut this = Self {
+            config: config.clone(),
+            score: score.clone(),
+            layout: GlobalLayout {
+                systems: Vec::new(),
+                total_width: 0.0,
+                total_height: 0.0,
+                units_per_space: config.units_per_space,
+                pages: Vec::new(),
+            },
+            stats: UpdateStats {
+                full: true,
+                measures_measured: context.measure_infos.len(),
+                ..UpdateStats::default()
+            },
+            context,
+            systems: Vec::new(),
+        };
+        this.relayout_systems(None);
+        this
+    }
+
+    /// The current layout
+    pub fn layout(&self) -> &GlobalLayout {
+        &self.layout
+    }
+
+    /// Work done by the last layout or update
+    pub fn stats(&self) -> UpdateStats {
+        self.stats
+    }
+
+    /// Re-layout after notes or rests changed within `changed`
+    ///
+    /// `changed` must cover every tick where notes or rests were added,
+    /// removed or modified. Ties, slurs and glissandos reaching into or out of
+    /// the range are followed automatically. Any other change to the score
+    /// (measures, clefs, keys, directions, instruments, ...) falls back to a
+    /// full layout.
+    pub fn update(&mut self, score: &serde_json::Value, changed: TickRange) -> &GlobalLayout {
+        let effective = effective_config(&self.config);
+        if without_notes(score) != without_notes(&self.score) {
+            self.score = score.clone();
+            self.context = prepare_score(score, &effective, |_, _, _| None);
+            self.systems.clear();
+            self.stats = UpdateStats {
+                full: true,
+                measures_measured: self.context.measure_infos.len(),
+                ..UpdateStats::default()
+            };
+            self.relayout_systems(None);
+            return &self.layout;
+        }
+
+        let old_measures = &self.context.measure_infos;
+        let context = prepare_score(score, &effective, |index, start, end| {
+            old_measures
+                .get(index)
+                .filter(|m| m.start_tick == start && m.end_tick == end)
+                .filter(|m| !overlaps(m.start_tick, m.end_tick, &changed))
+                .map(|m| m.width)
+        });
+        let measured = context
+            .measure_infos
+            .iter()
+            .enumerate()
+            .filter(|(i, m)| match old_measures.get(*i) {
+                Some(old) => {
+                    old.start_tick != m.start_tick
+                        || old.end_tick != m.end_tick
+                        || overlaps(m.start_tick, m.end_tick, &changed)
+                }
+                None => true,
+            })
+            .count();
+
+        // Spanners link systems: widen the range over old and new ones alike
+        let mut spans = note_links(&self.context);
+        spans.extend(note_links(&context));
+        let dirty = widen(changed, &spans);
+
+        self.score = score.clone();
+        self.context = context;
+        self.stats = UpdateStats {
+            measures_measured: measured,
+            ..UpdateStats::default()
+        };
+        self.relayout_systems(Some(dirty));
+        &self.layout
+    }
+
+    /// Re-layout with a new configuration, e.g. after a viewport resize
+    ///
+    /// Measure widths only depend on the engraving style's spacing and are
+    /// reused while it stays the same; every system is laid out again.
+    pub fn set_config(&mut self, config: &LayoutConfig) -> &GlobalLayout {
+        let effective = effective_config(config);
+        let same_spacing = config.engraving.spacing == self.config.engraving.spacing;
+        let old_measures = &self.context.measure_infos;
+        self.context = prepare_score(&self.score, &effective, |index, start, end| {
+            old_measures
+                .get(index)
+                .filter(|m| same_spacing && m.start_tick == start && m.end_tick == end)
+                .map(|m| m.width)
+        });
+        self.config = config.clone();
+        self.systems.clear();
+        self.stats = UpdateStats {
+            full: true,
+            measures_measured: if same_spacing {
+                0
+            } else {
+                self.context.measure_infos.len()
+            },
+            ..UpdateStats::default()
+        };
+        self.relayout_systems(None);
+        &self.layout
+    }
+
+    /// Break the score and lay out every system that cannot be reused
+    ///
+    /// A cached system is reused when a new system holds exactly the same
+    /// measures at the same width, both are or are not the first system,
+    /// and it does not overlap `dirty` (`None` = nothing can be reused).
+    fn relayout_systems(&mut self, dirty: Option<TickRange>) {
+        let config = effective_config(&self.config);
+        let context = &self.context;
+        let mut old: HashMap<u32, CachedSystem> = std::mem::take(&mut self.systems)
+            .into_iter()
+            .map(|cached| (cached.system.tick_range.start_tick, cached))
+            .collect();
+
+        let mut systems = Vec::new();
+        for mut system in break_score(context, &config) {
+            let measures: Vec<MeasureInfo> = context
+                .measure_infos
+                .iter()
+                .filter(|m| {
+                    m.start_tick < system.tick_range.end_tick
+                        && m.end_tick > system.tick_range.start_tick
+                })
+                .cloned()
+                .collect();
+            let reusable = dirty.is_some_and(|dirty| {
+                !overlaps(
+                    system.tick_range.start_tick,
+                    system.tick_range.end_tick,
+                    &dirty,
+                )
+            }) && old
+                .get(&system.tick_range.start_tick)
+                .is_some_and(|cached| {
+                    cached.system.tick_range == system.tick_range
+                        && cached.system.bounding_box.width == system.bounding_box.width
+                        && (cached.system.index == 0) == (system.index == 0)
+                        && cached.measures == measures
+                });
+
+            if reusable {
+                let mut cached = old
+                    .remove(&system.tick_range.start_tick)
+                    .expect("reusable system is cached");
+                cached.system.index = system.index;
+                systems.push(cached);
+                self.stats.systems_reused += 1;
+            } else {
+                let spacing = layout_system(context, &config, &mut system);
+                systems.push(CachedSystem {
+                    system,
+                    spacing,
+                    measures,
+                });
+                self.stats.systems_laid_out += 1;
+            }
+        }
+
+        let spacing: Vec<SystemSpacing> = systems
+            .iter()
+            .map(|cached| cached.spacing.clone())
+            .collect();
+        let placed: Vec<System> = systems.iter().map(|cached| cached.system.clone()).collect();
+        self.layout = stack_systems(context, &config, placed, &spacing);
+        self.systems = systems;
+    }
+}
+
+/// The score with the notes and rests of every voice removed
+fn without_notes(score: &serde_json::Value) -> serde_json::Value {
+    let mut skeleton = score.clone();
+    if let Some(instruments) = skeleton["instruments"].as_array_mut() {
+        for staff in instruments
+            .iter_mut()
+            .filter_map(|i| i["staves"].as_array_mut())
+            .flatten()
+        {
+            for voice in staff["voices"].as_array_mut().into_iter().flatten() {
+                if let Some(voice) = voice.as_object_mut() {
+                    for key in NOTE_KEYS {
+                        voice.remove(key);
+                    }
+                }
+            }
+        }
+    }
+    skeleton
+}
+
+/// Tick spans of ties, slurs and glissandos, from the first note's start to
+/// the end of the note they lead to
+fn note_links(context: &ScoreContext) -> Vec<(u32, u32)> {
+    let notes: Vec<_> = context
+        .instruments
+        .iter()
+        .flat_map(|i| &i.staves)
+        .flat_map(|s| &s.voices)
+        .flat_map(|v| &v.notes)
+        .collect();
+    let ends: HashMap<&str, u32> = notes
+        .iter()
+        .map(|n| (n.note_id.as_str(), n.start_tick + n.duration_ticks))
+        .collect();
+    notes
+        .iter()
+        .flat_map(|n| {
+            [&n.tie_next, &n.slur_next, &n.gliss_next]
+                .into_iter()
+                .flatten()
+                .filter_map(|target| ends.get(target.as_str()))
+                .map(|&end| (n.start_tick, end.max(n.start_tick + 1)))
+        })
+        .collect()
+}
+
+/// Grow `range` until no link span crosses its boundary
+fn widen(mut range: TickRange, spans: &[(u32, u32)]) -> TickRange {
+    loop {
+        let mut grown = range;
+        for &(start, end) in spans {
+            if overlaps(start, end, &grown) {
+                grown.start_tick = grown.start_tick.min(start);
+                grown.end_tick = grown.end_tick.max(end);
+            }
+        }
+        if grown == range {
+            return range;
+        }
+        range = grown;
+    }
+}
+
+/// Whether `[start, end)` shares a tick with `range`
+fn overlaps(start: u32, end: u32, range: &TickRange) -> bool {
+    start < range.end_tick && end > range.start_tick
+}
@@ -0,0 +1,197 @@
+//! Printed measure numbers
+//!
+//! Each measure's printed number comes from the score's `measure_numbers`
+//! (as written in the source, e.g. "12a") or, when the score has none, counts
+//! from 1 after any pickup measure. The measure-numbering policy chooses
+//! which of them appear. A number sits above the top staff at the start of
+//! its measure; the first measure of a system has its number over the clef.
+
+use crate::domain::events::direction::Enclosure;
+use crate::layout::breaker::MeasureInfo;
+use crate::layout::types::{
+    BoundingBox, EnclosureLayout, MeasureNumber, MeasureNumberLabel, MeasureNumberPlacement,
+    MeasureNumbering, Point, System,
+};
+
+/// Font size in staff spaces
+const FONT_SPACES: f32 = 2.0;
+/// Approximate advance width per character relative to font size
+const CHAR_WIDTH_FACTOR: f32 = 0.6;
+/// Text ascent and descent relative to font size
+const ASCENT: f32 = 0.75;
+const DESCENT: f32 = 0.25;
+/// Space between the number and its frame, in staff spaces
+const ENCLOSURE_PADDING_SPACES: f32 = 0.4;
+/// Baseline height above the top of the system, in staff spaces
+const BASELINE_SPACES: f32 = 1.5;
+/// Left edge of a number at the start of a system (over the clef), in staff spaces
+const SYSTEM_START_X_SPACES: f32 = 3.0;
+/// Distance of other numbers from their measure's barline, in staff spaces
+const BARLINE_OFFSET_SPACES: f32 = 0.5;
+
+/// Printed number of each of the score's `measure_count` measures, `None`
+/// for measures left out of the numbering
+pub(crate) fn printed_numbers(
+    score: &serde_json::Value,
+    measure_count: usize,
+    pickup_ticks: u32,
+) -> Vec<Option<String>> {
+    if let Some(numbers) = score["measure_numbers"].as_array() {
+        return (0..measure_count)
+            .map(|i| numbers.get(i).and_then(|n| n.as_str()).map(str::to_string))
+            .collect();
+    }
+    // The pickup is not numbered; the first full measure is measure 1
+    let pickup = usize::from(pickup_ticks > 0);
+    (0..measure_count)
+        .map(|i| (i >= pickup).then(|| (i + 1 - pickup).to_string()))
+        .collect()
+}
+
+/// Whether the policy prints the number of a measure
+fn is_shown(placement: MeasureNumberPlacement, number: &str, starts_system: bool) -> bool {
+    match placement {
+        MeasureNumberPlacement::SystemStart => starts_system,
+        MeasureNumberPlacement::EveryMeasure => true,
+        // Numbers with a suffix ("12a") are never multiples
+        MeasureNumberPlacement::Interval { every } => number
+            .parse::<u32>()
+            .is_ok_and(|n| every > 0 && n % every == 0),
+        MeasureNumberPlacement::Hidden => false,
+    }
+}
+
+/// Measure-number labels of a system whose measures and top are placed
+pub(crate) fn measure_number_labels(
+    system: &System,
+    measure_infos: &[MeasureInfo],
+    numbers: &[Option<String>],
+    numbering: MeasureNumbering,
+    units_per_space: f32,
+) -> Vec<MeasureNumberLabel> {
+    let baseline = system.bounding_box.y - BASELINE_SPACES * units_per_space;
+    measure_infos
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| {
+            m.start_tick >= system.tick_range.start_tick
+                && m.start_tick < system.tick_range.end_tick
+        })
+        .filter_map(|(index, m)| {
+            let number = numbers.get(index)?.as_deref()?;
+            let starts_system = m.start_tick == system.tick_range.start_tick;
+            if !is_shown(numbering.placement, number, starts_system) {
+                return None;
+            }
+            let x = if starts_system {
+                SYSTEM_START_X_SPACES * units_per_space
+            } else {
+                let measure = system
+                    .measure_layouts
+                    .iter()
+                    .find(|l| l.start_tick == m.start_tick)?;
+                measure.x_start + BARLINE_OFFSET_SPACES * units_per_space
+            };
+            Some(label(
+                index as u32,
+                number,
+                x,
+                baseline,
+                numbering.enclosure,
+                units_per_space,
+            ))
+        })
+        .collect()
+}
+
+/// Legacy single measure number of a system: the numeric part of the label
+/// of the system's first measure ("12a" gives 12), at that label's position.
+/// `None` when that measure has no label or its number has no digits.
+pub(crate) fn system_start_number(
+    system: &System,
+    measure_infos: &[MeasureInfo],
+) -> Option<MeasureNumber> {
+    let label = system.measure_number_labels.iter().find(|l| {
+        measure_infos
+            .get(l.measure_index as usize)
+            .is_some_and(|m| m.start_tick == system.tick_range.start_tick)
+    })?;
+    let digits = label.text.len()
+        - label
+            .text
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    Some(MeasureNumber {
+        number: label.text[..digits].parse().ok()?,
+        position: label.position,
+    })
+}
+
+/// Number with its (framed) left edge at `x` and its baseline at `baseline`
+fn label(
+    measure_index: u32,
+    text: &str,
+    x: f32,
+    baseline: f32,
+    enclosure: Enclosure,
+    units_per_space: f32,
+) -> MeasureNumberLabel {
+    let font_size = FONT_SPACES * units_per_space;
+    let padding = match enclosure {
+        Enclosure::None => 0.0,
+        Enclosure::Rectangle | Enclosure::Circle => ENCLOSURE_PADDING_SPACES * units_per_space,
+    };
+    let mut position = Point {
+        x: x + padding,
+        y: baseline,
+    };
+    let mut frame = BoundingBox {
+        x,
+        y: baseline - ASCENT * font_size - padding,
+        width: text.chars().count() as f32 * CHAR_WIDTH_FACTOR * font_size + 2.0 * padding,
+        height: (ASCENT + DESCENT) * font_size + 2.0 * padding,
+    };
+    if enclosure == Enclosure::Circle {
+        // Square around the text, so the circle clears its corners
+        let side = frame.width.max(frame.height);
+        frame.y -= side - frame.height;
+        position.x += (side - frame.width) / 2.0;
+        position.y -= (side - frame.height) / 2.0;
+        frame.width = side;
+        frame.height = side;
+    }
+    MeasureNumberLabel {
+        measure_index,
+        text: text.to_string(),
+        position,
+        font_size,
+        enclosure: (enclosure != Enclosure::None).then_some(EnclosureLayout {
+            shape: enclosure,
+            bounding_box: frame,
+        }),
+        bounding_box: frame,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_numbering_skips_the_pickup() {
+        let score = serde_json::json!({});
+        assert_eq!(
+            printed_numbers(&score, 3, 960),
+            vec![None, Some("1".to_string()), Some("2".to_string())]
+        );
+        assert_eq!(printed_numbers(&score, 2, 0)[0].as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_interval_ignores_suffixed_numbers() {
+        let every = MeasureNumberPlacement::Interval { every: 5 };
+        assert!(is_shown(every, "10", false));
+        assert!(!is_shown(every, "11", true));
+        assert!(!is_shown(every, "10a", false));
+    }
+}
@@ -9,9 +9,13 @@ use std::collections::HashMap;
 
 pub mod batcher;
 pub mod beams;
+pub mod binary;
 pub mod breaker;
+pub mod incremental;
 pub mod metrics;
 pub mod positioner;
+pub mod query;
+pub mod render;
 pub mod spacer;
 pub mod stems;
 pub mod types;
@@ -19,27 +23,44 @@ pub mod types;
 pub(crate) mod annotations;
 pub(crate) mod assembly;
 pub(crate) mod barlines;
+pub(crate) mod chord_symbols;
+pub(crate) mod cross_staff;
+pub(crate) mod directions;
 pub(crate) mod extraction;
+pub(crate) mod measure_numbers;
 pub(crate) mod note_layout;
+pub(crate) mod note_lines;
+pub(crate) mod pages;
+pub(crate) mod pedals;
+pub(crate) mod skyline;
 pub(crate) mod staff_groups;
 pub(crate) mod structural;
+pub(crate) mod tablature;
 
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
+pub use binary::{LayoutDecodeError, decode_layout, encode_layout};
 pub use breaker::MeasureInfo;
 pub use extraction::NoteData;
+pub use incremental::{IncrementalLayout, UpdateStats};
+pub use query::{CursorPosition, LayoutIndex, NoteHit, TickRegion};
 pub use types::{
-    BarLine, BarLineSegment, BarLineType, BoundingBox, BracketGlyph, BracketType, Color,
-    GlobalLayout, Glyph, GlyphRun, LayoutConfig, LedgerLine, MeasureNumber, NameLabel,
-    OttavaBracketLayout, Point, RepeatDotPosition, SourceReference, Staff, StaffGroup, StaffLine,
-    System, TickRange, VoltaBracketLayout,
+    ArpeggioLayout, BarLine, BarLineSegment, BarLineType, BoundingBox, BracketGlyph, BracketType,
+    ChordSymbolLayout, ChordSymbolRun, Color, DirectionKind, DirectionLayout, EnclosureLayout,
+    EngravingStyle, GlissandoLayout, GlobalLayout, Glyph, GlyphRun, GroupBracket, InstrumentNames,
+    LayoutConfig, LedgerLine, LineBreaking, MeasureLayout, MeasureNumber, MeasureNumberLabel,
+    MeasureNumberPlacement, MeasureNumbering, MetronomeLayout, NameLabel, OttavaBracketLayout,
+    Page, PageConfig, PageText, PedalLine, PedalMarkLayout, PedalText, Point, RepeatDotPosition,
+    SourceReference, Staff, StaffGroup, StaffLine, StickySignature, System, TabFret, TextAlign,
+    TickPosition, TickRange, VoltaBracketLayout,
 };
 
 use extraction::{
-    RestLayoutEvent, StaffData, actual_end, actual_start, actual_tick_to_measure,
-    extract_instruments, extract_measures,
+    RestLayoutEvent, StaffData, actual_end, actual_start, extract_instruments, extract_measures,
+    extract_part_groups, extract_rehearsal_marks, extract_tempo_marks,
 };
+use skyline::Skyline;
 
 /// Compute layout from a CompiledScore
 ///
@@ -51,6 +72,87 @@ use extraction::{
 /// Layout computation is deterministic - identical inputs always produce
 /// byte-identical outputs, enabling aggressive caching.
 pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> GlobalLayout {
+    let config = &effective_config(config);
+    let ctx = prepare_score(score, config, |_, _, _| None);
+    let mut systems = break_score(&ctx, config);
+    let spacing: Vec<SystemSpacing> = systems
+        .iter_mut()
+        .map(|system| layout_system(&ctx, config, system))
+        .collect();
+    stack_systems(&ctx, config, systems, &spacing)
+}
+
+/// The configuration layout actually uses
+///
+/// In paginated mode systems fill the page's content width; a single line
+/// is never paginated.
+fn effective_config(config: &LayoutConfig) -> LayoutConfig {
+    if let LineBreaking::SingleLine { .. } = config.line_breaking {
+        return LayoutConfig {
+            page: None,
+            ..config.clone()
+        };
+    }
+    match &config.page {
+        Some(page) => LayoutConfig {
+            max_system_width: page.content_width(config.units_per_space),
+            ..config.clone()
+        },
+        None => config.clone(),
+    }
+}
+
+/// Volta bracket extracted from the score (Feature 047)
+#[derive(Debug, Clone, PartialEq)]
+struct VoltaBracketData {
+    number: u8,
+    start_measure_index: u32,
+    end_measure_index: u32,
+    end_type_is_stop: bool,
+}
+
+/// Octave-shift region extracted from the score
+#[derive(Debug, Clone, PartialEq)]
+struct OttavaRegionData {
+    start_tick: u32,
+    end_tick: u32,
+    display_shift: i8,
+    staff_index: usize,
+}
+
+/// Score-wide data shared by the layout of every system
+struct ScoreContext {
+    ticks_per_measure: u32,
+    pickup_ticks: u32,
+    measure_end_ticks_vec: Vec<u32>,
+    measure_infos: Vec<breaker::MeasureInfo>,
+    /// Printed number of each measure, `None` = unnumbered
+    measure_numbers: Vec<Option<String>>,
+    volta_bracket_data: Vec<VoltaBracketData>,
+    ottava_regions: Vec<OttavaRegionData>,
+    tempo_marks: Vec<crate::domain::events::tempo::TempoEvent>,
+    rehearsal_marks: Vec<crate::domain::events::direction::RehearsalMark>,
+    part_groups: Vec<crate::domain::part_group::PartGroup>,
+    instruments: Vec<extraction::InstrumentData>,
+    spacing_config: spacer::SpacingConfig,
+    intra_staff_multiplier: f32,
+    inter_instrument_multiplier: f32,
+    effective_system_height: f32,
+    unified_left_margin: f32,
+    breaking_width: f32,
+    page_break_measures: Vec<usize>,
+    forced_breaks: Vec<usize>,
+}
+
+/// Extract everything the system layout needs from the score
+///
+/// `cached_width(index, start_tick, end_tick)` may supply a measure's width
+/// from an earlier layout; measures it returns `None` for are measured.
+fn prepare_score(
+    score: &serde_json::Value,
+    config: &LayoutConfig,
+    cached_width: impl Fn(usize, u32, u32) -> Option<f32>,
+) -> ScoreContext {
     // Extract time signature — check multiple JSON paths for compatibility:
     // 1. global_structural_events[].TimeSignature (ScoreDto from musicore-import)
     // 2. time_signature_changes[] (ConvertedScore from frontend LayoutView)
@@ -146,12 +248,6 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
     }
 
     // Extract volta brackets for layout rendering (Feature 047)
-    struct VoltaBracketData {
-        number: u8,
-        start_measure_index: u32,
-        end_measure_index: u32,
-        end_type_is_stop: bool,
-    }
     let mut volta_bracket_data: Vec<VoltaBracketData> = Vec::new();
     if let Some(volta_brackets) = score["volta_brackets"].as_array() {
         for vb in volta_brackets {
@@ -171,22 +267,29 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
         }
     }
 
+    // Printed tempo marks and rehearsal marks, laid out per system
+    let tempo_marks = extract_tempo_marks(score);
+    let rehearsal_marks = extract_rehearsal_marks(score);
+    let part_groups = extract_part_groups(score);
+
     // Compute measure widths using spacer
-    let spacing_config = spacer::SpacingConfig::default();
+    let spacing_config = config.engraving.spacing;
     let measure_infos: Vec<breaker::MeasureInfo> = measures
         .iter()
         .enumerate()
         .map(
             |(i, (note_durations, rest_durations, chord_second_count))| {
-                let width = spacer::compute_measure_width(
-                    note_durations,
-                    rest_durations,
-                    &spacing_config,
-                    *chord_second_count,
-                );
                 let start =
                     actual_start(i, &measure_end_ticks_vec, pickup_ticks, ticks_per_measure);
                 let end = actual_end(i, &measure_end_ticks_vec, pickup_ticks, ticks_per_measure);
+                let width = cached_width(i, start, end).unwrap_or_else(|| {
+                    spacer::compute_measure_width(
+                        note_durations,
+                        rest_durations,
+                        &spacing_config,
+                        *chord_second_count,
+                    )
+                });
                 breaker::MeasureInfo {
                     width,
                     start_tick: start,
@@ -202,13 +305,6 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
     let mut instruments = extract_instruments(score, time_numerator, time_denominator);
 
     // Inject octave-shift regions into appropriate StaffData
-    #[derive(Clone)]
-    struct OttavaRegionData {
-        start_tick: u32,
-        end_tick: u32,
-        display_shift: i8,
-        staff_index: usize,
-    }
     let mut ottava_regions: Vec<OttavaRegionData> = Vec::new();
     if let Some(regions) = score["octave_shift_regions"].as_array() {
         for r in regions {
@@ -279,9 +375,9 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
     let total_staves: usize = instruments.iter().map(|i| i.staves.len()).sum();
     let num_instruments = instruments.len();
 
-    // Spacing multipliers (in staff-space units)
-    let intra_staff_multiplier = 10.0_f32; // Between staves of the same instrument (grand-staff standard: ~6-space gap)
-    let inter_instrument_multiplier = 5.0_f32; // Extra gap between different instruments
+    // Spacing multipliers (in staff-space units) from the engraving style
+    let intra_staff_multiplier = config.engraving.staff_distance; // Between staves of the same instrument (standard 10: ~6-space gap)
+    let inter_instrument_multiplier = config.engraving.instrument_distance; // Extra gap between different instruments
 
     // Inter-instrument gap: extra spacing between different instruments
     let inter_instrument_gap = if num_instruments > 1 {
@@ -334,171 +430,309 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
     // measure content + left margin = max_system_width (no overflow).
     let breaking_width = (config.max_system_width - unified_left_margin).max(200.0);
 
-    // Break into systems using effective height that accommodates all staves
-    let mut systems = breaker::break_into_systems(
-        &measure_infos,
+    // Break into systems using effective height that accommodates all staves.
+    // The engraver's `<print new-system="yes">` breaks apply to every
+    // multi-system layout; `<print new-page="yes">` breaks only to pages
+    let measure_list = |key: &str| -> Vec<usize> {
+        score[key]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_u64().map(|i| i as usize))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let page_break_measures = if config.page.is_some() {
+        measure_list("page_breaks")
+    } else {
+        Vec::new()
+    };
+    let forced_breaks = if matches!(config.line_breaking, LineBreaking::SingleLine { .. }) {
+        Vec::new()
+    } else {
+        let mut systems = measure_list("system_breaks");
+        systems.extend(&page_break_measures);
+        systems
+    };
+
+    let measure_numbers =
+        measure_numbers::printed_numbers(score, measure_infos.len(), pickup_ticks);
+
+    ScoreContext {
+        ticks_per_measure,
+        pickup_ticks,
+        measure_end_ticks_vec,
+        measure_infos,
+        measure_numbers,
+        volta_bracket_data,
+        ottava_regions,
+        tempo_marks,
+        rehearsal_marks,
+        part_groups,
+        instruments,
+        spacing_config,
+        intra_staff_multiplier,
+        inter_instrument_multiplier,
+        effective_system_height,
+        unified_left_margin,
+        breaking_width,
+        page_break_measures,
+        forced_breaks,
+    }
+}
+
+/// Break the score into systems and size them
+fn break_score(ctx: &ScoreContext, config: &LayoutConfig) -> Vec<System> {
+    let ScoreContext {
+        ref measure_infos,
+        effective_system_height,
+        unified_left_margin,
+        breaking_width,
+        ref forced_breaks,
+        ..
+    } = *ctx;
+
+    let mut systems = breaker::break_systems(
+        measure_infos,
         breaking_width,
         effective_system_height,
         config.system_spacing,
+        forced_breaks,
+        config.line_breaking,
     );
 
     // After breaking, set every system's bounding box to max_system_width
-    // so all systems render at equal width (justified).
+    // so all systems render at equal width (justified). A ragged last
+    // system or a single line keeps its natural width plus the left margin.
+    let natural = matches!(config.line_breaking, LineBreaking::SingleLine { .. });
     for system in &mut systems {
-        system.bounding_box.width = config.max_system_width;
+        system.bounding_box.width = if natural || system.bounding_box.width < breaking_width {
+            system.bounding_box.width + unified_left_margin
+        } else {
+            config.max_system_width
+        };
     }
+    systems
+}
 
-    // Populate staff_groups for each system with positioned and batched glyphs
-    // Top margin: leave space above the first system so stems, beams, flags,
-    // and measure numbers above the top staff are not clipped by viewport y=0.
-    // 4 staff spaces (80 units at ups=20) matches standard engraving practice.
-    let top_margin = 4.0 * config.units_per_space;
-    let mut running_y: f32 = top_margin; // Track cumulative y position across systems (collision-aware)
-    for system in &mut systems {
-        // Update system y to account for collision-adjusted heights of previous systems
-        system.bounding_box.y = running_y;
+/// Minimum clearance between the skylines of consecutive systems, in staff
+/// spaces
+const SYSTEM_CLEARANCE_SPACES: f32 = 2.0;
+
+/// What `stack_systems` needs to place the system after this one
+#[derive(Debug, Clone)]
+pub(crate) struct SystemSpacing {
+    /// Nominal vertical advance from this system's top to the next one's
+    pub(crate) advance: f32,
+    /// Contours of everything drawn, relative to the system's top (y = 0)
+    pub(crate) skyline: Skyline,
+}
 
-        let mut staff_groups = Vec::new();
-        // Track cumulative vertical offset across instruments within this system
-        let mut global_staff_offset: usize = 0;
-        let mut cumulative_inter_gap: f32 = 0.0;
+/// Lay out one system with its top at y = 0
+///
+/// Populates the staff groups with positioned and batched glyphs, brackets
+/// and directions. Returns the nominal vertical advance to the next
+/// system's top and the system's skyline; `stack_systems` moves each system
+/// into place.
+fn layout_system(ctx: &ScoreContext, config: &LayoutConfig, system: &mut System) -> SystemSpacing {
+    let ScoreContext {
+        ticks_per_measure,
+        pickup_ticks,
+        ref measure_end_ticks_vec,
+        ref measure_infos,
+        ref measure_numbers,
+        ref volta_bracket_data,
+        ref ottava_regions,
+        ref tempo_marks,
+        ref rehearsal_marks,
+        ref part_groups,
+        ref instruments,
+        ref spacing_config,
+        intra_staff_multiplier,
+        inter_instrument_multiplier,
+        unified_left_margin,
+        ..
+    } = *ctx;
+    system.bounding_box.y = 0.0;
+
+    // Compute unified note positions across ALL instruments in this system.
+    // This ensures measures and notes at the same tick align horizontally
+    // across every staff group (e.g., violin beat 2 lines up with cello beat 2).
+    let all_staves: Vec<&StaffData> = instruments
+        .iter()
+        .flat_map(|inst| inst.staves.iter())
+        .collect();
+    // system.bounding_box.width = max_system_width (set above), which is
+    // the TOTAL system width including the left margin.  Passing it directly
+    // as system_width lets compute_unified_note_positions use
+    //   available_width = max_system_width - unified_left_margin
+    // and notes are placed from unified_left_margin to max_system_width.
+    // Collect all mid-system clef change ticks so the spacing
+    // algorithm can insert extra space for the clef glyph.
+    let clef_change_ticks: std::collections::HashSet<u32> = all_staves
+        .iter()
+        .flat_map(|s| s.clef_events.iter())
+        .filter(|(t, _)| *t > system.tick_range.start_tick && *t < system.tick_range.end_tick)
+        .map(|(t, _)| *t)
+        .collect();
+    let note_positions = note_layout::compute_unified_note_positions(
+        &all_staves,
+        &system.tick_range,
+        system.bounding_box.width,
+        unified_left_margin,
+        spacing_config,
+        ticks_per_measure,
+        &clef_change_ticks,
+    );
 
-        // Compute unified note positions across ALL instruments in this system.
-        // This ensures measures and notes at the same tick align horizontally
-        // across every staff group (e.g., violin beat 2 lines up with cello beat 2).
-        let all_staves: Vec<&StaffData> = instruments
-            .iter()
-            .flat_map(|inst| inst.staves.iter())
-            .collect();
-        // system.bounding_box.width = max_system_width (set above), which is
-        // the TOTAL system width including the left margin.  Passing it directly
-        // as system_width lets compute_unified_note_positions use
-        //   available_width = max_system_width - unified_left_margin
-        // and notes are placed from unified_left_margin to max_system_width.
-        // Collect all mid-system clef change ticks so the spacing
-        // algorithm can insert extra space for the clef glyph.
-        let clef_change_ticks: std::collections::HashSet<u32> = all_staves
+    // Compute measure boundary x positions for this system.
+    // Content-bearing measures end where their last event is (+clearance).
+    // Empty measures (only whole-measure rests) share the remaining width equally.
+    let measure_x_bounds: HashMap<u32, (f32, f32)> = {
+        let measures_in_sys: Vec<&breaker::MeasureInfo> = measure_infos
             .iter()
-            .flat_map(|s| s.clef_events.iter())
-            .filter(|(t, _)| *t > system.tick_range.start_tick && *t < system.tick_range.end_tick)
-            .map(|(t, _)| *t)
+            .filter(|m| {
+                m.start_tick < system.tick_range.end_tick
+                    && m.end_tick > system.tick_range.start_tick
+            })
             .collect();
-        let note_positions = note_layout::compute_unified_note_positions(
-            &all_staves,
-            &system.tick_range,
-            system.bounding_box.width,
-            unified_left_margin,
-            &spacing_config,
-            ticks_per_measure,
-            &clef_change_ticks,
-        );
-
-        // Compute measure boundary x positions for this system.
-        // Content-bearing measures end where their last event is (+clearance).
-        // Empty measures (only whole-measure rests) share the remaining width equally.
-        let measure_x_bounds: HashMap<u32, (f32, f32)> = {
-            let measures_in_sys: Vec<&breaker::MeasureInfo> = measure_infos
-                .iter()
-                .filter(|m| {
-                    m.start_tick < system.tick_range.end_tick
-                        && m.end_tick > system.tick_range.start_tick
-                })
-                .collect();
 
-            // First pass: determine content width for measures that have events
-            let clearance = 30.0_f32;
-            let mut content_widths: Vec<Option<f32>> = Vec::with_capacity(measures_in_sys.len());
-            let mut total_content_width = 0.0_f32;
-            let mut empty_count = 0_usize;
+        // First pass: determine content width for measures that have events
+        let clearance = 30.0_f32;
+        let mut content_widths: Vec<Option<f32>> = Vec::with_capacity(measures_in_sys.len());
+        let mut total_content_width = 0.0_f32;
+        let mut empty_count = 0_usize;
 
-            for m in &measures_in_sys {
-                let last_event = note_positions
-                    .iter()
-                    .filter(|(tick, _)| **tick >= m.start_tick && **tick < m.end_tick)
-                    .max_by_key(|(tick, _)| *tick);
+        for m in &measures_in_sys {
+            let last_event = note_positions
+                .iter()
+                .filter(|(tick, _)| **tick >= m.start_tick && **tick < m.end_tick)
+                .max_by_key(|(tick, _)| *tick);
 
-                if let Some((_, &x)) = last_event {
-                    // Content measure: width = last event position + clearance - left edge
-                    // We'll compute the actual width relative to running_x below
-                    content_widths.push(Some(x + clearance));
-                } else {
-                    content_widths.push(None);
-                    empty_count += 1;
-                }
+            if let Some((_, &x)) = last_event {
+                // Content measure: width = last event position + clearance - left edge
+                // We'll compute the actual width relative to running_x below
+                content_widths.push(Some(x + clearance));
+            } else {
+                content_widths.push(None);
+                empty_count += 1;
             }
+        }
 
-            // bounding_box.width already equals max_system_width (includes left margin).
-            let total_system_width: f32 = system.bounding_box.width;
-
-            // Second pass: compute actual content width (relative to running_x)
-            let mut running_x = unified_left_margin;
-            for cw in &content_widths {
-                if let Some(abs_end) = cw {
-                    total_content_width += (*abs_end - running_x).max(0.0);
-                    running_x = *abs_end;
-                } else {
-                    // placeholder — will be computed below
-                    running_x += 0.0;
-                }
-            }
+        // bounding_box.width already equals max_system_width (includes left margin).
+        let total_system_width: f32 = system.bounding_box.width;
 
-            // Remaining width for empty measures
-            let remaining_width = (total_system_width - unified_left_margin - total_content_width)
-                .max(empty_count as f32 * 100.0); // minimum 100 per empty measure
-            let empty_measure_width = if empty_count > 0 {
-                remaining_width / empty_count as f32
+        // Second pass: compute actual content width (relative to running_x)
+        let mut running_x = unified_left_margin;
+        for cw in &content_widths {
+            if let Some(abs_end) = cw {
+                total_content_width += (*abs_end - running_x).max(0.0);
+                running_x = *abs_end;
             } else {
-                0.0
-            };
-
-            // Third pass: assign bounds sequentially
-            let mut bounds = HashMap::new();
-            running_x = unified_left_margin;
-            for (i, m) in measures_in_sys.iter().enumerate() {
-                let start_x = running_x;
-                if let Some(abs_end) = content_widths[i] {
-                    running_x = abs_end;
-                } else {
-                    running_x += empty_measure_width;
-                }
-                bounds.insert(m.start_tick, (start_x, running_x));
+                // placeholder — will be computed below
+                running_x += 0.0;
             }
-            bounds
-        };
+        }
 
-        // --- Collision-aware spacing pre-scan ---
-        // Compute note Y extents for each staff (relative to staff origin) to detect
-        // collisions between adjacent staves. If notes from one staff extend into
-        // another staff's region, we increase the spacing for THIS system only.
-        let staff_extents: Vec<(f32, f32)> = instruments
-            .iter()
-            .flat_map(|inst| inst.staves.iter())
-            .map(|sd| {
-                note_layout::compute_staff_note_extents(
-                    sd,
-                    &system.tick_range,
-                    config.units_per_space,
-                )
-            })
-            .collect();
+        // Remaining width for empty measures
+        let remaining_width = (total_system_width - unified_left_margin - total_content_width)
+            .max(empty_count as f32 * 100.0); // minimum 100 per empty measure
+        let empty_measure_width = if empty_count > 0 {
+            remaining_width / empty_count as f32
+        } else {
+            0.0
+        };
 
-        // For each staff, record whether an inter-instrument gap precedes it
-        let mut has_inter_gap_before: Vec<bool> = Vec::new();
-        for (inst_idx, inst) in instruments.iter().enumerate() {
-            for (staff_idx, _) in inst.staves.iter().enumerate() {
-                has_inter_gap_before.push(inst_idx > 0 && staff_idx == 0);
+        // Third pass: assign bounds sequentially
+        let mut bounds = HashMap::new();
+        running_x = unified_left_margin;
+        for (i, m) in measures_in_sys.iter().enumerate() {
+            let start_x = running_x;
+            if let Some(abs_end) = content_widths[i] {
+                running_x = abs_end;
+            } else {
+                running_x += empty_measure_width;
             }
+            bounds.insert(m.start_tick, (start_x, running_x));
         }
+        bounds
+    };
 
-        let (cumulative_collision_extra, total_collision_extra) =
-            staff_groups::compute_collision_gaps(
-                &staff_extents,
-                &has_inter_gap_before,
-                intra_staff_multiplier,
-                inter_instrument_multiplier,
+    // Tick anchors for playback cursors and hit testing (see `query`)
+    system.measure_layouts = measure_infos
+        .iter()
+        .filter(|m| {
+            m.start_tick < system.tick_range.end_tick && m.end_tick > system.tick_range.start_tick
+        })
+        .filter_map(|m| {
+            let &(x_start, x_end) = measure_x_bounds.get(&m.start_tick)?;
+            Some(MeasureLayout {
+                start_tick: m.start_tick,
+                end_tick: m.end_tick,
+                x_start,
+                x_end,
+                // Filled in once the system's glyphs are placed
+                bounding_box: BoundingBox {
+                    x: x_start,
+                    y: 0.0,
+                    width: x_end - x_start,
+                    height: 0.0,
+                },
+            })
+        })
+        .collect();
+    system.tick_positions = note_positions
+        .iter()
+        .filter(|(tick, _)| {
+            **tick >= system.tick_range.start_tick && **tick < system.tick_range.end_tick
+        })
+        .map(|(&tick, &x)| TickPosition { tick, x })
+        .collect();
+    system.tick_positions.sort_by_key(|p| p.tick);
+    let mut sticky_anchors: Vec<TickPosition> = system
+        .measure_layouts
+        .iter()
+        .map(|m| TickPosition {
+            tick: m.start_tick,
+            x: m.x_start,
+        })
+        .chain(system.tick_positions.iter().copied())
+        .collect();
+    sticky_anchors.sort_by_key(|a| a.tick);
+
+    // Note y extents of each staff (relative to its top line), which keep
+    // chord symbols and pedal marks clear of the notes and stems
+    let staff_extents: Vec<(f32, f32)> = instruments
+        .iter()
+        .flat_map(|inst| inst.staves.iter())
+        .map(|sd| {
+            note_layout::compute_staff_note_extents(
+                sd,
+                &system.tick_range,
                 config.units_per_space,
-            );
+                &config.engraving,
+            )
+        })
+        .collect();
+
+    // For each staff, record whether an inter-instrument gap precedes it
+    let mut has_inter_gap_before: Vec<bool> = Vec::new();
+    for (inst_idx, inst) in instruments.iter().enumerate() {
+        for (staff_idx, _) in inst.staves.iter().enumerate() {
+            has_inter_gap_before.push(inst_idx > 0 && staff_idx == 0);
+        }
+    }
 
+    // Lay out every staff with the given collision-avoidance extra spacing.
+    // Also returns each staff's skyline relative to its top line, without
+    // cross-staff elements (which follow the staves wherever they are).
+    let layout_staves = |cumulative_collision_extra: &[f32]| {
+        let mut staff_groups = Vec::new();
+        let mut skylines = Vec::new();
+        // Track cumulative vertical offset across instruments within this system
+        let mut global_staff_offset: usize = 0;
+        let mut cumulative_inter_gap: f32 = 0.0;
         for (instrument_index, instrument) in instruments.iter().enumerate() {
             let mut staves = Vec::new();
 
@@ -507,32 +741,64 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
                 cumulative_inter_gap += inter_instrument_multiplier * config.units_per_space;
             }
 
+            // Calculate vertical offsets using global_staff_offset (accounts for all previous instruments' staves)
+            // plus inter-instrument gap accumulated from previous instruments
+            // plus collision-avoidance extra for this system
+            let staff_offsets: Vec<f32> = (0..instrument.staves.len())
+                .map(|staff_index| {
+                    let absolute_staff_index = global_staff_offset + staff_index;
+                    system.bounding_box.y
+                        + (absolute_staff_index as f32
+                            * intra_staff_multiplier
+                            * config.units_per_space)
+                        + cumulative_inter_gap
+                        + cumulative_collision_extra[absolute_staff_index]
+                })
+                .collect();
+            // Cross-staff notes are drawn with the clef and offset of their target staff
+            let cross_staff = cross_staff::CrossStaffContext::new(
+                &instrument.staves,
+                staff_offsets.clone(),
+                config.units_per_space,
+            );
+
             for (staff_index, staff_data) in instrument.staves.iter().enumerate() {
-                // Calculate vertical offset using global_staff_offset (accounts for all previous instruments' staves)
-                // plus inter-instrument gap accumulated from previous instruments
-                // plus collision-avoidance extra for this system
                 let absolute_staff_index = global_staff_offset + staff_index;
-                let staff_vertical_offset = system.bounding_box.y
-                    + (absolute_staff_index as f32
-                        * intra_staff_multiplier
-                        * config.units_per_space)
-                    + cumulative_inter_gap
-                    + cumulative_collision_extra[absolute_staff_index];
-
-                // Position glyphs for this staff using unified note positions
-                let glyphs = note_layout::position_glyphs_for_staff(
-                    staff_data,
-                    &system.tick_range,
-                    config.units_per_space,
-                    &instrument.id,
-                    staff_index,
-                    staff_vertical_offset,
-                    &note_positions,
-                    unified_left_margin,
-                    ticks_per_measure,
-                    &measure_x_bounds,
-                    pickup_ticks,
-                );
+                let staff_vertical_offset = staff_offsets[staff_index];
+
+                // Position glyphs for this staff using unified note positions;
+                // tablature staves get fret numbers instead of noteheads
+                let (glyphs, tab_frets) = if staff_data.tablature.is_some() {
+                    tablature::position_tab_staff(
+                        staff_data,
+                        &system.tick_range,
+                        config.units_per_space,
+                        &instrument.id,
+                        staff_index,
+                        staff_vertical_offset,
+                        &note_positions,
+                        unified_left_margin,
+                        &measure_x_bounds,
+                        pickup_ticks,
+                    )
+                } else {
+                    let glyphs = note_layout::position_glyphs_for_staff(
+                        staff_data,
+                        &system.tick_range,
+                        config.units_per_space,
+                        &instrument.id,
+                        staff_index,
+                        staff_vertical_offset,
+                        &note_positions,
+                        unified_left_margin,
+                        ticks_per_measure,
+                        &measure_x_bounds,
+                        pickup_ticks,
+                        &cross_staff,
+                        &config.engraving,
+                    );
+                    (glyphs, Vec::new())
+                };
 
                 // Separate pseudo-glyphs (stems U+0000, beams U+0001) from text glyphs
                 // so they don't break text batching efficiency. Pseudo-glyphs are
@@ -546,11 +812,12 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
                 // Add pseudo-glyphs as individual runs (each rendered separately)
                 glyph_runs.extend(batcher::batch_glyphs(pseudo_glyphs));
 
-                // Create staff lines (5 lines evenly spaced)
+                // Create staff lines (usually 5, evenly spaced)
                 let staff_lines = assembly::create_staff_lines(
                     staff_vertical_offset,
                     system.bounding_box.width,
                     config.units_per_space,
+                    staff_data.line_count,
                 );
 
                 // T036-T037: Generate structural glyphs (clef, time sig, key sig) at system start
@@ -565,14 +832,30 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
                     &measure_x_bounds,
                 );
 
+                // Clef and key to keep at the left edge while scrolling a single line
+                let sticky_signatures = match config.line_breaking {
+                    LineBreaking::SingleLine { sticky_interval } => {
+                        structural::render_sticky_signatures(
+                            staff_data,
+                            &sticky_anchors,
+                            sticky_interval,
+                            system.bounding_box.width,
+                            staff_vertical_offset,
+                            config.units_per_space,
+                        )
+                    }
+                    _ => Vec::new(),
+                };
+
                 // Create bar lines at measure boundaries
                 let bar_lines = barlines::create_bar_lines(
-                    &measure_infos,
+                    measure_infos,
                     &system.tick_range,
                     staff_vertical_offset,
                     unified_left_margin,
                     system.bounding_box.width,
                     config.units_per_space,
+                    staff_data.line_count,
                     &note_positions,
                     &measure_x_bounds,
                 );
@@ -583,16 +866,67 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
                     starts.sort();
                     starts
                 };
-                let ann = annotations::render_annotations(
+                let ann = if staff_data.tablature.is_some() {
+                    annotations::AnnotationResult::default()
+                } else {
+                    annotations::render_annotations(
+                        staff_data,
+                        &system.tick_range,
+                        system.index,
+                        system.bounding_box.width,
+                        staff_vertical_offset,
+                        unified_left_margin,
+                        config.units_per_space,
+                        &note_positions,
+                        &measure_starts,
+                        &cross_staff,
+                        staff_index,
+                    )
+                };
+
+                // Chord symbols above the staff, clear of the highest note/stem
+                let chord_symbols = chord_symbols::render_chord_symbols(
                     staff_data,
                     &system.tick_range,
-                    system.index,
-                    system.bounding_box.width,
                     staff_vertical_offset,
+                    staff_extents[absolute_staff_index].0,
+                    config.units_per_space,
+                    &note_positions,
+                    &measure_x_bounds,
                     unified_left_margin,
+                );
+
+                // Pedal marks below the staff, clear of the lowest note/stem
+                let pedal_marks = pedals::render_pedal_marks(
+                    staff_data,
+                    &system.tick_range,
+                    system.bounding_box.width,
+                    staff_vertical_offset,
+                    staff_extents[absolute_staff_index].1,
                     config.units_per_space,
                     &note_positions,
-                    &measure_starts,
+                    &measure_x_bounds,
+                    unified_left_margin,
+                    &instrument.id,
+                    staff_index,
+                );
+
+                // Arpeggio signs and glissando lines around the placed noteheads
+                let arpeggios = note_lines::render_arpeggios(
+                    staff_data,
+                    &system.tick_range,
+                    &glyph_runs,
+                    config.units_per_space,
+                );
+                let glissandos = note_lines::render_glissandos(
+                    staff_data,
+                    &system.tick_range,
+                    &glyph_runs,
+                    system.bounding_box.width,
+                    unified_left_margin,
+                    config.units_per_space,
+                    &cross_staff,
+                    staff_index,
                 );
 
                 // Create staff with batched glyphs and structural glyphs
@@ -606,11 +940,28 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
                     tie_arcs: ann.tie_arcs,
                     slur_arcs: ann.slur_arcs,
                     fingering_glyphs: ann.fingering_glyphs,
+                    chord_symbols,
+                    pedal_marks,
+                    arpeggios,
+                    glissandos,
+                    sticky_signatures,
+                    tab_frets,
                 };
 
                 staves.push(staff);
             }
 
+            for (staff_index, staff) in staves.iter().enumerate() {
+                let excluded = cross_staff.cross_staff_elements(staff_index, &system.tick_range);
+                let mut skyline =
+                    skyline::staff_skyline(staff, config.units_per_space, Some(&excluded));
+                skyline.translate(-staff_offsets[staff_index]);
+                skylines.push(skyline);
+            }
+
+            // A chord arpeggiated on adjacent staves gets one sign spanning both
+            note_lines::merge_cross_staff_arpeggios(&mut staves, config.units_per_space);
+
             // Calculate bracket glyph geometry if multi-staff instrument
             let bracket_type = if instrument.staves.len() > 1 {
                 BracketType::Brace
@@ -629,31 +980,33 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
             };
 
             // Compute name label position: to the left of bracket, vertically centered
-            let name_label = {
-                let first_staff_top = staves[0].staff_lines[0].y_position;
-                let last_staff_bottom = staves.last().unwrap().staff_lines[4].y_position;
-                let center_y = (first_staff_top + last_staff_bottom) / 2.0;
-
-                // Position x before the bracket/brace (bracket is at x=15)
-                // Use negative x — the viewport will be expanded to show this area
-                let label_x = -10.0; // Right-aligned text anchor, so text extends leftward
-
-                Some(NameLabel {
-                    text: instrument.name.clone(),
-                    position: Point {
-                        x: label_x,
-                        y: center_y,
-                    },
-                    font_size: 32.0,
-                    font_family: "serif".to_string(),
-                    color: Color {
-                        r: 0,
-                        g: 0,
-                        b: 0,
-                        a: 255,
-                    },
-                })
-            };
+            let name_label =
+                staff_groups::name_label_text(instrument, system.index, config.instrument_names)
+                    .map(|text| {
+                        let first_staff_top = staves[0].top_line_y();
+                        let last_staff_bottom = staves.last().unwrap().bottom_line_y();
+                        let center_y = (first_staff_top + last_staff_bottom) / 2.0;
+
+                        // Position x before the bracket/brace (bracket is at x=15)
+                        // Use negative x — the viewport will be expanded to show this area
+                        let label_x = -10.0; // Right-aligned text anchor, so text extends leftward
+
+                        NameLabel {
+                            text,
+                            position: Point {
+                                x: label_x,
+                                y: center_y,
+                            },
+                            font_size: 32.0,
+                            font_family: "serif".to_string(),
+                            color: Color {
+                                r: 0,
+                                g: 0,
+                                b: 0,
+                                a: 255,
+                            },
+                        }
+                    });
 
             // Create staff group for this instrument
             let staff_group = StaffGroup {
@@ -670,266 +1023,355 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
             // Update global_staff_offset for the next instrument
             global_staff_offset += instrument.staves.len();
         }
+        (staff_groups, skylines)
+    };
 
-        system.staff_groups = staff_groups;
+    // Staves start at their nominal distances and move apart only where
+    // their skylines would come too close; cross-staff notes are placed
+    // relative to the staff offsets, so the system is laid out again then.
+    let staff_count = has_inter_gap_before.len();
+    let (mut staff_groups, skylines) = layout_staves(&vec![0.0; staff_count]);
+    let (cumulative_collision_extra, total_collision_extra) = staff_groups::compute_collision_gaps(
+        &skylines,
+        &has_inter_gap_before,
+        intra_staff_multiplier,
+        inter_instrument_multiplier,
+        config.units_per_space,
+    );
+    if total_collision_extra > 0.0 {
+        staff_groups = layout_staves(&cumulative_collision_extra).0;
+    }
+    system.staff_groups = staff_groups;
 
-        // Find the rightmost barline x position across all staves.
-        // This is the end-of-system barline (every system must end with one).
-        let _max_barline_x = system
-            .staff_groups
-            .iter()
-            .flat_map(|sg| sg.staves.iter())
-            .flat_map(|s| s.bar_lines.iter())
-            .map(|bl| {
-                bl.segments
-                    .iter()
-                    .map(|seg| seg.x_position)
-                    .fold(0.0_f32, f32::max)
-            })
-            .fold(0.0_f32, f32::max);
-
-        // All systems are justified to max_system_width.  Use that as the
-        // authoritative content width so staff lines, barlines, and bounding
-        // boxes are identical across systems.
-        let content_width = config.max_system_width;
-
-        // Update all staff lines to end at the justified width
-        for staff_group in &mut system.staff_groups {
-            for staff in &mut staff_group.staves {
-                for line in &mut staff.staff_lines {
-                    line.end_x = content_width;
-                }
+    // Find the rightmost barline x position across all staves.
+    // This is the end-of-system barline (every system must end with one).
+    let _max_barline_x = system
+        .staff_groups
+        .iter()
+        .flat_map(|sg| sg.staves.iter())
+        .flat_map(|s| s.bar_lines.iter())
+        .map(|bl| {
+            bl.segments
+                .iter()
+                .map(|seg| seg.x_position)
+                .fold(0.0_f32, f32::max)
+        })
+        .fold(0.0_f32, f32::max);
+
+    // Systems are justified to max_system_width (set after breaking).
+    // Use that as the authoritative content width so staff lines,
+    // barlines, and bounding boxes are identical across systems; only a
+    // ragged last system is narrower.
+    let content_width = system.bounding_box.width;
+
+    // Update all staff lines to end at the justified width
+    for staff_group in &mut system.staff_groups {
+        for staff in &mut staff_group.staves {
+            for line in &mut staff.staff_lines {
+                line.end_x = content_width;
             }
         }
+    }
 
-        // Ensure bounding box matches the justified width
-        system.bounding_box.width = content_width;
+    // Ensure bounding box matches the justified width
+    system.bounding_box.width = content_width;
 
-        // Join measure barlines within each multi-staff group.
-        staff_groups::join_multi_staff_barlines(&mut system.staff_groups);
+    // Join measure barlines within each multi-staff group.
+    staff_groups::join_multi_staff_barlines(&mut system.staff_groups, config.units_per_space);
 
-        // Add a system-end barline at the justified right edge for every
-        // staff group.
-        barlines::render_system_barlines(
-            &mut system.staff_groups,
-            &measure_infos,
-            system.tick_range.end_tick,
-            content_width,
-            config.units_per_space,
-        );
+    // Add a system-end barline at the justified right edge for every
+    // staff group.
+    barlines::render_system_barlines(
+        &mut system.staff_groups,
+        measure_infos,
+        system.tick_range.end_tick,
+        content_width,
+        config.units_per_space,
+    );
 
-        // Update system height to include collision-avoidance extra spacing
-        system.bounding_box.height += total_collision_extra;
+    // Part groups: barlines through joined groups, nested brackets at the
+    // left, and instrument names moved clear of them
+    staff_groups::join_part_group_barlines(
+        &mut system.staff_groups,
+        part_groups,
+        config.units_per_space,
+    );
+    system.group_brackets = staff_groups::create_group_brackets(
+        &system.staff_groups,
+        part_groups,
+        config.units_per_space,
+    );
+    if let Some(bracket_x) = system
+        .group_brackets
+        .iter()
+        .map(|b| b.bracket_glyph.bounding_box.x)
+        .reduce(f32::min)
+    {
+        for label in system
+            .staff_groups
+            .iter_mut()
+            .filter_map(|g| g.name_label.as_mut())
+        {
+            label.position.x = label.position.x.min(bracket_x - 10.0);
+        }
+    }
+    system.indent = staff_groups::system_indent(&system.staff_groups, &system.group_brackets);
+
+    // Update system height to include collision-avoidance extra spacing
+    system.bounding_box.height += total_collision_extra;
 
-        // T010: Compute measure number for this system
-        // Derive measure number from the system's start tick using actual ticks per measure
-        let measure_num = actual_tick_to_measure(
-            system.tick_range.start_tick,
-            &measure_end_ticks_vec,
+    system.measure_number_labels = measure_numbers::measure_number_labels(
+        system,
+        measure_infos,
+        measure_numbers,
+        config.measure_numbers,
+        config.units_per_space,
+    );
+    // T010: The single number at the system start follows the printed label
+    // of the system's first measure; None when the policy hides it
+    system.measure_number = measure_numbers::system_start_number(system, measure_infos);
+
+    // Compute volta bracket layouts for this system (Feature 047)
+    for vbd in volta_bracket_data {
+        // Get the tick range for the bracket's measures
+        let bracket_start_tick = actual_start(
+            vbd.start_measure_index as usize,
+            measure_end_ticks_vec,
             pickup_ticks,
             ticks_per_measure,
-        ) as u32
-            + 1;
-        system.measure_number = Some(MeasureNumber {
-            number: measure_num,
-            position: Point {
-                x: 60.0,                         // Aligned with clef
-                y: system.bounding_box.y - 30.0, // Above topmost staff line
-            },
-        });
+        );
+        let bracket_end_tick = actual_end(
+            vbd.end_measure_index as usize,
+            measure_end_ticks_vec,
+            pickup_ticks,
+            ticks_per_measure,
+        );
 
-        // Compute volta bracket layouts for this system (Feature 047)
-        for vbd in &volta_bracket_data {
-            // Get the tick range for the bracket's measures
-            let bracket_start_tick = actual_start(
-                vbd.start_measure_index as usize,
-                &measure_end_ticks_vec,
-                pickup_ticks,
-                ticks_per_measure,
-            );
-            let bracket_end_tick = actual_end(
-                vbd.end_measure_index as usize,
-                &measure_end_ticks_vec,
-                pickup_ticks,
-                ticks_per_measure,
-            );
+        // Check if this bracket overlaps with this system's tick range
+        if bracket_start_tick >= system.tick_range.end_tick
+            || bracket_end_tick <= system.tick_range.start_tick
+        {
+            continue;
+        }
 
-            // Check if this bracket overlaps with this system's tick range
-            if bracket_start_tick >= system.tick_range.end_tick
-                || bracket_end_tick <= system.tick_range.start_tick
-            {
-                continue;
-            }
+        // Clamp bracket to this system's tick range
+        let effective_start = bracket_start_tick.max(system.tick_range.start_tick);
+        let effective_end = bracket_end_tick.min(system.tick_range.end_tick);
+
+        // Find x coordinates from measure_x_bounds
+        let x_start = measure_x_bounds
+            .get(&effective_start)
+            .map(|(start, _)| *start)
+            .unwrap_or(0.0);
+        let x_end = measure_x_bounds
+            .get(&(effective_end - ticks_per_measure).max(effective_start))
+            .map(|(_, end)| *end)
+            .unwrap_or(x_start + 100.0);
+
+        // Only close the right end if this system contains the bracket's true end
+        let closed_right = vbd.end_type_is_stop && bracket_end_tick <= system.tick_range.end_tick;
+
+        system.volta_bracket_layouts.push(VoltaBracketLayout {
+            number: vbd.number,
+            label: format!("{}.", vbd.number),
+            x_start,
+            x_end,
+            y: system.bounding_box.y - 20.0, // Above measure number
+            closed_right,
+        });
+    }
 
-            // Clamp bracket to this system's tick range
-            let effective_start = bracket_start_tick.max(system.tick_range.start_tick);
-            let effective_end = bracket_end_tick.min(system.tick_range.end_tick);
-
-            // Find x coordinates from measure_x_bounds
-            let x_start = measure_x_bounds
-                .get(&effective_start)
-                .map(|(start, _)| *start)
-                .unwrap_or(0.0);
-            let x_end = measure_x_bounds
-                .get(&(effective_end - ticks_per_measure).max(effective_start))
-                .map(|(_, end)| *end)
-                .unwrap_or(x_start + 100.0);
-
-            // Only close the right end if this system contains the bracket's true end
-            let closed_right =
-                vbd.end_type_is_stop && bracket_end_tick <= system.tick_range.end_tick;
-
-            system.volta_bracket_layouts.push(VoltaBracketLayout {
-                number: vbd.number,
-                label: format!("{}.", vbd.number),
-                x_start,
-                x_end,
-                y: system.bounding_box.y - 20.0, // Above measure number
-                closed_right,
-            });
+    // Compute ottava bracket layouts for this system (8va/8vb)
+    for r in ottava_regions {
+        // Check if this region overlaps with this system's tick range
+        if r.start_tick >= system.tick_range.end_tick || r.end_tick <= system.tick_range.start_tick
+        {
+            continue;
         }
 
-        // Compute ottava bracket layouts for this system (8va/8vb)
-        for r in &ottava_regions {
-            // Check if this region overlaps with this system's tick range
-            if r.start_tick >= system.tick_range.end_tick
-                || r.end_tick <= system.tick_range.start_tick
-            {
-                continue;
-            }
+        let effective_start = r.start_tick.max(system.tick_range.start_tick);
+        let effective_end = r.end_tick.min(system.tick_range.end_tick);
+
+        // Use the exact note x-position for the start tick (covers mid-measure starts).
+        // Fall back to the closest note before effective_start, then to measure boundary.
+        let x_start = note_positions
+            .get(&effective_start)
+            .copied()
+            .unwrap_or_else(|| {
+                // Find the closest note tick <= effective_start
+                note_positions
+                    .iter()
+                    .filter(|(t, _)| **t <= effective_start)
+                    .max_by_key(|(t, _)| **t)
+                    .map(|(_, &x)| x)
+                    .unwrap_or_else(|| {
+                        // Last resort: measure boundary
+                        measure_x_bounds
+                            .iter()
+                            .filter(|(t, _)| **t <= effective_start)
+                            .max_by_key(|(t, _)| **t)
+                            .map(|(_, (s, _))| *s)
+                            .unwrap_or(unified_left_margin)
+                    })
+            });
 
-            let effective_start = r.start_tick.max(system.tick_range.start_tick);
-            let effective_end = r.end_tick.min(system.tick_range.end_tick);
-
-            // Use the exact note x-position for the start tick (covers mid-measure starts).
-            // Fall back to the closest note before effective_start, then to measure boundary.
-            let x_start = note_positions
-                .get(&effective_start)
-                .copied()
-                .unwrap_or_else(|| {
-                    // Find the closest note tick <= effective_start
-                    note_positions
+        let x_end = {
+            // End at the barline of the measure containing the end tick
+            measure_x_bounds
+                .iter()
+                .filter(|(t, _)| **t < effective_end)
+                .max_by_key(|(t, _)| **t)
+                .map(|(_, (_, e))| *e)
+                .unwrap_or(x_start + 100.0)
+        };
+
+        // For 8va (above): place bracket just above the highest note in the region,
+        // hugging the note heads like Musescore (not fixed distance from staff top).
+        let above = r.display_shift < 0; // 8va is display_shift = -8, so display lower = above the staff
+
+        // Find the vertical position of the target staff
+        let bracket_y = if let Some(staff_group) = system.staff_groups.first() {
+            if let Some(staff) = staff_group.staves.get(r.staff_index) {
+                let top_staff_y = staff.top_line_y();
+                if above {
+                    // Find the topmost glyph in the bracket x range (excluding stems/beams)
+                    let top_note_y = staff
+                        .glyph_runs
                         .iter()
-                        .filter(|(t, _)| **t <= effective_start)
-                        .max_by_key(|(t, _)| **t)
-                        .map(|(_, &x)| x)
-                        .unwrap_or_else(|| {
-                            // Last resort: measure boundary
-                            measure_x_bounds
-                                .iter()
-                                .filter(|(t, _)| **t <= effective_start)
-                                .max_by_key(|(t, _)| **t)
-                                .map(|(_, (s, _))| *s)
-                                .unwrap_or(unified_left_margin)
+                        .flat_map(|gr| gr.glyphs.iter())
+                        .filter(|g| {
+                            g.position.x >= x_start - 5.0
+                                && g.position.x <= x_end + 5.0
+                                && g.codepoint != "\u{0000}"
+                                && g.codepoint != "\u{0001}"
                         })
-                });
+                        .map(|g| g.position.y)
+                        .fold(f32::INFINITY, f32::min);
 
-            let x_end = {
-                // End at the barline of the measure containing the end tick
-                measure_x_bounds
-                    .iter()
-                    .filter(|(t, _)| **t < effective_end)
-                    .max_by_key(|(t, _)| **t)
-                    .map(|(_, (_, e))| *e)
-                    .unwrap_or(x_start + 100.0)
-            };
-
-            // For 8va (above): place bracket just above the highest note in the region,
-            // hugging the note heads like Musescore (not fixed distance from staff top).
-            let above = r.display_shift < 0; // 8va is display_shift = -8, so display lower = above the staff
-
-            // Find the vertical position of the target staff
-            let bracket_y = if let Some(staff_group) = system.staff_groups.first() {
-                if let Some(staff) = staff_group.staves.get(r.staff_index) {
-                    let top_staff_y = staff.staff_lines[0].y_position;
-                    if above {
-                        // Find the topmost glyph in the bracket x range (excluding stems/beams)
-                        let top_note_y = staff
-                            .glyph_runs
-                            .iter()
-                            .flat_map(|gr| gr.glyphs.iter())
-                            .filter(|g| {
-                                g.position.x >= x_start - 5.0
-                                    && g.position.x <= x_end + 5.0
-                                    && g.codepoint != "\u{0000}"
-                                    && g.codepoint != "\u{0001}"
-                            })
-                            .map(|g| g.position.y)
-                            .fold(f32::INFINITY, f32::min);
-
-                        let clearance = 10.0;
-                        if top_note_y.is_finite() {
-                            // Sit just above the highest note, but never below staff top
-                            (top_note_y - clearance).min(top_staff_y - clearance)
-                        } else {
-                            top_staff_y - 25.0
-                        }
+                    let clearance = 10.0;
+                    if top_note_y.is_finite() {
+                        // Sit just above the highest note, but never below staff top
+                        (top_note_y - clearance).min(top_staff_y - clearance)
                     } else {
-                        let bottom_staff_y = staff.staff_lines[4].y_position;
-                        bottom_staff_y + 25.0 // Below the bottom staff line
+                        top_staff_y - 25.0
                     }
                 } else {
-                    system.bounding_box.y - 25.0
+                    let bottom_staff_y = staff.bottom_line_y();
+                    bottom_staff_y + 25.0 // Below the bottom staff line
                 }
             } else {
                 system.bounding_box.y - 25.0
-            };
+            }
+        } else {
+            system.bounding_box.y - 25.0
+        };
 
-            let closed_right = r.end_tick <= system.tick_range.end_tick;
-            let label = match r.display_shift {
-                -8 => "8va".to_string(),
-                8 => "8vb".to_string(),
-                -15 => "15ma".to_string(),
-                15 => "15mb".to_string(),
-                _ => format!("{}va", r.display_shift.unsigned_abs()),
-            };
+        let closed_right = r.end_tick <= system.tick_range.end_tick;
+        let label = match r.display_shift {
+            -8 => "8va".to_string(),
+            8 => "8vb".to_string(),
+            -15 => "15ma".to_string(),
+            15 => "15mb".to_string(),
+            _ => format!("{}va", r.display_shift.unsigned_abs()),
+        };
 
-            system.ottava_bracket_layouts.push(OttavaBracketLayout {
-                label,
-                x_start,
-                x_end,
-                y: bracket_y,
-                above,
-                closed_right,
-                staff_index: r.staff_index,
-            });
-        }
+        system.ottava_bracket_layouts.push(OttavaBracketLayout {
+            label,
+            x_start,
+            x_end,
+            y: bracket_y,
+            above,
+            closed_right,
+            staff_index: r.staff_index,
+        });
+    }
 
-        // Advance running_y for the next system
-        running_y = system.bounding_box.y + system.bounding_box.height + config.system_spacing;
-
-        // Expand system bounding box to cover all stem and beam extents.
-        // Stems (U+0000) and beams (U+0001) can extend well beyond the staff area
-        // (up to 70+ units). The bounding box must include them so the frontend's
-        // viewport-based virtualization (getVisibleSystems) renders these elements
-        // instead of clipping them at the viewBox edge.
-        let mut glyph_min_y = system.bounding_box.y;
-        let mut glyph_max_y = system.bounding_box.y + system.bounding_box.height;
-        for staff_group in &system.staff_groups {
-            for staff in &staff_group.staves {
-                for glyph_run in &staff.glyph_runs {
-                    for glyph in &glyph_run.glyphs {
-                        if glyph.codepoint == "\u{0000}" || glyph.codepoint == "\u{0001}" {
-                            let y_top = glyph.position.y.min(glyph.bounding_box.y);
-                            let y_bottom = (glyph.position.y + glyph.bounding_box.height)
-                                .max(glyph.bounding_box.y + glyph.bounding_box.height);
-                            glyph_min_y = glyph_min_y.min(y_top);
-                            glyph_max_y = glyph_max_y.max(y_bottom);
-                        }
-                    }
-                }
-            }
-        }
-        if glyph_min_y < system.bounding_box.y {
-            let extension = system.bounding_box.y - glyph_min_y;
-            system.bounding_box.y = glyph_min_y;
-            system.bounding_box.height += extension;
-        }
-        if glyph_max_y > system.bounding_box.y + system.bounding_box.height {
-            system.bounding_box.height = glyph_max_y - system.bounding_box.y;
+    // Tempo marks, rehearsal marks and text directions, clear of the brackets above
+    system.direction_layouts = directions::render_directions(
+        system,
+        instruments,
+        tempo_marks,
+        rehearsal_marks,
+        &note_positions,
+        &measure_x_bounds,
+        unified_left_margin,
+        config.units_per_space,
+    );
+
+    // The next system starts at least this far below this one's top;
+    // `stack_systems` moves it further down where the skylines come too close
+    let advance = system.bounding_box.height + config.system_spacing;
+
+    // The bounding box covers everything drawn (stems, beams, slurs,
+    // directions) so the frontend's viewport-based virtualization
+    // (getVisibleSystems) does not clip them at the viewBox edge
+    let skyline = skyline::system_skyline(system, config.units_per_space);
+    if let (Some(top), Some(bottom)) = (skyline.top(), skyline.bottom()) {
+        system.bounding_box.y = top;
+        system.bounding_box.height = bottom - top;
+    }
+    assembly::compute_measure_bounding_boxes(system);
+    SystemSpacing { advance, skyline }
+}
+
+/// Stack laid-out systems top to bottom and distribute them onto pages
+///
+/// Each system is placed its nominal advance below the previous one, or
+/// further down if their skylines would otherwise come closer than
+/// `SYSTEM_CLEARANCE_SPACES`.
+fn stack_systems(
+    ctx: &ScoreContext,
+    config: &LayoutConfig,
+    mut systems: Vec<System>,
+    spacing: &[SystemSpacing],
+) -> GlobalLayout {
+    let ScoreContext {
+        ref measure_infos,
+        ref page_break_measures,
+        ..
+    } = *ctx;
+
+    // Top margin: leave space above the first system so stems, beams, flags,
+    // and measure numbers above the top staff are not clipped by viewport y=0.
+    // 4 staff spaces (80 units at ups=20) matches standard engraving practice.
+    let top_margin = 4.0 * config.units_per_space;
+    let mut running_y: f32 = top_margin; // Track cumulative y position across systems (collision-aware)
+    // Systems are laid out at y = 0; moving them is exact for reused systems
+    for (i, system) in systems.iter_mut().enumerate() {
+        pages::translate_system(system, running_y);
+        if let (Some(current), Some(next)) = (spacing.get(i), spacing.get(i + 1)) {
+            let clear = Skyline::distance(&current.skyline, &next.skyline)
+                .map_or(0.0, |touching| {
+                    touching + SYSTEM_CLEARANCE_SPACES * config.units_per_space
+                });
+            running_y += current.advance.max(clear);
         }
     }
 
+    // Distribute systems onto pages
+    let pages = match &config.page {
+        Some(page) => {
+            let page_starts: Vec<usize> = systems
+                .iter()
+                .filter(|system| {
+                    page_break_measures.iter().any(|&i| {
+                        measure_infos.get(i).map(|m| m.start_tick)
+                            == Some(system.tick_range.start_tick)
+                    })
+                })
+                .map(|system| system.index)
+                .collect();
+            pages::paginate(
+                &mut systems,
+                &page_starts,
+                page,
+                config.units_per_space,
+                config.system_spacing,
+            )
+        }
+        None => Vec::new(),
+    };
+
     // Compute GlobalLayout dimensions
     // Use the maximum actual system width (already trimmed to content)
     let total_width = systems
@@ -938,7 +1380,9 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
         .max_by(|a, b| a.partial_cmp(b).unwrap())
         .unwrap_or(config.max_system_width);
 
-    let total_height = if systems.is_empty() {
+    let total_height = if let Some(last_page) = pages.last() {
+        last_page.bounding_box.y + last_page.bounding_box.height
+    } else if systems.is_empty() {
         0.0
     } else {
         let last_system = systems.last().unwrap();
@@ -952,6 +1396,7 @@ pub fn compute_layout(score: &serde_json::Value, config: &LayoutConfig) -> Globa
         total_width,
         total_height,
         units_per_space: config.units_per_space,
+        pages,
     }
 }
 
@@ -1090,8 +1535,8 @@ mod tests {
         let treble_staff = &staff_group.staves[0];
         let bass_staff = &staff_group.staves[1];
 
-        let treble_top = treble_staff.staff_lines[0].y_position;
-        let bass_top = bass_staff.staff_lines[0].y_position;
+        let treble_top = treble_staff.top_line_y();
+        let bass_top = bass_staff.top_line_y();
 
         // Vertical spacing should be 10 staff spaces (200 units at default units_per_space=20)
         // This matches the standard grand-staff gap (~6 spaces between staff edges).
@@ -1155,8 +1600,8 @@ mod tests {
         let system = &layout.systems[0];
         let sg = &system.staff_groups[0];
 
-        let treble_top = sg.staves[0].staff_lines[0].y_position;
-        let bass_top = sg.staves[1].staff_lines[0].y_position;
+        let treble_top = sg.staves[0].top_line_y();
+        let bass_top = sg.staves[1].top_line_y();
         let spacing = bass_top - treble_top;
 
         let default_spacing = 8.0 * config.units_per_space; // 160 units
@@ -1207,8 +1652,8 @@ mod tests {
         let system = &layout.systems[0];
         let sg = &system.staff_groups[0];
 
-        let treble_top = sg.staves[0].staff_lines[0].y_position;
-        let bass_top = sg.staves[1].staff_lines[0].y_position;
+        let treble_top = sg.staves[0].top_line_y();
+        let bass_top = sg.staves[1].top_line_y();
         let spacing = bass_top - treble_top;
 
         let default_spacing = 10.0 * config.units_per_space; // 200 units (grand-staff standard)
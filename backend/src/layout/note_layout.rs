@@ -7,11 +7,13 @@
 use std::collections::HashMap;
 
 use crate::layout::beams;
+use crate::layout::cross_staff::CrossStaffContext;
 use crate::layout::extraction::{NoteData, NoteEvent, RestLayoutEvent, StaffData};
 use crate::layout::positioner;
 use crate::layout::spacer;
 use crate::layout::stems;
-use crate::layout::types::{BoundingBox, Glyph, Point, SourceReference, TickRange};
+use crate::layout::tablature;
+use crate::layout::types::{BoundingBox, EngravingStyle, Glyph, Point, SourceReference, TickRange};
 
 /// Compute absolute diatonic staff position for a note.
 /// Uses the explicit spelling (step letter + alteration) when available,
@@ -248,6 +250,8 @@ pub(crate) fn position_glyphs_for_staff(
     ticks_per_measure: u32,
     measure_x_bounds: &HashMap<u32, (f32, f32)>,
     pickup_ticks: u32,
+    cross_staff: &CrossStaffContext,
+    style: &EngravingStyle,
 ) -> Vec<Glyph> {
     let mut all_glyphs = Vec::new();
     let num_voices = staff_data.voices.len();
@@ -281,10 +285,16 @@ pub(crate) fn position_glyphs_for_staff(
                 note.start_tick >= tick_range.start_tick && note.start_tick < tick_range.end_tick
             })
             .map(|note| {
+                // Grace notes are drawn with their written value (e.g. an
+                // eighth with a flag), not their short visual tick duration
+                let duration = match &note.grace {
+                    Some(grace) => grace.written_ticks(),
+                    None => note.duration_ticks,
+                };
                 (
                     note.pitch,
                     note.start_tick,
-                    note.duration_ticks,
+                    duration,
                     note.spelling,
                     note.staccato,
                     note.dot_count,
@@ -305,6 +315,17 @@ pub(crate) fn position_glyphs_for_staff(
             })
             .collect();
 
+        // Cross-staff notes: vertical shift onto the staff they are drawn on,
+        // and the index of that staff
+        let note_shifts: Vec<f32> = voice_notes_in_range
+            .iter()
+            .map(|n| cross_staff.shift(staff_index, n))
+            .collect();
+        let note_display_staves: Vec<usize> = voice_notes_in_range
+            .iter()
+            .map(|n| cross_staff.display_staff(staff_index, n))
+            .collect();
+
         let horizontal_offsets: Vec<f32> = {
             // Grace notes are not in note_positions (excluded from unified
             // spacing).  Position them at a fixed offset before their target.
@@ -386,8 +407,14 @@ pub(crate) fn position_glyphs_for_staff(
             .filter(|(_, note)| note.duration_ticks <= 480 || !note.beam_info.is_empty())
             .map(|(i, note)| {
                 let notehead_x = horizontal_offsets[i];
-                let notehead_y = positioner::pitch_to_y(note.pitch, note_clefs[i], units_per_space)
-                    + staff_vertical_offset;
+                let notehead_y = positioner::pitch_to_staff_y(
+                    note.pitch,
+                    note_clefs[i],
+                    units_per_space,
+                    note.spelling,
+                    staff_data.line_count,
+                ) + staff_vertical_offset
+                    + note_shifts[i];
 
                 let beam_types: Vec<String> =
                     note.beam_info.iter().map(|(_, bt)| bt.clone()).collect();
@@ -416,19 +443,24 @@ pub(crate) fn position_glyphs_for_staff(
             }
         }
 
-        let has_beam_info = beamable_for_analysis
+        beam_grace_runs(&mut beamable_for_analysis, &voice_notes_in_range);
+
+        // Grace notes are beamed among themselves, never with regular notes
+        let (grace_beamable, regular_beamable): (Vec<_>, Vec<_>) = beamable_for_analysis
             .iter()
-            .any(|n| !n.beam_types.is_empty());
+            .cloned()
+            .partition(|n| grace_note_indices.contains(&n.event_index));
+        let has_beam_info = regular_beamable.iter().any(|n| !n.beam_types.is_empty());
         let measure_starts: Vec<u32> = {
             let mut starts: Vec<u32> = measure_x_bounds.keys().copied().collect();
             starts.sort();
             starts
         };
-        let beam_groups = if has_beam_info {
-            beams::build_beam_groups_from_musicxml(&beamable_for_analysis, &measure_starts)
+        let mut beam_groups = if has_beam_info {
+            beams::build_beam_groups_from_musicxml(&regular_beamable, &measure_starts)
         } else {
             let groups = beams::group_beamable_by_time_signature(
-                &beamable_for_analysis,
+                &regular_beamable,
                 staff_data.time_numerator,
                 staff_data.time_denominator,
             );
@@ -440,6 +472,10 @@ pub(crate) fn position_glyphs_for_staff(
                 })
                 .collect()
         };
+        beam_groups.extend(beams::build_beam_groups_from_musicxml(
+            &grace_beamable,
+            &measure_starts,
+        ));
 
         let mut beamed_note_indices = std::collections::HashSet::<usize>::new();
         let mut tick_to_indices: std::collections::HashMap<u32, Vec<usize>> =
@@ -479,12 +515,14 @@ pub(crate) fn position_glyphs_for_staff(
             .iter()
             .enumerate()
             .map(|(i, (pitch, _, _, spelling, _, _, _))| {
-                positioner::pitch_to_y_with_spelling(
+                positioner::pitch_to_staff_y(
                     *pitch,
                     note_clefs[i],
                     units_per_space,
                     *spelling,
+                    staff_data.line_count,
                 ) + staff_vertical_offset
+                    + note_shifts[i]
             })
             .collect();
 
@@ -508,19 +546,34 @@ pub(crate) fn position_glyphs_for_staff(
         let mut chord_stem_data: Vec<(f32, f32, f32, usize)> = Vec::new();
         let mut chord_flag_data: Vec<(f32, f32, bool, u32, usize)> = Vec::new();
 
-        for indices in chord_tick_to_indices.values() {
-            if indices.len() < 2 {
-                continue;
-            }
+        // Single notes keep their combined note glyphs at the standard stem
+        // length; at any other length they are chords of one, with stems
+        // drawn apart from their noteheads. Grace notes then stand alone.
+        let split_single_stems = style.stem_length != EngravingStyle::standard().stem_length;
+        let chord_groups = chord_tick_to_indices
+            .values()
+            .filter(|indices| split_single_stems || indices.len() >= 2)
+            .cloned()
+            .chain(
+                (0..notes_in_range.len())
+                    .filter(|idx| split_single_stems && grace_note_indices.contains(idx))
+                    .map(|idx| vec![idx]),
+            );
+        for indices in chord_groups {
+            let is_grace = grace_note_indices.contains(&indices[0]);
+            let grace_scale: f32 = if is_grace { 0.75 } else { 1.0 };
 
-            let mut sorted: Vec<usize> = indices.clone();
+            let mut sorted = indices;
             sorted.sort_by(|&a, &b| {
                 chord_note_y_positions[b]
                     .partial_cmp(&chord_note_y_positions[a])
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
 
-            let chord_stem_down = if let Some(forced) = forced_stem_down {
+            let chord_stem_down = if is_grace {
+                // Grace notes always get stems up
+                false
+            } else if let Some(forced) = forced_stem_down {
                 forced
             } else {
                 // Use explicit MusicXML stem direction if present
@@ -552,7 +605,8 @@ pub(crate) fn position_glyphs_for_staff(
             let needs_explicit_stem = chord_duration < 3840;
             let any_beamed = sorted.iter().any(|idx| beamed_note_indices.contains(idx));
 
-            let notehead_scale: f32 = if chord_duration >= 3840 {
+            // Grace noteheads keep the size of beamed grace noteheads
+            let notehead_scale: f32 = if chord_duration >= 3840 || is_grace {
                 1.0
             } else if chord_duration >= 1920 {
                 345.0 / 300.0
@@ -560,7 +614,8 @@ pub(crate) fn position_glyphs_for_staff(
                 332.0 / 295.0
             };
             let chord_font_size = 80.0 * notehead_scale;
-            let scaled_half_width = stems::Stem::NOTEHEAD_WIDTH * notehead_scale;
+            let scaled_half_width = stems::Stem::NOTEHEAD_WIDTH * notehead_scale * grace_scale;
+            let stem_length = style.stem_length * units_per_space * grace_scale;
             let chord_displacement = scaled_half_width * 2.0;
 
             if needs_explicit_stem && !any_beamed {
@@ -574,7 +629,7 @@ pub(crate) fn position_glyphs_for_staff(
 
                 if chord_stem_down {
                     let stem_x = horizontal_offsets[anchor_idx] - scaled_half_width;
-                    let stem_tip_y = bottom_y + stems::Stem::STEM_LENGTH;
+                    let stem_tip_y = bottom_y + stem_length;
                     chord_stem_data.push((stem_x, top_y, stem_tip_y, anchor_idx));
                     if chord_duration < 960 {
                         chord_flag_data.push((
@@ -587,7 +642,7 @@ pub(crate) fn position_glyphs_for_staff(
                     }
                 } else {
                     let stem_x = horizontal_offsets[anchor_idx] + scaled_half_width;
-                    let stem_tip_y = top_y - stems::Stem::STEM_LENGTH;
+                    let stem_tip_y = top_y - stem_length;
                     chord_stem_data.push((stem_x, stem_tip_y, bottom_y, anchor_idx));
                     if chord_duration < 960 {
                         chord_flag_data.push((
@@ -655,9 +710,10 @@ pub(crate) fn position_glyphs_for_staff(
             forced_stem_down,
             &grace_note_indices,
             &explicit_stem_downs,
+            staff_data.line_count,
         );
 
-        all_glyphs.extend(glyphs);
+        all_glyphs.extend(shift_cross_staff_glyphs(glyphs, &note_shifts));
 
         for &(stem_x, y_top, y_bottom, event_index) in &chord_stem_data {
             let is_grace_stem = grace_note_indices.contains(&event_index);
@@ -747,24 +803,29 @@ pub(crate) fn position_glyphs_for_staff(
             ms.sort();
             ms
         };
-        let accidental_glyphs = positioner::position_note_accidentals(
-            &notes_in_range,
-            &adjusted_horizontal_offsets,
-            &note_clefs,
-            units_per_space,
-            instrument_id,
-            staff_index,
-            voice_index,
-            staff_vertical_offset,
-            staff_data.key_sharps,
-            ticks_per_measure,
-            &staff_data.key_signature_events,
-            pickup_ticks,
-            &measure_starts_sorted,
-            &grace_note_indices,
-        );
+        // One-line staves have no pitches to alter
+        let accidental_glyphs = if staff_data.line_count == 1 {
+            Vec::new()
+        } else {
+            positioner::position_note_accidentals(
+                &notes_in_range,
+                &adjusted_horizontal_offsets,
+                &note_clefs,
+                units_per_space,
+                instrument_id,
+                staff_index,
+                voice_index,
+                staff_vertical_offset,
+                staff_data.key_sharps,
+                ticks_per_measure,
+                &staff_data.key_signature_events,
+                pickup_ticks,
+                &measure_starts_sorted,
+                &grace_note_indices,
+            )
+        };
 
-        all_glyphs.extend(accidental_glyphs);
+        all_glyphs.extend(shift_cross_staff_glyphs(accidental_glyphs, &note_shifts));
 
         let staff_middle_y = staff_vertical_offset + 1.5 * units_per_space;
 
@@ -804,6 +865,122 @@ pub(crate) fn position_glyphs_for_staff(
                 .iter()
                 .all(|n| grace_note_indices.contains(&n.event_index));
 
+            // Cross-staff beam group: notes on two staves share a beam
+            // placed between the staves
+            let upper_staff = group
+                .notes
+                .iter()
+                .map(|n| note_display_staves[n.event_index])
+                .min()
+                .unwrap_or(staff_index);
+            let is_cross_staff_group = group
+                .notes
+                .iter()
+                .any(|n| note_display_staves[n.event_index] != upper_staff);
+            if is_cross_staff_group {
+                let visual_y_offset = 0.5 * units_per_space;
+                let notehead_width = stems::Stem::NOTEHEAD_WIDTH;
+                let y_range = |n: &beams::BeamableNote| {
+                    chord_y_range
+                        .get(&(n.tick, is_grace_group))
+                        .copied()
+                        .unwrap_or((n.y, n.y))
+                };
+                let on_upper =
+                    |n: &beams::BeamableNote| note_display_staves[n.event_index] == upper_staff;
+
+                // Kneed beam: midway between the lowest upper-staff notehead
+                // and the highest lower-staff notehead
+                let upper_bottom = group
+                    .notes
+                    .iter()
+                    .filter(|n| on_upper(n))
+                    .map(|n| y_range(n).1 + visual_y_offset)
+                    .fold(f32::NEG_INFINITY, f32::max);
+                let lower_top = group
+                    .notes
+                    .iter()
+                    .filter(|n| !on_upper(n))
+                    .map(|n| y_range(n).0 + visual_y_offset)
+                    .fold(f32::INFINITY, f32::min);
+                let beam_y = (upper_bottom + lower_top) / 2.0;
+
+                let mut beamable_with_stems = Vec::new();
+                for note in &group.notes {
+                    // Upper-staff notes hang their stems down to the beam,
+                    // lower-staff notes reach up to it
+                    let (min_y, max_y) = y_range(note);
+                    let (direction, far_visual_y) = if on_upper(note) {
+                        (stems::StemDirection::Down, min_y + visual_y_offset)
+                    } else {
+                        (stems::StemDirection::Up, max_y + visual_y_offset)
+                    };
+                    let stem = stems::create_stem(note.x, far_visual_y, direction, notehead_width);
+                    let stem_top_y = stem.y_start.min(beam_y);
+                    let stem_height = (beam_y - stem.y_start).abs();
+                    stem_glyphs.push(Glyph {
+                        codepoint: '\u{0000}'.to_string(),
+                        position: Point {
+                            x: stem.x,
+                            y: stem_top_y,
+                        },
+                        bounding_box: BoundingBox {
+                            x: stem.x - (stem.thickness / 2.0),
+                            y: stem_top_y,
+                            width: stem.thickness,
+                            height: stem_height,
+                        },
+                        source_reference: SourceReference {
+                            instrument_id: instrument_id.to_string(),
+                            staff_index,
+                            voice_index,
+                            event_index: note.event_index,
+                        },
+                        font_size: None,
+                        opacity: None,
+                    });
+                    beamable_with_stems.push(beams::BeamableNote {
+                        x: stem.x,
+                        stem_end_y: beam_y,
+                        ..note.clone()
+                    });
+                }
+
+                // Secondary beams stack towards the first note's staff
+                let stem_direction_up = !on_upper(&group.notes[0]);
+                let updated_group = beams::BeamGroup {
+                    notes: beamable_with_stems,
+                    beam_count: group.beam_count,
+                };
+                let primary = beams::create_beam(&updated_group.notes, 0.0);
+                let secondary =
+                    beams::create_multi_level_beams(&updated_group, 0.0, stem_direction_up);
+                for beam in primary.into_iter().chain(secondary) {
+                    all_glyphs.push(Glyph {
+                        codepoint: '\u{0001}'.to_string(),
+                        position: Point {
+                            x: beam.x_start,
+                            y: beam.y_start,
+                        },
+                        bounding_box: BoundingBox {
+                            x: beam.x_start,
+                            y: beam.y_end,
+                            width: beam.x_end - beam.x_start,
+                            height: beam.thickness,
+                        },
+                        source_reference: SourceReference {
+                            instrument_id: instrument_id.to_string(),
+                            staff_index,
+                            voice_index,
+                            event_index: updated_group.notes.first().map_or(0, |n| n.event_index),
+                        },
+                        font_size: None,
+                        opacity: None,
+                    });
+                }
+                continue;
+            }
+
             let group_direction = if is_grace_group {
                 // Grace notes always get stems up (music convention).
                 stems::StemDirection::Up
@@ -878,8 +1055,10 @@ pub(crate) fn position_glyphs_for_staff(
             // Extend minimum stem for multi-beam groups (16ths, 32nds)
             // so stems aren't cramped by the extra beam lines.
             let max_beam_levels = group.notes.iter().map(|n| n.beam_levels).max().unwrap_or(1);
-            let beam_level_extra = (max_beam_levels.saturating_sub(1) as f32) * 5.0 * grace_scale;
-            let min_length = stems::Stem::MIN_BEAMED_STEM_LENGTH * grace_scale + beam_level_extra;
+            let beam_level_extra =
+                (max_beam_levels.saturating_sub(1) as f32) * 0.25 * units_per_space * grace_scale;
+            let min_length =
+                style.min_beamed_stem_length * units_per_space * grace_scale + beam_level_extra;
             for note in &group.notes {
                 // For chords, use the notehead closest to the beam
                 // direction as the stem origin, so the minimum stem length
@@ -923,7 +1102,7 @@ pub(crate) fn position_glyphs_for_staff(
                 0.0
             };
 
-            let max_slope_units = beams::Beam::MAX_SLOPE * units_per_space;
+            let max_slope_units = style.max_beam_slope * units_per_space;
             let max_slope_per_unit = if dx.abs() > 0.001 {
                 max_slope_units / dx.abs()
             } else {
@@ -941,7 +1120,7 @@ pub(crate) fn position_glyphs_for_staff(
             for (i, stem) in initial_stems.iter().enumerate() {
                 let beam_y = beam_y_at_stems[i];
                 let min_length =
-                    stems::Stem::MIN_BEAMED_STEM_LENGTH * grace_scale + beam_level_extra;
+                    style.min_beamed_stem_length * units_per_space * grace_scale + beam_level_extra;
                 // For chords, enforce minimum clearance from the
                 // notehead closest to the beam, not the stem origin
                 // (which is at the far side of the chord).
@@ -1090,6 +1269,29 @@ pub(crate) fn position_glyphs_for_staff(
         }
 
         all_glyphs.extend(stem_glyphs);
+
+        let voice_source = SourceReference {
+            instrument_id: instrument_id.to_string(),
+            staff_index,
+            voice_index,
+            event_index: 0,
+        };
+        let slashes = grace_slash_glyphs(
+            &voice_notes_in_range,
+            &beam_groups,
+            &beamed_note_indices,
+            &all_glyphs,
+            units_per_space,
+            voice_source.clone(),
+        );
+        all_glyphs.extend(slashes);
+        let tremolos = tremolo_glyphs(
+            &voice_notes_in_range,
+            &all_glyphs,
+            units_per_space,
+            voice_source,
+        );
+        all_glyphs.extend(tremolos);
     }
 
     // Position rests for this staff (all voices combined)
@@ -1123,6 +1325,380 @@ pub(crate) fn position_glyphs_for_staff(
     all_glyphs
 }
 
+/// Move noteheads and accidentals of cross-staff notes onto the staff they
+/// are drawn on. Glyphs are matched to notes by `source_reference.event_index`.
+fn shift_cross_staff_glyphs(mut glyphs: Vec<Glyph>, note_shifts: &[f32]) -> Vec<Glyph> {
+    for glyph in &mut glyphs {
+        let shift = note_shifts
+            .get(glyph.source_reference.event_index)
+            .copied()
+            .unwrap_or(0.0);
+        if shift != 0.0 {
+            glyph.position.y += shift;
+            glyph.bounding_box.y += shift;
+        }
+    }
+    glyphs
+}
+
+/// Vertical thickness of an acciaccatura slash
+const GRACE_SLASH_THICKNESS: f32 = 1.5;
+
+/// Give runs of consecutive grace notes written without `<beam>` elements
+/// synthetic beam annotations, so they are drawn with small beams like
+/// MusicXML-beamed grace groups. The beam count follows the written value.
+fn beam_grace_runs(beamable: &mut [beams::BeamableNote], voice_notes: &[&NoteEvent]) {
+    let levels = |n: &beams::BeamableNote| -> Option<u8> {
+        let note = voice_notes[n.event_index];
+        if !note.is_grace || !note.beam_info.is_empty() {
+            return None;
+        }
+        match note.grace.as_ref().map_or(480, |g| g.written_ticks()) {
+            t if t >= 960 => None,
+            t if t >= 480 => Some(1),
+            t if t >= 240 => Some(2),
+            t if t >= 120 => Some(3),
+            _ => Some(4),
+        }
+    };
+
+    let mut start = 0;
+    while start < beamable.len() {
+        let mut end = start;
+        while end < beamable.len()
+            && levels(&beamable[end]).is_some()
+            && (end == start
+                || beamable[end].tick == beamable[end - 1].tick + beamable[end - 1].duration_ticks)
+        {
+            end += 1;
+        }
+        if end - start >= 2 {
+            let count = beamable[start..end]
+                .iter()
+                .filter_map(&levels)
+                .min()
+                .unwrap_or(1);
+            let last = end - start - 1;
+            for (k, note) in beamable[start..end].iter_mut().enumerate() {
+                let beam_type = if k == 0 {
+                    "Begin"
+                } else if k == last {
+                    "End"
+                } else {
+                    "Continue"
+                };
+                note.beam_levels = count;
+                note.beam_types = vec![beam_type.to_string(); count as usize];
+            }
+        }
+        start = end.max(start + 1);
+    }
+}
+
+/// Slashes through the stems of slashed grace notes (acciaccaturas): one on
+/// every unbeamed slashed grace note and one across the first stem of a
+/// slashed grace beam group. Slashes use the sloped beam encoding (U+0001).
+fn grace_slash_glyphs(
+    voice_notes: &[&NoteEvent],
+    beam_groups: &[beams::BeamGroup],
+    beamed_note_indices: &std::collections::HashSet<usize>,
+    glyphs: &[Glyph],
+    units_per_space: f32,
+    source: SourceReference,
+) -> Vec<Glyph> {
+    let scale: f32 = 0.75;
+    let group_starts: std::collections::HashSet<usize> = beam_groups
+        .iter()
+        .filter(|g| g.notes.len() >= 2)
+        .map(|g| g.notes[0].event_index)
+        .collect();
+
+    let mut slashes = Vec::new();
+    for (i, note) in voice_notes.iter().enumerate() {
+        if !note.grace.as_ref().is_some_and(|g| g.slash) {
+            continue;
+        }
+        if beamed_note_indices.contains(&i) && !group_starts.contains(&i) {
+            continue;
+        }
+        let Some((stem_x, tip_y, _, up)) =
+            drawn_stem(&[i], source.voice_index, glyphs, units_per_space)
+        else {
+            continue;
+        };
+
+        // Cross the stem just inside its tip, rising to the right
+        let inset = 1.2 * units_per_space * scale;
+        let center_y = if up { tip_y + inset } else { tip_y - inset };
+        let (half_dx, half_dy) = (0.8 * units_per_space * scale, 0.6 * units_per_space * scale);
+        slashes.push(Glyph {
+            codepoint: '\u{0001}'.to_string(),
+            position: Point {
+                x: stem_x - half_dx,
+                y: center_y + half_dy,
+            },
+            bounding_box: BoundingBox {
+                x: stem_x - half_dx,
+                y: center_y - half_dy,
+                width: 2.0 * half_dx,
+                height: GRACE_SLASH_THICKNESS,
+            },
+            source_reference: SourceReference {
+                event_index: i,
+                ..source.clone()
+            },
+            font_size: None,
+            opacity: None,
+        });
+    }
+    slashes
+}
+
+/// SMuFL tremolo1 … tremolo5 (single-note tremolo strokes)
+const TREMOLO_GLYPHS: [(char, &str); 5] = [
+    ('\u{E220}', "tremolo1"),
+    ('\u{E221}', "tremolo2"),
+    ('\u{E222}', "tremolo3"),
+    ('\u{E223}', "tremolo4"),
+    ('\u{E224}', "tremolo5"),
+];
+/// SMuFL buzzRoll (unmeasured tremolo)
+const BUZZ_ROLL_GLYPH: (char, &str) = ('\u{E22A}', "buzzRoll");
+
+/// Tremolo strokes for a voice: SMuFL stroke glyphs across the stem of
+/// single-note tremolos (above the notehead of stemless notes), and sloped
+/// beam-like strokes (U+0001) between the two chords of a two-note tremolo.
+fn tremolo_glyphs(
+    voice_notes: &[&NoteEvent],
+    glyphs: &[Glyph],
+    units_per_space: f32,
+    source: SourceReference,
+) -> Vec<Glyph> {
+    use crate::domain::events::ornament::TremoloKind;
+
+    let ups = units_per_space;
+    // Chords in voice order: (tick, note indices)
+    let mut chords: Vec<(u32, Vec<usize>)> = Vec::new();
+    for (i, note) in voice_notes.iter().enumerate() {
+        if note.is_grace {
+            continue;
+        }
+        match chords.iter_mut().find(|(tick, _)| *tick == note.start_tick) {
+            Some((_, indices)) => indices.push(i),
+            None => chords.push((note.start_tick, vec![i])),
+        }
+    }
+    let chord_tremolo = |indices: &[usize]| indices.iter().find_map(|&i| voice_notes[i].tremolo);
+    let head_of = |indices: &[usize]| {
+        glyphs.iter().find(|g| {
+            g.source_reference.voice_index == source.voice_index
+                && indices.contains(&g.source_reference.event_index)
+                && g.codepoint != "\u{0000}"
+                && g.codepoint != "\u{0001}"
+        })
+    };
+
+    let mut result = Vec::new();
+    for (chord_index, (_, indices)) in chords.iter().enumerate() {
+        let Some(tremolo) = chord_tremolo(indices) else {
+            continue;
+        };
+        let Some(head) = head_of(indices) else {
+            continue;
+        };
+        let scale = head.font_size.unwrap_or(80.0) / 80.0;
+        let source_reference = SourceReference {
+            event_index: indices[0],
+            ..source.clone()
+        };
+
+        match tremolo.kind {
+            TremoloKind::Single | TremoloKind::Unmeasured => {
+                let (codepoint, name) = if tremolo.kind == TremoloKind::Unmeasured {
+                    BUZZ_ROLL_GLYPH
+                } else {
+                    TREMOLO_GLYPHS[(tremolo.strokes.clamp(1, 5) - 1) as usize]
+                };
+                // Across the stem, nearer the tip; above the notehead if stemless
+                let position = match drawn_stem(indices, source.voice_index, glyphs, ups) {
+                    Some((x, tip, base, _)) => Point {
+                        x,
+                        y: tip + (base - tip) * 0.45,
+                    },
+                    None => Point {
+                        x: head.position.x,
+                        y: head.position.y + 0.5 * ups - 2.5 * ups,
+                    },
+                };
+                result.push(Glyph {
+                    bounding_box: positioner::compute_glyph_bounding_box(
+                        name,
+                        &position,
+                        40.0 * scale,
+                        ups,
+                    ),
+                    position,
+                    codepoint: codepoint.to_string(),
+                    source_reference,
+                    font_size: Some(80.0 * scale),
+                    opacity: None,
+                });
+            }
+            TremoloKind::Start => {
+                let Some((_, partner)) = chords
+                    .get(chord_index + 1)
+                    .filter(|(_, p)| chord_tremolo(p).is_some_and(|t| t.kind == TremoloKind::Stop))
+                else {
+                    continue;
+                };
+                let Some(partner_head) = head_of(partner) else {
+                    continue;
+                };
+                // (x, stroke band center y) at each end
+                let end_point = |indices: &[usize], head: &Glyph, right: bool| {
+                    let floating = matches!(
+                        head.codepoint.chars().next(),
+                        Some('\u{E0A2}' | '\u{E0A3}' | '\u{E1D3}' | '\u{E1D4}')
+                    );
+                    match drawn_stem(indices, source.voice_index, glyphs, ups) {
+                        // Strokes of half-note tremolos float between the stems
+                        Some((x, tip, base, _)) => {
+                            let inset = if floating { 0.5 * ups } else { 0.0 };
+                            let x = if right { x + inset } else { x - inset };
+                            (x, tip + (base - tip) * 0.45)
+                        }
+                        None => {
+                            let half = stems::Stem::NOTEHEAD_WIDTH * scale + 0.5 * ups;
+                            let x = if right {
+                                head.position.x + half
+                            } else {
+                                head.position.x - half
+                            };
+                            (x, head.position.y + 0.5 * ups)
+                        }
+                    }
+                };
+                let (x_start, y_start) = end_point(indices, head, true);
+                let (x_end, y_end) = end_point(partner, partner_head, false);
+                if x_end <= x_start {
+                    continue;
+                }
+                let slope = ((y_end - y_start) / (x_end - x_start)).clamp(-0.25, 0.25);
+                let mid_y = (y_start + y_end) / 2.0;
+                let thickness = beams::Beam::BEAM_THICKNESS * scale;
+                let pitch = (beams::Beam::BEAM_THICKNESS + beams::Beam::INTER_BEAM_GAP) * scale;
+                let strokes = tremolo.strokes.clamp(1, 5);
+                for k in 0..strokes {
+                    let offset = (k as f32 - (strokes - 1) as f32 / 2.0) * pitch - thickness / 2.0;
+                    let y_left = mid_y - slope * (x_end - x_start) / 2.0 + offset;
+                    result.push(Glyph {
+                        codepoint: '\u{0001}'.to_string(),
+                        position: Point {
+                            x: x_start,
+                            y: y_left,
+                        },
+                        bounding_box: BoundingBox {
+                            x: x_start,
+                            y: y_left + slope * (x_end - x_start),
+                            width: x_end - x_start,
+                            height: thickness,
+                        },
+                        source_reference: source_reference.clone(),
+                        font_size: None,
+                        opacity: None,
+                    });
+                }
+            }
+            TremoloKind::Stop => {}
+        }
+    }
+    result
+}
+
+/// Stem of a note (or chord, given all its note indices) as drawn:
+/// `(stem x, stem tip y, stem base y, stem up)`. Uses the stem pseudo-glyph of beamed and
+/// chord notes, else the stem embedded in a combined note glyph
+/// (e.g. noteQuarterUp). `None` for stemless notes.
+fn drawn_stem(
+    indices: &[usize],
+    voice_index: usize,
+    glyphs: &[Glyph],
+    units_per_space: f32,
+) -> Option<(f32, f32, f32, bool)> {
+    let in_voice = |g: &&Glyph| {
+        g.source_reference.voice_index == voice_index
+            && indices.contains(&g.source_reference.event_index)
+    };
+    let head = glyphs.iter().filter(in_voice).find(|g| {
+        g.codepoint.chars().next().is_some_and(|c| {
+            ('\u{E0A0}'..='\u{E0FF}').contains(&c) || ('\u{E1D0}'..='\u{E1FF}').contains(&c)
+        })
+    })?;
+
+    if let Some(stem) = glyphs
+        .iter()
+        .filter(in_voice)
+        .find(|g| g.codepoint == "\u{0000}")
+    {
+        let top = stem.bounding_box.y;
+        let bottom = top + stem.bounding_box.height;
+        let up = (head.position.y - top).abs() > (head.position.y - bottom).abs();
+        return if up {
+            Some((stem.position.x, top, bottom, true))
+        } else {
+            Some((stem.position.x, bottom, top, false))
+        };
+    }
+
+    // Combined glyphs U+E1D3..U+E1DA alternate stem up (odd) and stem down (even)
+    let code = head.codepoint.chars().next()? as u32;
+    if !(0xE1D3..=0xE1DA).contains(&code) {
+        return None;
+    }
+    let up = code % 2 == 1;
+    let scale = head.font_size.unwrap_or(80.0) / 80.0;
+    let stem_x = if up {
+        head.position.x + stems::Stem::NOTEHEAD_WIDTH * scale
+    } else {
+        head.position.x - stems::Stem::NOTEHEAD_WIDTH * scale
+    };
+    let head_y = head.position.y + 0.5 * units_per_space;
+    let tip_y = if up {
+        head_y - stems::Stem::STEM_LENGTH * scale
+    } else {
+        head_y + stems::Stem::STEM_LENGTH * scale
+    };
+    Some((stem_x, tip_y, head_y, up))
+}
+
+/// Horizontal position for a tick: the note at that tick, else the closest
+/// earlier note, else the start of the containing measure.
+pub(crate) fn tick_to_x(
+    tick: u32,
+    note_positions: &HashMap<u32, f32>,
+    measure_x_bounds: &HashMap<u32, (f32, f32)>,
+    unified_left_margin: f32,
+) -> f32 {
+    if let Some(&x) = note_positions.get(&tick) {
+        return x;
+    }
+    let measure_start = measure_x_bounds
+        .iter()
+        .filter(|(t, _)| **t <= tick)
+        .max_by_key(|(t, _)| **t);
+    let note_before = note_positions
+        .iter()
+        .filter(|(t, _)| **t <= tick)
+        .max_by_key(|(t, _)| **t);
+    match (note_before, measure_start) {
+        // Only use the earlier note if it lies in the same measure
+        (Some((nt, &nx)), Some((mt, _))) if nt >= mt => nx,
+        (_, Some((_, &(start, _)))) => start + 10.0,
+        (Some((_, &nx)), None) => nx,
+        (None, None) => unified_left_margin,
+    }
+}
+
 /// Compute the vertical extent of notes in a staff for a given tick range.
 ///
 /// Returns (min_y, max_y) relative to the staff origin (top line = 0).
@@ -1130,20 +1706,27 @@ pub(crate) fn compute_staff_note_extents(
     staff_data: &StaffData,
     tick_range: &TickRange,
     units_per_space: f32,
+    style: &EngravingStyle,
 ) -> (f32, f32) {
+    if staff_data.tablature.is_some() {
+        return tablature::tab_staff_extents(staff_data, units_per_space);
+    }
     let mut min_y = 0.0_f32;
     let mut max_y = 4.0 * units_per_space;
     let middle_y = 2.0 * units_per_space;
+    let stem_length = style.stem_length * units_per_space;
 
     for voice in &staff_data.voices {
-        for note in &voice.notes {
+        // Cross-staff notes are drawn on (and near) another staff
+        for note in voice.notes.iter().filter(|n| n.cross_staff.is_none()) {
             if note.start_tick >= tick_range.start_tick && note.start_tick < tick_range.end_tick {
                 let active_clef = staff_data.get_clef_at_tick(note.start_tick);
-                let y = positioner::pitch_to_y_with_spelling(
+                let y = positioner::pitch_to_staff_y(
                     note.pitch,
                     active_clef,
                     units_per_space,
                     note.spelling,
+                    staff_data.line_count,
                 );
                 if y < min_y {
                     min_y = y;
@@ -1152,12 +1735,12 @@ pub(crate) fn compute_staff_note_extents(
                     max_y = y;
                 }
                 if y < middle_y {
-                    let stem_tip = y + stems::Stem::STEM_LENGTH;
+                    let stem_tip = y + stem_length;
                     if stem_tip > max_y {
                         max_y = stem_tip;
                     }
                 } else {
-                    let stem_tip = y - stems::Stem::STEM_LENGTH;
+                    let stem_tip = y - stem_length;
                     if stem_tip < min_y {
                         min_y = stem_tip;
                     }
@@ -1187,6 +1770,11 @@ mod tests {
             key_signature_events: vec![],
             clef_events: vec![],
             octave_shift_regions: vec![],
+            chord_symbols: vec![],
+            pedal_regions: vec![],
+            text_directions: vec![],
+            line_count: 5,
+            tablature: None,
             voices: vec![VoiceData {
                 notes: vec![NoteEvent {
                     pitch: 67,
@@ -1204,6 +1792,13 @@ mod tests {
                     has_explicit_accidental: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    cross_staff: None,
+                    grace: None,
+                    arpeggio: None,
+                    gliss_next: None,
+                    gliss_wavy: false,
+                    tremolo: None,
+                    tab: None,
                 }],
                 rests: vec![],
             }],
@@ -1212,7 +1807,8 @@ mod tests {
             start_tick: 0,
             end_tick: 3840,
         };
-        let (min_y, max_y) = compute_staff_note_extents(&staff_data, &tick_range, ups);
+        let (min_y, max_y) =
+            compute_staff_note_extents(&staff_data, &tick_range, ups, &EngravingStyle::default());
         assert!(
             (min_y - (-20.0)).abs() < 0.1,
             "min_y should be ~-20 for G4 (stem extends above staff), got {}",
@@ -1236,6 +1832,11 @@ mod tests {
             key_signature_events: vec![],
             clef_events: vec![],
             octave_shift_regions: vec![],
+            chord_symbols: vec![],
+            pedal_regions: vec![],
+            text_directions: vec![],
+            line_count: 5,
+            tablature: None,
             voices: vec![VoiceData {
                 notes: vec![NoteEvent {
                     pitch: 60,
@@ -1253,6 +1854,13 @@ mod tests {
                     has_explicit_accidental: false,
                     stem_down: None,
                     fingering: Vec::new(),
+                    cross_staff: None,
+                    grace: None,
+                    arpeggio: None,
+                    gliss_next: None,
+                    gliss_wavy: false,
+                    tremolo: None,
+                    tab: None,
                 }],
                 rests: vec![],
             }],
@@ -1261,7 +1869,8 @@ mod tests {
             start_tick: 0,
             end_tick: 3840,
         };
-        let (_min_y, max_y) = compute_staff_note_extents(&staff_data, &tick_range, ups);
+        let (_min_y, max_y) =
+            compute_staff_note_extents(&staff_data, &tick_range, ups, &EngravingStyle::default());
         assert!(
             max_y > 4.0 * ups,
             "max_y should extend below staff for C4 in treble, got {}",
@@ -1316,7 +1925,7 @@ mod tests {
         );
 
         // Verify treble staff note is positioned relative to treble staff lines
-        let treble_line_0 = staff_group.staves[0].staff_lines[0].y_position;
+        let treble_line_0 = staff_group.staves[0].top_line_y();
         let treble_glyph = &staff_group.staves[0].glyph_runs[0].glyphs[0];
         assert!(
             treble_glyph.position.y >= treble_line_0 - 100.0,
@@ -1324,7 +1933,7 @@ mod tests {
         );
 
         // Verify bass staff note is positioned relative to bass staff lines
-        let bass_line_0 = staff_group.staves[1].staff_lines[0].y_position;
+        let bass_line_0 = staff_group.staves[1].top_line_y();
         let bass_glyph = &staff_group.staves[1].glyph_runs[0].glyphs[0];
         assert!(
             bass_glyph.position.y >= bass_line_0 - 100.0,
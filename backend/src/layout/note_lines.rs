@@ -0,0 +1,391 @@
+//! Arpeggio and glissando line rendering
+//!
+//! Both marks are drawn relative to noteheads that have already been placed,
+//! so they are computed from a staff's positioned glyphs: arpeggio signs sit
+//! left of the chord (and of its accidentals), glissando lines run from the
+//! right edge of one notehead to the left edge of the next. A glissando
+//! crossing a system break is split into two open-ended lines.
+
+use std::collections::HashMap;
+
+use crate::domain::events::ornament::ArpeggioStyle;
+use crate::layout::cross_staff::CrossStaffContext;
+use crate::layout::extraction::{NoteEvent, StaffData};
+use crate::layout::positioner;
+use crate::layout::types::{
+    ArpeggioLayout, GlissandoLayout, Glyph, GlyphRun, Point, Staff, TickRange,
+};
+
+/// Gap between the arpeggio line and the leftmost notehead/accidental, in staff spaces
+const ARPEGGIO_GAP_SPACES: f32 = 0.5;
+/// How far the arpeggio extends beyond the outer noteheads, in staff spaces
+const ARPEGGIO_OVERHANG_SPACES: f32 = 0.5;
+/// Zigzag amplitude (half width) and half period of wavy lines, in staff spaces
+const WAVE_AMPLITUDE_SPACES: f32 = 0.25;
+const WAVE_HALF_PERIOD_SPACES: f32 = 0.375;
+/// Width of the non-arpeggio bracket hooks, in staff spaces
+const BRACKET_HOOK_SPACES: f32 = 0.4;
+/// Arrowhead half width and length, in staff spaces
+const ARROW_HALF_WIDTH_SPACES: f32 = 0.4;
+const ARROW_LENGTH_SPACES: f32 = 0.75;
+/// Gap between a glissando end and the notehead, in staff spaces
+const GLISS_GAP_SPACES: f32 = 0.3;
+
+/// Noteheads and accidentals of the staff's notes, keyed by (voice, event index)
+fn note_glyphs(glyph_runs: &[GlyphRun]) -> HashMap<(usize, usize), Vec<&Glyph>> {
+    let mut by_note: HashMap<(usize, usize), Vec<&Glyph>> = HashMap::new();
+    for glyph in glyph_runs.iter().flat_map(|run| run.glyphs.iter()) {
+        let Some(c) = glyph.codepoint.chars().next() else {
+            continue;
+        };
+        let head_or_accidental = ('\u{E0A0}'..='\u{E0FF}').contains(&c)
+            || ('\u{E1D0}'..='\u{E1FF}').contains(&c)
+            || ('\u{E260}'..='\u{E26F}').contains(&c);
+        if head_or_accidental {
+            let key = (
+                glyph.source_reference.voice_index,
+                glyph.source_reference.event_index,
+            );
+            by_note.entry(key).or_default().push(glyph);
+        }
+    }
+    by_note
+}
+
+fn is_accidental(glyph: &Glyph) -> bool {
+    glyph
+        .codepoint
+        .chars()
+        .next()
+        .is_some_and(|c| ('\u{E260}'..='\u{E26F}').contains(&c))
+}
+
+/// Notes of each voice in the system, indexed like the glyphs' `event_index`
+fn voice_notes_in_range<'a>(
+    staff_data: &'a StaffData,
+    tick_range: &TickRange,
+) -> Vec<Vec<&'a NoteEvent>> {
+    staff_data
+        .voices
+        .iter()
+        .map(|voice| {
+            voice
+                .notes
+                .iter()
+                .filter(|n| {
+                    n.start_tick >= tick_range.start_tick && n.start_tick < tick_range.end_tick
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Zigzag polyline from `from` to `to`
+fn wavy_points(from: Point, to: Point, units_per_space: f32) -> Vec<Point> {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length = (dx * dx + dy * dy).sqrt();
+    let half_period = WAVE_HALF_PERIOD_SPACES * units_per_space;
+    if length < 2.0 * half_period {
+        return vec![from, to];
+    }
+    let (ux, uy) = (dx / length, dy / length);
+    // Unit normal, scaled to the wave amplitude
+    let (nx, ny) = (
+        -uy * WAVE_AMPLITUDE_SPACES * units_per_space,
+        ux * WAVE_AMPLITUDE_SPACES * units_per_space,
+    );
+    let steps = (length / half_period).floor() as usize;
+    let step = length / steps as f32;
+    let mut points = vec![from];
+    for k in 1..steps {
+        let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+        let along = step * k as f32;
+        points.push(Point {
+            x: from.x + ux * along + nx * sign,
+            y: from.y + uy * along + ny * sign,
+        });
+    }
+    points.push(to);
+    points
+}
+
+/// Arpeggio sign geometry for a line at `x` spanning `y_top..y_bottom`
+fn arpeggio_layout(
+    style: ArpeggioStyle,
+    start_tick: u32,
+    x: f32,
+    y_top: f32,
+    y_bottom: f32,
+    units_per_space: f32,
+) -> ArpeggioLayout {
+    let ups = units_per_space;
+    let top = Point { x, y: y_top };
+    let bottom = Point { x, y: y_bottom };
+    let points = match style {
+        ArpeggioStyle::NonArpeggio => {
+            let hook = BRACKET_HOOK_SPACES * ups;
+            vec![
+                Point {
+                    x: x + hook,
+                    y: y_top,
+                },
+                top,
+                bottom,
+                Point {
+                    x: x + hook,
+                    y: y_bottom,
+                },
+            ]
+        }
+        _ => wavy_points(bottom, top, ups),
+    };
+    let (half_width, length) = (ARROW_HALF_WIDTH_SPACES * ups, ARROW_LENGTH_SPACES * ups);
+    let arrow = match style {
+        ArpeggioStyle::Up => vec![
+            Point {
+                x: x - half_width,
+                y: y_top + length,
+            },
+            top,
+            Point {
+                x: x + half_width,
+                y: y_top + length,
+            },
+        ],
+        ArpeggioStyle::Down => vec![
+            Point {
+                x: x - half_width,
+                y: y_bottom - length,
+            },
+            bottom,
+            Point {
+                x: x + half_width,
+                y: y_bottom - length,
+            },
+        ],
+        _ => Vec::new(),
+    };
+    ArpeggioLayout {
+        style,
+        start_tick,
+        x,
+        y_top,
+        y_bottom,
+        points,
+        arrow,
+    }
+}
+
+/// Render arpeggio signs for a single staff within a system.
+///
+/// All arpeggiated notes of the staff starting at the same tick form one
+/// chord; the sign spans their noteheads and sits left of the leftmost
+/// notehead or accidental.
+pub(crate) fn render_arpeggios(
+    staff_data: &StaffData,
+    tick_range: &TickRange,
+    glyph_runs: &[GlyphRun],
+    units_per_space: f32,
+) -> Vec<ArpeggioLayout> {
+    let ups = units_per_space;
+    let glyphs = note_glyphs(glyph_runs);
+    let voices = voice_notes_in_range(staff_data, tick_range);
+
+    // tick -> (style, glyphs of the chord's notes)
+    let mut chords: Vec<(u32, ArpeggioStyle, Vec<&Glyph>)> = Vec::new();
+    for (voice_index, notes) in voices.iter().enumerate() {
+        for (event_index, note) in notes.iter().enumerate() {
+            let Some(style) = note.arpeggio else {
+                continue;
+            };
+            let note_glyphs = glyphs
+                .get(&(voice_index, event_index))
+                .cloned()
+                .unwrap_or_default();
+            match chords
+                .iter_mut()
+                .find(|(tick, _, _)| *tick == note.start_tick)
+            {
+                Some((_, _, chord_glyphs)) => chord_glyphs.extend(note_glyphs),
+                None => chords.push((note.start_tick, style, note_glyphs)),
+            }
+        }
+    }
+    chords.sort_by_key(|(tick, _, _)| *tick);
+
+    chords
+        .into_iter()
+        .filter_map(|(tick, style, chord_glyphs)| {
+            let left = chord_glyphs
+                .iter()
+                .map(|g| g.bounding_box.x)
+                .reduce(f32::min)?;
+            let head_ys = chord_glyphs
+                .iter()
+                .filter(|g| !is_accidental(g))
+                .map(|g| g.position.y + 0.5 * ups);
+            let (top, bottom) = head_ys.fold((f32::MAX, f32::MIN), |(top, bottom), y| {
+                (top.min(y), bottom.max(y))
+            });
+            if top > bottom {
+                return None;
+            }
+            let overhang = ARPEGGIO_OVERHANG_SPACES * ups;
+            let x = left - ARPEGGIO_GAP_SPACES * ups - WAVE_AMPLITUDE_SPACES * ups;
+            Some(arpeggio_layout(
+                style,
+                tick,
+                x,
+                top - overhang,
+                bottom + overhang,
+                ups,
+            ))
+        })
+        .collect()
+}
+
+/// Merge arpeggios of the same chord on adjacent staves of one instrument
+/// (e.g. a piano chord rolled across both hands) into one sign on the
+/// upper staff.
+pub(crate) fn merge_cross_staff_arpeggios(staves: &mut [Staff], units_per_space: f32) {
+    for upper in 0..staves.len().saturating_sub(1) {
+        let (head, tail) = staves.split_at_mut(upper + 1);
+        let (upper_staff, lower_staff) = (&mut head[upper], &mut tail[0]);
+        for arpeggio in upper_staff.arpeggios.iter_mut() {
+            let Some(position) = lower_staff
+                .arpeggios
+                .iter()
+                .position(|a| a.start_tick == arpeggio.start_tick && a.style == arpeggio.style)
+            else {
+                continue;
+            };
+            let lower = lower_staff.arpeggios.remove(position);
+            *arpeggio = arpeggio_layout(
+                arpeggio.style,
+                arpeggio.start_tick,
+                arpeggio.x.min(lower.x),
+                arpeggio.y_top,
+                lower.y_bottom,
+                units_per_space,
+            );
+        }
+    }
+}
+
+/// Render glissando lines for a single staff within a system.
+///
+/// Lines start at notes of this staff; a glissando whose end note is in a
+/// later system runs to the right margin (`open_end`), and one arriving from
+/// an earlier system starts at the left margin (`open_start`). The open end
+/// is placed at the height of the note it leads to or comes from.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_glissandos(
+    staff_data: &StaffData,
+    tick_range: &TickRange,
+    glyph_runs: &[GlyphRun],
+    system_width: f32,
+    unified_left_margin: f32,
+    units_per_space: f32,
+    cross_staff: &CrossStaffContext,
+    staff_index: usize,
+) -> Vec<GlissandoLayout> {
+    let ups = units_per_space;
+    let gap = GLISS_GAP_SPACES * ups;
+    let glyphs = note_glyphs(glyph_runs);
+    let voices = voice_notes_in_range(staff_data, tick_range);
+
+    // note id -> (right edge, left edge incl. accidentals, notehead y)
+    let mut placed: HashMap<&str, (f32, f32, f32)> = HashMap::new();
+    for (voice_index, notes) in voices.iter().enumerate() {
+        for (event_index, note) in notes.iter().enumerate() {
+            let Some(note_glyphs) = glyphs.get(&(voice_index, event_index)) else {
+                continue;
+            };
+            let Some(head) = note_glyphs.iter().find(|g| !is_accidental(g)) else {
+                continue;
+            };
+            let left = note_glyphs
+                .iter()
+                .map(|g| g.bounding_box.x)
+                .fold(head.bounding_box.x, f32::min);
+            let right = head.bounding_box.x + head.bounding_box.width;
+            placed.insert(&note.note_id, (right, left, head.position.y + 0.5 * ups));
+        }
+    }
+    // Height of a note drawn in another system, for open ends
+    let staff_y = |note: &NoteEvent| {
+        let clef = staff_data.get_clef_at_tick(note.start_tick);
+        positioner::pitch_to_staff_y(note.pitch, clef, ups, note.spelling, staff_data.line_count)
+            + cross_staff.staff_offset(staff_index).unwrap_or_default()
+            + cross_staff.shift(staff_index, note)
+            + 0.5 * ups
+    };
+    let all_notes: HashMap<&str, &NoteEvent> = staff_data
+        .voices
+        .iter()
+        .flat_map(|v| v.notes.iter())
+        .map(|n| (n.note_id.as_str(), n))
+        .collect();
+
+    let mut glissandos = Vec::new();
+    for voice in &staff_data.voices {
+        for note in &voice.notes {
+            let Some(target_id) = note.gliss_next.as_deref() else {
+                continue;
+            };
+            let Some(target) = all_notes.get(target_id) else {
+                continue;
+            };
+            let (start, end, open_start, open_end) =
+                match (placed.get(note.note_id.as_str()), placed.get(target_id)) {
+                    (Some(&(right, _, y)), Some(&(_, left, end_y))) => (
+                        Point { x: right + gap, y },
+                        Point {
+                            x: left - gap,
+                            y: end_y,
+                        },
+                        false,
+                        false,
+                    ),
+                    (Some(&(right, _, y)), None) if target.start_tick >= tick_range.end_tick => (
+                        Point { x: right + gap, y },
+                        Point {
+                            x: system_width,
+                            y: staff_y(target),
+                        },
+                        false,
+                        true,
+                    ),
+                    (None, Some(&(_, left, end_y))) if note.start_tick < tick_range.start_tick => (
+                        Point {
+                            x: (unified_left_margin - ups).min(left - 2.0 * ups),
+                            y: staff_y(note),
+                        },
+                        Point {
+                            x: left - gap,
+                            y: end_y,
+                        },
+                        true,
+                        false,
+                    ),
+                    _ => continue,
+                };
+            if end.x <= start.x {
+                continue;
+            }
+            glissandos.push(GlissandoLayout {
+                wavy: note.gliss_wavy,
+                points: if note.gliss_wavy {
+                    wavy_points(start, end, ups)
+                } else {
+                    vec![start, end]
+                },
+                note_id_start: note.note_id.clone(),
+                note_id_end: target_id.to_string(),
+                open_start,
+                open_end,
+            });
+        }
+    }
+    glissandos
+}
@@ -0,0 +1,286 @@
+//! Paginated layout
+//!
+//! Distributes laid-out systems onto pages of a fixed size, justifies them
+//! vertically and adds page numbers, headers and footers. Systems keep their
+//! internal layout; each is moved as a whole into its slot on the page.
+
+use crate::layout::types::{
+    BoundingBox, Glyph, Page, PageConfig, PageText, Point, Staff, StaffGroup, System, TextAlign,
+};
+
+/// Font size of headers, footers and page numbers, in staff spaces
+const PAGE_TEXT_SIZE_SPACES: f32 = 1.75;
+
+/// Assign systems to pages and move them into place
+///
+/// `page_starts` lists system indices that must begin a new page
+/// (`<print new-page="yes">`). A page holds as many systems as fit between
+/// its margins with at least `system_spacing` between them; systems that are
+/// taller than a page get a page of their own.
+pub(crate) fn paginate(
+    systems: &mut [System],
+    page_starts: &[usize],
+    config: &PageConfig,
+    units_per_space: f32,
+    system_spacing: f32,
+) -> Vec<Page> {
+    let mm = config.units_per_mm(units_per_space);
+    let page_width = config.width_mm * mm;
+    let page_height = config.height_mm * mm;
+    let (margin_top, margin_bottom) = (config.margin_top_mm * mm, config.margin_bottom_mm * mm);
+    let margin_left = config.margin_left_mm * mm;
+    let content_width = config.content_width(units_per_space);
+    let title_space = config.title_space_mm * mm;
+
+    // Content area of a page, relative to the page top
+    let content_area = |page_index: usize| {
+        let top = margin_top + if page_index == 0 { title_space } else { 0.0 };
+        (top, (page_height - margin_bottom - top).max(0.0))
+    };
+
+    // Group systems into pages
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut current: Vec<usize> = Vec::new();
+    let mut used = 0.0;
+    for (index, system) in systems.iter().enumerate() {
+        let height = system.bounding_box.height;
+        let needed = if current.is_empty() {
+            height
+        } else {
+            used + system_spacing + height
+        };
+        let (_, available) = content_area(groups.len());
+        let forced = page_starts.contains(&index);
+        if !current.is_empty() && (forced || needed > available) {
+            groups.push(std::mem::take(&mut current));
+            used = height;
+        } else {
+            used = needed;
+        }
+        current.push(index);
+    }
+    if !current.is_empty() || groups.is_empty() {
+        groups.push(current);
+    }
+
+    let page_count = groups.len();
+    let mut pages = Vec::with_capacity(page_count);
+    for (page_index, system_indices) in groups.into_iter().enumerate() {
+        let page_top = page_index as f32 * (page_height + config.page_gap);
+        let (content_offset, available) = content_area(page_index);
+        let content_top = page_top + content_offset;
+
+        // Spread systems down to the bottom margin once the page is full enough
+        let heights: f32 = system_indices
+            .iter()
+            .map(|&i| systems[i].bounding_box.height)
+            .sum();
+        let gaps = system_indices.len().saturating_sub(1) as f32;
+        let natural = heights + gaps * system_spacing;
+        let spacing = if gaps > 0.0
+            && available > 0.0
+            && natural < available
+            && natural / available >= config.justify_threshold
+        {
+            (available - heights) / gaps
+        } else {
+            system_spacing
+        };
+
+        let mut y = content_top;
+        for &i in &system_indices {
+            let system = &mut systems[i];
+            translate_system(system, y - system.bounding_box.y);
+            y += system.bounding_box.height + spacing;
+        }
+
+        let number = page_index as u32 + 1;
+        let text_size = PAGE_TEXT_SIZE_SPACES * units_per_space;
+        // Baselines centred in the top and bottom margins
+        let header_y = page_top + (margin_top + text_size) / 2.0;
+        let footer_y = page_top + page_height - (margin_bottom - text_size) / 2.0;
+        let text = |text: &str, x: f32, y: f32, align: TextAlign| PageText {
+            text: text.to_string(),
+            position: Point { x, y },
+            font_size: text_size,
+            align,
+        };
+        let (first, center, last) = (0.0, content_width / 2.0, content_width);
+
+        pages.push(Page {
+            number,
+            bounding_box: BoundingBox {
+                x: -margin_left,
+                y: page_top,
+                width: page_width,
+                height: page_height,
+            },
+            content_box: BoundingBox {
+                x: 0.0,
+                y: content_top,
+                width: content_width,
+                height: available,
+            },
+            system_indices,
+            header: config
+                .header
+                .as_deref()
+                .filter(|_| page_index > 0)
+                .map(|header| text(header, center, header_y, TextAlign::Middle)),
+            footer: config
+                .footer
+                .as_deref()
+                .map(|footer| text(footer, center, footer_y, TextAlign::Middle)),
+            // Outer corner: left on even (verso) pages, right on odd (recto)
+            number_label: (page_index > 0).then(|| {
+                let label = number.to_string();
+                if number % 2 == 0 {
+                    text(&label, first, header_y, TextAlign::Start)
+                } else {
+                    text(&label, last, header_y, TextAlign::End)
+                }
+            }),
+        });
+    }
+    pages
+}
+
+/// Move a system and everything in it vertically by `dy`
+pub(crate) fn translate_system(system: &mut System, dy: f32) {
+    if dy == 0.0 {
+        return;
+    }
+    system.bounding_box.y += dy;
+    if let Some(number) = &mut system.measure_number {
+        number.position.y += dy;
+    }
+    for label in &mut system.measure_number_labels {
+        label.position.y += dy;
+        label.bounding_box.y += dy;
+        if let Some(enclosure) = &mut label.enclosure {
+            enclosure.bounding_box.y += dy;
+        }
+    }
+    for volta in &mut system.volta_bracket_layouts {
+        volta.y += dy;
+    }
+    for ottava in &mut system.ottava_bracket_layouts {
+        ottava.y += dy;
+    }
+    for measure in &mut system.measure_layouts {
+        measure.bounding_box.y += dy;
+    }
+    for direction in &mut system.direction_layouts {
+        direction.y += dy;
+        direction.bounding_box.y += dy;
+        if let Some(enclosure) = &mut direction.enclosure {
+            enclosure.bounding_box.y += dy;
+        }
+    }
+    for group in &mut system.staff_groups {
+        translate_staff_group(group, dy);
+    }
+    for group_bracket in &mut system.group_brackets {
+        group_bracket.bracket_glyph.y += dy;
+        group_bracket.bracket_glyph.bounding_box.y += dy;
+    }
+}
+
+fn translate_staff_group(group: &mut StaffGroup, dy: f32) {
+    if let Some(bracket) = &mut group.bracket_glyph {
+        bracket.y += dy;
+        bracket.bounding_box.y += dy;
+    }
+    if let Some(label) = &mut group.name_label {
+        label.position.y += dy;
+    }
+    for staff in &mut group.staves {
+        translate_staff(staff, dy);
+    }
+}
+
+fn translate_staff(staff: &mut Staff, dy: f32) {
+    let shift = |points: &mut [Point]| points.iter_mut().for_each(|p| p.y += dy);
+
+    for line in &mut staff.staff_lines {
+        line.y_position += dy;
+    }
+    for glyph in staff
+        .glyph_runs
+        .iter_mut()
+        .flat_map(|run| run.glyphs.iter_mut())
+        .chain(staff.structural_glyphs.iter_mut())
+        .chain(
+            staff
+                .sticky_signatures
+                .iter_mut()
+                .flat_map(|s| s.glyphs.iter_mut()),
+        )
+        .chain(
+            staff
+                .pedal_marks
+                .iter_mut()
+                .flat_map(|m| m.glyphs.iter_mut()),
+        )
+    {
+        translate_glyph(glyph, dy);
+    }
+    for bar_line in &mut staff.bar_lines {
+        for segment in &mut bar_line.segments {
+            segment.y_start += dy;
+            segment.y_end += dy;
+        }
+        for dot in &mut bar_line.dots {
+            dot.y += dy;
+        }
+    }
+    for ledger in &mut staff.ledger_lines {
+        ledger.y_position += dy;
+    }
+    for dot in &mut staff.notation_dots {
+        dot.y += dy;
+    }
+    for arc in staff.tie_arcs.iter_mut().chain(staff.slur_arcs.iter_mut()) {
+        arc.start.y += dy;
+        arc.end.y += dy;
+        arc.cp1.y += dy;
+        arc.cp2.y += dy;
+    }
+    for fingering in &mut staff.fingering_glyphs {
+        fingering.y += dy;
+    }
+    for fret in &mut staff.tab_frets {
+        fret.y += dy;
+        fret.bounding_box.y += dy;
+    }
+    for chord in &mut staff.chord_symbols {
+        chord.bounding_box.y += dy;
+        for run in &mut chord.runs {
+            run.y += dy;
+        }
+    }
+    for mark in &mut staff.pedal_marks {
+        for text in &mut mark.texts {
+            text.y += dy;
+        }
+        if let Some(line) = &mut mark.line {
+            shift(&mut line.points);
+        }
+    }
+    for arpeggio in &mut staff.arpeggios {
+        arpeggio.y_top += dy;
+        arpeggio.y_bottom += dy;
+        shift(&mut arpeggio.points);
+        shift(&mut arpeggio.arrow);
+    }
+    for glissando in &mut staff.glissandos {
+        shift(&mut glissando.points);
+    }
+}
+
+/// Glyph positions and boxes are absolute, including the beam encoding
+/// where `bounding_box.y` holds the right end of the beam
+fn translate_glyph(glyph: &mut Glyph, dy: f32) {
+    glyph.position.y += dy;
+    glyph.bounding_box.y += dy;
+}
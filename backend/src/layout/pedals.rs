@@ -0,0 +1,284 @@
+//! Piano pedal mark rendering
+//!
+//! Draws pedal regions below a staff, either as SMuFL signs ("Ped." … "*")
+//! or as a bracket line with Λ notches at pedal changes. Regions crossing a
+//! system break are split into one mark per system, with open ends.
+
+use std::collections::HashMap;
+
+use crate::domain::events::pedal::PedalKind;
+use crate::layout::extraction::StaffData;
+use crate::layout::note_layout;
+use crate::layout::positioner::compute_glyph_bounding_box;
+use crate::layout::types::{
+    Glyph, PedalLine, PedalMarkLayout, PedalText, Point, SourceReference, TickRange,
+};
+
+/// SMuFL keyboardPedalPed ("Ped.")
+const PED_GLYPH: &str = "\u{E650}";
+/// SMuFL keyboardPedalUp ("*")
+const PEDAL_UP_GLYPH: &str = "\u{E655}";
+/// SMuFL keyboardPedalSost ("Sost.")
+const SOST_GLYPH: &str = "\u{E659}";
+
+/// Pedal sign font size in staff spaces (smaller than the 4-space notation font)
+const SIGN_FONT_SPACES: f32 = 3.0;
+/// Approximate advance widths of the signs at `SIGN_FONT_SPACES`, in staff spaces
+const PED_WIDTH_SPACES: f32 = 3.2;
+const PEDAL_UP_WIDTH_SPACES: f32 = 1.2;
+/// Text label font size in staff spaces ("una corda")
+const TEXT_FONT_SPACES: f32 = 1.6;
+/// Clearance between the lowest note/stem (or bottom line) and the pedal baseline
+const CLEARANCE_SPACES: f32 = 2.5;
+/// Height of line hooks and change notches, in staff spaces
+const HOOK_SPACES: f32 = 1.0;
+/// Half-width of a change notch, in staff spaces
+const NOTCH_HALF_WIDTH_SPACES: f32 = 0.5;
+
+/// Render pedal marks for a single staff within a system.
+///
+/// All marks in the system share one baseline below the lowest note or stem
+/// (`staff_max_y`, relative to the staff top line). Regions with neither a
+/// sign nor a line (sound-only pedaling) produce no mark.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_pedal_marks(
+    staff_data: &StaffData,
+    tick_range: &TickRange,
+    system_width: f32,
+    staff_vertical_offset: f32,
+    staff_max_y: f32,
+    units_per_space: f32,
+    note_positions: &HashMap<u32, f32>,
+    measure_x_bounds: &HashMap<u32, (f32, f32)>,
+    unified_left_margin: f32,
+    instrument_id: &str,
+    staff_index: usize,
+) -> Vec<PedalMarkLayout> {
+    let ups = units_per_space;
+    let baseline = staff_vertical_offset + staff_max_y.max(4.0 * ups) + CLEARANCE_SPACES * ups;
+    let tick_x = |tick: u32| {
+        note_layout::tick_to_x(tick, note_positions, measure_x_bounds, unified_left_margin)
+    };
+
+    let mut result = Vec::new();
+    for (region_index, region) in staff_data.pedal_regions.iter().enumerate() {
+        if !region.sign && !region.line {
+            continue;
+        }
+        if region.start_tick >= tick_range.end_tick || region.end_tick <= tick_range.start_tick {
+            continue;
+        }
+
+        let open_start = region.start_tick < tick_range.start_tick;
+        let open_end = region.end_tick > tick_range.end_tick;
+        let x_start = if open_start {
+            unified_left_margin
+        } else {
+            tick_x(region.start_tick)
+        };
+        let x_end = if open_end {
+            system_width
+        } else {
+            // Release just before the note at the end tick
+            (tick_x(region.end_tick) - 0.5 * ups).max(x_start + ups)
+        };
+        let changes: Vec<f32> = region
+            .change_ticks
+            .iter()
+            .filter(|&&t| t >= tick_range.start_tick && t < tick_range.end_tick)
+            .map(|&t| tick_x(t))
+            .collect();
+
+        let glyph = |codepoint: &str, name: &str, x: f32| {
+            let position = Point { x, y: baseline };
+            let font_size = SIGN_FONT_SPACES * ups;
+            Glyph {
+                bounding_box: compute_glyph_bounding_box(name, &position, font_size, ups),
+                position,
+                codepoint: codepoint.to_string(),
+                source_reference: SourceReference {
+                    instrument_id: instrument_id.to_string(),
+                    staff_index,
+                    voice_index: 0,
+                    event_index: region_index,
+                },
+                font_size: Some(font_size),
+                opacity: None,
+            }
+        };
+
+        let mut glyphs = Vec::new();
+        let mut texts = Vec::new();
+        let mut line_start = x_start;
+
+        if region.kind == PedalKind::UnaCorda {
+            let text = |text: &str, x: f32| PedalText {
+                text: text.to_string(),
+                x,
+                y: baseline,
+                font_size: TEXT_FONT_SPACES * ups,
+            };
+            if region.sign && !open_start {
+                texts.push(text("una corda", x_start));
+            }
+            if region.sign && !open_end && !region.line {
+                texts.push(text("tre corde", x_end));
+            }
+        } else if region.sign {
+            let (down, down_name) = match region.kind {
+                PedalKind::Sostenuto => (SOST_GLYPH, "keyboardPedalSost"),
+                _ => (PED_GLYPH, "keyboardPedalPed"),
+            };
+            if !open_start {
+                glyphs.push(glyph(down, down_name, x_start));
+                line_start = x_start + PED_WIDTH_SPACES * ups;
+            }
+            if !region.line {
+                // Sign style: "*" just before each change, then a new "Ped."
+                for &cx in &changes {
+                    glyphs.push(glyph(
+                        PEDAL_UP_GLYPH,
+                        "keyboardPedalUp",
+                        cx - (PEDAL_UP_WIDTH_SPACES + 0.5) * ups,
+                    ));
+                    glyphs.push(glyph(down, down_name, cx));
+                }
+                if !open_end {
+                    glyphs.push(glyph(PEDAL_UP_GLYPH, "keyboardPedalUp", x_end));
+                }
+            }
+        }
+
+        // Start hook unless a sign or a system break already marks the start
+        let start_hook = !open_start && !region.sign;
+        let line = region.line.then(|| {
+            pedal_line(
+                line_start, x_end, &changes, baseline, start_hook, !open_end, ups,
+            )
+        });
+
+        result.push(PedalMarkLayout {
+            kind: region.kind,
+            start_tick: region.start_tick,
+            end_tick: region.end_tick,
+            glyphs,
+            texts,
+            line,
+            open_start,
+            open_end,
+        });
+    }
+
+    result
+}
+
+/// Build the polyline for a bracket-line pedal mark.
+fn pedal_line(
+    x_start: f32,
+    x_end: f32,
+    changes: &[f32],
+    y: f32,
+    start_hook: bool,
+    end_hook: bool,
+    ups: f32,
+) -> PedalLine {
+    let hook = HOOK_SPACES * ups;
+    let notch = NOTCH_HALF_WIDTH_SPACES * ups;
+    let mut points = Vec::new();
+
+    if start_hook {
+        points.push(Point {
+            x: x_start,
+            y: y - hook,
+        });
+    }
+    points.push(Point { x: x_start, y });
+    for &cx in changes {
+        if cx - notch <= x_start || cx + notch >= x_end {
+            continue;
+        }
+        points.push(Point { x: cx - notch, y });
+        points.push(Point { x: cx, y: y - hook });
+        points.push(Point { x: cx + notch, y });
+    }
+    points.push(Point { x: x_end, y });
+    if end_hook {
+        points.push(Point {
+            x: x_end,
+            y: y - hook,
+        });
+    }
+
+    PedalLine { points }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::compute_layout;
+    use crate::layout::types::LayoutConfig;
+    use serde_json::json;
+
+    fn pedaled_score(line: bool) -> serde_json::Value {
+        json!({
+            "instruments": [{
+                "id": "piano",
+                "name": "Piano",
+                "staves": [{
+                    "clef": "Treble",
+                    "voices": [{
+                        "notes": [
+                            {"tick": 0, "duration": 960, "pitch": 60},
+                            {"tick": 960, "duration": 960, "pitch": 62},
+                            {"tick": 1920, "duration": 960, "pitch": 64},
+                            {"tick": 2880, "duration": 960, "pitch": 65}
+                        ]
+                    }],
+                    "pedal_regions": [
+                        {"kind": "Sustain", "start_tick": 0, "end_tick": 2880,
+                         "change_ticks": [1920], "line": line, "sign": !line}
+                    ]
+                }]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_sign_style_pedal_marks() {
+        let layout = compute_layout(&pedaled_score(false), &LayoutConfig::default());
+        let staff = &layout.systems[0].staff_groups[0].staves[0];
+        let bottom_line = staff.bottom_line_y();
+
+        assert_eq!(staff.pedal_marks.len(), 1);
+        let mark = &staff.pedal_marks[0];
+        assert!(mark.line.is_none());
+        let codepoints: Vec<&str> = mark.glyphs.iter().map(|g| g.codepoint.as_str()).collect();
+        // Ped. … * Ped. (change) … *
+        assert_eq!(
+            codepoints,
+            vec!["\u{E650}", "\u{E655}", "\u{E650}", "\u{E655}"]
+        );
+        assert!(mark.glyphs.iter().all(|g| g.position.y > bottom_line));
+        assert!(
+            mark.glyphs
+                .windows(2)
+                .all(|w| w[0].position.x < w[1].position.x)
+        );
+    }
+
+    #[test]
+    fn test_line_style_pedal_has_hooks_and_notch() {
+        let layout = compute_layout(&pedaled_score(true), &LayoutConfig::default());
+        let staff = &layout.systems[0].staff_groups[0].staves[0];
+        let mark = &staff.pedal_marks[0];
+        assert!(mark.glyphs.is_empty());
+
+        let points = &mark.line.as_ref().expect("line style").points;
+        // hook, start, notch (3 points), end, hook
+        assert_eq!(points.len(), 7);
+        let y = points[1].y;
+        assert!(points[0].y < y, "start hook points up");
+        assert!(points[3].y < y, "change notch points up");
+        assert!(points[6].y < y, "end hook points up");
+        assert!(points.windows(2).all(|w| w[0].x <= w[1].x));
+    }
+}
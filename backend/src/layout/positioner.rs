@@ -5,6 +5,19 @@
 use crate::layout::metrics::get_glyph_bbox;
 use crate::layout::types::{BoundingBox, Glyph, LedgerLine, Point, SourceReference};
 
+/// Staff-space offsets of a staff's top and bottom lines from the top line
+/// of a five-line staff
+///
+/// Pitches keep their five-line positions on every staff. Other line counts
+/// are drawn centred on the five-line staff's middle line, on whole staff
+/// spaces: one line on the middle line, three on the middle three, six with
+/// an extra line above the five.
+pub fn staff_line_span(line_count: u8) -> (f32, f32) {
+    let count = line_count.max(1) as i32;
+    let top = (5 - count).div_euclid(2);
+    (top as f32, (top + count - 1) as f32)
+}
+
 /// Convert pitch to y-coordinate on staff
 ///
 /// Uses standard music notation positioning based on clef type and diatonic scale degrees.
@@ -34,7 +47,7 @@ use crate::layout::types::{BoundingBox, Glyph, LedgerLine, Point, SourceReferenc
 ///
 /// # Arguments
 /// * `pitch` - MIDI pitch number (60 = middle C, 69 = A440)
-/// * `clef_type` - Type of clef ("Treble", "Bass", "Alto", "Tenor")
+/// * `clef_type` - Type of clef ("Treble", "Bass", "Alto", "Tenor", "Tab")
 /// * `units_per_space` - Scaling factor (default: 20.0 logical units = 1 staff space)
 ///
 /// # Returns
@@ -157,6 +170,26 @@ pub fn pitch_to_y_with_spelling(
     (staff_spaces_from_reference * 0.5 - 0.5) * units_per_space
 }
 
+/// Convert MIDI pitch to Y coordinate on a staff with `line_count` lines
+///
+/// One-line staves are rhythm and percussion staves: every note sits on
+/// the line. Notes on other staves are placed by
+/// `pitch_to_y_with_spelling`.
+pub fn pitch_to_staff_y(
+    pitch: u8,
+    clef_type: &str,
+    units_per_space: f32,
+    spelling: Option<(char, i8)>,
+    line_count: u8,
+) -> f32 {
+    if line_count == 1 {
+        // The middle line, less the SMuFL baseline offset applied below
+        let (line, _) = staff_line_span(1);
+        return (line - 0.5) * units_per_space;
+    }
+    pitch_to_y_with_spelling(pitch, clef_type, units_per_space, spelling)
+}
+
 /// Compute glyph bounding box using SMuFL metrics
 ///
 /// Fetches glyph metrics from embedded Bravura font and scales to font size
@@ -228,6 +261,8 @@ pub fn position_noteheads(
     grace_note_indices: &std::collections::HashSet<usize>,
     // Explicit stem direction per note from MusicXML <stem> element
     explicit_stem_downs: &[Option<bool>],
+    // Number of staff lines (see `pitch_to_staff_y`)
+    line_count: u8,
 ) -> Vec<Glyph> {
     notes
         .iter()
@@ -236,14 +271,15 @@ pub fn position_noteheads(
         .map(|(i, ((pitch, _start, duration, spelling, _, _, _), &x))| {
             // Use explicit spelling for Y position when available (e.g., Eb vs D#)
             let clef_type = clef_types[i];
-            let y = pitch_to_y_with_spelling(*pitch, clef_type, units_per_space, *spelling)
+            let y = pitch_to_staff_y(*pitch, clef_type, units_per_space, *spelling, line_count)
                 + staff_vertical_offset;
             let position = Point { x, y };
 
             // T021-T022: Choose notehead codepoint based on duration_ticks.
             // Beamed notes (in beamed_note_indices) use bare noteheadBlack (U+E0A4).
-            // Chord notes (chord_scale_map) use a bare notehead at a scaled font_size so
-            // their visual diameter matches the notehead embedded in combined glyphs.
+            // Notes whose stems are drawn apart (chord_scale_map) use a bare notehead at a
+            // scaled font_size so their visual diameter matches the notehead embedded in
+            // combined glyphs.
             let is_beamed = beamed_note_indices.contains(&i) && *duration < 960;
             let chord_font_size = if is_beamed {
                 None
@@ -368,6 +404,10 @@ pub fn position_clef(
             // C clef on 4th line (C4), y from pitch_to_y
             ('\u{E05D}', 10.0)
         }
+        "Tab" => {
+            // TAB sign centred between the outer strings of a 4 or 6 line staff
+            ('\u{E06D}', 20.0)
+        }
         _ => {
             // Default to treble clef
             ('\u{E050}', 50.0)
@@ -419,6 +459,7 @@ pub fn position_courtesy_clef(
         "Bass" => ('\u{E062}', 10.0),
         "Alto" => ('\u{E05C}', 30.0),
         "Tenor" => ('\u{E05D}', 10.0),
+        "Tab" => ('\u{E06D}', 20.0),
         _ => ('\u{E050}', 50.0),
     };
 
@@ -1100,15 +1141,16 @@ pub fn position_note_accidentals(
     accidental_glyphs
 }
 
-/// Position ledger lines for notes above or below the 5-line staff
+/// Position ledger lines for notes above or below the staff
 ///
 /// Ledger lines are short horizontal lines drawn at every staff-space interval
 /// between the staff boundary and the note position, for notes outside the
-/// standard 5-line range.
+/// staff's lines.
 ///
-/// Staff lines span y=0 (top) to y=4*units_per_space = 80 (bottom) relative
-/// to the staff's vertical offset. Notes above the top line (y < 0) or below
-/// the bottom line (y > 80) need ledger lines.
+/// A five-line staff spans y=0 (top) to y=4*units_per_space = 80 (bottom)
+/// relative to the staff's vertical offset; other line counts span
+/// `staff_line_span`. Notes above the top line or below the bottom line need
+/// ledger lines. Notes on one-line staves all sit on the line and never do.
 ///
 /// # Arguments
 /// * `notes` - Note events (pitch, start_tick, duration)
@@ -1116,6 +1158,7 @@ pub fn position_note_accidentals(
 /// * `clef_type` - Clef type for pitch positioning
 /// * `units_per_space` - Scaling factor (20.0 = 1 staff space)
 /// * `staff_vertical_offset` - Vertical offset for this staff
+/// * `line_count` - Number of staff lines
 ///
 /// # Returns
 /// Vector of LedgerLine structs for notes outside staff range
@@ -1125,14 +1168,19 @@ pub fn position_ledger_lines(
     clef_types: &[&str],
     units_per_space: f32,
     staff_vertical_offset: f32,
+    line_count: u8,
 ) -> Vec<LedgerLine> {
     let mut ledger_lines = Vec::new();
+    if line_count == 1 {
+        return ledger_lines;
+    }
 
-    // Staff line positions relative to staff_vertical_offset:
+    // Staff line positions relative to staff_vertical_offset, e.g. for five lines:
     // Top line: y = staff_vertical_offset + 0
     // Bottom line: y = staff_vertical_offset + 4 * units_per_space (= 80 at ups=20)
-    let top_line_y = staff_vertical_offset;
-    let bottom_line_y = staff_vertical_offset + 4.0 * units_per_space;
+    let (top_line, bottom_line) = staff_line_span(line_count);
+    let top_line_y = staff_vertical_offset + top_line * units_per_space;
+    let bottom_line_y = staff_vertical_offset + bottom_line * units_per_space;
 
     // Ledger line half-width: notehead is ~1.18 staff spaces wide (half = 0.59),
     // plus a standard 0.5 staff-space overhang on each side = 1.1 total.
@@ -1236,6 +1284,40 @@ pub fn rest_glyph_codepoint(note_type: Option<&str>, duration_ticks: u32) -> cha
     }
 }
 
+/// Augmentation dots of a rest: how far its duration exceeds the value of its
+/// `note_type` (a dotted quarter rest is a "quarter" of 1440 ticks).
+pub fn rest_dot_count(note_type: Option<&str>, duration_ticks: u32) -> u8 {
+    let base: u32 = match note_type {
+        Some("whole") => 3840,
+        Some("half") => 1920,
+        Some("quarter") => 960,
+        Some("eighth") => 480,
+        Some("16th") => 240,
+        Some("32nd") => 120,
+        Some("64th") => 60,
+        _ => return 0,
+    };
+    // Each dot adds half of the previous value
+    (1..=2)
+        .find(|&dots| (0..=u32::from(dots)).map(|d| base >> d).sum::<u32>() == duration_ticks)
+        .unwrap_or(0)
+}
+
+/// X of a rest that does not fill its measure: the note position at or just
+/// before its start
+pub(super) fn beat_rest_x(
+    start_tick: u32,
+    note_positions: &std::collections::HashMap<u32, f32>,
+    left_margin: f32,
+) -> f32 {
+    note_positions
+        .iter()
+        .filter(|&(&t, _)| t <= start_tick)
+        .max_by_key(|&(&t, _)| t)
+        .map(|(_, &x)| x)
+        .unwrap_or(left_margin)
+}
+
 /// Compute the Y-position for a rest glyph (before adding `staff_vertical_offset`).
 ///
 /// Base positions use the standard single-voice rest placement:
@@ -1363,13 +1445,7 @@ pub(super) fn position_rests_for_staff(
                 left_margin + 90.0
             }
         } else {
-            // Beat-aligned: use note position at or just before the rest's start_tick
-            note_positions
-                .iter()
-                .filter(|&(&t, _)| t <= rest.start_tick)
-                .max_by_key(|&(&t, _)| t)
-                .map(|(_, &x)| x)
-                .unwrap_or(left_margin)
+            beat_rest_x(rest.start_tick, note_positions, left_margin)
         };
 
         glyphs.push(Glyph {
@@ -1536,6 +1612,7 @@ mod tests {
             None,                              // no multi-voice override
             &std::collections::HashSet::new(), // no grace notes
             &vec![None; notes.len()],          // no explicit stem directions
+            5,
         );
 
         // Verify correct number of glyphs
@@ -1877,6 +1954,7 @@ mod tests {
             None,
             &std::collections::HashSet::new(), // no grace notes
             &vec![None; notes.len()],          // no explicit stem directions
+            5,
         );
 
         assert_eq!(glyphs.len(), 2);
@@ -1917,6 +1995,7 @@ mod tests {
             None,
             &std::collections::HashSet::new(), // no grace notes
             &vec![None; notes.len()],          // no explicit stem directions
+            5,
         );
 
         assert_eq!(glyphs.len(), 1);
@@ -1953,6 +2032,7 @@ mod tests {
             None,
             &std::collections::HashSet::new(), // no grace notes
             &vec![None; notes.len()],          // no explicit stem directions
+            5,
         );
 
         assert_eq!(glyphs.len(), 1);
@@ -1987,6 +2067,15 @@ mod tests {
     }
 
     /// T-REST-04: None note_type falls back to duration_ticks.
+    #[test]
+    fn test_rest_dot_count() {
+        assert_eq!(rest_dot_count(Some("quarter"), 960), 0);
+        assert_eq!(rest_dot_count(Some("quarter"), 1440), 1);
+        assert_eq!(rest_dot_count(Some("eighth"), 840), 2);
+        assert_eq!(rest_dot_count(Some("whole"), 2880), 0);
+        assert_eq!(rest_dot_count(None, 1440), 0);
+    }
+
     #[test]
     fn test_rest_glyph_codepoint_fallback() {
         assert_eq!(
@@ -0,0 +1,320 @@
+//! Layout queries
+//!
+//! Indexes a `GlobalLayout` once for the questions an interactive score
+//! viewer asks many times per second: which note or rest is under the
+//! pointer, where the playback cursor is at a tick, and which regions a tick
+//! range covers for loop highlighting.
+
+use serde::Serialize;
+
+use crate::layout::types::{
+    BoundingBox, GlobalLayout, MeasureLayout, Point, SourceReference, TickPosition,
+};
+
+/// Nearest note or rest to a point
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteHit {
+    /// Index of the system holding the glyph
+    pub system_index: usize,
+    /// Index of the staff group (instrument) within the system
+    pub staff_group_index: usize,
+    /// Index of the staff within the staff group
+    pub staff_index: usize,
+    /// Link back to the score element
+    pub source_reference: SourceReference,
+    /// Glyph position
+    pub position: Point,
+    /// Distance from the query point to the glyph's bounding box (0 = inside)
+    pub distance: f32,
+}
+
+/// Playback cursor position at a tick
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CursorPosition {
+    /// Index of the system holding the tick
+    pub system_index: usize,
+    /// x-position, interpolated between the onsets around the tick
+    pub x: f32,
+    /// Top staff line of the system
+    pub y_top: f32,
+    /// Bottom staff line of the system
+    pub y_bottom: f32,
+}
+
+/// Part of a tick range within one system
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TickRegion {
+    /// Index of the system
+    pub system_index: usize,
+    /// From the range start (or the system's left edge) to the range end
+    /// (or the system's right edge), top to bottom staff line
+    pub bounding_box: BoundingBox,
+}
+
+/// A note or rest glyph
+struct IndexedGlyph {
+    bounding_box: BoundingBox,
+    position: Point,
+    staff_group_index: usize,
+    staff_index: usize,
+    source_reference: SourceReference,
+}
+
+struct IndexedSystem {
+    index: usize,
+    bounding_box: BoundingBox,
+    start_tick: u32,
+    end_tick: u32,
+    /// Staff line extent: left, right, top, bottom
+    left: f32,
+    right: f32,
+    top: f32,
+    bottom: f32,
+    measures: Vec<MeasureLayout>,
+    positions: Vec<TickPosition>,
+    /// Sorted by bounding box centre x
+    glyphs: Vec<IndexedGlyph>,
+    /// Half the width of the widest glyph
+    max_half_width: f32,
+}
+
+/// Query index over a `GlobalLayout`
+///
+/// Built once per layout so that queries only look at one system and,
+/// within it, at the glyphs and onsets near the query.
+pub struct LayoutIndex {
+    systems: Vec<IndexedSystem>,
+}
+
+impl LayoutIndex {
+    /// Index a layout
+    pub fn new(layout: &GlobalLayout) -> Self {
+        let systems = layout
+            .systems
+            .iter()
+            .map(|system| {
+                let staves = || system.staff_groups.iter().flat_map(|g| &g.staves);
+                let first_line = staves().next().and_then(|s| s.staff_lines.first());
+                let last_line = staves().last().and_then(|s| s.staff_lines.last());
+                let b = system.bounding_box;
+
+                let mut glyphs: Vec<IndexedGlyph> = system
+                    .staff_groups
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(group_index, group)| {
+                        group
+                            .staves
+                            .iter()
+                            .enumerate()
+                            .map(move |(staff_index, staff)| (group_index, staff_index, staff))
+                    })
+                    .flat_map(|(group_index, staff_index, staff)| {
+                        // Notes on tablature staves are drawn as fret numbers
+                        let frets = staff.tab_frets.iter().map(move |f| IndexedGlyph {
+                            bounding_box: f.bounding_box,
+                            position: Point { x: f.x, y: f.y },
+                            staff_group_index: group_index,
+                            staff_index,
+                            source_reference: f.source_reference.clone(),
+                        });
+                        staff
+                            .glyph_runs
+                            .iter()
+                            .flat_map(|run| &run.glyphs)
+                            .filter(|g| is_note_or_rest(&g.codepoint))
+                            .map(move |g| IndexedGlyph {
+                                bounding_box: g.bounding_box,
+                                position: g.position,
+                                staff_group_index: group_index,
+                                staff_index,
+                                source_reference: g.source_reference.clone(),
+                            })
+                            .chain(frets)
+                    })
+                    .collect();
+                glyphs.sort_by(|a, b| {
+                    center_x(&a.bounding_box).total_cmp(&center_x(&b.bounding_box))
+                });
+                let max_half_width = glyphs
+                    .iter()
+                    .map(|g| g.bounding_box.width / 2.0)
+                    .fold(0.0, f32::max);
+
+                IndexedSystem {
+                    index: system.index,
+                    bounding_box: b,
+                    start_tick: system.tick_range.start_tick,
+                    end_tick: system.tick_range.end_tick,
+                    left: first_line.map_or(b.x, |l| l.start_x),
+                    right: first_line.map_or(b.x + b.width, |l| l.end_x),
+                    top: first_line.map_or(b.y, |l| l.y_position),
+                    bottom: last_line.map_or(b.y + b.height, |l| l.y_position),
+                    measures: system.measure_layouts.clone(),
+                    positions: system.tick_positions.clone(),
+                    glyphs,
+                    max_half_width,
+                }
+            })
+            .collect();
+        Self { systems }
+    }
+
+    /// The note or rest nearest to `point` in the systems under it
+    ///
+    /// System bounding boxes include everything drawn above and below the
+    /// staves and may overlap, so every system spanning `point.y` is searched.
+    /// Returns `None` when no system spans `point.y` or none of them has
+    /// notes or rests.
+    pub fn hit_test(&self, point: Point) -> Option<NoteHit> {
+        self.systems
+            .iter()
+            .filter(|s| {
+                point.y >= s.bounding_box.y && point.y <= s.bounding_box.y + s.bounding_box.height
+            })
+            .filter_map(|system| nearest_in(system, point).map(|hit| (system, hit)))
+            .min_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))
+            .map(|(system, (distance, g))| NoteHit {
+                system_index: system.index,
+                staff_group_index: g.staff_group_index,
+                staff_index: g.staff_index,
+                source_reference: g.source_reference.clone(),
+                position: g.position,
+                distance,
+            })
+    }
+
+    /// Playback cursor position at `tick`
+    ///
+    /// Onsets are exact; between onsets (and between the last onset and the
+    /// barline) the x-position is interpolated linearly in ticks. Returns
+    /// `None` for ticks outside the laid-out score.
+    pub fn tick_position(&self, tick: u32) -> Option<CursorPosition> {
+        let system = self.system_at(tick)?;
+        Some(CursorPosition {
+            system_index: system.index,
+            x: x_at(system, tick),
+            y_top: system.top,
+            y_bottom: system.bottom,
+        })
+    }
+
+    /// Regions covered by the ticks `[start_tick, end_tick)`, one per system
+    pub fn range_regions(&self, start_tick: u32, end_tick: u32) -> Vec<TickRegion> {
+        self.systems
+            .iter()
+            .filter(|s| start_tick < s.end_tick && end_tick > s.start_tick)
+            .filter_map(|system| {
+                let x_start = if start_tick <= system.start_tick {
+                    system.left
+                } else {
+                    x_at(system, start_tick)
+                };
+                let x_end = if end_tick >= system.end_tick {
+                    system.right
+                } else {
+                    x_at(system, end_tick)
+                };
+                (x_end > x_start).then_some(TickRegion {
+                    system_index: system.index,
+                    bounding_box: BoundingBox {
+                        x: x_start,
+                        y: system.top,
+                        width: x_end - x_start,
+                        height: system.bottom - system.top,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn system_at(&self, tick: u32) -> Option<&IndexedSystem> {
+        let i = self.systems.partition_point(|s| s.end_tick <= tick);
+        self.systems
+            .get(i)
+            .filter(|s| s.start_tick <= tick && tick < s.end_tick)
+    }
+}
+
+/// SMuFL noteheads, precomposed notes and rests
+fn is_note_or_rest(codepoint: &str) -> bool {
+    codepoint.chars().next().is_some_and(|c| {
+        matches!(c, '\u{E0A0}'..='\u{E0FF}' | '\u{E1D0}'..='\u{E1EF}' | '\u{E4E0}'..='\u{E4FF}')
+    })
+}
+
+fn center_x(b: &BoundingBox) -> f32 {
+    b.x + b.width / 2.0
+}
+
+/// Distance from a point to a box (0 inside)
+fn distance_to(b: &BoundingBox, p: Point) -> f32 {
+    let dx = (b.x - p.x).max(p.x - (b.x + b.width)).max(0.0);
+    let dy = (b.y - p.y).max(p.y - (b.y + b.height)).max(0.0);
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Nearest glyph of a system to a point
+///
+/// Walks outwards from the glyphs nearest in x until even the widest glyph
+/// would be further away horizontally than the best match.
+fn nearest_in(system: &IndexedSystem, point: Point) -> Option<(f32, &IndexedGlyph)> {
+    let glyphs = &system.glyphs;
+    let split = glyphs.partition_point(|g| center_x(&g.bounding_box) < point.x);
+    let reach = |best: &Option<(f32, &IndexedGlyph)>| {
+        best.map_or(f32::INFINITY, |(d, _)| d) + system.max_half_width
+    };
+    let mut best = None;
+    for glyph in &glyphs[split..] {
+        if center_x(&glyph.bounding_box) - point.x > reach(&best) {
+            break;
+        }
+        consider(&mut best, glyph, point);
+    }
+    for glyph in glyphs[..split].iter().rev() {
+        if point.x - center_x(&glyph.bounding_box) > reach(&best) {
+            break;
+        }
+        consider(&mut best, glyph, point);
+    }
+    best
+}
+
+fn consider<'a>(best: &mut Option<(f32, &'a IndexedGlyph)>, glyph: &'a IndexedGlyph, p: Point) {
+    let distance = distance_to(&glyph.bounding_box, p);
+    if best.map_or(true, |(d, _)| distance < d) {
+        *best = Some((distance, glyph));
+    }
+}
+
+/// x-position of `tick` within a system
+fn x_at(system: &IndexedSystem, tick: u32) -> f32 {
+    let Some(measure) = system
+        .measures
+        .iter()
+        .find(|m| m.start_tick <= tick && tick < m.end_tick)
+    else {
+        return system.left;
+    };
+
+    // Anchors: the measure start, its onsets and its end barline
+    let onsets = system
+        .positions
+        .iter()
+        .filter(|p| p.tick >= measure.start_tick && p.tick < measure.end_tick);
+    let mut before = (measure.start_tick, measure.x_start);
+    let mut after = (measure.end_tick, measure.x_end);
+    for onset in onsets {
+        if onset.tick <= tick {
+            before = (onset.tick, onset.x);
+        } else {
+            after = (onset.tick, onset.x);
+            break;
+        }
+    }
+    if tick == before.0 || after.0 == before.0 {
+        return before.1;
+    }
+    let fraction = (tick - before.0) as f32 / (after.0 - before.0) as f32;
+    before.1 + fraction * (after.1 - before.1)
+}
@@ -0,0 +1,9 @@
+//! Layout rendering
+//!
+//! Turns a computed [`GlobalLayout`](crate::layout::GlobalLayout) into
+//! standalone documents for thumbnails, printing and regression screenshots,
+//! without going through the frontend.
+
+pub mod svg;
+
+pub use svg::{SvgOptions, render_svg};
@@ -0,0 +1,906 @@
+//! Standalone SVG rendering of a `GlobalLayout`
+//!
+//! Mirrors the frontend renderer (stroke widths, glyph baselines, the stem
+//! and beam pseudo-glyph encodings, brace and slur shapes) so server-side
+//! output matches what users see. All positions come from the layout;
+//! nothing is repositioned here.
+//!
+//! Output is deterministic: coordinates are rounded to two decimals and
+//! elements are written in layout order, so rendered files can be compared
+//! byte for byte.
+
+use std::fmt::Write;
+
+use crate::domain::events::direction::Enclosure;
+use crate::layout::types::{
+    BarLine, BracketGlyph, BracketType, Color, DirectionLayout, EnclosureLayout, GlobalLayout,
+    Glyph, GlyphRun, Page, PageText, Point, Staff, StaffGroup, System, TextAlign,
+};
+
+/// Staff line stroke width
+const STAFF_LINE_STROKE_WIDTH: f32 = 1.5;
+/// Ledger line stroke width
+const LEDGER_LINE_STROKE_WIDTH: f32 = 2.0;
+/// Tie arc stroke width
+const TIE_STROKE_WIDTH: f32 = 1.5;
+/// Slur crescent thickness at its middle
+const SLUR_THICKNESS: f32 = 2.5;
+/// Stroke width of pedal, glissando and ottava lines
+const THIN_LINE_STROKE_WIDTH: f32 = 1.5;
+/// Stroke width of arpeggio signs, volta brackets and enclosures
+const LINE_STROKE_WIDTH: f32 = 2.0;
+/// Default font size of structural glyphs (clefs, key and time signatures)
+const STRUCTURAL_FONT_SIZE: f32 = 80.0;
+const VOLTA_LABEL_FONT_SIZE: f32 = 32.0;
+const VOLTA_HOOK_LENGTH: f32 = 15.0;
+const OTTAVA_LABEL_FONT_SIZE: f32 = 28.0;
+const OTTAVA_HOOK_LENGTH: f32 = 12.0;
+
+/// Options for [`render_svg`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// Music font family for SMuFL glyphs
+    pub font_family: String,
+    /// Where to load the music font from; emitted as an `@font-face` rule
+    pub font_url: Option<String>,
+    /// Font family for plain text (measure numbers, labels, directions)
+    pub text_font_family: String,
+    /// Ink color for lines and glyphs drawn in the default (black) color
+    pub color: String,
+    /// Background fill; `None` leaves the background transparent
+    pub background: Option<String>,
+    /// Margin around the content in logical units
+    pub margin: f32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            font_family: "Bravura".to_string(),
+            font_url: None,
+            text_font_family: "serif".to_string(),
+            color: "#000000".to_string(),
+            background: Some("#FFFFFF".to_string()),
+            margin: 40.0,
+        }
+    }
+}
+
+/// Render a layout as a standalone SVG document
+pub fn render_svg(layout: &GlobalLayout, options: &SvgOptions) -> String {
+    let (min_x, min_y, max_x, max_y) = content_bounds(layout);
+    let x = min_x - options.margin;
+    let y = min_y - options.margin;
+    let width = max_x - min_x + 2.0 * options.margin;
+    let height = max_y - min_y + 2.0 * options.margin;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+        num(x),
+        num(y),
+        num(width),
+        num(height),
+        num(width),
+        num(height)
+    );
+    if let Some(url) = &options.font_url {
+        let _ = writeln!(
+            out,
+            r#"<defs><style>@font-face {{ font-family: "{}"; src: url("{}"); }}</style></defs>"#,
+            escape(&options.font_family),
+            escape(url)
+        );
+    }
+    if let Some(background) = &options.background {
+        let _ = writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            num(x),
+            num(y),
+            num(width),
+            num(height),
+            escape(background)
+        );
+    }
+
+    let mut svg = SvgWriter { out, options };
+    for page in &layout.pages {
+        svg.page(page);
+    }
+    for system in &layout.systems {
+        svg.system(system, layout.units_per_space);
+    }
+    svg.out.push_str("</svg>\n");
+    svg.out
+}
+
+/// Extent of everything drawn: (min_x, min_y, max_x, max_y)
+fn content_bounds(layout: &GlobalLayout) -> (f32, f32, f32, f32) {
+    let (mut min_x, mut min_y) = (0.0_f32, 0.0_f32);
+    let (mut max_x, max_y) = (layout.total_width, layout.total_height);
+    for page in &layout.pages {
+        min_x = min_x.min(page.bounding_box.x);
+        max_x = max_x.max(page.bounding_box.x + page.bounding_box.width);
+    }
+    for system in &layout.systems {
+        // Instrument names and group brackets extend into the indent
+        min_x = min_x.min(system.bounding_box.x - system.indent);
+        min_y = min_y.min(system.bounding_box.y);
+        for label in &system.measure_number_labels {
+            min_y = min_y.min(label.bounding_box.y);
+        }
+        for volta in &system.volta_bracket_layouts {
+            min_y = min_y.min(volta.y - VOLTA_LABEL_FONT_SIZE);
+        }
+        for ottava in &system.ottava_bracket_layouts {
+            min_y = min_y.min(ottava.y - OTTAVA_LABEL_FONT_SIZE);
+        }
+        for direction in &system.direction_layouts {
+            min_y = min_y.min(direction.bounding_box.y);
+        }
+        for group_bracket in &system.group_brackets {
+            let bb = group_bracket.bracket_glyph.bounding_box;
+            min_x = min_x.min(bb.x - bb.width * 0.25);
+        }
+        for group in &system.staff_groups {
+            if let Some(bracket) = &group.bracket_glyph {
+                min_x = min_x.min(bracket.bounding_box.x - bracket.bounding_box.width * 0.25);
+            }
+        }
+    }
+    (min_x, min_y, max_x, max_y)
+}
+
+struct SvgWriter<'a> {
+    out: String,
+    options: &'a SvgOptions,
+}
+
+impl<'a> SvgWriter<'a> {
+    fn system(&mut self, system: &System, units_per_space: f32) {
+        let _ = writeln!(
+            self.out,
+            r#"<g class="system" data-system-index="{}">"#,
+            system.index
+        );
+
+        for label in &system.measure_number_labels {
+            if let Some(enclosure) = &label.enclosure {
+                self.enclosure(enclosure);
+            }
+            self.text(
+                label.position.x,
+                label.position.y,
+                label.font_size,
+                "",
+                &label.text,
+            );
+        }
+
+        for volta in &system.volta_bracket_layouts {
+            self.out.push_str("<g class=\"volta\">\n");
+            self.line(
+                volta.x_start,
+                volta.y,
+                volta.x_end,
+                volta.y,
+                LINE_STROKE_WIDTH,
+            );
+            self.line(
+                volta.x_start,
+                volta.y,
+                volta.x_start,
+                volta.y + VOLTA_HOOK_LENGTH,
+                LINE_STROKE_WIDTH,
+            );
+            if volta.closed_right {
+                self.line(
+                    volta.x_end,
+                    volta.y,
+                    volta.x_end,
+                    volta.y + VOLTA_HOOK_LENGTH,
+                    LINE_STROKE_WIDTH,
+                );
+            }
+            self.text(
+                volta.x_start + 5.0,
+                volta.y - 3.0,
+                VOLTA_LABEL_FONT_SIZE,
+                "",
+                &volta.label,
+            );
+            self.out.push_str("</g>\n");
+        }
+
+        for ottava in &system.ottava_bracket_layouts {
+            self.out.push_str("<g class=\"ottava\">\n");
+            // Dashed while the bracket continues into the next system
+            let dash = if ottava.closed_right {
+                ""
+            } else {
+                r#" stroke-dasharray="4 3""#
+            };
+            let _ = writeln!(
+                self.out,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"{dash}/>"#,
+                num(ottava.x_start),
+                num(ottava.y),
+                num(ottava.x_end),
+                num(ottava.y),
+                self.ink(),
+                num(THIN_LINE_STROKE_WIDTH),
+            );
+            // Hooks point toward the staff
+            let hook = if ottava.above {
+                OTTAVA_HOOK_LENGTH
+            } else {
+                -OTTAVA_HOOK_LENGTH
+            };
+            self.line(
+                ottava.x_start,
+                ottava.y,
+                ottava.x_start,
+                ottava.y + hook,
+                THIN_LINE_STROKE_WIDTH,
+            );
+            if ottava.closed_right {
+                self.line(
+                    ottava.x_end,
+                    ottava.y,
+                    ottava.x_end,
+                    ottava.y + hook,
+                    THIN_LINE_STROKE_WIDTH,
+                );
+            }
+            let label_y = if ottava.above {
+                ottava.y - 4.0
+            } else {
+                ottava.y + 20.0
+            };
+            self.text(
+                ottava.x_start + 4.0,
+                label_y,
+                OTTAVA_LABEL_FONT_SIZE,
+                r#" font-style="italic""#,
+                &ottava.label,
+            );
+            self.out.push_str("</g>\n");
+        }
+
+        for group in &system.staff_groups {
+            self.staff_group(group, system.staff_groups.len(), units_per_space);
+        }
+        for group_bracket in &system.group_brackets {
+            self.bracket(group_bracket.bracket_type, &group_bracket.bracket_glyph);
+        }
+
+        // System bracket joining all instruments
+        if system.staff_groups.len() > 1
+            && let (Some(first), Some(last)) = (
+                system.staff_groups.first().and_then(|g| g.staves.first()),
+                system.staff_groups.last().and_then(|g| g.staves.last()),
+            )
+        {
+            self.line(0.0, first.top_line_y(), 0.0, last.bottom_line_y(), 3.0);
+        }
+
+        for direction in &system.direction_layouts {
+            self.direction(direction);
+        }
+
+        self.out.push_str("</g>\n");
+    }
+
+    /// Page sheet with its header, footer and page number
+    fn page(&mut self, page: &Page) {
+        let _ = writeln!(
+            self.out,
+            r#"<g class="page" data-page-number="{}">"#,
+            page.number
+        );
+        let bb = page.bounding_box;
+        let fill = self.options.background.as_deref().unwrap_or("none");
+        let _ = writeln!(
+            self.out,
+            r##"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="#999999" stroke-width="1"/>"##,
+            num(bb.x),
+            num(bb.y),
+            num(bb.width),
+            num(bb.height),
+            escape(fill)
+        );
+        for text in [&page.header, &page.footer, &page.number_label]
+            .into_iter()
+            .flatten()
+        {
+            self.page_text(text);
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    fn page_text(&mut self, text: &PageText) {
+        let anchor = match text.align {
+            TextAlign::Start => "start",
+            TextAlign::Middle => "middle",
+            TextAlign::End => "end",
+        };
+        let _ = writeln!(
+            self.out,
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}" text-anchor="{anchor}">{}</text>"#,
+            num(text.position.x),
+            num(text.position.y),
+            escape(&self.options.text_font_family),
+            num(text.font_size),
+            self.ink(),
+            escape(&text.text)
+        );
+    }
+
+    /// Bracket, brace or line at the left of a group of staves
+    fn bracket(&mut self, bracket_type: BracketType, bracket: &BracketGlyph) {
+        let bb = bracket.bounding_box;
+        match bracket_type {
+            BracketType::Bracket => {
+                // Thick vertical bar with horizontal serifs
+                let (top, bottom) = (bb.y, bb.y + bb.height);
+                self.line(bracket.x, top, bracket.x, bottom, 5.0);
+                self.line(bracket.x - 2.5, top, bracket.x + 12.0, top, 2.5);
+                self.line(bracket.x - 2.5, bottom, bracket.x + 12.0, bottom, 2.5);
+            }
+            BracketType::Brace => {
+                // Tips at the right, two lobes bulging left, spike at the center
+                let (top, h) = (bb.y, bb.height);
+                let (bottom, center) = (top + h, top + h / 2.0);
+                let (right, bulge, spike) = (bb.x + bb.width, bb.x, bb.x - bb.width * 0.25);
+                let d = format!(
+                    "M {},{} C {},{} {},{} {},{} C {},{} {},{} {},{} C {},{} {},{} {},{} C {},{} {},{} {},{}",
+                    num(right),
+                    num(top),
+                    num(right),
+                    num(top + h * 0.08),
+                    num(bulge),
+                    num(top + h * 0.04),
+                    num(bulge),
+                    num(top + h * 0.25),
+                    num(bulge),
+                    num(top + h * 0.44),
+                    num(right),
+                    num(top + h * 0.44),
+                    num(spike),
+                    num(center),
+                    num(right),
+                    num(center + h * 0.06),
+                    num(bulge),
+                    num(bottom - h * 0.44),
+                    num(bulge),
+                    num(bottom - h * 0.25),
+                    num(bulge),
+                    num(bottom - h * 0.04),
+                    num(right),
+                    num(bottom - h * 0.08),
+                    num(right),
+                    num(bottom),
+                );
+                let _ = writeln!(
+                    self.out,
+                    r#"<path class="brace" d="{d}" fill="none" stroke="{}" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"/>"#,
+                    self.ink()
+                );
+            }
+            BracketType::Square => {
+                // Thin vertical line with square hooks
+                let (top, bottom) = (bb.y, bb.y + bb.height);
+                self.line(bracket.x, top, bracket.x, bottom, 2.0);
+                self.line(bracket.x, top, bracket.x + 8.0, top, 2.0);
+                self.line(bracket.x, bottom, bracket.x + 8.0, bottom, 2.0);
+            }
+            BracketType::Line => {
+                self.line(bracket.x, bb.y, bracket.x, bb.y + bb.height, 2.0);
+            }
+            BracketType::None => {}
+        }
+    }
+
+    fn staff_group(&mut self, group: &StaffGroup, group_count: usize, units_per_space: f32) {
+        self.out.push_str("<g class=\"staff-group\">\n");
+        for staff in &group.staves {
+            self.staff(staff, units_per_space);
+        }
+
+        if group.staves.len() > 1
+            && let Some(bracket) = &group.bracket_glyph
+        {
+            self.bracket(group.bracket_type, bracket);
+        }
+
+        if group_count > 1
+            && let Some(label) = &group.name_label
+        {
+            let _ = writeln!(
+                self.out,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}" text-anchor="end" dominant-baseline="central">{}</text>"#,
+                num(label.position.x),
+                num(label.position.y),
+                escape(&label.font_family),
+                num(label.font_size),
+                color(&label.color, self.ink()),
+                escape(&label.text)
+            );
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    fn staff(&mut self, staff: &Staff, units_per_space: f32) {
+        self.out.push_str("<g class=\"staff\">\n");
+        for line in &staff.staff_lines {
+            self.line(
+                line.start_x,
+                line.y_position,
+                line.end_x,
+                line.y_position,
+                STAFF_LINE_STROKE_WIDTH,
+            );
+        }
+        for bar_line in &staff.bar_lines {
+            self.bar_line(bar_line);
+        }
+        for ledger in &staff.ledger_lines {
+            self.line(
+                ledger.start_x,
+                ledger.y_position,
+                ledger.end_x,
+                ledger.y_position,
+                LEDGER_LINE_STROKE_WIDTH,
+            );
+        }
+
+        for run in &staff.glyph_runs {
+            self.glyph_run(run);
+        }
+        for glyph in &staff.structural_glyphs {
+            let ink = self.ink();
+            self.glyph(glyph, STRUCTURAL_FONT_SIZE, ink);
+        }
+
+        for dot in &staff.notation_dots {
+            self.circle(dot.x, dot.y, dot.radius);
+        }
+        for arc in &staff.tie_arcs {
+            let _ = writeln!(
+                self.out,
+                r#"<path class="tie" d="M {} C {} {} {}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                point(arc.start),
+                point(arc.cp1),
+                point(arc.cp2),
+                point(arc.end),
+                self.ink(),
+                num(TIE_STROKE_WIDTH)
+            );
+        }
+        for arc in &staff.slur_arcs {
+            // Tapered crescent: outer curve, then back along control points
+            // shifted toward the staff
+            let inward = if arc.above {
+                SLUR_THICKNESS
+            } else {
+                -SLUR_THICKNESS
+            };
+            let shifted = |p: Point| Point {
+                x: p.x,
+                y: p.y + inward,
+            };
+            let _ = writeln!(
+                self.out,
+                r#"<path class="slur" d="M {} C {} {} {} C {} {} {} Z" fill="{}"/>"#,
+                point(arc.start),
+                point(arc.cp1),
+                point(arc.cp2),
+                point(arc.end),
+                point(shifted(arc.cp2)),
+                point(shifted(arc.cp1)),
+                point(arc.start),
+                self.ink()
+            );
+        }
+        for fingering in &staff.fingering_glyphs {
+            let _ = writeln!(
+                self.out,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+                num(fingering.x),
+                num(fingering.y),
+                escape(&self.options.text_font_family),
+                num(units_per_space * 1.4),
+                self.ink(),
+                fingering.digit
+            );
+        }
+        // Tablature frets knock out the string line behind them
+        let knockout = self.options.background.as_deref().unwrap_or("#FFFFFF");
+        for fret in &staff.tab_frets {
+            let bb = fret.bounding_box;
+            let _ = writeln!(
+                self.out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                num(bb.x),
+                num(bb.y),
+                num(bb.width),
+                num(bb.height),
+                escape(knockout)
+            );
+            let _ = writeln!(
+                self.out,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+                num(fret.x),
+                num(fret.y),
+                escape(&self.options.text_font_family),
+                num(fret.font_size),
+                self.ink(),
+                escape(&fret.text)
+            );
+        }
+        for chord in &staff.chord_symbols {
+            for run in &chord.runs {
+                self.text(run.x, run.y, run.font_size, "", &run.text);
+            }
+        }
+        for mark in &staff.pedal_marks {
+            for glyph in &mark.glyphs {
+                let ink = self.ink();
+                self.glyph(glyph, STRUCTURAL_FONT_SIZE, ink);
+            }
+            for text in &mark.texts {
+                self.text(
+                    text.x,
+                    text.y,
+                    text.font_size,
+                    r#" font-style="italic""#,
+                    &text.text,
+                );
+            }
+            if let Some(line) = &mark.line {
+                self.polyline(&line.points, THIN_LINE_STROKE_WIDTH);
+            }
+        }
+        for arpeggio in &staff.arpeggios {
+            self.polyline(&arpeggio.points, LINE_STROKE_WIDTH);
+            if !arpeggio.arrow.is_empty() {
+                self.polyline(&arpeggio.arrow, LINE_STROKE_WIDTH);
+            }
+        }
+        for glissando in &staff.glissandos {
+            self.polyline(&glissando.points, THIN_LINE_STROKE_WIDTH);
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    fn bar_line(&mut self, bar_line: &BarLine) {
+        let _ = writeln!(
+            self.out,
+            r#"<g class="bar-line" data-bar-type="{:?}">"#,
+            bar_line.bar_type
+        );
+        for segment in &bar_line.segments {
+            self.line(
+                segment.x_position,
+                segment.y_start,
+                segment.x_position,
+                segment.y_end,
+                segment.stroke_width,
+            );
+        }
+        for dot in &bar_line.dots {
+            self.circle(dot.x, dot.y, dot.radius);
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    fn glyph_run(&mut self, run: &GlyphRun) {
+        let fill = color(&run.color, self.ink());
+        if run.opacity < 1.0 {
+            let _ = writeln!(self.out, r#"<g opacity="{}">"#, num(run.opacity));
+        } else {
+            self.out.push_str("<g>\n");
+        }
+        for glyph in &run.glyphs {
+            self.glyph(glyph, run.font_size, &fill);
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    /// A glyph: stems (U+0000) become lines, beams (U+0001) polygons, and
+    /// everything else SMuFL text centered on its position
+    fn glyph(&mut self, glyph: &Glyph, font_size: f32, fill: &str) {
+        let (p, bb) = (glyph.position, glyph.bounding_box);
+        let opacity = match glyph.opacity {
+            Some(opacity) if opacity < 1.0 => format!(r#" opacity="{}""#, num(opacity)),
+            _ => String::new(),
+        };
+        match glyph.codepoint.as_str() {
+            "\u{0000}" => {
+                let _ = writeln!(
+                    self.out,
+                    r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{fill}" stroke-width="{}"{opacity}/>"#,
+                    num(p.x),
+                    num(p.y),
+                    num(p.x),
+                    num(p.y + bb.height),
+                    num(bb.width)
+                );
+            }
+            // position = left end, bbox.y = right end y, bbox.width = span,
+            // bbox.height = thickness
+            "\u{0001}" => {
+                let (x1, x2) = (p.x, p.x + bb.width);
+                let _ = writeln!(
+                    self.out,
+                    r#"<polygon points="{},{} {},{} {},{} {},{}" fill="{fill}"{opacity}/>"#,
+                    num(x1),
+                    num(p.y),
+                    num(x2),
+                    num(bb.y),
+                    num(x2),
+                    num(bb.y + bb.height),
+                    num(x1),
+                    num(p.y + bb.height)
+                );
+            }
+            codepoint => {
+                // Control characters are not valid XML text
+                if codepoint.is_empty() || codepoint.chars().any(char::is_control) {
+                    return;
+                }
+                // Rests and flags are placed by their font origin; other
+                // glyphs are vertically centered on their position
+                let first = codepoint.chars().next().map_or(0, u32::from);
+                let baseline =
+                    if (0xE4E3..=0xE4EB).contains(&first) || (0xE240..=0xE24F).contains(&first) {
+                        ""
+                    } else {
+                        r#" dominant-baseline="middle""#
+                    };
+                let _ = writeln!(
+                    self.out,
+                    r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{fill}" text-anchor="middle"{baseline}{opacity}>{}</text>"#,
+                    num(p.x),
+                    num(p.y),
+                    escape(&self.options.font_family),
+                    num(glyph.font_size.unwrap_or(font_size)),
+                    escape(codepoint)
+                );
+            }
+        }
+    }
+
+    /// Frame of a rehearsal mark or measure number
+    fn enclosure(&mut self, enclosure: &EnclosureLayout) {
+        let bb = enclosure.bounding_box;
+        match enclosure.shape {
+            Enclosure::Rectangle => {
+                let _ = writeln!(
+                    self.out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                    num(bb.x),
+                    num(bb.y),
+                    num(bb.width),
+                    num(bb.height),
+                    self.ink(),
+                    num(LINE_STROKE_WIDTH)
+                );
+            }
+            Enclosure::Circle => {
+                let _ = writeln!(
+                    self.out,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+                    num(bb.x + bb.width / 2.0),
+                    num(bb.y + bb.height / 2.0),
+                    num(bb.width / 2.0),
+                    self.ink(),
+                    num(LINE_STROKE_WIDTH)
+                );
+            }
+            Enclosure::None => {}
+        }
+    }
+
+    fn direction(&mut self, direction: &DirectionLayout) {
+        let _ = writeln!(
+            self.out,
+            r#"<g class="direction" data-kind="{:?}">"#,
+            direction.kind
+        );
+        if let Some(enclosure) = &direction.enclosure {
+            self.enclosure(enclosure);
+        }
+        if !direction.text.is_empty() {
+            let mut style = String::new();
+            if direction.bold {
+                style.push_str(r#" font-weight="bold""#);
+            }
+            if direction.italic {
+                style.push_str(r#" font-style="italic""#);
+            }
+            self.text(
+                direction.x,
+                direction.y,
+                direction.font_size,
+                &style,
+                &direction.text,
+            );
+        }
+        if let Some(metronome) = &direction.metronome {
+            let _ = writeln!(
+                self.out,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}">{}</text>"#,
+                num(metronome.glyph_x),
+                num(direction.y),
+                escape(&self.options.font_family),
+                num(metronome.glyph_font_size),
+                self.ink(),
+                escape(&metronome.glyph)
+            );
+            self.text(
+                metronome.text_x,
+                direction.y,
+                direction.font_size,
+                "",
+                &metronome.text,
+            );
+        }
+        self.out.push_str("</g>\n");
+    }
+
+    fn ink(&self) -> &'a str {
+        &self.options.color
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, stroke_width: f32) {
+        let _ = writeln!(
+            self.out,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+            num(x1),
+            num(y1),
+            num(x2),
+            num(y2),
+            self.ink(),
+            num(stroke_width)
+        );
+    }
+
+    fn circle(&mut self, cx: f32, cy: f32, r: f32) {
+        let _ = writeln!(
+            self.out,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+            num(cx),
+            num(cy),
+            num(r),
+            self.ink()
+        );
+    }
+
+    fn polyline(&mut self, points: &[Point], stroke_width: f32) {
+        let points: Vec<String> = points.iter().map(|p| point(*p)).collect();
+        let _ = writeln!(
+            self.out,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            points.join(" "),
+            self.ink(),
+            num(stroke_width)
+        );
+    }
+
+    /// Plain text in the text font, left-aligned on its baseline
+    fn text(&mut self, x: f32, y: f32, font_size: f32, style: &str, text: &str) {
+        let _ = writeln!(
+            self.out,
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" fill="{}"{style}>{}</text>"#,
+            num(x),
+            num(y),
+            escape(&self.options.text_font_family),
+            num(font_size),
+            self.ink(),
+            escape(text)
+        );
+    }
+}
+
+/// The default ink for black, otherwise the color itself
+fn color(color: &Color, ink: &str) -> String {
+    if *color == Color::BLACK {
+        ink.to_string()
+    } else if color.a == 255 {
+        format!("rgb({},{},{})", color.r, color.g, color.b)
+    } else {
+        format!(
+            "rgba({},{},{},{})",
+            color.r,
+            color.g,
+            color.b,
+            num(f32::from(color.a) / 255.0)
+        )
+    }
+}
+
+fn point(p: Point) -> String {
+    format!("{},{}", num(p.x), num(p.y))
+}
+
+/// Coordinate rounded to two decimals, without trailing zeros
+fn num(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    // Avoid "-0"
+    let rounded = if rounded == 0.0 { 0.0 } else { rounded };
+    let text = format!("{rounded:.2}");
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{LayoutConfig, compute_layout};
+
+    #[test]
+    fn test_number_formatting_and_escaping() {
+        assert_eq!(num(12.0), "12");
+        assert_eq!(num(12.345), "12.35");
+        assert_eq!(num(-0.001), "0");
+        assert_eq!(num(-2.5), "-2.5");
+        assert_eq!(
+            escape("Ped. & <\"rit\">"),
+            "Ped. &amp; &lt;&quot;rit&quot;&gt;"
+        );
+    }
+
+    #[test]
+    fn test_renders_stems_beams_and_font_reference() {
+        let score = serde_json::json!({
+            "instruments": [{
+                "id": "piano",
+                "name": "Piano",
+                "staves": [{
+                    "clef": "Treble",
+                    "time_signature": { "numerator": 4, "denominator": 4 },
+                    "key_signature": { "sharps": 0 },
+                    "voices": [{
+                        "notes": [
+                            { "pitch": 60, "tick": 0, "duration": 480 },
+                            { "pitch": 62, "tick": 480, "duration": 480 },
+                            { "pitch": 64, "tick": 960, "duration": 1920 }
+                        ]
+                    }]
+                }]
+            }]
+        });
+        let layout = compute_layout(&score, &LayoutConfig::default());
+        let options = SvgOptions {
+            font_family: "Leland".to_string(),
+            font_url: Some("fonts/Leland.woff2".to_string()),
+            ..SvgOptions::default()
+        };
+        let svg = render_svg(&layout, &options);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"src: url("fonts/Leland.woff2")"#));
+        assert!(svg.contains(r#"font-family="Leland""#));
+        assert!(svg.contains("<polygon"), "eighth notes are beamed");
+        assert!(svg.matches(r#"stroke-width="1.5"/>"#).count() >= 5);
+        assert!(!svg.contains('\u{0000}') && !svg.contains('\u{0001}'));
+    }
+}
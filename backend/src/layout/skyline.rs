@@ -0,0 +1,306 @@
+//! Skylines for vertical spacing
+//!
+//! A skyline is the outline of everything drawn on a staff or system, seen
+//! from above (top contour) and from below (bottom contour), sampled in
+//! narrow horizontal slices. Staves and systems are spaced by the smallest
+//! clearance between the bottom contour of the upper one and the top
+//! contour of the lower one, so a high note only pushes the staff above
+//! away where it actually sits.
+
+use crate::layout::cross_staff::CrossStaffElements;
+use crate::layout::types::{BoundingBox, Glyph, Point, Staff, System, TieArc};
+
+/// Width of one skyline slice in logical units (a quarter staff space)
+const SLICE_WIDTH: f32 = 5.0;
+
+/// Segments used to approximate a tie or slur curve
+const ARC_SEGMENTS: usize = 12;
+
+/// Top and bottom contours over x
+///
+/// Slice `i` covers `[i * SLICE_WIDTH, (i + 1) * SLICE_WIDTH)`; anything
+/// left of x = 0 (instrument names, braces) falls into the first slice.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Skyline {
+    /// Smallest y drawn in each slice (`INFINITY` = nothing drawn)
+    top: Vec<f32>,
+    /// Largest y drawn in each slice (`NEG_INFINITY` = nothing drawn)
+    bottom: Vec<f32>,
+}
+
+impl Skyline {
+    /// Add the rectangle `[x_start, x_end] × [y_top, y_bottom]`
+    pub(crate) fn add(&mut self, x_start: f32, x_end: f32, y_top: f32, y_bottom: f32) {
+        if !(x_start.is_finite() && x_end.is_finite() && y_top.is_finite() && y_bottom.is_finite())
+        {
+            return;
+        }
+        let (x_start, x_end) = (x_start.min(x_end), x_start.max(x_end));
+        let (y_top, y_bottom) = (y_top.min(y_bottom), y_top.max(y_bottom));
+        let first = slice(x_start);
+        let last = slice(x_end);
+        if self.top.len() <= last {
+            self.top.resize(last + 1, f32::INFINITY);
+            self.bottom.resize(last + 1, f32::NEG_INFINITY);
+        }
+        for i in first..=last {
+            self.top[i] = self.top[i].min(y_top);
+            self.bottom[i] = self.bottom[i].max(y_bottom);
+        }
+    }
+
+    pub(crate) fn add_box(&mut self, b: &BoundingBox) {
+        self.add(b.x, b.x + b.width, b.y, b.y + b.height);
+    }
+
+    /// Add a polyline, one rectangle per segment
+    pub(crate) fn add_polyline(&mut self, points: &[Point]) {
+        for pair in points.windows(2) {
+            self.add(pair[0].x, pair[1].x, pair[0].y, pair[1].y);
+        }
+        if let [point] = points {
+            self.add(point.x, point.x, point.y, point.y);
+        }
+    }
+
+    /// Add everything of `other`
+    pub(crate) fn merge(&mut self, other: &Skyline) {
+        if self.top.len() < other.top.len() {
+            self.top.resize(other.top.len(), f32::INFINITY);
+            self.bottom.resize(other.bottom.len(), f32::NEG_INFINITY);
+        }
+        for (i, (&top, &bottom)) in other.top.iter().zip(&other.bottom).enumerate() {
+            self.top[i] = self.top[i].min(top);
+            self.bottom[i] = self.bottom[i].max(bottom);
+        }
+    }
+
+    /// Move the contours down by `dy`
+    pub(crate) fn translate(&mut self, dy: f32) {
+        for y in self.top.iter_mut().chain(self.bottom.iter_mut()) {
+            *y += dy;
+        }
+    }
+
+    /// Highest point of the top contour
+    pub(crate) fn top(&self) -> Option<f32> {
+        let top = self.top.iter().copied().fold(f32::INFINITY, f32::min);
+        top.is_finite().then_some(top)
+    }
+
+    /// Lowest point of the bottom contour
+    pub(crate) fn bottom(&self) -> Option<f32> {
+        let bottom = self
+            .bottom
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        bottom.is_finite().then_some(bottom)
+    }
+
+    /// How far below `upper` the skyline `lower` must be placed for the two
+    /// contours to just touch, both measured from their own origin
+    ///
+    /// `None` when they share no slice, i.e. cannot collide.
+    pub(crate) fn distance(upper: &Skyline, lower: &Skyline) -> Option<f32> {
+        let distance = upper
+            .bottom
+            .iter()
+            .zip(&lower.top)
+            .filter(|(bottom, top)| bottom.is_finite() && top.is_finite())
+            .map(|(bottom, top)| bottom - top)
+            .fold(f32::NEG_INFINITY, f32::max);
+        distance.is_finite().then_some(distance)
+    }
+}
+
+fn slice(x: f32) -> usize {
+    (x.max(0.0) / SLICE_WIDTH) as usize
+}
+
+/// Vertical extent of a glyph
+///
+/// Stems (U+0000) and beams (U+0001) are drawn from their position rather
+/// than their bounding box: a stem's box holds its length, a beam's box
+/// holds its end point.
+fn glyph_extent(glyph: &Glyph) -> (f32, f32) {
+    let b = glyph.bounding_box;
+    if glyph.codepoint == "\u{0000}" || glyph.codepoint == "\u{0001}" {
+        let top = glyph.position.y.min(b.y);
+        let bottom = (glyph.position.y + b.height).max(b.y + b.height);
+        (top, bottom)
+    } else {
+        (b.y, b.y + b.height)
+    }
+}
+
+fn add_glyph(skyline: &mut Skyline, glyph: &Glyph) {
+    let (top, bottom) = glyph_extent(glyph);
+    let b = glyph.bounding_box;
+    skyline.add(b.x, b.x + b.width, top, bottom);
+}
+
+fn add_arc(skyline: &mut Skyline, arc: &TieArc) {
+    let points: Vec<Point> = (0..=ARC_SEGMENTS)
+        .map(|i| {
+            let t = i as f32 / ARC_SEGMENTS as f32;
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+            Point {
+                x: a * arc.start.x + b * arc.cp1.x + c * arc.cp2.x + d * arc.end.x,
+                y: a * arc.start.y + b * arc.cp1.y + c * arc.cp2.y + d * arc.end.y,
+            }
+        })
+        .collect();
+    skyline.add_polyline(&points);
+}
+
+/// Skyline of one staff: lines, glyphs, beams, ties, slurs and annotations
+///
+/// Elements in `excluded` (cross-staff notes, their beams and slurs) are
+/// drawn towards a neighbouring staff wherever the staves end up and are
+/// left out.
+pub(crate) fn staff_skyline(
+    staff: &Staff,
+    units_per_space: f32,
+    excluded: Option<&CrossStaffElements>,
+) -> Skyline {
+    let mut skyline = Skyline::default();
+    let is_excluded_glyph = |g: &Glyph| excluded.is_some_and(|e| e.contains_glyph(g));
+    let is_excluded_arc = |a: &TieArc| excluded.is_some_and(|e| e.contains_arc(a));
+
+    for line in &staff.staff_lines {
+        skyline.add(line.start_x, line.end_x, line.y_position, line.y_position);
+    }
+    for glyph in staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| &run.glyphs)
+        .filter(|g| !is_excluded_glyph(g))
+    {
+        add_glyph(&mut skyline, glyph);
+    }
+    for glyph in &staff.structural_glyphs {
+        add_glyph(&mut skyline, glyph);
+    }
+    for line in &staff.ledger_lines {
+        skyline.add(line.start_x, line.end_x, line.y_position, line.y_position);
+    }
+    for dot in &staff.notation_dots {
+        let r = dot.radius;
+        skyline.add(dot.x - r, dot.x + r, dot.y - r, dot.y + r);
+    }
+    for arc in staff
+        .tie_arcs
+        .iter()
+        .chain(&staff.slur_arcs)
+        .filter(|a| !is_excluded_arc(a))
+    {
+        add_arc(&mut skyline, arc);
+    }
+    // Digits are drawn centred at 1.4 staff spaces
+    let half = 0.7 * units_per_space;
+    for fingering in &staff.fingering_glyphs {
+        skyline.add(
+            fingering.x - half,
+            fingering.x + half,
+            fingering.y - half,
+            fingering.y + half,
+        );
+    }
+    for fret in &staff.tab_frets {
+        skyline.add_box(&fret.bounding_box);
+    }
+    for chord in &staff.chord_symbols {
+        skyline.add_box(&chord.bounding_box);
+    }
+    for pedal in &staff.pedal_marks {
+        for glyph in &pedal.glyphs {
+            add_glyph(&mut skyline, glyph);
+        }
+        for text in &pedal.texts {
+            let width = 0.6 * text.font_size * text.text.chars().count() as f32;
+            skyline.add(text.x, text.x + width, text.y - text.font_size, text.y);
+        }
+        if let Some(line) = &pedal.line {
+            skyline.add_polyline(&line.points);
+        }
+    }
+    for arpeggio in &staff.arpeggios {
+        skyline.add(
+            arpeggio.x,
+            arpeggio.x + 0.5 * units_per_space,
+            arpeggio.y_top,
+            arpeggio.y_bottom,
+        );
+    }
+    skyline
+}
+
+/// Skyline of a laid-out system, including the measure numbers, brackets
+/// and directions above and below its staves
+pub(crate) fn system_skyline(system: &System, units_per_space: f32) -> Skyline {
+    let mut skyline = Skyline::default();
+    for staff in system.staff_groups.iter().flat_map(|g| &g.staves) {
+        skyline.merge(&staff_skyline(staff, units_per_space, None));
+    }
+    for label in &system.measure_number_labels {
+        let b = label.bounding_box;
+        skyline.add(b.x, b.x + b.width, b.y, b.y + b.height);
+    }
+    // Font sizes match the renderer: 32 for volta labels, 28 for ottava labels
+    for volta in &system.volta_bracket_layouts {
+        skyline.add(volta.x_start, volta.x_end, volta.y - 27.0, volta.y + 15.0);
+    }
+    for ottava in &system.ottava_bracket_layouts {
+        let (top, bottom) = if ottava.above {
+            (ottava.y - 25.0, ottava.y + 12.0)
+        } else {
+            (ottava.y - 12.0, ottava.y + 25.0)
+        };
+        skyline.add(ottava.x_start, ottava.x_end, top, bottom);
+    }
+    for direction in &system.direction_layouts {
+        skyline.add_box(&direction.bounding_box);
+    }
+    skyline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_only_counts_overlapping_slices() {
+        let mut upper = Skyline::default();
+        upper.add(0.0, 100.0, 0.0, 80.0);
+        // A low note far to the right
+        upper.add(400.0, 420.0, 150.0, 160.0);
+
+        let mut lower = Skyline::default();
+        lower.add(0.0, 100.0, 0.0, 80.0);
+        assert_eq!(Skyline::distance(&upper, &lower), Some(80.0));
+
+        // A high note under the low one
+        lower.add(405.0, 415.0, -60.0, -50.0);
+        assert_eq!(Skyline::distance(&upper, &lower), Some(220.0));
+
+        // Nothing below the upper skyline's empty slices
+        let mut apart = Skyline::default();
+        apart.add(200.0, 300.0, 0.0, 10.0);
+        assert_eq!(Skyline::distance(&upper, &apart), None);
+    }
+
+    #[test]
+    fn test_merge_translate_and_extremes() {
+        let mut a = Skyline::default();
+        a.add(-20.0, 10.0, 5.0, 15.0);
+        let mut b = Skyline::default();
+        b.add(50.0, 60.0, -10.0, 40.0);
+        a.merge(&b);
+        assert_eq!((a.top(), a.bottom()), (Some(-10.0), Some(40.0)));
+        a.translate(100.0);
+        assert_eq!((a.top(), a.bottom()), (Some(90.0), Some(140.0)));
+        assert_eq!(Skyline::default().top(), None);
+    }
+}
@@ -4,8 +4,11 @@
 //! Uses sqrt-based scaling (standard in music engraving) so shorter notes
 //! receive proportionally more space, preventing accidental/flag overlap.
 
+use serde::{Deserialize, Serialize};
+
 /// Configuration for horizontal spacing algorithm
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SpacingConfig {
     /// Base space for any note in logical units (default: 10.0 = 0.5 staff spaces)
     pub base_spacing: f32,
@@ -15,13 +18,18 @@ pub struct SpacingConfig {
     pub minimum_spacing: f32,
 }
 
+impl SpacingConfig {
+    /// Spacing of the default engraving style
+    pub const STANDARD: SpacingConfig = SpacingConfig {
+        base_spacing: 15.0,
+        duration_factor: 25.0,
+        minimum_spacing: 38.0,
+    };
+}
+
 impl Default for SpacingConfig {
     fn default() -> Self {
-        Self {
-            base_spacing: 15.0,
-            duration_factor: 25.0,
-            minimum_spacing: 38.0,
-        }
+        Self::STANDARD
     }
 }
 
@@ -6,36 +6,100 @@
 
 use std::collections::HashMap;
 
+use crate::domain::part_group::{GroupSymbol, PartGroup};
+use crate::layout::barlines;
+use crate::layout::extraction::InstrumentData;
+use crate::layout::skyline::Skyline;
 use crate::layout::types::{
-    BoundingBox, BracketGlyph, BracketType, LayoutConfig, RepeatDotPosition, Staff, StaffGroup,
+    BarLine, BoundingBox, BracketGlyph, BracketType, GroupBracket, InstrumentNames, LayoutConfig,
+    NameLabel, RepeatDotPosition, Staff, StaffGroup,
 };
 
+/// Minimum clearance between the contours of adjacent staves, in staff spaces
+const STAFF_CLEARANCE_SPACES: f32 = 1.0;
+
+/// X of an instrument's own brace (the left margin)
+const INSTRUMENT_BRACKET_X: f32 = 5.0;
+
+/// Horizontal distance between nested group brackets, in staff spaces
+const GROUP_BRACKET_STEP_SPACES: f32 = 1.25;
+
+/// Approximate average character width as a fraction of the font size
+const CHAR_WIDTH_FACTOR: f32 = 0.6;
+
+/// Name printed left of an instrument on the system with `system_index`
+pub(crate) fn name_label_text(
+    instrument: &InstrumentData,
+    system_index: usize,
+    names: InstrumentNames,
+) -> Option<String> {
+    match names {
+        InstrumentNames::Full => Some(instrument.name.clone()),
+        InstrumentNames::FullThenShort if system_index > 0 => Some(
+            instrument
+                .short_name
+                .clone()
+                .unwrap_or_else(|| instrument.name.clone()),
+        ),
+        InstrumentNames::FullThenShort => Some(instrument.name.clone()),
+        InstrumentNames::Hidden => None,
+    }
+}
+
+/// Width of a right-aligned name label
+pub(crate) fn name_label_width(label: &NameLabel) -> f32 {
+    label.text.chars().count() as f32 * CHAR_WIDTH_FACTOR * label.font_size
+}
+
+/// Width left of x = 0 taken by a system's instrument names, braces and
+/// group brackets
+///
+/// Names only count on systems of several instruments, the only ones they
+/// are drawn on.
+pub(crate) fn system_indent(staff_groups: &[StaffGroup], group_brackets: &[GroupBracket]) -> f32 {
+    let names_drawn = staff_groups.len() > 1;
+    let brackets = staff_groups
+        .iter()
+        .filter_map(|g| g.bracket_glyph.as_ref())
+        .chain(group_brackets.iter().map(|b| &b.bracket_glyph))
+        .map(|bracket| bracket.bounding_box.x);
+    let labels = staff_groups
+        .iter()
+        .filter(|_| names_drawn)
+        .filter_map(|g| g.name_label.as_ref())
+        .map(|label| label.position.x - name_label_width(label));
+    -brackets.chain(labels).fold(0.0_f32, f32::min)
+}
+
 /// Compute cumulative collision-avoidance extra spacing per staff.
 ///
+/// `skylines[i]` is the skyline of staff `i` measured from its top line.
+/// Adjacent staves keep their nominal distance unless their contours come
+/// closer than the minimum clearance anywhere along the system; only then
+/// is the lower staff moved down, by just enough.
+///
 /// Returns `(cumulative_collision_extra, total_collision_extra)` where
 /// `cumulative_collision_extra[i]` is the total extra spacing accumulated
 /// up to and including staff `i`, and `total_collision_extra` is the
 /// grand total across all staves.
 pub(crate) fn compute_collision_gaps(
-    staff_extents: &[(f32, f32)],
+    skylines: &[Skyline],
     has_inter_gap_before: &[bool],
     intra_staff_multiplier: f32,
     inter_instrument_multiplier: f32,
     units_per_space: f32,
 ) -> (Vec<f32>, f32) {
-    let min_clearance = 2.0 * units_per_space;
-    let mut cumulative: Vec<f32> = vec![0.0; staff_extents.len()];
-    for i in 0..staff_extents.len().saturating_sub(1) {
-        let (_, max_y_upper) = staff_extents[i];
-        let (min_y_lower, _) = staff_extents[i + 1];
-
+    let min_clearance = STAFF_CLEARANCE_SPACES * units_per_space;
+    let mut cumulative: Vec<f32> = vec![0.0; skylines.len()];
+    for i in 0..skylines.len().saturating_sub(1) {
         let mut pair_spacing = intra_staff_multiplier * units_per_space;
         if has_inter_gap_before[i + 1] {
             pair_spacing += inter_instrument_multiplier * units_per_space;
         }
 
-        let needed = max_y_upper - min_y_lower + min_clearance;
-        let extra = (needed - pair_spacing).max(0.0);
+        let extra = Skyline::distance(&skylines[i], &skylines[i + 1]).map_or(0.0, |touching| {
+            (touching + min_clearance - pair_spacing).max(0.0)
+        });
         cumulative[i + 1] = cumulative[i] + extra;
     }
     let total = cumulative.last().copied().unwrap_or(0.0);
@@ -52,23 +116,34 @@ pub(crate) fn create_bracket_glyph(
     let last_staff = &staves[staves.len() - 1];
 
     // Span from top of first staff to bottom of last staff
-    let top_y = first_staff.staff_lines[0].y_position;
-    let bottom_y = last_staff.staff_lines[4].y_position;
+    bracket_glyph_at(
+        first_staff.top_line_y(),
+        last_staff.bottom_line_y(),
+        bracket_type,
+        INSTRUMENT_BRACKET_X,
+    )
+}
+
+/// Bracket/brace glyph from `top_y` to `bottom_y` with its left edge near `x_position`
+fn bracket_glyph_at(
+    top_y: f32,
+    bottom_y: f32,
+    bracket_type: &BracketType,
+    x_position: f32,
+) -> BracketGlyph {
     let height = bottom_y - top_y;
-    let _center_y = (top_y + bottom_y) / 2.0;
 
     // Scale glyph to match actual bracket height
     const BRACE_NATURAL_HEIGHT: f32 = 320.0; // SMuFL brace U+E000 at fontSize 80
     let scale_y = height / BRACE_NATURAL_HEIGHT;
 
+    // Square brackets and lines have no SMuFL glyph and are drawn as lines
     let codepoint = match bracket_type {
         BracketType::Brace => "\u{E000}".to_string(),
         BracketType::Bracket => "\u{E002}".to_string(),
-        BracketType::None => String::new(),
+        BracketType::Square | BracketType::Line | BracketType::None => String::new(),
     };
 
-    let x_position = 5.0; // Left margin
-
     BracketGlyph {
         codepoint,
         x: x_position,
@@ -94,47 +169,21 @@ pub(crate) fn create_bracket_glyph(
 /// We adjust the y_start/y_end of each barline segment on staves[0] to
 /// cover the full group height, collect repeat dots from all staves onto
 /// staves[0], then clear barlines from staves[1..].
-pub(crate) fn join_multi_staff_barlines(staff_groups: &mut [StaffGroup]) {
+pub(crate) fn join_multi_staff_barlines(staff_groups: &mut [StaffGroup], units_per_space: f32) {
     for staff_group in staff_groups.iter_mut() {
         if staff_group.staves.len() >= 2 {
-            let top_y = staff_group.staves[0].staff_lines[0].y_position;
-            let bottom_y = staff_group.staves.last().unwrap().staff_lines[4].y_position;
+            let (top_y, _) =
+                barlines::staff_barline_extent(&staff_group.staves[0], units_per_space);
+            let (_, bottom_y) =
+                barlines::staff_barline_extent(staff_group.staves.last().unwrap(), units_per_space);
 
             // Collect repeat dots from subsequent staves, keyed by barline X
             // so they can be merged onto the matching barline on staves[0].
-            let mut extra_dots: HashMap<i32, Vec<RepeatDotPosition>> = HashMap::new();
-            for staff in staff_group.staves[1..].iter() {
-                for bar_line in &staff.bar_lines {
-                    if bar_line.dots.is_empty() {
-                        continue;
-                    }
-                    let x_key = bar_line
-                        .segments
-                        .first()
-                        .map(|s| (s.x_position * 10.0) as i32)
-                        .unwrap_or(0);
-                    extra_dots
-                        .entry(x_key)
-                        .or_default()
-                        .extend_from_slice(&bar_line.dots);
-                }
-            }
+            let extra_dots =
+                collect_repeat_dots(staff_group.staves[1..].iter().flat_map(|s| &s.bar_lines));
 
             // Extend staves[0] barlines to span the full group height.
-            for bar_line in &mut staff_group.staves[0].bar_lines {
-                for segment in &mut bar_line.segments {
-                    segment.y_start = top_y;
-                    segment.y_end = bottom_y;
-                }
-                let x_key = bar_line
-                    .segments
-                    .first()
-                    .map(|s| (s.x_position * 10.0) as i32)
-                    .unwrap_or(0);
-                if let Some(more_dots) = extra_dots.remove(&x_key) {
-                    bar_line.dots.extend(more_dots);
-                }
-            }
+            extend_barlines(&mut staff_group.staves[0], top_y, bottom_y, extra_dots);
 
             // Remove barlines from subsequent staves (merged into staves[0]).
             for staff in staff_group.staves[1..].iter_mut() {
@@ -144,6 +193,154 @@ pub(crate) fn join_multi_staff_barlines(staff_groups: &mut [StaffGroup]) {
     }
 }
 
+/// Join barlines through the staves of part groups with `barline` set.
+///
+/// Runs after `join_multi_staff_barlines`, when each instrument's barlines
+/// are on its first staff: the first instrument of the outermost joined
+/// group takes barlines reaching the bottom of the group's last staff, and
+/// the other instruments' barlines are merged into them.
+pub(crate) fn join_part_group_barlines(
+    staff_groups: &mut [StaffGroup],
+    part_groups: &[PartGroup],
+    units_per_space: f32,
+) {
+    let joined: Vec<&PartGroup> = part_groups.iter().filter(|g| g.barline).collect();
+    for group in &joined {
+        let outermost = !joined
+            .iter()
+            .any(|other| other.encloses(group) && !group.encloses(other));
+        let (first, last) = (group.first_instrument, group.last_instrument);
+        if !outermost || last <= first || last >= staff_groups.len() {
+            continue;
+        }
+        let Some(bottom_staff) = staff_groups[last].staves.last() else {
+            continue;
+        };
+        let (_, bottom_y) = barlines::staff_barline_extent(bottom_staff, units_per_space);
+
+        let below: Vec<BarLine> = staff_groups[first + 1..=last]
+            .iter_mut()
+            .filter_map(|g| g.staves.first_mut())
+            .flat_map(|staff| std::mem::take(&mut staff.bar_lines))
+            .collect();
+        let extra_dots = collect_repeat_dots(&below);
+        if let Some(top_staff) = staff_groups[first].staves.first_mut() {
+            let (top_y, _) = barlines::staff_barline_extent(top_staff, units_per_space);
+            extend_barlines(top_staff, top_y, bottom_y, extra_dots);
+        }
+    }
+}
+
+/// Brackets of the part groups drawn in this system, nested left of the
+/// instruments' own braces.
+///
+/// A group's bracket sits one step left of the brackets of the groups it
+/// encloses, and of any instrument brace inside it.
+pub(crate) fn create_group_brackets(
+    staff_groups: &[StaffGroup],
+    part_groups: &[PartGroup],
+    units_per_space: f32,
+) -> Vec<GroupBracket> {
+    let drawn: Vec<&PartGroup> = part_groups
+        .iter()
+        .filter(|g| g.symbol != GroupSymbol::None && g.last_instrument < staff_groups.len())
+        .collect();
+
+    // Depths in order of increasing span, so enclosed groups come first;
+    // of two groups with the same span the one listed first is outside
+    let mut order: Vec<usize> = (0..drawn.len()).collect();
+    order.sort_by_key(|&i| {
+        (
+            drawn[i].last_instrument - drawn[i].first_instrument,
+            std::cmp::Reverse(i),
+        )
+    });
+    let mut depths = vec![0usize; drawn.len()];
+    for (n, &i) in order.iter().enumerate() {
+        let group = drawn[i];
+        let has_brace = (group.first_instrument..=group.last_instrument)
+            .any(|k| staff_groups[k].bracket_glyph.is_some());
+        let mut depth = usize::from(has_brace);
+        for &j in &order[..n] {
+            if group.encloses(drawn[j]) {
+                depth = depth.max(depths[j] + 1);
+            }
+        }
+        depths[i] = depth;
+    }
+
+    drawn
+        .iter()
+        .zip(depths)
+        .filter_map(|(group, depth)| {
+            let top_y = staff_groups[group.first_instrument]
+                .staves
+                .first()?
+                .top_line_y();
+            let bottom_y = staff_groups[group.last_instrument]
+                .staves
+                .last()?
+                .bottom_line_y();
+            let bracket_type = match group.symbol {
+                GroupSymbol::Bracket => BracketType::Bracket,
+                GroupSymbol::Brace => BracketType::Brace,
+                GroupSymbol::Square => BracketType::Square,
+                GroupSymbol::Line => BracketType::Line,
+                GroupSymbol::None => BracketType::None,
+            };
+            let x =
+                INSTRUMENT_BRACKET_X - depth as f32 * GROUP_BRACKET_STEP_SPACES * units_per_space;
+            Some(GroupBracket {
+                bracket_type,
+                depth,
+                bracket_glyph: bracket_glyph_at(top_y, bottom_y, &bracket_type, x),
+            })
+        })
+        .collect()
+}
+
+/// Repeat dots of the barlines, keyed by barline X
+fn collect_repeat_dots<'a>(
+    bar_lines: impl IntoIterator<Item = &'a BarLine>,
+) -> HashMap<i32, Vec<RepeatDotPosition>> {
+    let mut dots: HashMap<i32, Vec<RepeatDotPosition>> = HashMap::new();
+    for bar_line in bar_lines {
+        if !bar_line.dots.is_empty() {
+            dots.entry(barline_x_key(bar_line))
+                .or_default()
+                .extend_from_slice(&bar_line.dots);
+        }
+    }
+    dots
+}
+
+/// Stretch a staff's barlines from `top_y` to `bottom_y`, adding the
+/// repeat dots of the barlines they replace
+fn extend_barlines(
+    staff: &mut Staff,
+    top_y: f32,
+    bottom_y: f32,
+    mut extra_dots: HashMap<i32, Vec<RepeatDotPosition>>,
+) {
+    for bar_line in &mut staff.bar_lines {
+        for segment in &mut bar_line.segments {
+            segment.y_start = top_y;
+            segment.y_end = bottom_y;
+        }
+        if let Some(more_dots) = extra_dots.remove(&barline_x_key(bar_line)) {
+            bar_line.dots.extend(more_dots);
+        }
+    }
+}
+
+fn barline_x_key(bar_line: &BarLine) -> i32 {
+    bar_line
+        .segments
+        .first()
+        .map(|s| (s.x_position * 10.0) as i32)
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,10 +352,10 @@ mod tests {
         let config = LayoutConfig::default();
 
         // Create two dummy staves at different vertical positions
-        let staff_0_lines = assembly::create_staff_lines(0.0, 1200.0, config.units_per_space);
+        let staff_0_lines = assembly::create_staff_lines(0.0, 1200.0, config.units_per_space, 5);
         let staff_1_offset = 14.0 * config.units_per_space;
         let staff_1_lines =
-            assembly::create_staff_lines(staff_1_offset, 1200.0, config.units_per_space);
+            assembly::create_staff_lines(staff_1_offset, 1200.0, config.units_per_space, 5);
 
         let staff_0 = Staff {
             staff_lines: staff_0_lines,
@@ -170,6 +367,12 @@ mod tests {
             tie_arcs: vec![],
             slur_arcs: vec![],
             fingering_glyphs: vec![],
+            chord_symbols: vec![],
+            pedal_marks: vec![],
+            arpeggios: vec![],
+            glissandos: vec![],
+            sticky_signatures: vec![],
+            tab_frets: vec![],
         };
 
         let staff_1 = Staff {
@@ -182,6 +385,12 @@ mod tests {
             tie_arcs: vec![],
             slur_arcs: vec![],
             fingering_glyphs: vec![],
+            chord_symbols: vec![],
+            pedal_marks: vec![],
+            arpeggios: vec![],
+            glissandos: vec![],
+            sticky_signatures: vec![],
+            tab_frets: vec![],
         };
 
         let staves = vec![staff_0, staff_1];
@@ -205,8 +414,8 @@ mod tests {
         );
 
         // Verify bounding box spans both staves
-        let first_staff_top = staves[0].staff_lines[0].y_position;
-        let last_staff_bottom = staves[1].staff_lines[4].y_position;
+        let first_staff_top = staves[0].top_line_y();
+        let last_staff_bottom = staves[1].bottom_line_y();
 
         // Brace should extend to cover both staves
         assert!(
@@ -224,10 +433,10 @@ mod tests {
     fn test_create_bracket_glyph_bracket() {
         let config = LayoutConfig::default();
 
-        let staff_0_lines = assembly::create_staff_lines(0.0, 1200.0, config.units_per_space);
+        let staff_0_lines = assembly::create_staff_lines(0.0, 1200.0, config.units_per_space, 5);
         let staff_1_offset = 14.0 * config.units_per_space;
         let staff_1_lines =
-            assembly::create_staff_lines(staff_1_offset, 1200.0, config.units_per_space);
+            assembly::create_staff_lines(staff_1_offset, 1200.0, config.units_per_space, 5);
 
         let staff_0 = Staff {
             staff_lines: staff_0_lines,
@@ -239,6 +448,12 @@ mod tests {
             tie_arcs: vec![],
             slur_arcs: vec![],
             fingering_glyphs: vec![],
+            chord_symbols: vec![],
+            pedal_marks: vec![],
+            arpeggios: vec![],
+            glissandos: vec![],
+            sticky_signatures: vec![],
+            tab_frets: vec![],
         };
 
         let staff_1 = Staff {
@@ -251,6 +466,12 @@ mod tests {
             tie_arcs: vec![],
             slur_arcs: vec![],
             fingering_glyphs: vec![],
+            chord_symbols: vec![],
+            pedal_marks: vec![],
+            arpeggios: vec![],
+            glissandos: vec![],
+            sticky_signatures: vec![],
+            tab_frets: vec![],
         };
 
         let staves = vec![staff_0, staff_1];
@@ -243,6 +243,7 @@ mod tests {
     /// This test FAILS before T021 (STEM_LENGTH=35.0 < 70.0).
     /// This test PASSES after T021 (STEM_LENGTH=70.0 ≥ 70.0).
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_stem_length_standard_note() {
         assert!(
             Stem::STEM_LENGTH >= 70.0,
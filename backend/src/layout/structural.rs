@@ -1,13 +1,15 @@
 //! Structural glyph rendering
 //!
 //! Positions clef, key signature, and time signature glyphs at system
-//! starts and handles mid-system clef and key signature changes.
+//! starts and handles mid-system clef and key signature changes. For
+//! single-line layout, also provides the clef and key signature in effect
+//! along the line.
 
 use std::collections::HashMap;
 
 use crate::layout::extraction::StaffData;
 use crate::layout::positioner;
-use crate::layout::types::{Glyph, TickRange};
+use crate::layout::types::{Glyph, StickySignature, TickPosition, TickRange};
 
 /// Render structural glyphs (clef, key sig, time sig) at the system start
 /// and mid-system key/clef changes within a single staff.
@@ -193,3 +195,50 @@ pub(crate) fn render_structural_glyphs(
 
     structural_glyphs
 }
+
+/// Clef and key signature in effect every `interval` units along a single
+/// line, positioned as at a system start
+///
+/// `anchors` are tick/x pairs sorted by tick (measure starts and onsets); the
+/// signature at x is the one in effect at the last anchor at or before x.
+pub(crate) fn render_sticky_signatures(
+    staff_data: &StaffData,
+    anchors: &[TickPosition],
+    interval: f32,
+    system_width: f32,
+    staff_vertical_offset: f32,
+    units_per_space: f32,
+) -> Vec<StickySignature> {
+    let Some(first) = anchors.first() else {
+        return Vec::new();
+    };
+    let interval = interval.max(units_per_space);
+    let count = (system_width / interval).ceil().max(1.0) as usize;
+
+    (0..count)
+        .map(|k| {
+            let x = k as f32 * interval;
+            let tick = anchors
+                .iter()
+                .take_while(|a| a.x <= x)
+                .last()
+                .unwrap_or(first)
+                .tick;
+            let clef = staff_data.get_clef_at_tick(tick);
+            let mut glyphs = vec![positioner::position_clef(
+                clef,
+                60.0,
+                units_per_space,
+                staff_vertical_offset,
+            )];
+            glyphs.extend(positioner::position_key_signature(
+                staff_data.get_key_at_tick(tick),
+                clef,
+                120.0,
+                units_per_space,
+                staff_vertical_offset,
+            ));
+            StickySignature { x, glyphs }
+        })
+        .collect()
+}
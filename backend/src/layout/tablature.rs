@@ -0,0 +1,325 @@
+//! Tablature staff layout
+//!
+//! Chooses string and fret for tablature notes the score leaves open, and
+//! lays out tablature staves: fret numbers on the string lines (tied notes
+//! in parentheses) and rhythm stems, flags and beams below the staff.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::domain::tablature::TabRequest;
+use crate::layout::beams::Beam;
+use crate::layout::extraction::{NoteEvent, RestLayoutEvent, StaffData};
+use crate::layout::positioner;
+use crate::layout::stems::Stem;
+use crate::layout::types::{BoundingBox, Glyph, Point, SourceReference, TabFret, TickRange};
+
+/// Clef name of tablature staves, drawn as the TAB sign
+pub(crate) const TAB_CLEF: &str = "Tab";
+
+/// Fret number font size, in staff spaces
+const FRET_FONT_SIZE: f32 = 1.4;
+/// Advance width per digit relative to the font size
+const FRET_CHAR_WIDTH: f32 = 0.55;
+/// Gap between the bottom string line and the top of the stems, in staff spaces
+const STEM_GAP: f32 = 0.75;
+/// Stem length of quarter and shorter notes, in staff spaces
+const STEM_LENGTH: f32 = 2.5;
+/// Stem length of half notes, in staff spaces
+const HALF_NOTE_STEM_LENGTH: f32 = 1.5;
+
+/// Fill in string and fret for the notes of a tablature staff that have
+/// none, using `Tablature::assign_positions`.
+///
+/// Notes sounding together across all voices are assigned as one chord.
+/// A tied note takes the position of the note it continues. Grace notes
+/// are not drawn on tablature and are left out.
+pub(crate) fn assign_tab_positions(staff: &mut StaffData) {
+    let Some(tablature) = &staff.tablature else {
+        return;
+    };
+
+    let mut chords: BTreeMap<u32, Vec<(usize, usize)>> = BTreeMap::new();
+    for (voice_index, voice) in staff.voices.iter().enumerate() {
+        for (note_index, note) in voice.notes.iter().enumerate() {
+            if !note.is_grace {
+                chords
+                    .entry(note.start_tick)
+                    .or_default()
+                    .push((voice_index, note_index));
+            }
+        }
+    }
+    let note = |&(v, n): &(usize, usize)| &staff.voices[v].notes[n];
+    // (chord, note in chord) of each note, by id
+    let locations: HashMap<&str, (usize, usize)> = chords
+        .values()
+        .enumerate()
+        .flat_map(|(c, chord)| {
+            chord
+                .iter()
+                .enumerate()
+                .map(move |(i, location)| (note(location).note_id.as_str(), (c, i)))
+        })
+        .collect();
+    // Tie continuations and the location of the note each continues
+    let tied_from: HashMap<&str, (usize, usize)> = chords
+        .values()
+        .flatten()
+        .map(note)
+        .filter_map(|n| Some((n.tie_next.as_deref()?, *locations.get(n.note_id.as_str())?)))
+        .collect();
+    let requests: Vec<Vec<_>> = chords
+        .values()
+        .map(|chord| {
+            chord
+                .iter()
+                .map(|i| {
+                    let n = note(i);
+                    let request = match (n.tab, tied_from.get(n.note_id.as_str())) {
+                        (Some(tab), _) => TabRequest::Written(tab),
+                        (None, Some(&(chord, note))) => TabRequest::Tied { chord, note },
+                        (None, None) => TabRequest::Free,
+                    };
+                    (n.pitch, request)
+                })
+                .collect()
+        })
+        .collect();
+    let positions = tablature.assign_positions(&requests);
+
+    for (chord, chord_positions) in chords.values().zip(positions) {
+        for (&(v, n), position) in chord.iter().zip(chord_positions) {
+            staff.voices[v].notes[n].tab = position;
+        }
+    }
+}
+
+/// Note y extents of a tablature staff relative to its top line: the string
+/// lines and the stems below them
+pub(crate) fn tab_staff_extents(staff_data: &StaffData, units_per_space: f32) -> (f32, f32) {
+    let (top, bottom) = positioner::staff_line_span(staff_data.line_count);
+    (
+        top * units_per_space,
+        (bottom + STEM_GAP + STEM_LENGTH) * units_per_space,
+    )
+}
+
+/// Lay out the notes and rests of a tablature staff
+///
+/// Returns the stem, beam, flag and rest glyphs and the fret numbers.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn position_tab_staff(
+    staff_data: &StaffData,
+    tick_range: &TickRange,
+    units_per_space: f32,
+    instrument_id: &str,
+    staff_index: usize,
+    staff_vertical_offset: f32,
+    note_positions: &HashMap<u32, f32>,
+    left_margin: f32,
+    measure_x_bounds: &HashMap<u32, (f32, f32)>,
+    pickup_ticks: u32,
+) -> (Vec<Glyph>, Vec<TabFret>) {
+    let ups = units_per_space;
+    let (top_line, bottom_line) = positioner::staff_line_span(staff_data.line_count);
+    let stem_top = staff_vertical_offset + (bottom_line + STEM_GAP) * ups;
+    let font_size = FRET_FONT_SIZE * ups;
+
+    // Notes some other note is tied to are drawn in parentheses
+    let tied_to: HashSet<&str> = staff_data
+        .voices
+        .iter()
+        .flat_map(|v| &v.notes)
+        .filter_map(|n| n.tie_next.as_deref())
+        .collect();
+
+    let mut glyphs = Vec::new();
+    let mut frets = Vec::new();
+    for (voice_index, voice) in staff_data.voices.iter().enumerate() {
+        let source = |event_index| SourceReference {
+            instrument_id: instrument_id.to_string(),
+            staff_index,
+            voice_index,
+            event_index,
+        };
+        let notes_in_range: Vec<&NoteEvent> = voice
+            .notes
+            .iter()
+            .filter(|n| n.start_tick >= tick_range.start_tick && n.start_tick < tick_range.end_tick)
+            .collect();
+
+        // One stem per chord: (x, event index and note of its first note)
+        let mut chords: Vec<(f32, usize, &NoteEvent)> = Vec::new();
+        for (event_index, note) in notes_in_range.iter().enumerate() {
+            if note.is_grace {
+                continue;
+            }
+            let Some(&x) = note_positions.get(&note.start_tick) else {
+                continue;
+            };
+            if !chords
+                .last()
+                .is_some_and(|(_, _, first)| first.start_tick == note.start_tick)
+            {
+                chords.push((x, event_index, note));
+            }
+
+            let Some(tab) = note.tab else {
+                continue;
+            };
+            if tab.string == 0 || tab.string > staff_data.line_count {
+                continue;
+            }
+            let text = if tied_to.contains(note.note_id.as_str()) {
+                format!("({})", tab.fret)
+            } else {
+                tab.fret.to_string()
+            };
+            let y = staff_vertical_offset + (top_line + f32::from(tab.string - 1)) * ups;
+            let width = text.chars().count() as f32 * FRET_CHAR_WIDTH * font_size + 0.2 * ups;
+            frets.push(TabFret {
+                x,
+                y,
+                string: tab.string,
+                font_size,
+                bounding_box: BoundingBox {
+                    x: x - width / 2.0,
+                    y: y - 0.5 * ups,
+                    width,
+                    height: ups,
+                },
+                text,
+                source_reference: source(event_index),
+            });
+        }
+
+        // Stems, flags and beams below the staff
+        let mut open_beams: HashMap<u8, f32> = HashMap::new();
+        for &(x, event_index, note) in &chords {
+            if note.duration_ticks >= 3840 {
+                continue;
+            }
+            let length = if note.duration_ticks >= 1920 {
+                HALF_NOTE_STEM_LENGTH
+            } else {
+                STEM_LENGTH
+            };
+            let stem_bottom = stem_top + length * ups;
+            glyphs.push(Glyph {
+                codepoint: '\u{0000}'.to_string(),
+                position: Point { x, y: stem_top },
+                bounding_box: BoundingBox {
+                    x: x - Stem::STEM_THICKNESS / 2.0,
+                    y: stem_top,
+                    width: Stem::STEM_THICKNESS,
+                    height: stem_bottom - stem_top,
+                },
+                source_reference: source(event_index),
+                font_size: None,
+                opacity: None,
+            });
+
+            if note.duration_ticks < 960 && note.beam_info.is_empty() {
+                glyphs.push(flag_glyph(x, stem_bottom, note, ups, source(event_index)));
+            }
+            for (level, beam_type) in &note.beam_info {
+                let span = match beam_type.as_str() {
+                    "Begin" => {
+                        open_beams.insert(*level, x);
+                        None
+                    }
+                    "End" => open_beams.remove(level).map(|start| (start, x)),
+                    "ForwardHook" => Some((x, x + Beam::BEAM_HOOK_LENGTH)),
+                    "BackwardHook" => Some((x - Beam::BEAM_HOOK_LENGTH, x)),
+                    _ => None,
+                };
+                if let Some((x_start, x_end)) = span {
+                    let y = stem_bottom
+                        - Beam::BEAM_THICKNESS
+                        - f32::from(level.saturating_sub(1))
+                            * (Beam::BEAM_THICKNESS + Beam::INTER_BEAM_GAP);
+                    glyphs.push(beam_glyph(x_start, x_end, y, source(event_index)));
+                }
+            }
+        }
+    }
+
+    // Rests, as on a regular staff
+    let rests: Vec<RestLayoutEvent> = staff_data
+        .voices
+        .iter()
+        .flat_map(|v| v.rests.iter().cloned())
+        .collect();
+    if !rests.is_empty() {
+        glyphs.extend(positioner::position_rests_for_staff(
+            &rests,
+            tick_range.start_tick,
+            tick_range.end_tick,
+            note_positions,
+            staff_data.time_numerator,
+            staff_data.time_denominator,
+            staff_data.voices.len() > 1,
+            units_per_space,
+            staff_vertical_offset,
+            left_margin,
+            instrument_id,
+            staff_index,
+            measure_x_bounds,
+            pickup_ticks,
+        ));
+    }
+
+    (glyphs, frets)
+}
+
+/// Downward flag at the bottom of a stem
+fn flag_glyph(
+    x: f32,
+    stem_bottom: f32,
+    note: &NoteEvent,
+    units_per_space: f32,
+    source_reference: SourceReference,
+) -> Glyph {
+    let (codepoint, name) = if note.duration_ticks < 240 {
+        ('\u{E245}', "flag32ndDown")
+    } else if note.duration_ticks < 480 {
+        ('\u{E243}', "flag16thDown")
+    } else {
+        ('\u{E241}', "flag8thDown")
+    };
+    // Flags extend right from the stem; glyphs are drawn centred on x
+    let position = Point {
+        x: x + 0.54 * units_per_space,
+        y: stem_bottom,
+    };
+    Glyph {
+        codepoint: codepoint.to_string(),
+        bounding_box: positioner::compute_glyph_bounding_box(
+            name,
+            &position,
+            40.0,
+            units_per_space,
+        ),
+        position,
+        source_reference,
+        font_size: Some(80.0),
+        opacity: None,
+    }
+}
+
+/// Horizontal beam with its top edge at `y`
+fn beam_glyph(x_start: f32, x_end: f32, y: f32, source_reference: SourceReference) -> Glyph {
+    Glyph {
+        codepoint: '\u{0001}'.to_string(),
+        position: Point { x: x_start, y },
+        bounding_box: BoundingBox {
+            x: x_start,
+            y,
+            width: x_end - x_start,
+            height: Beam::BEAM_THICKNESS,
+        },
+        source_reference,
+        font_size: None,
+        opacity: None,
+    }
+}
@@ -3,6 +3,10 @@
 //! Defines all entities in the spatial model hierarchy:
 //! GlobalLayout → Systems → StaffGroups → Staves → GlyphRuns → Glyphs
 
+use crate::domain::events::direction::Enclosure;
+use crate::domain::events::ornament::ArpeggioStyle;
+use crate::domain::events::pedal::PedalKind;
+use crate::layout::spacer::SpacingConfig;
 use serde::{Deserialize, Serialize, Serializer};
 
 /// Custom serializer for f32 that rounds to 2 decimal places
@@ -31,6 +35,53 @@ pub struct GlobalLayout {
     /// Scaling factor: how many logical units = 1 staff space (default: 10.0)
     #[serde(serialize_with = "round_f32")]
     pub units_per_space: f32,
+    /// Pages in paginated mode (`LayoutConfig::page`); empty when scrolling
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pages: Vec<Page>,
+}
+
+/// One printed page in paginated layout mode
+///
+/// Pages are stacked top to bottom in the same coordinate space as the
+/// systems, separated by `PageConfig::page_gap`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page {
+    /// 1-based page number
+    pub number: u32,
+    /// Page rectangle in logical units
+    pub bounding_box: BoundingBox,
+    /// Area inside the margins (below the title space on the first page)
+    pub content_box: BoundingBox,
+    /// Indices into `GlobalLayout::systems` of the systems on this page
+    pub system_indices: Vec<usize>,
+    /// Running header (pages after the first)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<PageText>,
+    /// Footer text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<PageText>,
+    /// Printed page number at the outer top corner (pages after the first)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_label: Option<PageText>,
+}
+
+/// Text printed in a page margin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageText {
+    pub text: String,
+    /// Anchor point on the text baseline
+    pub position: Point,
+    #[serde(serialize_with = "round_f32")]
+    pub font_size: f32,
+    pub align: TextAlign,
+}
+
+/// Horizontal alignment of text relative to its anchor point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextAlign {
+    Start,
+    Middle,
+    End,
 }
 
 /// System containing 1-N measures of music arranged horizontally
@@ -46,14 +97,64 @@ pub struct System {
     pub staff_groups: Vec<StaffGroup>,
     /// Musical time span covered by system (in 960 PPQ ticks)
     pub tick_range: TickRange,
-    /// Measure number displayed at start of system (1-based)
+    /// Measure number displayed at start of system (1-based): the numeric
+    /// part of the first measure's label in `measure_number_labels`, `None`
+    /// when the measure-numbering policy leaves it unprinted
     pub measure_number: Option<MeasureNumber>,
+    /// Printed measure numbers in this system, per the measure-numbering policy
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub measure_number_labels: Vec<MeasureNumberLabel>,
     /// Volta bracket layouts positioned over this system (Feature 047)
     #[serde(default)]
     pub volta_bracket_layouts: Vec<VoltaBracketLayout>,
     /// Ottava bracket layouts positioned over this system (8va/8vb)
     #[serde(default)]
     pub ottava_bracket_layouts: Vec<OttavaBracketLayout>,
+    /// Tempo marks, rehearsal marks and text directions, clear of brackets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub direction_layouts: Vec<DirectionLayout>,
+    /// Horizontal extent of each measure in this system, in tick order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub measure_layouts: Vec<MeasureLayout>,
+    /// x-position of every note and rest onset, shared by all staves and
+    /// sorted by tick (used for playback cursors and hit testing)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tick_positions: Vec<TickPosition>,
+    /// Brackets and braces of part groups, left of the instruments' own braces
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_brackets: Vec<GroupBracket>,
+    /// Width left of the staves (x < 0) taken by instrument names and
+    /// brackets; names are only drawn on systems of several instruments
+    #[serde(default, serialize_with = "round_f32")]
+    pub indent: f32,
+}
+
+/// Horizontal extent of one measure within a system
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeasureLayout {
+    /// First tick of the measure (inclusive, 960 PPQ)
+    pub start_tick: u32,
+    /// Tick after the measure (exclusive, 960 PPQ)
+    pub end_tick: u32,
+    /// x-position of the measure's left barline (or the system's content start)
+    #[serde(serialize_with = "round_f32")]
+    pub x_start: f32,
+    /// x-position of the measure's right barline
+    #[serde(serialize_with = "round_f32")]
+    pub x_end: f32,
+    /// Everything drawn for the measure on every staff, including glyphs
+    /// overhanging its barlines (for culling measures outside the viewport)
+    pub bounding_box: BoundingBox,
+}
+
+/// x-position of a note or rest onset
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TickPosition {
+    /// Onset tick (960 PPQ)
+    pub tick: u32,
+    /// x-position of the onset's noteheads and rests
+    #[serde(serialize_with = "round_f32")]
+    pub x: f32,
 }
 
 /// Positioned measure number at the start of a system
@@ -68,6 +169,24 @@ pub struct MeasureNumber {
     pub position: Point,
 }
 
+/// A printed measure number placed above the top staff of a system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasureNumberLabel {
+    /// Index of the numbered measure in the score
+    pub measure_index: u32,
+    /// Number as printed ("12", "12a")
+    pub text: String,
+    /// Left end of the text baseline
+    pub position: Point,
+    #[serde(serialize_with = "round_f32")]
+    pub font_size: f32,
+    /// Frame around the number, if the policy asks for one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enclosure: Option<EnclosureLayout>,
+    /// Extent of the text and its frame
+    pub bounding_box: BoundingBox,
+}
+
 /// A positioned volta bracket in layout coordinates (Feature 047)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoltaBracketLayout {
@@ -110,16 +229,87 @@ pub struct OttavaBracketLayout {
     pub staff_index: usize,
 }
 
+/// Kind of a positioned text direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DirectionKind {
+    /// Tempo mark above the system: bold text and/or a metronome mark
+    Tempo,
+    /// Boxed (or circled) rehearsal mark above the system
+    Rehearsal,
+    /// Italic expression text above or below a staff
+    Expression,
+}
+
+/// A positioned tempo mark, rehearsal mark or text direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectionLayout {
+    pub kind: DirectionKind,
+    /// Tick the direction is anchored to
+    pub tick: u32,
+    /// Display text (empty for a metronome mark without tempo text)
+    pub text: String,
+    /// Left edge of the text in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub x: f32,
+    /// Text baseline in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub y: f32,
+    /// Font size in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub font_size: f32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub italic: bool,
+    /// Metronome mark ("♩ = 120") following the text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metronome: Option<MetronomeLayout>,
+    /// Frame drawn around a rehearsal mark
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enclosure: Option<EnclosureLayout>,
+    /// Extent of the whole direction, used for collision avoidance
+    pub bounding_box: BoundingBox,
+    /// Instrument (staff group) index for staff-anchored text; system-level
+    /// marks use the top staff (0, 0)
+    pub staff_group_index: usize,
+    /// Staff index within the instrument
+    pub staff_index: usize,
+}
+
+/// Metronome mark: SMuFL note glyph followed by "= 120" on the same baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetronomeLayout {
+    /// SMuFL metronome note (U+ECA2–U+ECAB) plus augmentation dots (U+ECB7)
+    pub glyph: String,
+    #[serde(serialize_with = "round_f32")]
+    pub glyph_x: f32,
+    /// Music font size for the note glyph
+    #[serde(serialize_with = "round_f32")]
+    pub glyph_font_size: f32,
+    /// "= 120" (fractional tempos keep one decimal: "= 72.5")
+    pub text: String,
+    #[serde(serialize_with = "round_f32")]
+    pub text_x: f32,
+}
+
+/// Frame around a rehearsal mark or measure number
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnclosureLayout {
+    pub shape: Enclosure,
+    /// Rectangle (or the circle's bounding square) in logical units
+    pub bounding_box: BoundingBox,
+}
+
 /// Groups related staves for multi-staff instruments
 ///
-/// Piano has 2 staves (treble + bass), solo instruments have 1
+/// Piano has 2 staves (treble + bass), organ 3, solo instruments 1
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StaffGroup {
     /// Links to CompiledScore.Instrument.id
     pub instrument_id: String,
     /// Human-readable instrument name (FR-009)
     pub instrument_name: String,
-    /// 1-2 staves per group (MVP limit)
+    /// The instrument's staves, top to bottom
     pub staves: Vec<Staff>,
     /// Visual grouping indicator
     pub bracket_type: BracketType,
@@ -129,6 +319,18 @@ pub struct StaffGroup {
     pub name_label: Option<NameLabel>,
 }
 
+/// Bracket, brace or line of a part group spanning several instruments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupBracket {
+    /// Visual grouping indicator
+    pub bracket_type: BracketType,
+    /// Nesting level: 0 next to the staves, counting up for enclosing groups
+    pub depth: usize,
+    /// Glyph geometry, from the top line of the group's first staff to the
+    /// bottom line of its last staff
+    pub bracket_glyph: BracketGlyph,
+}
+
 /// Positioned text label for instrument name at system start
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NameLabel {
@@ -160,11 +362,12 @@ pub struct BracketGlyph {
     pub bounding_box: BoundingBox,
 }
 
-/// Single 5-line staff with positioned glyphs
+/// Single staff with positioned glyphs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Staff {
-    /// Exactly 5 horizontal lines (standard music staff)
-    pub staff_lines: [StaffLine; 5],
+    /// Horizontal lines, top to bottom: 5 on a regular staff, 1 on a rhythm
+    /// staff, 6 on guitar tablature
+    pub staff_lines: Vec<StaffLine>,
     /// Batched glyphs for efficient rendering
     pub glyph_runs: Vec<GlyphRun>,
     /// Clefs, key signatures, time signatures at staff start
@@ -185,6 +388,52 @@ pub struct Staff {
     /// Fingering glyphs: positioned numerals (1–5) above or below noteheads
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fingering_glyphs: Vec<FingeringGlyph>,
+    /// Chord symbols: formatted harmony text above the staff
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chord_symbols: Vec<ChordSymbolLayout>,
+    /// Piano pedal marks below the staff ("Ped." / "*" signs and pedal lines)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pedal_marks: Vec<PedalMarkLayout>,
+    /// Arpeggio signs (wavy lines or non-arpeggio brackets) left of chords
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arpeggios: Vec<ArpeggioLayout>,
+    /// Glissando lines between noteheads
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub glissandos: Vec<GlissandoLayout>,
+    /// Clef and key signature in effect at regular x-intervals, for
+    /// single-line layout (`LineBreaking::SingleLine`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sticky_signatures: Vec<StickySignature>,
+    /// Fret numbers on the string lines of a tablature staff
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tab_frets: Vec<TabFret>,
+}
+
+impl Staff {
+    /// y of the top staff line
+    pub fn top_line_y(&self) -> f32 {
+        self.staff_lines.first().map_or(0.0, |l| l.y_position)
+    }
+
+    /// y of the bottom staff line
+    pub fn bottom_line_y(&self) -> f32 {
+        self.staff_lines.last().map_or(0.0, |l| l.y_position)
+    }
+}
+
+/// Clef and key signature to keep visible at the left edge of a scrolling
+/// single-line layout
+///
+/// Entries are `LineBreaking::SingleLine::sticky_interval` apart, starting at
+/// x = 0: when the view's left edge is at x, the renderer draws the glyphs of
+/// entry `floor(x / sticky_interval)` shifted right by x.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StickySignature {
+    /// x-position from which this entry applies
+    #[serde(serialize_with = "round_f32")]
+    pub x: f32,
+    /// Clef and key signature glyphs, positioned as at a system start
+    pub glyphs: Vec<Glyph>,
 }
 
 /// Short horizontal line for notes outside the 5-line staff range
@@ -231,6 +480,154 @@ pub struct FingeringGlyph {
     pub above: bool,
 }
 
+/// A fret number on a string line of a tablature staff.
+///
+/// Drawn centred on the line over a knockout box (`bounding_box`) that
+/// hides the line behind the digits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TabFret {
+    /// Horizontal centre of the text (same x as the note's stem)
+    #[serde(serialize_with = "round_f32")]
+    pub x: f32,
+    /// y of the string line the text is centred on
+    #[serde(serialize_with = "round_f32")]
+    pub y: f32,
+    /// Text to draw: the fret number, in parentheses for a tied note
+    pub text: String,
+    /// String number, 1 = top line
+    pub string: u8,
+    /// Font size in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub font_size: f32,
+    /// Knockout box behind the text
+    pub bounding_box: BoundingBox,
+    /// Link back to the note for interaction
+    pub source_reference: SourceReference,
+}
+
+/// A chord symbol (e.g. "B♭m⁷/F") positioned above a staff.
+///
+/// Rendered as a sequence of text runs so the extension can be drawn as
+/// superscript; all runs of one staff line share the same baseline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChordSymbolLayout {
+    /// Tick the chord symbol is anchored to
+    pub tick: u32,
+    /// Plain display text (root + quality + extension + "/bass")
+    pub text: String,
+    /// Text runs, left to right
+    pub runs: Vec<ChordSymbolRun>,
+    /// Bounding box covering all runs
+    pub bounding_box: BoundingBox,
+}
+
+/// One text segment of a chord symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChordSymbolRun {
+    pub text: String,
+    /// Left edge of the run in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub x: f32,
+    /// Text baseline in logical units (raised for superscript runs)
+    #[serde(serialize_with = "round_f32")]
+    pub y: f32,
+    /// Font size in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub font_size: f32,
+    /// true for the raised extension ("7", "maj9", "♭13")
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub superscript: bool,
+}
+
+/// The part of a pedal region that falls within one system.
+///
+/// A region crossing a system break is split into one mark per system;
+/// `open_start` / `open_end` tell which ends continue from/into another system.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PedalMarkLayout {
+    pub kind: PedalKind,
+    /// Start tick of the whole pedal region
+    pub start_tick: u32,
+    /// End tick of the whole pedal region
+    pub end_tick: u32,
+    /// SMuFL pedal signs: "Ped." (U+E650), "Sost." (U+E659), "*" (U+E655)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub glyphs: Vec<Glyph>,
+    /// Text labels ("una corda" / "tre corde")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub texts: Vec<PedalText>,
+    /// Bracket-line style pedal line, if the region uses `line="yes"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<PedalLine>,
+    /// true if the region started in an earlier system
+    pub open_start: bool,
+    /// true if the region continues into the next system
+    pub open_end: bool,
+}
+
+/// Pedal line drawn as a polyline: start hook, notches (Λ) at pedal changes,
+/// end hook. Hooks are omitted at system breaks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PedalLine {
+    pub points: Vec<Point>,
+}
+
+/// Plain text pedal label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PedalText {
+    pub text: String,
+    /// Left edge of the text in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub x: f32,
+    /// Text baseline in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub y: f32,
+    /// Font size in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub font_size: f32,
+}
+
+/// Arpeggio sign drawn to the left of a chord.
+///
+/// A chord arpeggiated across staves is drawn once, on the upper staff,
+/// spanning down to the lowest note on the staff below.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArpeggioLayout {
+    pub style: ArpeggioStyle,
+    /// Tick of the arpeggiated chord
+    pub start_tick: u32,
+    /// Horizontal center of the line in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub x: f32,
+    /// Top of the line in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub y_top: f32,
+    /// Bottom of the line in logical units
+    #[serde(serialize_with = "round_f32")]
+    pub y_bottom: f32,
+    /// Polyline: a zigzag for wavy arpeggios, a bracket for non-arpeggio
+    pub points: Vec<Point>,
+    /// Arrowhead polyline for `Up` / `Down` arpeggios
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arrow: Vec<Point>,
+}
+
+/// Glissando line from one notehead to the next.
+///
+/// A glissando crossing a system break is split in two, with open ends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlissandoLayout {
+    pub wavy: bool,
+    /// Polyline: two points for a straight line, a zigzag for wavy lines
+    pub points: Vec<Point>,
+    pub note_id_start: String,
+    pub note_id_end: String,
+    /// true if the glissando started in an earlier system
+    pub open_start: bool,
+    /// true if the glissando continues into the next system
+    pub open_end: bool,
+}
+
 /// A cubic Bézier curve connecting two tied notes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TieArc {
@@ -372,7 +769,7 @@ pub struct Point {
 }
 
 /// Rectangular hit-testing and clipping region
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct BoundingBox {
     /// X-coordinate of top-left corner in logical units
     #[serde(serialize_with = "round_f32")]
@@ -407,7 +804,7 @@ impl BoundingBox {
 }
 
 /// Musical time span using 960 PPQ resolution
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct TickRange {
     /// First tick in range (inclusive, 960 PPQ)
     pub start_tick: u32,
@@ -435,6 +832,10 @@ pub enum BracketType {
     Brace,
     /// Square bracket (choir, strings)
     Bracket,
+    /// Thin bracket with square ends (sub-groups)
+    Square,
+    /// Plain vertical line
+    Line,
     /// No bracket (solo instruments)
     None,
 }
@@ -473,15 +874,299 @@ pub struct LayoutConfig {
     pub system_spacing: f32,
     /// System height in logical units (default: 200.0 for grand staff)
     pub system_height: f32,
+    /// Paginated (print) mode; `None` lays out one continuous column of systems.
+    /// When set, the page's content width replaces `max_system_width`.
+    #[serde(default)]
+    pub page: Option<PageConfig>,
+    /// How measures are distributed onto systems (default: greedy)
+    #[serde(default)]
+    pub line_breaking: LineBreaking,
+    /// Spacing, staff distances, stems and beams (default: standard)
+    #[serde(default)]
+    pub engraving: EngravingStyle,
+    /// Instrument names left of the systems (default: full, then short)
+    #[serde(default)]
+    pub instrument_names: InstrumentNames,
+    /// Which measures get a printed number (default: each system start)
+    #[serde(default)]
+    pub measure_numbers: MeasureNumbering,
+}
+
+/// Which instrument names are printed left of each system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InstrumentNames {
+    /// Full names on every system
+    Full,
+    /// Full names on the first system, short names on later systems;
+    /// instruments without a short name keep their full name
+    #[default]
+    FullThenShort,
+    /// No instrument names
+    Hidden,
+}
+
+/// Measure-numbering policy: where numbers are printed and how they are framed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeasureNumbering {
+    pub placement: MeasureNumberPlacement,
+    /// Frame around each number (default: none)
+    pub enclosure: Enclosure,
+}
+
+impl MeasureNumbering {
+    /// Unframed numbers at the start of each system
+    pub const fn standard() -> Self {
+        Self {
+            placement: MeasureNumberPlacement::SystemStart,
+            enclosure: Enclosure::None,
+        }
+    }
+}
+
+impl Default for MeasureNumbering {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// Which measures get a printed number
+///
+/// Pickups and other unnumbered measures never get one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MeasureNumberPlacement {
+    /// The first measure of each system
+    #[default]
+    SystemStart,
+    /// Every measure
+    EveryMeasure,
+    /// Measures whose number is a multiple of `every` (5, 10, ...)
+    Interval { every: u32 },
+    /// No measure numbers
+    Hidden,
+}
+
+/// System breaking strategy
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LineBreaking {
+    /// Fill each system as far as it goes, then stretch it to full width
+    #[default]
+    Greedy,
+    /// Choose all breaks together so that stretch and compression are spread
+    /// evenly over the whole piece (Knuth–Plass style total fit)
+    TotalFit {
+        /// Preferred number of measures per system, if any
+        #[serde(default)]
+        target_measures_per_system: Option<u32>,
+        /// Leave the last system at its natural width instead of justifying it
+        #[serde(default)]
+        ragged_last_system: bool,
+    },
+    /// One system of natural width for horizontal scrolling; pages and
+    /// engraver breaks are ignored
+    SingleLine {
+        /// Distance between clef/key signature entries (`StickySignature`)
+        #[serde(default = "default_sticky_interval")]
+        sticky_interval: f32,
+    },
+}
+
+fn default_sticky_interval() -> f32 {
+    LineBreaking::DEFAULT_STICKY_INTERVAL
+}
+
+impl LineBreaking {
+    /// Default `SingleLine::sticky_interval` in logical units
+    pub const DEFAULT_STICKY_INTERVAL: f32 = 200.0;
+
+    /// Single-line layout with the default sticky interval
+    pub const fn single_line() -> Self {
+        Self::SingleLine {
+            sticky_interval: Self::DEFAULT_STICKY_INTERVAL,
+        }
+    }
+}
+
+/// Engraving style: how loosely or tightly notation is drawn
+///
+/// Lengths are in staff spaces, so a style draws the same at any
+/// `units_per_space`; only the horizontal note spacing is in logical units.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngravingStyle {
+    /// Horizontal note spacing
+    pub spacing: SpacingConfig,
+    /// Distance between the top lines of adjacent staves of one instrument,
+    /// in staff spaces
+    pub staff_distance: f32,
+    /// Extra distance between instruments, in staff spaces
+    pub instrument_distance: f32,
+    /// Length of unbeamed stems, from the outermost notehead, in staff
+    /// spaces. Single notes keep their combined note glyphs at the standard
+    /// length and draw their stems apart at any other.
+    pub stem_length: f32,
+    /// Shortest stem in a beamed group (before extra beam levels), in staff
+    /// spaces
+    pub min_beamed_stem_length: f32,
+    /// Steepest beam: largest rise or fall from first to last stem, in
+    /// staff spaces
+    pub max_beam_slope: f32,
+}
+
+impl EngravingStyle {
+    /// Standard engraving
+    pub const fn standard() -> Self {
+        Self {
+            spacing: SpacingConfig::STANDARD,
+            staff_distance: 10.0,
+            instrument_distance: 5.0,
+            stem_length: 3.5,
+            min_beamed_stem_length: 2.5,
+            max_beam_slope: 0.5,
+        }
+    }
+
+    /// Tighter spacing and staves, to fit more music on a screen or page
+    pub const fn compact() -> Self {
+        Self {
+            spacing: SpacingConfig {
+                base_spacing: 12.0,
+                duration_factor: 20.0,
+                minimum_spacing: 32.0,
+            },
+            staff_distance: 9.0,
+            instrument_distance: 4.0,
+            stem_length: 3.5,
+            min_beamed_stem_length: 2.25,
+            max_beam_slope: 0.5,
+        }
+    }
+
+    /// Generous spacing, staff distances and flatter beams for beginners
+    /// and young readers
+    pub const fn spacious() -> Self {
+        Self {
+            spacing: SpacingConfig {
+                base_spacing: 22.0,
+                duration_factor: 35.0,
+                minimum_spacing: 52.0,
+            },
+            staff_distance: 12.0,
+            instrument_distance: 7.0,
+            stem_length: 3.5,
+            min_beamed_stem_length: 2.75,
+            max_beam_slope: 0.25,
+        }
+    }
+
+    /// Preset by name: `compact`, `default` (or `standard`), `spacious` (or
+    /// `beginner`)
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "compact" => Some(Self::compact()),
+            "default" | "standard" => Some(Self::standard()),
+            "spacious" | "beginner" => Some(Self::spacious()),
+            _ => None,
+        }
+    }
+}
+
+impl Default for EngravingStyle {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl LayoutConfig {
+    /// `LayoutConfig::default()` as a constant, so constant configurations
+    /// can override a few fields with `..LayoutConfig::DEFAULT`
+    pub const DEFAULT: LayoutConfig = LayoutConfig {
+        max_system_width: 2400.0, // Wide enough for 3+ measures per system
+        units_per_space: 20.0,    // SMuFL: font_size 80 = 4 spaces, so 1 space = 20 units
+        system_spacing: 100.0,    // Spacing between systems (gap after system_height)
+        system_height: 200.0,     // Base height for a single staff system
+        page: None,
+        line_breaking: LineBreaking::Greedy,
+        engraving: EngravingStyle::standard(),
+        instrument_names: InstrumentNames::FullThenShort,
+        measure_numbers: MeasureNumbering::standard(),
+    };
 }
 
 impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Page geometry for paginated layout
+///
+/// Physical sizes are in millimetres and converted to logical units through
+/// the staff size: a staff of `staff_height_mm` spans four staff spaces.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PageConfig {
+    /// Page width in millimetres
+    pub width_mm: f32,
+    /// Page height in millimetres
+    pub height_mm: f32,
+    pub margin_top_mm: f32,
+    pub margin_bottom_mm: f32,
+    pub margin_left_mm: f32,
+    pub margin_right_mm: f32,
+    /// Height of a five-line staff in millimetres (staff size)
+    pub staff_height_mm: f32,
+    /// Space kept free above the first system of page 1 for title and credits
+    pub title_space_mm: f32,
+    /// Vertical gap between stacked pages in logical units
+    pub page_gap: f32,
+    /// Pages filled at least this much (0.0–1.0) have their systems spread
+    /// down to the bottom margin; emptier pages keep normal spacing
+    pub justify_threshold: f32,
+    /// Running header printed on pages after the first
+    pub header: Option<String>,
+    /// Footer printed on every page
+    pub footer: Option<String>,
+}
+
+impl Default for PageConfig {
+    /// A4 portrait with a 7 mm staff
     fn default() -> Self {
         Self {
-            max_system_width: 2400.0, // Wide enough for 3+ measures per system
-            units_per_space: 20.0,    // SMuFL: font_size 80 = 4 spaces, so 1 space = 20 units
-            system_spacing: 100.0,    // Spacing between systems (gap after system_height)
-            system_height: 200.0,     // Base height for a single staff system
+            width_mm: 210.0,
+            height_mm: 297.0,
+            margin_top_mm: 15.0,
+            margin_bottom_mm: 15.0,
+            margin_left_mm: 15.0,
+            margin_right_mm: 15.0,
+            staff_height_mm: 7.0,
+            title_space_mm: 30.0,
+            page_gap: 100.0,
+            justify_threshold: 0.6,
+            header: None,
+            footer: None,
         }
     }
 }
+
+impl PageConfig {
+    /// US Letter portrait with a 7 mm staff
+    pub fn letter() -> Self {
+        Self {
+            width_mm: 215.9,
+            height_mm: 279.4,
+            ..Self::default()
+        }
+    }
+
+    /// Logical units per millimetre for the given staff space size
+    pub fn units_per_mm(&self, units_per_space: f32) -> f32 {
+        4.0 * units_per_space / self.staff_height_mm
+    }
+
+    /// Width available for systems, in logical units
+    pub fn content_width(&self, units_per_space: f32) -> f32 {
+        (self.width_mm - self.margin_left_mm - self.margin_right_mm)
+            * self.units_per_mm(units_per_space)
+    }
+}
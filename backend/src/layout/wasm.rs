@@ -2,7 +2,13 @@
 //!
 //! Provides string-based JSON interface suitable for JavaScript interop
 
-use crate::layout::{LayoutConfig, compute_layout};
+use crate::domain::events::direction::Enclosure;
+use crate::layout::{
+    EngravingStyle, GlobalLayout, IncrementalLayout, InstrumentNames, LayoutConfig, LayoutIndex,
+    LineBreaking, MeasureNumberPlacement, MeasureNumbering, PageConfig, Point, TickRange,
+    compute_layout, encode_layout,
+};
+use serde::Serialize;
 use serde_json::Value;
 use serde_wasm_bindgen;
 use wasm_bindgen::prelude::*;
@@ -43,6 +49,141 @@ pub fn compute_layout_wasm(score_json: &str, config_json: &str) -> Result<JsValu
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize layout: {}", e)))
 }
 
+/// Binary variant of `compute_layout_wasm`
+///
+/// Returns the GlobalLayout encoded as described in `layout::binary`, as a
+/// `Uint8Array`: glyphs arrive as flat typed arrays instead of one
+/// JavaScript object each.
+#[wasm_bindgen]
+pub fn compute_layout_binary_wasm(score_json: &str, config_json: &str) -> Result<Vec<u8>, JsValue> {
+    let score = parse_score(score_json)?;
+    let config = parse_config(config_json)?;
+    Ok(encode_layout(&compute_layout(&score, &config)))
+}
+
+/// Layout kept alive between edits for incremental re-layout
+///
+/// Re-lays out only the measures and systems an edit touches instead of
+/// the whole score (see `IncrementalLayout`).
+#[wasm_bindgen]
+pub struct IncrementalLayoutWasm {
+    inner: IncrementalLayout,
+}
+
+#[wasm_bindgen]
+impl IncrementalLayoutWasm {
+    /// Lay out a score from scratch
+    ///
+    /// # Arguments
+    /// * `score_json` - CompiledScore as JSON string
+    /// * `config_json` - LayoutConfig as JSON string (optional, uses defaults if empty)
+    #[wasm_bindgen(constructor)]
+    pub fn new(score_json: &str, config_json: &str) -> Result<IncrementalLayoutWasm, JsValue> {
+        let score = parse_score(score_json)?;
+        let config = parse_config(config_json)?;
+        Ok(Self {
+            inner: IncrementalLayout::new(&score, &config),
+        })
+    }
+
+    /// Current GlobalLayout as JsValue
+    pub fn layout(&self) -> Result<JsValue, JsValue> {
+        to_js(self.inner.layout())
+    }
+
+    /// Current GlobalLayout in the binary encoding (`layout::binary`)
+    pub fn layout_binary(&self) -> Vec<u8> {
+        encode_layout(self.inner.layout())
+    }
+
+    /// Re-layout after notes changed between `start_tick` and `end_tick`
+    pub fn update(
+        &mut self,
+        score_json: &str,
+        start_tick: u32,
+        end_tick: u32,
+    ) -> Result<JsValue, JsValue> {
+        let score = parse_score(score_json)?;
+        let layout = self.inner.update(
+            &score,
+            TickRange {
+                start_tick,
+                end_tick,
+            },
+        );
+        to_js(layout)
+    }
+
+    /// Re-layout with a new configuration, e.g. after a viewport resize
+    pub fn set_config(&mut self, config_json: &str) -> Result<JsValue, JsValue> {
+        let config = parse_config(config_json)?;
+        to_js(self.inner.set_config(&config))
+    }
+
+    /// Query index over the current layout
+    pub fn index(&self) -> LayoutIndexWasm {
+        LayoutIndexWasm {
+            inner: LayoutIndex::new(self.inner.layout()),
+        }
+    }
+}
+
+/// Handle to a query index over one layout
+///
+/// Build it once per layout and keep it for hit testing and cursor queries
+/// (see `LayoutIndex`).
+#[wasm_bindgen]
+pub struct LayoutIndexWasm {
+    inner: LayoutIndex,
+}
+
+#[wasm_bindgen]
+impl LayoutIndexWasm {
+    /// Index a GlobalLayout object as returned by `compute_layout_wasm`
+    #[wasm_bindgen(constructor)]
+    pub fn new(layout: JsValue) -> Result<LayoutIndexWasm, JsValue> {
+        let layout: GlobalLayout = serde_wasm_bindgen::from_value(layout)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse layout: {}", e)))?;
+        Ok(Self {
+            inner: LayoutIndex::new(&layout),
+        })
+    }
+
+    /// Nearest note or rest to (x, y), or undefined
+    pub fn hit_test(&self, x: f32, y: f32) -> Result<JsValue, JsValue> {
+        to_js(&self.inner.hit_test(Point { x, y }))
+    }
+
+    /// Playback cursor position at a tick, or undefined
+    pub fn tick_position(&self, tick: u32) -> Result<JsValue, JsValue> {
+        to_js(&self.inner.tick_position(tick))
+    }
+
+    /// Regions covered by the ticks [start_tick, end_tick), one per system
+    pub fn range_regions(&self, start_tick: u32, end_tick: u32) -> Result<JsValue, JsValue> {
+        to_js(&self.inner.range_regions(start_tick, end_tick))
+    }
+}
+
+fn parse_score(score_json: &str) -> Result<Value, JsValue> {
+    serde_json::from_str(score_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse score JSON: {}", e)))
+}
+
+fn parse_config(config_json: &str) -> Result<LayoutConfig, JsValue> {
+    if config_json.is_empty() {
+        Ok(LayoutConfig::default())
+    } else {
+        serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse config JSON: {}", e)))
+    }
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(value)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize result: {}", e)))
+}
+
 /// WASM-compatible version of LayoutConfig for TypeScript bindings
 ///
 /// Exists as a separate type to provide cleaner TypeScript interface
@@ -53,6 +194,11 @@ pub struct LayoutConfigWasm {
     units_per_space: f32,
     system_spacing: f32,
     system_height: f32,
+    page: Option<PageConfig>,
+    line_breaking: LineBreaking,
+    engraving: EngravingStyle,
+    instrument_names: InstrumentNames,
+    measure_numbers: MeasureNumbering,
 }
 
 #[wasm_bindgen]
@@ -66,6 +212,11 @@ impl LayoutConfigWasm {
             units_per_space: defaults.units_per_space,
             system_spacing: defaults.system_spacing,
             system_height: defaults.system_height,
+            page: defaults.page,
+            line_breaking: defaults.line_breaking,
+            engraving: defaults.engraving,
+            instrument_names: defaults.instrument_names,
+            measure_numbers: defaults.measure_numbers,
         }
     }
 
@@ -117,6 +268,222 @@ impl LayoutConfigWasm {
         self.system_height = value;
     }
 
+    /// Paginate with a PageConfig given as JSON string (page size, margins,
+    /// staff size, header, footer); an empty string lays out one continuous
+    /// column of systems
+    pub fn set_page(&mut self, page_json: &str) -> Result<(), JsValue> {
+        self.page = if page_json.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::from_str(page_json)
+                    .map_err(|e| JsValue::from_str(&format!("Failed to parse page JSON: {}", e)))?,
+            )
+        };
+        Ok(())
+    }
+
+    /// Choose how measures are distributed onto systems: "greedy",
+    /// "total-fit" or "single-line"
+    pub fn set_line_breaking(&mut self, mode: &str) -> Result<(), JsValue> {
+        self.line_breaking = match mode {
+            "greedy" => LineBreaking::Greedy,
+            "total-fit" => LineBreaking::TotalFit {
+                target_measures_per_system: None,
+                ragged_last_system: false,
+            },
+            "single-line" => LineBreaking::SingleLine {
+                sticky_interval: LineBreaking::DEFAULT_STICKY_INTERVAL,
+            },
+            _ => {
+                return Err(JsValue::from_str(&format!(
+                    "Unknown line breaking mode: {}",
+                    mode
+                )));
+            }
+        };
+        Ok(())
+    }
+
+    /// Total-fit system breaking with a preferred number of measures per
+    /// system (if any), optionally leaving the last system unjustified
+    pub fn set_total_fit(
+        &mut self,
+        target_measures_per_system: Option<u32>,
+        ragged_last_system: bool,
+    ) {
+        self.line_breaking = LineBreaking::TotalFit {
+            target_measures_per_system,
+            ragged_last_system,
+        };
+    }
+
+    /// Single-line layout for horizontal scrolling, with clef/key signature
+    /// entries every `sticky_interval` logical units
+    pub fn set_single_line(&mut self, sticky_interval: f32) {
+        self.line_breaking = LineBreaking::SingleLine { sticky_interval };
+    }
+
+    /// Apply an engraving style preset: "compact", "default" or "spacious"
+    /// ("beginner")
+    pub fn set_engraving_preset(&mut self, name: &str) -> Result<(), JsValue> {
+        self.engraving = EngravingStyle::preset(name)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown engraving preset: {}", name)))?;
+        Ok(())
+    }
+
+    /// Choose the instrument names printed left of the systems: "full",
+    /// "full-then-short" or "none"
+    pub fn set_instrument_names(&mut self, mode: &str) -> Result<(), JsValue> {
+        self.instrument_names = match mode {
+            "full" => InstrumentNames::Full,
+            "full-then-short" => InstrumentNames::FullThenShort,
+            "none" => InstrumentNames::Hidden,
+            _ => {
+                return Err(JsValue::from_str(&format!(
+                    "Unknown instrument name mode: {}",
+                    mode
+                )));
+            }
+        };
+        Ok(())
+    }
+
+    /// Choose which measures are numbered: "system-start", "every-measure",
+    /// "every-N" (e.g. "every-5") or "none"
+    pub fn set_measure_numbers(&mut self, placement: &str) -> Result<(), JsValue> {
+        self.measure_numbers.placement = match placement {
+            "system-start" => MeasureNumberPlacement::SystemStart,
+            "every-measure" => MeasureNumberPlacement::EveryMeasure,
+            "none" => MeasureNumberPlacement::Hidden,
+            _ => match placement
+                .strip_prefix("every-")
+                .and_then(|n| n.parse::<u32>().ok())
+            {
+                Some(every) if every > 0 => MeasureNumberPlacement::Interval { every },
+                _ => {
+                    return Err(JsValue::from_str(&format!(
+                        "Unknown measure number placement: {}",
+                        placement
+                    )));
+                }
+            },
+        };
+        Ok(())
+    }
+
+    /// Frame measure numbers: "none", "rectangle" or "circle"
+    pub fn set_measure_number_enclosure(&mut self, enclosure: &str) -> Result<(), JsValue> {
+        self.measure_numbers.enclosure = match enclosure {
+            "none" => Enclosure::None,
+            "rectangle" => Enclosure::Rectangle,
+            "circle" => Enclosure::Circle,
+            _ => {
+                return Err(JsValue::from_str(&format!(
+                    "Unknown measure number enclosure: {}",
+                    enclosure
+                )));
+            }
+        };
+        Ok(())
+    }
+
+    /// Get base note spacing
+    #[wasm_bindgen(getter)]
+    pub fn base_spacing(&self) -> f32 {
+        self.engraving.spacing.base_spacing
+    }
+
+    /// Set base note spacing
+    #[wasm_bindgen(setter)]
+    pub fn set_base_spacing(&mut self, value: f32) {
+        self.engraving.spacing.base_spacing = value;
+    }
+
+    /// Get duration spacing factor
+    #[wasm_bindgen(getter)]
+    pub fn duration_factor(&self) -> f32 {
+        self.engraving.spacing.duration_factor
+    }
+
+    /// Set duration spacing factor
+    #[wasm_bindgen(setter)]
+    pub fn set_duration_factor(&mut self, value: f32) {
+        self.engraving.spacing.duration_factor = value;
+    }
+
+    /// Get minimum note spacing
+    #[wasm_bindgen(getter)]
+    pub fn minimum_spacing(&self) -> f32 {
+        self.engraving.spacing.minimum_spacing
+    }
+
+    /// Set minimum note spacing
+    #[wasm_bindgen(setter)]
+    pub fn set_minimum_spacing(&mut self, value: f32) {
+        self.engraving.spacing.minimum_spacing = value;
+    }
+
+    /// Get staff distance (staff spaces)
+    #[wasm_bindgen(getter)]
+    pub fn staff_distance(&self) -> f32 {
+        self.engraving.staff_distance
+    }
+
+    /// Set staff distance (staff spaces)
+    #[wasm_bindgen(setter)]
+    pub fn set_staff_distance(&mut self, value: f32) {
+        self.engraving.staff_distance = value;
+    }
+
+    /// Get instrument distance (staff spaces)
+    #[wasm_bindgen(getter)]
+    pub fn instrument_distance(&self) -> f32 {
+        self.engraving.instrument_distance
+    }
+
+    /// Set instrument distance (staff spaces)
+    #[wasm_bindgen(setter)]
+    pub fn set_instrument_distance(&mut self, value: f32) {
+        self.engraving.instrument_distance = value;
+    }
+
+    /// Get stem length (staff spaces)
+    #[wasm_bindgen(getter)]
+    pub fn stem_length(&self) -> f32 {
+        self.engraving.stem_length
+    }
+
+    /// Set stem length (staff spaces)
+    #[wasm_bindgen(setter)]
+    pub fn set_stem_length(&mut self, value: f32) {
+        self.engraving.stem_length = value;
+    }
+
+    /// Get minimum beamed stem length (staff spaces)
+    #[wasm_bindgen(getter)]
+    pub fn min_beamed_stem_length(&self) -> f32 {
+        self.engraving.min_beamed_stem_length
+    }
+
+    /// Set minimum beamed stem length (staff spaces)
+    #[wasm_bindgen(setter)]
+    pub fn set_min_beamed_stem_length(&mut self, value: f32) {
+        self.engraving.min_beamed_stem_length = value;
+    }
+
+    /// Get maximum beam slope (staff spaces)
+    #[wasm_bindgen(getter)]
+    pub fn max_beam_slope(&self) -> f32 {
+        self.engraving.max_beam_slope
+    }
+
+    /// Set maximum beam slope (staff spaces)
+    #[wasm_bindgen(setter)]
+    pub fn set_max_beam_slope(&mut self, value: f32) {
+        self.engraving.max_beam_slope = value;
+    }
+
     /// Convert to JSON string
     pub fn to_json(&self) -> String {
         let config = LayoutConfig {
@@ -124,6 +491,11 @@ impl LayoutConfigWasm {
             units_per_space: self.units_per_space,
             system_spacing: self.system_spacing,
             system_height: self.system_height,
+            page: self.page.clone(),
+            line_breaking: self.line_breaking,
+            engraving: self.engraving.clone(),
+            instrument_names: self.instrument_names,
+            measure_numbers: self.measure_numbers,
         };
         serde_json::to_string(&config).unwrap_or_default()
     }
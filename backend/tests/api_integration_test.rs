@@ -115,6 +115,44 @@ async fn test_get_nonexistent_score() {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_render_score_svg() {
+    let app = setup_app().await;
+
+    let (_, create_body) =
+        make_request(app.clone(), "POST", "/api/v1/scores", Some(json!({}))).await;
+    let score_id = create_body["id"].as_str().unwrap();
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/v1/scores/{}/render.svg", score_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/svg+xml");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let svg = String::from_utf8(body.to_vec()).unwrap();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[tokio::test]
+async fn test_render_nonexistent_score_svg() {
+    let app = setup_app().await;
+
+    let (status, _body) = make_request(
+        app,
+        "GET",
+        "/api/v1/scores/550e8400-e29b-41d4-a716-446655440000/render.svg",
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 #[tokio::test]
 async fn test_delete_score() {
     let app = setup_app().await;
@@ -977,3 +1015,155 @@ async fn test_import_musicxml_compressed_mxl() {
         "Should have 8 notes from compressed file"
     );
 }
+
+// ===== Instrument Catalog Endpoint Tests =====
+
+#[tokio::test]
+async fn test_list_instruments() {
+    let app = setup_app().await;
+
+    let (status, body) = make_request(app, "GET", "/api/v1/instruments", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let instruments = body["instruments"].as_array().unwrap();
+    let violin = instruments.iter().find(|i| i["id"] == "violin").unwrap();
+    assert_eq!(violin["short_name"], "Vln.");
+    assert_eq!(violin["family"], "Strings");
+    assert_eq!(violin["midi_program"], 41);
+    assert!(violin.get("keywords").is_none());
+}
+
+#[tokio::test]
+async fn test_get_instrument() {
+    let app = setup_app().await;
+
+    let (status, body) =
+        make_request(app.clone(), "GET", "/api/v1/instruments/clarinet", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["transposition"], -2);
+    assert_eq!(body["clefs"], json!(["Treble"]));
+
+    let (status, _body) = make_request(app, "GET", "/api/v1/instruments/theremin", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ===== Lint Endpoint Tests =====
+
+#[tokio::test]
+async fn test_lint_score() {
+    let app = setup_app().await;
+
+    let (_, score_body) =
+        make_request(app.clone(), "POST", "/api/v1/scores", Some(json!({}))).await;
+    let score_id = score_body["id"].as_str().unwrap();
+    let lint_uri = format!("/api/v1/scores/{}/lint", score_id);
+
+    let (status, body) = make_request(app.clone(), "GET", &lint_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["findings"], json!([]));
+
+    // A lone half note leaves the rest of the measure empty
+    let (_, inst_body) = make_request(
+        app.clone(),
+        "POST",
+        &format!("/api/v1/scores/{}/instruments", score_id),
+        Some(json!({"name": "Piano"})),
+    )
+    .await;
+    let instrument_id = inst_body["id"].as_str().unwrap();
+    let staff_id = inst_body["staves"][0]["id"].as_str().unwrap();
+    let voice_id = inst_body["staves"][0]["voices"][0]["id"].as_str().unwrap();
+    make_request(
+        app.clone(),
+        "POST",
+        &format!(
+            "/api/v1/scores/{}/instruments/{}/staves/{}/voices/{}/notes",
+            score_id, instrument_id, staff_id, voice_id
+        ),
+        Some(json!({"start_tick": 0, "duration_ticks": 1920, "pitch": 60})),
+    )
+    .await;
+
+    let (status, body) = make_request(app.clone(), "GET", &lint_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let findings = body["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["rule"], "underfilled_measure");
+    assert_eq!(findings[0]["severity"], "warning");
+    assert_eq!(
+        findings[0]["location"],
+        json!({"instrument": 0, "staff": 0, "voice": 0, "measure": 0, "tick": 1920})
+    );
+
+    let (status, _body) = make_request(
+        app,
+        "GET",
+        "/api/v1/scores/550e8400-e29b-41d4-a716-446655440000/lint",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_fill_rests() {
+    let app = setup_app().await;
+
+    let (_, score_body) =
+        make_request(app.clone(), "POST", "/api/v1/scores", Some(json!({}))).await;
+    let score_id = score_body["id"].as_str().unwrap();
+    let (_, inst_body) = make_request(
+        app.clone(),
+        "POST",
+        &format!("/api/v1/scores/{}/instruments", score_id),
+        Some(json!({"name": "Piano"})),
+    )
+    .await;
+    let instrument_id = inst_body["id"].as_str().unwrap();
+    let staff_id = inst_body["staves"][0]["id"].as_str().unwrap();
+    let voice_id = inst_body["staves"][0]["voices"][0]["id"].as_str().unwrap();
+    make_request(
+        app.clone(),
+        "POST",
+        &format!(
+            "/api/v1/scores/{}/instruments/{}/staves/{}/voices/{}/notes",
+            score_id, instrument_id, staff_id, voice_id
+        ),
+        Some(json!({"start_tick": 0, "duration_ticks": 960, "pitch": 60})),
+    )
+    .await;
+
+    let (status, body) = make_request(
+        app.clone(),
+        "POST",
+        &format!("/api/v1/scores/{}/fill-rests", score_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let rests = body["instruments"][0]["staves"][0]["voices"][0]["rest_events"]
+        .as_array()
+        .unwrap();
+    assert_eq!(rests.len(), 2);
+    assert_eq!(rests[0]["note_type"], "quarter");
+    assert_eq!(rests[1]["note_type"], "half");
+
+    // The filled score is saved
+    let (_, body) = make_request(
+        app.clone(),
+        "GET",
+        &format!("/api/v1/scores/{}/lint", score_id),
+        None,
+    )
+    .await;
+    assert_eq!(body["findings"], json!([]));
+
+    let (status, _body) = make_request(
+        app,
+        "POST",
+        "/api/v1/scores/550e8400-e29b-41d4-a716-446655440000/fill-rests",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -36,7 +36,7 @@ fn test_bach_cross_system_incoming_tie_arcs() {
         for system in &layout.systems {
             let sys_width = system.bounding_box.width;
             for staff_group in &system.staff_groups {
-                for (_staff_idx, staff) in staff_group.staves.iter().enumerate() {
+                for staff in staff_group.staves.iter() {
                     for arc in &staff.tie_arcs {
                         let is_outgoing = (arc.end.x - sys_width).abs() < 1.0;
                         let is_incoming = arc.start.x < 250.0;
@@ -0,0 +1,106 @@
+//! Integration tests for the binary layout encoding: decoding an encoded
+//! layout gives back the same layout, and the encoding is smaller than JSON.
+
+mod common;
+
+use common::{import_file, layout, layout_with};
+use musicore_backend::layout::binary::{LAYOUT_FORMAT_VERSION, LAYOUT_MAGIC};
+use musicore_backend::layout::{
+    GlobalLayout, LayoutConfig, LayoutDecodeError, PageConfig, compute_layout, decode_layout,
+    encode_layout,
+};
+
+fn assert_round_trip(layout: &GlobalLayout) {
+    let decoded = decode_layout(&encode_layout(layout)).expect("decode failed");
+    assert_eq!(
+        serde_json::to_string(&decoded).unwrap(),
+        serde_json::to_string(layout).unwrap()
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let paged = LayoutConfig {
+        page: Some(PageConfig::default()),
+        ..LayoutConfig::default()
+    };
+    for name in [
+        "Chopin_NocturneOp9No2.mxl",
+        "Bach_InventionNo1.mxl",
+        "Pachelbel_CanonD.mxl",
+    ] {
+        assert_round_trip(&layout(&import_file(name)));
+    }
+    assert_round_trip(&layout_with(
+        &import_file("Chopin_NocturneOp9No2.mxl"),
+        &paged,
+    ));
+    assert_round_trip(&compute_layout(
+        &serde_json::json!({ "instruments": [] }),
+        &LayoutConfig::default(),
+    ));
+}
+
+#[test]
+fn test_glyphs_keep_full_precision() {
+    let layout = layout(&import_file("Bach_InventionNo1.mxl"));
+    let decoded = decode_layout(&encode_layout(&layout)).unwrap();
+    let glyphs = |l: &GlobalLayout| -> Vec<(f32, f32)> {
+        l.systems
+            .iter()
+            .flat_map(|s| &s.staff_groups)
+            .flat_map(|g| &g.staves)
+            .flat_map(|s| &s.glyph_runs)
+            .flat_map(|r| &r.glyphs)
+            .map(|g| (g.position.x, g.bounding_box.height))
+            .collect()
+    };
+    assert!(!glyphs(&layout).is_empty());
+    assert_eq!(glyphs(&decoded), glyphs(&layout));
+}
+
+#[test]
+fn test_encoding_is_smaller_than_json() {
+    let layout = layout(&import_file("Chopin_NocturneOp9No2.mxl"));
+    let binary = encode_layout(&layout).len();
+    let json = serde_json::to_vec(&layout).unwrap().len();
+    assert!(
+        binary * 2 < json,
+        "binary {binary} bytes, JSON {json} bytes"
+    );
+}
+
+#[test]
+fn test_header_and_alignment() {
+    let bytes = encode_layout(&layout(&import_file("Bach_InventionNo1.mxl")));
+    assert_eq!(bytes[..4], LAYOUT_MAGIC);
+    assert_eq!(
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        LAYOUT_FORMAT_VERSION
+    );
+    assert_eq!(bytes.len() % 4, 0);
+}
+
+#[test]
+fn test_rejects_invalid_buffers() {
+    let bytes = encode_layout(&layout(&import_file("Bach_InventionNo1.mxl")));
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = b'X';
+    assert!(matches!(
+        decode_layout(&bad_magic),
+        Err(LayoutDecodeError::BadMagic)
+    ));
+
+    let mut future = bytes.clone();
+    future[4] = 99;
+    assert!(matches!(
+        decode_layout(&future),
+        Err(LayoutDecodeError::UnsupportedVersion(99))
+    ));
+
+    assert!(matches!(
+        decode_layout(&bytes[..bytes.len() / 2]),
+        Err(LayoutDecodeError::Truncated { .. })
+    ));
+}
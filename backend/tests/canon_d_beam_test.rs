@@ -21,6 +21,7 @@ fn layout_canon_d() -> serde_json::Value {
         units_per_space: 20.0,
         system_spacing: 200.0,
         system_height: 200.0,
+        ..LayoutConfig::default()
     };
     let layout = compute_layout(&json, &config);
     serde_json::to_value(&layout).unwrap()
@@ -0,0 +1,120 @@
+//! Integration tests for chord symbol (`<harmony>`) import and layout.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::events::chord_symbol::ChordKind;
+use musicore_backend::domain::value_objects::{NoteSpelling, Tick};
+
+const LEAD_SHEET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list>
+    <score-part id="P1"><part-name>Lead</part-name></score-part>
+  </part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>2</divisions>
+        <key><fifths>-1</fifths></key>
+        <time><beats>4</beats><beat-type>4</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+      </attributes>
+      <harmony>
+        <root><root-step>F</root-step></root>
+        <kind text="maj7">major-seventh</kind>
+      </harmony>
+      <note><pitch><step>A</step><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>half</type></note>
+      <harmony>
+        <root><root-step>B</root-step><root-alter>-1</root-alter></root>
+        <kind>dominant</kind>
+        <bass><bass-step>D</bass-step></bass>
+        <degree><degree-value>9</degree-value><degree-alter>-1</degree-alter><degree-type>add</degree-type></degree>
+        <offset>2</offset>
+      </harmony>
+      <note><pitch><step>B</step><alter>-1</alter><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>half</type></note>
+    </measure>
+    <measure number="2">
+      <harmony print-object="no">
+        <root><root-step>C</root-step></root>
+        <kind>major</kind>
+      </harmony>
+      <harmony>
+        <kind>none</kind>
+      </harmony>
+      <note><rest/><duration>8</duration><voice>1</voice><type>whole</type></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+fn import_lead_sheet() -> musicore_backend::domain::score::Score {
+    import(LEAD_SHEET)
+}
+
+#[test]
+fn test_import_harmony_to_chord_symbols() {
+    let score = import_lead_sheet();
+    let chords = &score.instruments[0].staves[0].chord_symbols;
+
+    // print-object="no" harmony is dropped
+    assert_eq!(chords.len(), 3);
+
+    assert_eq!(chords[0].tick, Tick::new(0));
+    assert_eq!(chords[0].kind, ChordKind::MajorSeventh);
+    assert_eq!(chords[0].kind_text.as_deref(), Some("maj7"));
+
+    // Second harmony: after the half note (1920 ticks) plus an offset of 2 divisions (960 ticks)
+    assert_eq!(chords[1].tick, Tick::new(1920 + 960));
+    assert_eq!(
+        chords[1].root,
+        Some(NoteSpelling {
+            step: 'B',
+            alter: -1
+        })
+    );
+    assert_eq!(chords[1].format().to_string(), "B♭7add♭9/D");
+
+    assert_eq!(chords[2].tick, Tick::new(3840));
+    assert_eq!(chords[2].kind, ChordKind::None);
+    assert_eq!(chords[2].format().to_string(), "N.C.");
+}
+
+#[test]
+fn test_chord_symbols_survive_dto_and_layout() {
+    let score = import_lead_sheet();
+    let dto = ScoreDto::from(&score);
+    let json = serde_json::to_value(&dto).expect("DTO serialization failed");
+    assert_eq!(
+        json["instruments"][0]["staves"][0]["chord_symbols"]
+            .as_array()
+            .map(|a| a.len()),
+        Some(3)
+    );
+
+    let layout = layout(&score);
+    let texts: Vec<String> = layout
+        .systems
+        .iter()
+        .flat_map(|s| s.staff_groups.iter())
+        .flat_map(|g| g.staves.iter())
+        .flat_map(|st| st.chord_symbols.iter())
+        .map(|c| c.text.clone())
+        .collect();
+    assert_eq!(texts, vec!["Fmaj7", "B♭7add♭9/D", "N.C."]);
+}
+
+#[test]
+fn test_scores_without_harmony_have_no_chord_symbols() {
+    let score = import(
+        &LEAD_SHEET
+            .lines()
+            .filter(|l| !l.contains("harmony") && !l.contains("<root>") && !l.contains("kind"))
+            .filter(|l| !l.contains("<bass>") && !l.contains("<degree>") && !l.contains("offset"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    assert!(score.instruments[0].staves[0].chord_symbols.is_empty());
+
+    let json = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert!(json["instruments"][0]["staves"][0]["chord_symbols"].is_null());
+}
@@ -19,7 +19,7 @@ fn check_clef_events() {
     // Print measure_end_ticks for m8-m16
     eprintln!("pickup_ticks={}", score.pickup_ticks);
     for (i, &end_tick) in score.measure_end_ticks.iter().enumerate() {
-        if i >= 7 && i <= 16 {
+        if (7..=16).contains(&i) {
             eprintln!("measure_end_ticks[{i}] = {end_tick}");
         }
     }
@@ -50,7 +50,7 @@ fn check_beam_info_m14_m15() {
     for voice in &staff.voices {
         for note in &voice.interval_events {
             let tick = note.start_tick.value();
-            if tick >= 17000 && tick < 22000 {
+            if (17000..22000).contains(&tick) {
                 eprintln!(
                     "LH note tick={tick} pitch={:?} dur={} beams={:?}",
                     note.pitch, note.duration_ticks, note.beams
@@ -82,7 +82,7 @@ fn check_lh_rests_m15_m18() {
     for (vi, voice) in staff.voices.iter().enumerate() {
         for note in &voice.interval_events {
             let tick = note.start_tick.value();
-            if tick >= 20000 && tick < 25000 {
+            if (20000..25000).contains(&tick) {
                 eprintln!(
                     "  voice={vi} NOTE tick={tick} pitch={:?} dur={}",
                     note.pitch, note.duration_ticks
@@ -91,7 +91,7 @@ fn check_lh_rests_m15_m18() {
         }
         for rest in &voice.rest_events {
             let tick = rest.start_tick.value();
-            if tick >= 20000 && tick < 25000 {
+            if (20000..25000).contains(&tick) {
                 eprintln!("  voice={vi} REST tick={tick} dur={}", rest.duration_ticks);
             }
         }
@@ -100,7 +100,7 @@ fn check_lh_rests_m15_m18() {
     for event in &staff.staff_structural_events {
         if let musicore_backend::domain::events::staff::StaffStructuralEvent::Clef(ce) = event {
             let t = ce.tick.value();
-            if t >= 20000 && t <= 25000 {
+            if (20000..=25000).contains(&t) {
                 eprintln!("  CLEF tick={t} clef={:?}", ce.clef);
             }
         }
@@ -132,7 +132,7 @@ fn check_system_boundaries_m16_m18() {
                     for glyph in &staff.structural_glyphs {
                         let cp_val = glyph.codepoint.chars().next().unwrap_or('\0') as u32;
                         // SMuFL clefs range: E050-E07F
-                        if cp_val >= 0xE050 && cp_val <= 0xE07F {
+                        if (0xE050..=0xE07F).contains(&cp_val) {
                             eprintln!(
                                 "  sg={sg_i} st={st_i} STRUCTURAL CLEF U+{:04X} x={:.1} y={:.1} font_size={:?}",
                                 cp_val, glyph.position.x, glyph.position.y, glyph.font_size,
@@ -143,7 +143,7 @@ fn check_system_boundaries_m16_m18() {
                     for run in &staff.glyph_runs {
                         for glyph in &run.glyphs {
                             let cp_val = glyph.codepoint.chars().next().unwrap_or('\0') as u32;
-                            if cp_val >= 0xE050 && cp_val <= 0xE07F {
+                            if (0xE050..=0xE07F).contains(&cp_val) {
                                 eprintln!(
                                     "  sg={sg_i} st={st_i} GLYPH_RUN CLEF U+{:04X} x={:.1} y={:.1} font_size={:?}",
                                     cp_val, glyph.position.x, glyph.position.y, glyph.font_size,
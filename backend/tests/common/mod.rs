@@ -0,0 +1,39 @@
+//! Helpers shared by the integration tests: import a MusicXML document or
+//! one of the scores in `../scores`, and lay out the resulting score.
+
+#![allow(dead_code)]
+
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::importers::musicxml::{
+    ImportContext, MusicXMLConverter, MusicXMLImporter, MusicXMLParser,
+};
+use musicore_backend::domain::score::Score;
+use musicore_backend::layout::{GlobalLayout, LayoutConfig, compute_layout};
+use musicore_backend::ports::importers::IMusicXMLImporter;
+use std::path::Path;
+
+/// Score imported from a MusicXML document
+pub fn import(xml: &str) -> Score {
+    let mut context = ImportContext::new();
+    let doc = MusicXMLParser::parse(xml, &mut context).expect("Failed to parse");
+    MusicXMLConverter::convert(doc, &mut context).expect("Failed to convert")
+}
+
+/// Score imported from a file in `../scores`
+pub fn import_file(name: &str) -> Score {
+    MusicXMLImporter::new()
+        .import_file(Path::new(&format!("../scores/{name}")))
+        .expect("Failed to import")
+        .score
+}
+
+/// Layout of a score with the default configuration
+pub fn layout(score: &Score) -> GlobalLayout {
+    layout_with(score, &LayoutConfig::default())
+}
+
+/// Layout of a score with the given configuration
+pub fn layout_with(score: &Score, config: &LayoutConfig) -> GlobalLayout {
+    let json = serde_json::to_value(ScoreDto::from(score)).expect("DTO serialization failed");
+    compute_layout(&json, config)
+}
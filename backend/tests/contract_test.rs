@@ -41,6 +41,7 @@ fn test_violin_fixture_contract() {
         units_per_space: 20.0,
         system_spacing: 220.0,
         system_height: 200.0,
+        ..musicore_backend::layout::LayoutConfig::default()
     };
 
     // T007: Assert output structure matches fixture (WILL FAIL - empty staff_groups)
@@ -164,6 +165,7 @@ fn test_piano_fixture_contract() {
         units_per_space: 20.0,
         system_spacing: 220.0,
         system_height: 200.0,
+        ..musicore_backend::layout::LayoutConfig::default()
     };
 
     let actual_output = musicore_backend::layout::compute_layout(&test_input, &config);
@@ -13,6 +13,7 @@ const CONFIG: LayoutConfig = LayoutConfig {
     units_per_space: 20.0,
     system_spacing: 200.0,
     system_height: 200.0,
+    ..LayoutConfig::DEFAULT
 };
 
 fn layout_score(mxl_path: &str) -> serde_json::Value {
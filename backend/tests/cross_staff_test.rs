@@ -0,0 +1,149 @@
+//! Integration tests for cross-staff notes and cross-staff beaming.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::layout::Staff;
+
+/// Piano measure: voice 1 plays four beamed eighths, the last two written on
+/// the bass staff; a second pass (after <backup>) reuses voice 1 for a
+/// regular bass-staff half note.
+const CROSS_STAFF: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Piano</part-name></score-part></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>2</divisions>
+        <time><beats>2</beats><beat-type>4</beat-type></time>
+        <staves>2</staves>
+        <clef number="1"><sign>G</sign><line>2</line></clef>
+        <clef number="2"><sign>F</sign><line>4</line></clef>
+      </attributes>
+      <note><pitch><step>E</step><octave>4</octave></pitch><duration>1</duration><voice>1</voice><type>eighth</type><staff>1</staff><beam number="1">begin</beam></note>
+      <note><pitch><step>C</step><octave>4</octave></pitch><duration>1</duration><voice>1</voice><type>eighth</type><staff>1</staff><beam number="1">continue</beam></note>
+      <note><pitch><step>G</step><octave>3</octave></pitch><duration>1</duration><voice>1</voice><type>eighth</type><staff>2</staff><beam number="1">continue</beam></note>
+      <note><pitch><step>E</step><octave>3</octave></pitch><duration>1</duration><voice>1</voice><type>eighth</type><staff>2</staff><beam number="1">end</beam></note>
+      <backup><duration>4</duration></backup>
+      <note><pitch><step>C</step><octave>3</octave></pitch><duration>4</duration><voice>1</voice><type>half</type><staff>2</staff></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+fn pseudo_glyphs<'a>(
+    staff: &'a Staff,
+    codepoint: &'a str,
+) -> Vec<&'a musicore_backend::layout::Glyph> {
+    staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| run.glyphs.iter())
+        .filter(|g| g.codepoint == codepoint)
+        .collect()
+}
+
+#[test]
+fn test_cross_staff_notes_stay_in_their_voice() {
+    let score = import(CROSS_STAFF);
+    let treble = &score.instruments[0].staves[0];
+    let bass = &score.instruments[0].staves[1];
+
+    let voice_notes = &treble.voices[0].interval_events;
+    let ticks: Vec<u32> = voice_notes.iter().map(|n| n.start_tick.value()).collect();
+    assert_eq!(ticks, vec![0, 480, 960, 1440]);
+    let cross: Vec<Option<u8>> = voice_notes.iter().map(|n| n.cross_staff).collect();
+    assert_eq!(cross, vec![None, None, Some(1), Some(1)]);
+
+    // The bass staff only holds the regular bass note from the second pass
+    let bass_notes: Vec<_> = bass
+        .voices
+        .iter()
+        .flat_map(|v| v.interval_events.iter())
+        .collect();
+    assert_eq!(bass_notes.len(), 1);
+    assert_eq!(bass_notes[0].start_tick.value(), 0);
+    assert_eq!(bass_notes[0].cross_staff, None);
+}
+
+#[test]
+fn test_cross_staff_noteheads_drawn_on_target_staff() {
+    let layout = layout(&import(CROSS_STAFF));
+    let group = &layout.systems[0].staff_groups[0];
+    let (treble, bass) = (&group.staves[0], &group.staves[1]);
+    let bass_top = bass.staff_lines[0].y_position;
+    let bass_bottom = bass.staff_lines[4].y_position;
+    let treble_bottom = treble.staff_lines[4].y_position;
+
+    // Noteheads of the treble voice's cross-staff notes (event indices 2 and 3)
+    let cross_heads: Vec<f32> = treble
+        .glyph_runs
+        .iter()
+        .flat_map(|run| run.glyphs.iter())
+        .filter(|g| g.codepoint != "\u{0000}" && g.codepoint != "\u{0001}")
+        .filter(|g| g.source_reference.event_index >= 2)
+        .map(|g| g.position.y)
+        .collect();
+    assert!(!cross_heads.is_empty());
+    for y in cross_heads {
+        assert!(
+            y > treble_bottom && y >= bass_top - 1.0 && y <= bass_bottom + 1.0,
+            "cross-staff notehead at y={y} should sit on the bass staff ({bass_top}..{bass_bottom})"
+        );
+    }
+
+    // No ledger lines are drawn between the staves for the cross-staff notes
+    assert!(
+        treble
+            .ledger_lines
+            .iter()
+            .all(|l| l.y_position < treble_bottom + 40.0),
+        "cross-staff notes must not produce treble ledger lines"
+    );
+}
+
+#[test]
+fn test_cross_staff_beam_between_staves() {
+    let layout = layout(&import(CROSS_STAFF));
+    let group = &layout.systems[0].staff_groups[0];
+    let (treble, bass) = (&group.staves[0], &group.staves[1]);
+    let treble_bottom = treble.staff_lines[4].y_position;
+    let bass_top = bass.staff_lines[0].y_position;
+
+    let beams = pseudo_glyphs(treble, "\u{0001}");
+    assert!(!beams.is_empty(), "cross-staff group should be beamed");
+    for beam in &beams {
+        assert!(
+            beam.position.y > treble_bottom - 20.0 && beam.position.y < bass_top + 20.0,
+            "beam at y={} should lie between the staves",
+            beam.position.y
+        );
+    }
+
+    // Upper-staff stems hang down to the beam; lower-staff stems reach up to it
+    let stems = pseudo_glyphs(treble, "\u{0000}");
+    assert_eq!(stems.len(), 4);
+    let beam_y = beams[0].position.y;
+    for stem in &stems {
+        let top = stem.bounding_box.y;
+        let bottom = top + stem.bounding_box.height;
+        if stem.source_reference.event_index < 2 {
+            assert!((bottom - beam_y).abs() < 1.0, "upper stem ends at the beam");
+        } else {
+            assert!((top - beam_y).abs() < 1.0, "lower stem starts at the beam");
+        }
+    }
+    // Stem-down stems sit left of their notehead, stem-up stems right
+    let down_x = stems
+        .iter()
+        .find(|s| s.source_reference.event_index == 0)
+        .unwrap()
+        .position
+        .x;
+    let up_x = stems
+        .iter()
+        .find(|s| s.source_reference.event_index == 2)
+        .unwrap()
+        .position
+        .x;
+    assert!(down_x < up_x);
+}
@@ -9,6 +9,7 @@ const CONFIG: LayoutConfig = LayoutConfig {
     units_per_space: 20.0,
     system_spacing: 200.0,
     system_height: 200.0,
+    ..LayoutConfig::DEFAULT
 };
 
 #[test]
@@ -0,0 +1,138 @@
+//! Integration tests for rehearsal marks, tempo marks and text directions:
+//! import and collision-free layout around ottava and volta brackets.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::domain::events::direction::{
+    Enclosure, Placement, RehearsalMark, TextDirectionStyle,
+};
+use musicore_backend::layout::DirectionKind;
+
+/// Measure 1: rehearsal "A", "Allegro ♩ = 132" over an 8va passage, "dolce"
+/// below. Measure 2: circled rehearsal "B" and "rit." under a first ending.
+const DIRECTIONS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Flute</part-name></score-part></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>1</divisions>
+        <time><beats>4</beats><beat-type>4</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+      </attributes>
+      <direction placement="above"><direction-type><rehearsal>A</rehearsal></direction-type></direction>
+      <direction placement="above">
+        <direction-type><words>Allegro</words></direction-type>
+        <direction-type><metronome><beat-unit>quarter</beat-unit><per-minute>132</per-minute></metronome></direction-type>
+        <sound tempo="132"/>
+      </direction>
+      <direction placement="below"><direction-type><words>dolce</words></direction-type></direction>
+      <direction placement="above"><direction-type><octave-shift type="down" size="8"/></direction-type></direction>
+      <note><pitch><step>A</step><octave>6</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+      <note><pitch><step>B</step><octave>6</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+      <note><pitch><step>C</step><octave>7</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+      <note><pitch><step>B</step><octave>6</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+      <direction><direction-type><octave-shift type="stop" size="8"/></direction-type></direction>
+    </measure>
+    <measure number="2">
+      <barline location="left"><ending number="1" type="start"/></barline>
+      <direction placement="above"><direction-type><rehearsal enclosure="circle">B</rehearsal></direction-type></direction>
+      <note><pitch><step>G</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>half</type></note>
+      <direction placement="above"><direction-type><words>rit.</words></direction-type></direction>
+      <note><pitch><step>E</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>half</type></note>
+      <barline location="right"><ending number="1" type="stop"/></barline>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+#[test]
+fn test_import_rehearsal_marks_tempo_mark_and_text() {
+    let score = import(DIRECTIONS);
+
+    let mut circled = RehearsalMark::new(3840, "B");
+    circled.enclosure = Enclosure::Circle;
+    assert_eq!(
+        score.rehearsal_marks,
+        vec![RehearsalMark::new(0, "A"), circled]
+    );
+
+    // Words printed with a metronome mark belong to the tempo mark
+    let tempo = &score.tempo_events()[0];
+    let mark = tempo.mark.as_ref().expect("metronome mark is printed");
+    assert_eq!(mark.text.as_deref(), Some("Allegro"));
+    assert!(mark.show_metronome);
+    assert_eq!(tempo.bpm.value(), 132);
+
+    let texts = &score.instruments[0].staves[0].text_directions;
+    assert_eq!(texts.len(), 2);
+    assert_eq!(
+        (texts[0].tick, texts[0].text.as_str(), texts[0].placement),
+        (0, "dolce", Placement::Below)
+    );
+    assert_eq!(texts[0].style, TextDirectionStyle::Expression);
+    assert_eq!(
+        (texts[1].tick, texts[1].text.as_str(), texts[1].style),
+        (5760, "rit.", TextDirectionStyle::Tempo)
+    );
+}
+
+#[test]
+fn test_layout_places_directions_clear_of_brackets() {
+    let layout = layout(&import(DIRECTIONS));
+    let system = &layout.systems[0];
+    let staff = &system.staff_groups[0].staves[0];
+    let (top_line, bottom_line) = (
+        staff.staff_lines[0].y_position,
+        staff.staff_lines[4].y_position,
+    );
+    let find = |kind: DirectionKind, text: &str| {
+        system
+            .direction_layouts
+            .iter()
+            .find(|d| d.kind == kind && d.text == text)
+            .unwrap_or_else(|| panic!("{text} not laid out"))
+    };
+
+    // Tempo mark: bold text followed by ♩ = 132, above the 8va bracket
+    let tempo = find(DirectionKind::Tempo, "Allegro");
+    assert!(tempo.bold);
+    let metronome = tempo.metronome.as_ref().expect("metronome mark");
+    assert_eq!(metronome.glyph, "\u{ECA5}");
+    assert_eq!(metronome.text, "= 132");
+    assert!(metronome.glyph_x > tempo.x && metronome.text_x > metronome.glyph_x);
+    let ottava = &system.ottava_bracket_layouts[0];
+    assert!(tempo.bounding_box.y + tempo.bounding_box.height < ottava.y - 20.0);
+
+    // Rehearsal marks are framed; "B" clears the first-ending bracket
+    let a = find(DirectionKind::Rehearsal, "A");
+    assert_eq!(
+        a.enclosure.as_ref().map(|e| e.shape),
+        Some(Enclosure::Rectangle)
+    );
+    let b = find(DirectionKind::Rehearsal, "B");
+    assert_eq!(
+        b.enclosure.as_ref().map(|e| e.shape),
+        Some(Enclosure::Circle)
+    );
+    let volta = &system.volta_bracket_layouts[0];
+    assert!(b.bounding_box.y + b.bounding_box.height < volta.y - 20.0);
+
+    // Expression text below the staff, tempo words above it
+    let dolce = find(DirectionKind::Expression, "dolce");
+    assert!(dolce.italic && dolce.bounding_box.y > bottom_line);
+    let rit = find(DirectionKind::Tempo, "rit.");
+    assert!(rit.y < top_line);
+
+    // No direction overlaps another
+    let boxes: Vec<_> = system
+        .direction_layouts
+        .iter()
+        .map(|d| d.bounding_box)
+        .collect();
+    for (i, a) in boxes.iter().enumerate() {
+        assert!(boxes[i + 1..].iter().all(|b| !a.intersects(b)));
+    }
+    // The system grows to include its directions
+    assert!(system.bounding_box.y <= tempo.bounding_box.y);
+}
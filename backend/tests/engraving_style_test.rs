@@ -0,0 +1,163 @@
+//! Integration tests for `EngravingStyle`: presets, serialization and their
+//! effect on spacing, staff distances, stems and beams.
+
+mod common;
+
+use common::{import_file, layout_with};
+use musicore_backend::layout::spacer::SpacingConfig;
+use musicore_backend::layout::{EngravingStyle, GlobalLayout, Glyph, LayoutConfig, System};
+
+fn layout(name: &str, engraving: EngravingStyle) -> GlobalLayout {
+    let config = LayoutConfig {
+        engraving,
+        ..LayoutConfig::default()
+    };
+    layout_with(&import_file(name), &config)
+}
+
+fn glyphs<'a>(system: &'a System, codepoint: &'a str) -> impl Iterator<Item = &'a Glyph> {
+    system
+        .staff_groups
+        .iter()
+        .flat_map(|g| &g.staves)
+        .flat_map(|s| &s.glyph_runs)
+        .flat_map(|r| &r.glyphs)
+        .filter(move |g| g.codepoint == codepoint)
+}
+
+/// Average distance between consecutive onsets within each system
+fn mean_onset_gap(layout: &GlobalLayout) -> f32 {
+    let gaps: Vec<f32> = layout
+        .systems
+        .iter()
+        .flat_map(|s| s.tick_positions.windows(2).map(|w| w[1].x - w[0].x))
+        .collect();
+    gaps.iter().sum::<f32>() / gaps.len() as f32
+}
+
+#[test]
+fn test_presets_and_serialization() {
+    assert_eq!(EngravingStyle::default(), EngravingStyle::standard());
+    assert_eq!(EngravingStyle::standard().spacing, SpacingConfig::default());
+    assert_eq!(
+        EngravingStyle::preset("beginner"),
+        Some(EngravingStyle::spacious())
+    );
+    assert_eq!(
+        EngravingStyle::preset("compact"),
+        Some(EngravingStyle::compact())
+    );
+    assert_eq!(EngravingStyle::preset("huge"), None);
+
+    // Older configs without a style get the standard one; partial styles
+    // fill in the rest from the standard style
+    let config: LayoutConfig = serde_json::from_str(
+        r#"{"max_system_width": 1200, "units_per_space": 20, "system_spacing": 100,
+            "system_height": 200}"#,
+    )
+    .unwrap();
+    assert_eq!(config.engraving, EngravingStyle::standard());
+    let config: LayoutConfig = serde_json::from_str(
+        r#"{"max_system_width": 1200, "units_per_space": 20, "system_spacing": 100,
+            "system_height": 200,
+            "engraving": {"staff_distance": 12, "spacing": {"minimum_spacing": 50}}}"#,
+    )
+    .unwrap();
+    assert_eq!(config.engraving.staff_distance, 12.0);
+    assert_eq!(config.engraving.spacing.minimum_spacing, 50.0);
+    assert_eq!(
+        config.engraving.spacing.base_spacing,
+        SpacingConfig::STANDARD.base_spacing
+    );
+    assert_eq!(
+        config.engraving.stem_length,
+        EngravingStyle::standard().stem_length
+    );
+}
+
+#[test]
+fn test_spacing_and_staff_distance_follow_the_style() {
+    let compact = layout("Bach_InventionNo1.mxl", EngravingStyle::compact());
+    let standard = layout("Bach_InventionNo1.mxl", EngravingStyle::standard());
+    let spacious = layout("Bach_InventionNo1.mxl", EngravingStyle::spacious());
+
+    // Looser spacing puts fewer measures on each system
+    assert!(compact.systems.len() <= standard.systems.len());
+    assert!(standard.systems.len() < spacious.systems.len());
+    assert!(mean_onset_gap(&compact) < mean_onset_gap(&standard));
+    assert!(mean_onset_gap(&standard) < mean_onset_gap(&spacious));
+
+    // Piano staves are `staff_distance` spaces apart
+    for (layout, style) in [
+        (&compact, EngravingStyle::compact()),
+        (&standard, EngravingStyle::standard()),
+        (&spacious, EngravingStyle::spacious()),
+    ] {
+        let system = &layout.systems[0];
+        let staves = &system.staff_groups[0].staves;
+        let gap = staves[1].staff_lines[0].y_position - staves[0].staff_lines[0].y_position;
+        let ups = layout.units_per_space;
+        // Collision avoidance may only add to it
+        assert!(gap >= style.staff_distance * ups - 1e-3, "{gap}");
+        assert!(gap < (style.staff_distance + 4.0) * ups);
+    }
+}
+
+#[test]
+fn test_stems_and_beams_follow_the_style() {
+    let standard = layout("Bach_InventionNo1.mxl", EngravingStyle::standard());
+    let long_stems = EngravingStyle {
+        stem_length: 5.0,
+        min_beamed_stem_length: 4.0,
+        ..EngravingStyle::standard()
+    };
+    let long = layout("Bach_InventionNo1.mxl", long_stems);
+    let shortest_stem = |layout: &GlobalLayout| {
+        layout
+            .systems
+            .iter()
+            .flat_map(|s| glyphs(s, "\u{0000}"))
+            .map(|g| g.bounding_box.height)
+            .fold(f32::INFINITY, f32::min)
+    };
+    assert!(shortest_stem(&standard) < 80.0);
+    assert!(shortest_stem(&long) >= 80.0 - 1e-3);
+
+    // Unbeamed notes keep their combined glyphs (e.g. noteQuarterUp) only at
+    // the standard stem length
+    let combined = |layout: &GlobalLayout| {
+        ('\u{E1D3}'..='\u{E1DA}')
+            .map(|c| {
+                let codepoint = c.to_string();
+                layout
+                    .systems
+                    .iter()
+                    .map(|s| glyphs(s, &codepoint).count())
+                    .sum::<usize>()
+            })
+            .sum::<usize>()
+    };
+    assert!(combined(&standard) > 0);
+    assert_eq!(combined(&long), 0);
+
+    // Beams rise or fall at most `max_beam_slope` staff spaces
+    let steepest = |layout: &GlobalLayout| {
+        layout
+            .systems
+            .iter()
+            .flat_map(|s| glyphs(s, "\u{0001}"))
+            .map(|g| (g.bounding_box.y - g.position.y).abs())
+            .fold(0.0_f32, f32::max)
+    };
+    let ups = standard.units_per_space;
+    assert!(steepest(&standard) > 0.25 * ups);
+    assert!(steepest(&standard) <= 0.5 * ups + 1e-3);
+    let flat = layout(
+        "Bach_InventionNo1.mxl",
+        EngravingStyle {
+            max_beam_slope: 0.25,
+            ..EngravingStyle::standard()
+        },
+    );
+    assert!(steepest(&flat) <= 0.25 * ups + 1e-3);
+}
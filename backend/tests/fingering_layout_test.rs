@@ -17,6 +17,7 @@ const CONFIG: LayoutConfig = LayoutConfig {
     units_per_space: 20.0,
     system_spacing: 200.0,
     system_height: 200.0,
+    ..LayoutConfig::DEFAULT
 };
 
 /// Test that `<fingering>` elements from the Chopin Nocturne are parsed
@@ -123,7 +124,7 @@ fn test_fingering_glyphs_in_layout_output() {
                                     .as_u64()
                                     .expect("FingeringGlyph must have digit");
                                 assert!(
-                                    digit >= 1 && digit <= 5,
+                                    (1..=5).contains(&digit),
                                     "Fingering digit must be 1-5, got {}",
                                     digit
                                 );
@@ -256,10 +257,10 @@ fn test_fingering_staccato_coexistence() {
                     for staff in staves {
                         let has_dots = staff["notation_dots"]
                             .as_array()
-                            .map_or(false, |a| !a.is_empty());
+                            .is_some_and(|a| !a.is_empty());
                         let has_fingering = staff["fingering_glyphs"]
                             .as_array()
-                            .map_or(false, |a| !a.is_empty());
+                            .is_some_and(|a| !a.is_empty());
                         if has_dots && has_fingering {
                             has_both = true;
                         }
@@ -298,12 +299,11 @@ fn test_fingering_slur_coexistence() {
             for sg in staff_groups {
                 if let Some(staves) = sg["staves"].as_array() {
                     for staff in staves {
-                        let has_slurs = staff["slur_arcs"]
-                            .as_array()
-                            .map_or(false, |a| !a.is_empty());
+                        let has_slurs =
+                            staff["slur_arcs"].as_array().is_some_and(|a| !a.is_empty());
                         let has_fingering = staff["fingering_glyphs"]
                             .as_array()
-                            .map_or(false, |a| !a.is_empty());
+                            .is_some_and(|a| !a.is_empty());
                         if has_slurs && has_fingering {
                             has_both = true;
                         }
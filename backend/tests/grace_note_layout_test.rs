@@ -3,6 +3,7 @@
 //! cross-staff tick alignment (regression for T124).
 
 use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::events::grace::GraceKind;
 use musicore_backend::domain::importers::musicxml::MusicXMLImporter;
 use musicore_backend::domain::importers::musicxml::{
     CompressionHandler, ImportContext, MusicXMLConverter, MusicXMLParser,
@@ -16,6 +17,7 @@ const CONFIG: LayoutConfig = LayoutConfig {
     units_per_space: 20.0,
     system_spacing: 200.0,
     system_height: 200.0,
+    ..LayoutConfig::DEFAULT
 };
 
 fn layout_fur_elise() -> serde_json::Value {
@@ -314,3 +316,109 @@ fn test_grace_notes_at_system_start_have_distinct_x_positions() {
         }
     }
 }
+
+/// One 2/4 measure: a slashed eighth acciaccatura before a quarter, then two
+/// unbeamed sixteenth appoggiaturas stealing 20% of the previous quarter.
+const GRACE_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Flute</part-name></score-part></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>1</divisions>
+        <time><beats>2</beats><beat-type>4</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+      </attributes>
+      <note><grace slash="yes"/><pitch><step>D</step><octave>5</octave></pitch><voice>1</voice><type>eighth</type></note>
+      <note><pitch><step>C</step><octave>5</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+      <note><grace steal-time-previous="20"/><pitch><step>F</step><octave>5</octave></pitch><voice>1</voice><type>16th</type></note>
+      <note><grace steal-time-previous="20"/><pitch><step>E</step><octave>5</octave></pitch><voice>1</voice><type>16th</type></note>
+      <note><pitch><step>D</step><octave>5</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+fn import_grace_types() -> musicore_backend::domain::score::Score {
+    let mut ctx = ImportContext::new();
+    let doc = MusicXMLParser::parse(GRACE_TYPES, &mut ctx).expect("parse");
+    MusicXMLConverter::convert(doc, &mut ctx).expect("convert")
+}
+
+#[test]
+fn test_grace_types_and_playback_timing_imported() {
+    let score = import_grace_types();
+    let voice = &score.instruments[0].staves[0].voices[0];
+    let graces: Vec<_> = voice
+        .interval_events
+        .iter()
+        .filter(|n| n.is_grace)
+        .collect();
+    assert_eq!(graces.len(), 3);
+
+    let acciaccatura = graces[0].grace.as_ref().expect("grace annotation");
+    assert_eq!(acciaccatura.kind, GraceKind::Acciaccatura);
+    assert!(acciaccatura.slash);
+    assert_eq!(acciaccatura.note_type.as_deref(), Some("eighth"));
+    let appoggiatura = graces[1].grace.as_ref().unwrap();
+    assert_eq!(appoggiatura.kind, GraceKind::Appoggiatura);
+    assert_eq!(appoggiatura.steal_time_previous, Some(20));
+
+    let timings = voice.grace_timings();
+    let timing_of = |id| timings.iter().find(|t| &t.note_id == id).unwrap();
+    // Acciaccatura on the beat, shortening the principal C5
+    let first = timing_of(&graces[0].id);
+    assert_eq!((first.start_tick, first.duration_ticks), (0, 120));
+    // 20% of the C5 quarter (192 ticks) before beat 2, split in two
+    let (second, third) = (timing_of(&graces[1].id), timing_of(&graces[2].id));
+    assert_eq!((second.start_tick, second.duration_ticks), (768, 96));
+    assert_eq!((third.start_tick, third.duration_ticks), (864, 96));
+
+    let json = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    let dto_timings = json["instruments"][0]["staves"][0]["grace_timings"]
+        .as_array()
+        .expect("grace_timings in DTO");
+    assert_eq!(dto_timings.len(), timings.len());
+}
+
+#[test]
+fn test_grace_runs_beamed_and_acciaccatura_slashed() {
+    let json = serde_json::to_value(ScoreDto::from(&import_grace_types())).unwrap();
+    let layout = compute_layout(&json, &CONFIG);
+    let staff = &layout.systems[0].staff_groups[0].staves[0];
+    let glyphs: Vec<_> = staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| run.glyphs.iter())
+        .collect();
+
+    // Unbeamed single acciaccatura: small eighth-note glyph (note8thUp)
+    let single = glyphs
+        .iter()
+        .find(|g| g.source_reference.event_index == 0 && g.codepoint == "\u{E1D7}")
+        .expect("grace eighth drawn as note8thUp");
+    assert_eq!(single.font_size, Some(60.0));
+
+    // Sixteenth grace run: two small beams, no combined flag glyphs
+    let beams: Vec<_> = glyphs
+        .iter()
+        .filter(|g| g.codepoint == "\u{0001}" && g.bounding_box.height > 4.0)
+        .collect();
+    assert_eq!(beams.len(), 2, "two 16th beam levels across the grace run");
+    assert!(beams.iter().all(|b| b.bounding_box.height < 9.0));
+
+    // One thin slash, crossing the acciaccatura's stem above the notehead
+    let slashes: Vec<_> = glyphs
+        .iter()
+        .filter(|g| g.codepoint == "\u{0001}" && g.bounding_box.height < 4.0)
+        .collect();
+    assert_eq!(slashes.len(), 1);
+    let slash = slashes[0];
+    assert_eq!(slash.source_reference.event_index, 0);
+    assert!(slash.position.x < single.position.x + 15.0);
+    assert!(slash.position.x + slash.bounding_box.width > single.position.x);
+    assert!(
+        slash.position.y < single.position.y,
+        "slash rises to the right"
+    );
+    assert!(slash.bounding_box.y < slash.position.y);
+}
@@ -0,0 +1,227 @@
+//! Integration tests for incremental re-layout: after every edit the
+//! incrementally updated layout must serialize byte for byte like a full
+//! `compute_layout` of the edited score.
+
+mod common;
+
+use common::import_file;
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::layout::{
+    EngravingStyle, GlobalLayout, IncrementalLayout, LayoutConfig, PageConfig, TickRange,
+    compute_layout,
+};
+use serde_json::Value;
+
+fn score_json(name: &str) -> Value {
+    serde_json::to_value(ScoreDto::from(&import_file(name))).expect("DTO serialization failed")
+}
+
+fn assert_same_as_full(incremental: &GlobalLayout, score: &Value, config: &LayoutConfig) {
+    let full = compute_layout(score, config);
+    assert_eq!(
+        serde_json::to_string(incremental).unwrap(),
+        serde_json::to_string(&full).unwrap()
+    );
+}
+
+fn notes_mut(score: &mut Value, staff: usize) -> &mut Vec<Value> {
+    score["instruments"][0]["staves"][staff]["voices"][0]["interval_events"]
+        .as_array_mut()
+        .expect("voice has notes")
+}
+
+/// Move a note up an octave; returns the ticks it occupies
+fn transpose_note(score: &mut Value, staff: usize, index: usize) -> TickRange {
+    let note = &mut notes_mut(score, staff)[index];
+    note["pitch"] = (note["pitch"].as_u64().unwrap() + 12).into();
+    let start = note["start_tick"].as_u64().unwrap() as u32;
+    let duration = note["duration_ticks"].as_u64().unwrap() as u32;
+    TickRange {
+        start_tick: start,
+        end_tick: start + duration,
+    }
+}
+
+#[test]
+fn test_note_edits_match_full_layout_and_reuse_other_systems() {
+    for name in [
+        "Bach_InventionNo1.mxl",
+        "Beethoven_FurElise.mxl",
+        "Chopin_NocturneOp9No2.mxl",
+        "Pachelbel_CanonD.mxl",
+    ] {
+        let config = LayoutConfig::default();
+        let mut score = score_json(name);
+        let mut layout = IncrementalLayout::new(&score, &config);
+        assert_same_as_full(layout.layout(), &score, &config);
+        let system_count = layout.layout().systems.len();
+
+        let note_count = notes_mut(&mut score, 0).len();
+        for index in [note_count / 4, note_count / 2, note_count * 3 / 4] {
+            let changed = transpose_note(&mut score, 0, index);
+            let updated = layout.update(&score, changed);
+            assert_same_as_full(updated, &score, &config);
+
+            let stats = layout.stats();
+            assert!(!stats.full, "{name}: note edit fell back to full layout");
+            assert_eq!(stats.measures_measured, 1, "{name}");
+            assert!(stats.systems_reused > 0, "{name}: {stats:?}");
+            assert_eq!(
+                stats.systems_laid_out + stats.systems_reused,
+                layout.layout().systems.len()
+            );
+            assert!(stats.systems_laid_out < system_count, "{name}: {stats:?}");
+        }
+    }
+}
+
+#[test]
+fn test_wider_measure_rebreaks_until_systems_resynchronise() {
+    let config = LayoutConfig::default();
+    let mut score = score_json("Bach_InventionNo1.mxl");
+    let mut layout = IncrementalLayout::new(&score, &config);
+    let starts = |layout: &GlobalLayout| -> Vec<u32> {
+        layout
+            .systems
+            .iter()
+            .map(|s| s.tick_range.start_tick)
+            .collect()
+    };
+    let before = starts(layout.layout());
+
+    // Split every note of the second system's first measure into four,
+    // widening that measure so the following breaks move
+    let measure = TickRange {
+        start_tick: before[1],
+        end_tick: before[1] + 3840,
+    };
+    let notes = notes_mut(&mut score, 0);
+    let mut split = Vec::new();
+    notes.retain(|note| {
+        let start = note["start_tick"].as_u64().unwrap() as u32;
+        let in_measure = start >= measure.start_tick && start < measure.end_tick;
+        if in_measure {
+            let quarter = note["duration_ticks"].as_u64().unwrap() / 4;
+            for k in 0..4 {
+                let mut part = note.clone();
+                part["start_tick"] = (start as u64 + k * quarter).into();
+                part["duration_ticks"] = quarter.into();
+                part["id"] = format!("{}-{k}", note["id"].as_str().unwrap()).into();
+                part.as_object_mut().unwrap().remove("beams");
+                split.push(part);
+            }
+        }
+        !in_measure
+    });
+    notes.extend(split);
+    notes.sort_by_key(|n| n["start_tick"].as_u64().unwrap());
+
+    let updated = layout.update(&score, measure);
+    assert_same_as_full(updated, &score, &config);
+    assert_ne!(starts(layout.layout()), before);
+    let stats = layout.stats();
+    assert!(!stats.full);
+    assert_eq!(stats.measures_measured, 1);
+    // The first system is untouched
+    assert!(stats.systems_reused >= 1, "{stats:?}");
+}
+
+#[test]
+fn test_edits_follow_ties_and_slurs_into_other_systems() {
+    // Edit only the end note of slurs and ties that cross a system break;
+    // the system holding the start must be laid out again too
+    let config = LayoutConfig::default();
+    let mut score = score_json("Chopin_NocturneOp9No2.mxl");
+    let mut layout = IncrementalLayout::new(&score, &config);
+    let system_starts: Vec<u32> = layout
+        .layout()
+        .systems
+        .iter()
+        .map(|s| s.tick_range.start_tick)
+        .collect();
+    let system_of = |tick: u32| system_starts.iter().rposition(|&s| s <= tick);
+
+    let mut crossing = Vec::new();
+    for staff in 0..2 {
+        let notes = notes_mut(&mut score, staff);
+        for note in notes.iter() {
+            for link in ["slur_next", "tie_next"] {
+                let Some(target) = note[link].as_str() else {
+                    continue;
+                };
+                if let Some(end) = notes.iter().position(|n| n["id"] == target) {
+                    let start_tick = note["start_tick"].as_u64().unwrap() as u32;
+                    let end_tick = notes[end]["start_tick"].as_u64().unwrap() as u32;
+                    if system_of(start_tick) != system_of(end_tick) {
+                        crossing.push((staff, end));
+                    }
+                }
+            }
+        }
+    }
+    assert!(!crossing.is_empty(), "expected slurs across system breaks");
+
+    for (staff, end) in crossing.into_iter().take(4) {
+        let changed = transpose_note(&mut score, staff, end);
+        let updated = layout.update(&score, changed);
+        assert_same_as_full(updated, &score, &config);
+        assert!(layout.stats().systems_laid_out >= 2);
+    }
+}
+
+#[test]
+fn test_other_edits_fall_back_to_full_layout() {
+    let config = LayoutConfig::default();
+    let mut score = score_json("Beethoven_FurElise.mxl");
+    let mut layout = IncrementalLayout::new(&score, &config);
+
+    score["instruments"][0]["staves"][1]["clef"] = "Treble".into();
+    let updated = layout.update(
+        &score,
+        TickRange {
+            start_tick: 0,
+            end_tick: 0,
+        },
+    );
+    assert_same_as_full(updated, &score, &config);
+    let stats = layout.stats();
+    assert!(stats.full);
+    assert_eq!(stats.systems_reused, 0);
+}
+
+#[test]
+fn test_resize_and_paginated_updates_match_full_layout() {
+    let mut score = score_json("Chopin_NocturneOp9No2.mxl");
+    let mut layout = IncrementalLayout::new(&score, &LayoutConfig::default());
+
+    // A viewport resize reuses every measure width
+    let narrow = LayoutConfig {
+        max_system_width: 1100.0,
+        ..LayoutConfig::default()
+    };
+    let resized = layout.set_config(&narrow);
+    assert_same_as_full(resized, &score, &narrow);
+    assert_eq!(layout.stats().measures_measured, 0);
+
+    // Another engraving style spaces every measure anew
+    let spacious = LayoutConfig {
+        engraving: EngravingStyle::spacious(),
+        ..narrow.clone()
+    };
+    let restyled = layout.set_config(&spacious);
+    assert_same_as_full(restyled, &score, &spacious);
+    assert!(layout.stats().measures_measured > 0);
+
+    // Pages are rebuilt around reused systems
+    let paged = LayoutConfig {
+        page: Some(PageConfig::default()),
+        ..LayoutConfig::default()
+    };
+    layout.set_config(&paged);
+    let count = notes_mut(&mut score, 0).len();
+    let changed = transpose_note(&mut score, 0, count / 2);
+    let updated = layout.update(&score, changed);
+    assert_same_as_full(updated, &score, &paged);
+    assert!(layout.stats().systems_reused > 0);
+    assert!(!layout.layout().pages.is_empty());
+}
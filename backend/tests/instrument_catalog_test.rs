@@ -0,0 +1,145 @@
+//! Integration tests for the built-in instrument catalog: recognising
+//! imported parts from `<score-instrument>`/`<midi-instrument>` or their
+//! names, default clefs, and MIDI settings.
+
+mod common;
+
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::instrument::{Instrument, MidiSettings};
+use musicore_backend::domain::instrument_catalog;
+use musicore_backend::domain::score::Score;
+use musicore_backend::domain::value_objects::{Clef, Tick};
+
+/// One-measure part with a whole note and the given attributes
+fn part(id: &str, attributes: &str) -> String {
+    format!(
+        r#"<part id="{id}"><measure number="1">
+      <attributes><divisions>1</divisions><time><beats>4</beats><beat-type>4</beat-type></time>{attributes}</attributes>
+      <note><pitch><step>C</step><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type></note>
+    </measure></part>"#
+    )
+}
+
+/// A clarinet recognised from its instrument sound, a viola without a
+/// written clef, a part named after nothing in the catalog but playing a
+/// cello program, and an unknown instrument.
+fn ensemble() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list>
+    <score-part id="P1">
+      <part-name>Solo</part-name>
+      <score-instrument id="P1-I1">
+        <instrument-name>Clarinet</instrument-name>
+        <instrument-sound>wind.reed.clarinet.b-flat</instrument-sound>
+      </score-instrument>
+      <midi-instrument id="P1-I1">
+        <midi-channel>2</midi-channel>
+        <midi-program>72</midi-program>
+        <volume>64.5</volume>
+        <pan>-45</pan>
+      </midi-instrument>
+    </score-part>
+    <score-part id="P2"><part-name>Viola</part-name></score-part>
+    <score-part id="P3">
+      <part-name>Continuo</part-name>
+      <midi-instrument id="P3-I1"><midi-program>43</midi-program></midi-instrument>
+    </score-part>
+    <score-part id="P4"><part-name>Theremin</part-name></score-part>
+  </part-list>
+  {}
+  {}
+  {}
+  {}
+</score-partwise>"#,
+        part("P1", "<clef><sign>G</sign><line>2</line></clef>"),
+        part("P2", ""),
+        part("P3", "<clef><sign>F</sign><line>4</line></clef>"),
+        part("P4", ""),
+    )
+}
+
+fn import() -> Score {
+    common::import(&ensemble())
+}
+
+fn clef(instrument: &Instrument) -> Option<Clef> {
+    instrument.staves[0]
+        .get_clef_at(Tick::new(0))
+        .map(|event| event.clef)
+}
+
+#[test]
+fn test_import_recognises_catalog_instruments() {
+    let score = import();
+    let types: Vec<_> = score
+        .instruments
+        .iter()
+        .map(|i| i.instrument_type.as_str())
+        .collect();
+    assert_eq!(types, ["clarinet", "viola", "cello", "piano"]);
+
+    assert_eq!(
+        score.instruments[0].catalog_entry().unwrap().transposition,
+        -2
+    );
+    assert!(
+        score.instruments[3]
+            .catalog_entry()
+            .is_some_and(|i| i.id == "piano")
+    );
+}
+
+#[test]
+fn test_import_midi_settings() {
+    let score = import();
+    assert_eq!(
+        score.instruments[0].midi,
+        Some(MidiSettings {
+            program: 72,
+            channel: 2,
+            volume: 65,
+            pan: -45,
+        })
+    );
+    // Catalog program when the part has no <midi-instrument>
+    assert_eq!(score.instruments[1].midi, Some(MidiSettings::new(42)));
+    assert_eq!(score.instruments[2].midi.map(|m| m.program), Some(43));
+    assert_eq!(score.instruments[3].midi, None);
+
+    let dto = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert_eq!(dto["instruments"][0]["midi"]["channel"], 2);
+    assert!(dto["instruments"][3].get("midi").is_none());
+}
+
+#[test]
+fn test_import_default_clef_from_catalog() {
+    let score = import();
+    assert_eq!(clef(&score.instruments[1]), Some(Clef::Alto));
+    // A written clef is kept
+    assert_eq!(clef(&score.instruments[2]), Some(Clef::Bass));
+}
+
+#[test]
+fn test_instrument_from_catalog() {
+    let organ = Instrument::from_catalog(instrument_catalog::find("organ").unwrap());
+    assert_eq!(organ.name, "Organ");
+    assert_eq!(organ.instrument_type, "organ");
+    let clefs: Vec<_> = organ
+        .staves
+        .iter()
+        .map(|s| s.get_clef_at(Tick::new(0)).unwrap().clef)
+        .collect();
+    assert_eq!(clefs, [Clef::Treble, Clef::Bass, Clef::Bass]);
+    assert_eq!(organ.midi.map(|m| m.program), Some(20));
+
+    assert_eq!(
+        Instrument::new("Violin II".to_string()).instrument_type,
+        "violin"
+    );
+    assert_eq!(
+        Instrument::new("Theremin".to_string()).instrument_type,
+        "piano"
+    );
+}
@@ -0,0 +1,141 @@
+//! Integration tests for instrument names: import of `<part-abbreviation>`
+//! and `<part-name-display>`, full and short names by system, and system
+//! indents sized to the names.
+
+mod common;
+
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::score::Score;
+use musicore_backend::layout::{GlobalLayout, InstrumentNames, LayoutConfig};
+
+/// Eight measures of whole notes, enough for several narrow systems
+fn part(id: &str) -> String {
+    let measures: String = (1..=8)
+        .map(|n| {
+            format!(
+                r#"<measure number="{n}">{}<note><pitch><step>C</step><octave>5</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type></note></measure>"#,
+                if n == 1 {
+                    "<attributes><divisions>1</divisions><time><beats>4</beats><beat-type>4</beat-type></time><clef><sign>G</sign><line>2</line></clef></attributes>"
+                } else {
+                    ""
+                }
+            )
+        })
+        .collect();
+    format!(r#"<part id="{id}">{measures}</part>"#)
+}
+
+fn duet() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list>
+    <score-part id="P1">
+      <part-name>Violin I</part-name>
+      <part-abbreviation>Vln. I</part-abbreviation>
+    </score-part>
+    <score-part id="P2">
+      <part-name>Clarinet in Bb</part-name>
+      <part-name-display>
+        <display-text>Clarinet in B</display-text>
+        <accidental-text>flat</accidental-text>
+      </part-name-display>
+      <part-abbreviation>Cl.</part-abbreviation>
+      <part-abbreviation-display>
+        <display-text>Cl. in B</display-text>
+        <accidental-text>flat</accidental-text>
+      </part-abbreviation-display>
+    </score-part>
+  </part-list>
+  {}
+  {}
+</score-partwise>"#,
+        part("P1"),
+        part("P2"),
+    )
+}
+
+fn import() -> Score {
+    common::import(&duet())
+}
+
+fn layout(instrument_names: InstrumentNames) -> GlobalLayout {
+    let config = LayoutConfig {
+        max_system_width: 1000.0,
+        instrument_names,
+        ..LayoutConfig::default()
+    };
+    common::layout_with(&import(), &config)
+}
+
+fn names(layout: &GlobalLayout, system: usize) -> Vec<Option<String>> {
+    layout.systems[system]
+        .staff_groups
+        .iter()
+        .map(|g| g.name_label.as_ref().map(|l| l.text.clone()))
+        .collect()
+}
+
+#[test]
+fn test_import_short_names_and_name_display() {
+    let score = import();
+    assert_eq!(score.instruments[0].name, "Violin I");
+    assert_eq!(score.instruments[0].short_name.as_deref(), Some("Vln. I"));
+    assert_eq!(score.instruments[1].name, "Clarinet in B♭");
+    assert_eq!(
+        score.instruments[1].short_name.as_deref(),
+        Some("Cl. in B♭")
+    );
+
+    let dto = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert_eq!(dto["instruments"][0]["short_name"], "Vln. I");
+}
+
+#[test]
+fn test_full_then_short_names() {
+    let layout = layout(InstrumentNames::FullThenShort);
+    assert!(layout.systems.len() > 1);
+    assert_eq!(
+        names(&layout, 0),
+        [
+            Some("Violin I".to_string()),
+            Some("Clarinet in B♭".to_string())
+        ]
+    );
+    assert_eq!(
+        names(&layout, 1),
+        [Some("Vln. I".to_string()), Some("Cl. in B♭".to_string())]
+    );
+
+    // The indent fits the widest name: wider for full names
+    let (first, later) = (layout.systems[0].indent, layout.systems[1].indent);
+    assert!(first > later && later > 0.0, "{first} vs {later}");
+    for system in &layout.systems {
+        for label in system
+            .staff_groups
+            .iter()
+            .filter_map(|g| g.name_label.as_ref())
+        {
+            assert!(label.position.x < 0.0 && label.position.x > -system.indent);
+        }
+    }
+}
+
+#[test]
+fn test_full_names_on_every_system() {
+    let layout = layout(InstrumentNames::Full);
+    let first = names(&layout, 0);
+    for system in 1..layout.systems.len() {
+        assert_eq!(names(&layout, system), first);
+        assert_eq!(layout.systems[system].indent, layout.systems[0].indent);
+    }
+}
+
+#[test]
+fn test_hidden_names() {
+    let layout = layout(InstrumentNames::Hidden);
+    for system in &layout.systems {
+        assert!(system.staff_groups.iter().all(|g| g.name_label.is_none()));
+        assert_eq!(system.indent, 0.0);
+    }
+}
@@ -98,6 +98,17 @@ else
     exit 1
 fi
 
+# Test 6: Quiet lint
+echo "Test 6: Quiet lint..."
+LINT_OUTPUT=$($BINARY ../../../tests/fixtures/musicxml/simple_melody.musicxml --lint --quiet --validate-only 2>&1)
+
+if echo "$LINT_OUTPUT" | grep -q "Lint:"; then
+    echo "ERROR: Expected no lint report with --quiet"
+    exit 1
+else
+    echo "✓ Test 6 passed: Lint report suppressed"
+fi
+
 echo ""
 echo "=== All CLI tests passed! ==="
 exit 0
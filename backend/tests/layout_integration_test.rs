@@ -29,6 +29,7 @@ fn test_single_staff_layout_structure() {
         units_per_space: 20.0,
         system_spacing: 220.0,
         system_height: 200.0,
+        ..LayoutConfig::default()
     };
 
     let output = compute_layout(&input, &config);
@@ -141,6 +142,7 @@ fn test_multi_staff_layout_structure() {
         units_per_space: 20.0,
         system_spacing: 220.0,
         system_height: 200.0,
+        ..LayoutConfig::default()
     };
 
     let output = compute_layout(&input, &config);
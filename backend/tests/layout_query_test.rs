@@ -0,0 +1,209 @@
+//! Integration tests for `layout::query`: hit testing, playback cursor
+//! positions and tick range regions over laid-out scores.
+
+mod common;
+
+use musicore_backend::layout::{BoundingBox, GlobalLayout, Glyph, LayoutIndex, Point, System};
+
+fn layout(name: &str) -> GlobalLayout {
+    common::layout(&common::import_file(name))
+}
+
+fn is_notehead(glyph: &Glyph) -> bool {
+    ('\u{E0A0}'..='\u{E0FF}').contains(&glyph.codepoint.chars().next().unwrap())
+}
+
+fn center(b: &BoundingBox) -> Point {
+    Point {
+        x: b.x + b.width / 2.0,
+        y: b.y + b.height / 2.0,
+    }
+}
+
+fn distance(b: &BoundingBox, p: Point) -> f32 {
+    let dx = (b.x - p.x).max(p.x - (b.x + b.width)).max(0.0);
+    let dy = (b.y - p.y).max(p.y - (b.y + b.height)).max(0.0);
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[test]
+fn test_hit_test_finds_the_glyph_under_the_point() {
+    let layout = layout("Chopin_NocturneOp9No2.mxl");
+    let index = LayoutIndex::new(&layout);
+
+    let mut checked = 0;
+    for system in layout.systems.iter().step_by(3) {
+        for (staff_index, staff) in system.staff_groups[0].staves.iter().enumerate() {
+            for glyph in staff
+                .glyph_runs
+                .iter()
+                .flat_map(|r| &r.glyphs)
+                .filter(|g| is_notehead(g))
+                .step_by(7)
+            {
+                let hit = index
+                    .hit_test(center(&glyph.bounding_box))
+                    .expect("a glyph is under the point");
+                assert_eq!(hit.distance, 0.0);
+                assert_eq!(hit.system_index, system.index);
+                // Overlapping noteheads (seconds, unisons) may win instead,
+                // but never from another staff
+                assert_eq!(hit.staff_index, staff_index);
+                assert_eq!(hit.staff_group_index, 0);
+                checked += 1;
+            }
+        }
+    }
+    assert!(checked > 20);
+
+    // Nothing above the first system
+    assert!(
+        index
+            .hit_test(Point {
+                x: 500.0,
+                y: -500.0
+            })
+            .is_none()
+    );
+}
+
+#[test]
+fn test_hit_test_returns_the_nearest_note_or_rest() {
+    let layout = layout("Bach_InventionNo1.mxl");
+    let index = LayoutIndex::new(&layout);
+    let note_or_rest = |g: &&Glyph| {
+        let c = g.codepoint.chars().next().unwrap();
+        matches!(c, '\u{E0A0}'..='\u{E0FF}' | '\u{E1D0}'..='\u{E1EF}' | '\u{E4E0}'..='\u{E4FF}')
+    };
+    let glyphs_of = |system: &System| -> Vec<Glyph> {
+        system
+            .staff_groups
+            .iter()
+            .flat_map(|g| &g.staves)
+            .flat_map(|s| &s.glyph_runs)
+            .flat_map(|r| &r.glyphs)
+            .filter(note_or_rest)
+            .cloned()
+            .collect()
+    };
+    let system = &layout.systems[1];
+
+    // Brute force over a grid of points covering the system
+    let b = system.bounding_box;
+    for i in 0..40 {
+        for j in 0..12 {
+            let point = Point {
+                x: b.x + b.width * i as f32 / 39.0,
+                y: b.y + b.height * j as f32 / 11.0,
+            };
+            // System bounding boxes overlap: every system under the point counts
+            let expected = layout
+                .systems
+                .iter()
+                .filter(|s| {
+                    let b = s.bounding_box;
+                    point.y >= b.y && point.y <= b.y + b.height
+                })
+                .flat_map(glyphs_of)
+                .map(|g| distance(&g.bounding_box, point))
+                .fold(f32::INFINITY, f32::min);
+            let hit = index.hit_test(point).expect("system under point");
+            assert!(
+                (hit.distance - expected).abs() < 1e-3,
+                "at ({}, {}): {} vs {}",
+                point.x,
+                point.y,
+                hit.distance,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn test_tick_position_follows_onsets_and_interpolates_between_them() {
+    let layout = layout("Beethoven_FurElise.mxl");
+    let index = LayoutIndex::new(&layout);
+
+    for system in &layout.systems {
+        assert!(!system.measure_layouts.is_empty());
+        assert!(!system.tick_positions.is_empty());
+        assert_eq!(
+            system.measure_layouts[0].start_tick,
+            system.tick_range.start_tick
+        );
+        assert_eq!(
+            system.measure_layouts.last().unwrap().end_tick,
+            system.tick_range.end_tick
+        );
+
+        // Exact at onsets, strictly between neighbouring onsets in between
+        for pair in system.tick_positions.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let cursor = index.tick_position(a.tick).unwrap();
+            assert_eq!(cursor.system_index, system.index);
+            assert!((cursor.x - a.x).abs() < 1e-3);
+            let top = system.staff_groups[0].staves[0].staff_lines[0].y_position;
+            assert_eq!(cursor.y_top, top);
+
+            let measure_of = |tick| {
+                system
+                    .measure_layouts
+                    .iter()
+                    .position(|m| m.start_tick <= tick && tick < m.end_tick)
+            };
+            if b.tick > a.tick + 1 && measure_of(a.tick) == measure_of(b.tick) {
+                let mid = index.tick_position((a.tick + b.tick) / 2).unwrap();
+                assert!(a.x < mid.x && mid.x < b.x, "{a:?} {mid:?} {b:?}");
+            }
+        }
+    }
+
+    let end = layout.systems.last().unwrap().tick_range.end_tick;
+    assert!(index.tick_position(end).is_none());
+}
+
+#[test]
+fn test_range_regions_cover_each_system_of_a_loop() {
+    let layout = layout("Beethoven_FurElise.mxl");
+    let index = LayoutIndex::new(&layout);
+    let (first, second) = (&layout.systems[0], &layout.systems[1]);
+
+    // From an onset in the first system to an onset in the second
+    let start = first.tick_positions[first.tick_positions.len() / 2];
+    let end = second.tick_positions[second.tick_positions.len() / 2];
+    let regions = index.range_regions(start.tick, end.tick);
+    assert_eq!(regions.len(), 2);
+
+    let lines = |system: &System| {
+        let staves: Vec<_> = system.staff_groups.iter().flat_map(|g| &g.staves).collect();
+        (
+            staves[0].staff_lines[0].clone(),
+            staves.last().unwrap().staff_lines[4].clone(),
+        )
+    };
+    let (top, bottom) = lines(first);
+    let a = regions[0].bounding_box;
+    assert_eq!(regions[0].system_index, 0);
+    assert!((a.x - start.x).abs() < 1e-3);
+    assert!((a.x + a.width - top.end_x).abs() < 1e-3);
+    assert_eq!((a.y, a.y + a.height), (top.y_position, bottom.y_position));
+
+    let (top, _) = lines(second);
+    let b = regions[1].bounding_box;
+    assert_eq!(regions[1].system_index, 1);
+    assert!((b.x - top.start_x).abs() < 1e-3);
+    assert!((b.x + b.width - end.x).abs() < 1e-3);
+
+    // One measure: from the cursor at its first tick to the cursor at the next
+    let measure = first.measure_layouts[1];
+    let inner = index.range_regions(measure.start_tick, measure.end_tick);
+    assert_eq!(inner.len(), 1);
+    let r = inner[0].bounding_box;
+    let from = index.tick_position(measure.start_tick).unwrap();
+    let to = index.tick_position(measure.end_tick).unwrap();
+    assert!((r.x - from.x).abs() < 1e-3);
+    assert!((r.x + r.width - to.x).abs() < 1e-3);
+
+    assert!(index.range_regions(10_000_000, 10_000_100).is_empty());
+}
@@ -183,6 +183,7 @@ mod us1_us2_tests {
             units_per_space: 10.0,
             system_spacing: 150.0,
             system_height: 200.0,
+            ..LayoutConfig::default()
         };
 
         // Compute layout twice
@@ -217,6 +218,7 @@ mod us1_us2_tests {
             units_per_space: 10.0,
             system_spacing: 150.0,
             system_height: 200.0,
+            ..LayoutConfig::default()
         };
 
         let layout = compute_layout(&score, &config);
@@ -255,6 +257,7 @@ mod us1_us2_tests {
             units_per_space: 10.0,
             system_spacing: 150.0,
             system_height: 200.0,
+            ..LayoutConfig::default()
         };
 
         let layout = compute_layout(&score, &config);
@@ -292,6 +295,7 @@ mod us1_us2_tests {
             units_per_space: 10.0,
             system_spacing: 150.0,
             system_height: 200.0,
+            ..LayoutConfig::default()
         };
 
         let layout = compute_layout(&score, &config);
@@ -318,6 +322,7 @@ mod us1_us2_tests {
             units_per_space: 10.0,
             system_spacing: 150.0,
             system_height: 200.0,
+            ..LayoutConfig::default()
         };
 
         let layout = compute_layout(&score, &config);
@@ -1261,6 +1266,7 @@ mod measure_numbering_tests {
             units_per_space: 20.0,
             system_spacing: 200.0,
             system_height: 600.0,
+            ..LayoutConfig::default()
         };
         let layout = compute_layout(&score, &config);
 
@@ -1365,6 +1371,7 @@ mod measure_numbering_tests {
             units_per_space: 20.0,
             system_spacing: 200.0,
             system_height: 600.0,
+            ..LayoutConfig::default()
         };
 
         let layout1 = compute_layout(&score, &config);
@@ -2017,6 +2024,7 @@ fn test_key_signature_appears_on_all_systems() {
         units_per_space: 20.0,
         system_spacing: 220.0,
         system_height: 200.0,
+        ..LayoutConfig::default()
     };
 
     let layout = compute_layout(&score, &config);
@@ -2222,6 +2230,7 @@ mod top_margin_tests {
             units_per_space: 20.0,
             system_spacing: 200.0,
             system_height: 200.0,
+            ..LayoutConfig::default()
         };
 
         let layout = compute_layout(&score, &config);
@@ -0,0 +1,355 @@
+//! Integration tests for system breaking strategies: greedy vs total-fit,
+//! target measures per system, ragged last systems, the engraver's
+//! `<print new-system="yes">` breaks and single-line horizontal layout.
+
+mod common;
+
+use common::{import_file, layout_with};
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::layout::breaker::{break_into_systems, break_into_systems_total_fit};
+use musicore_backend::layout::{
+    GlobalLayout, LayoutConfig, LineBreaking, MeasureInfo, PageConfig, System, compute_layout,
+};
+fn measures(widths: &[f32]) -> Vec<MeasureInfo> {
+    widths
+        .iter()
+        .enumerate()
+        .map(|(i, &width)| MeasureInfo {
+            width,
+            start_tick: i as u32 * 3840,
+            end_tick: (i as u32 + 1) * 3840,
+            start_repeat: false,
+            end_repeat: false,
+        })
+        .collect()
+}
+
+/// Number of measures in each system
+fn counts(systems: &[System]) -> Vec<u32> {
+    systems
+        .iter()
+        .map(|s| (s.tick_range.end_tick - s.tick_range.start_tick) / 3840)
+        .collect()
+}
+
+fn total_fit(widths: &[f32], forced: &[usize], target: Option<u32>, ragged: bool) -> Vec<System> {
+    break_into_systems_total_fit(
+        &measures(widths),
+        1000.0,
+        200.0,
+        100.0,
+        forced,
+        target,
+        ragged,
+    )
+}
+
+#[test]
+fn test_total_fit_balances_systems_greedy_leaves_a_short_last_system() {
+    // Seven equal measures, three fit per system: greedy fills 3+3+1,
+    // total fit spreads them 3+2+2 (or any order of the same loads)
+    let widths = [300.0; 7];
+    let greedy = break_into_systems(&measures(&widths), 1000.0, 200.0, 100.0);
+    assert_eq!(counts(&greedy), vec![3, 3, 1]);
+
+    let balanced = total_fit(&widths, &[], None, false);
+    let mut loads = counts(&balanced);
+    loads.sort_unstable();
+    assert_eq!(loads, vec![2, 2, 3]);
+    // Systems are contiguous and justified
+    for pair in balanced.windows(2) {
+        assert_eq!(pair[0].tick_range.end_tick, pair[1].tick_range.start_tick);
+    }
+    assert!(balanced.iter().all(|s| s.bounding_box.width == 1000.0));
+    assert_eq!(balanced.last().unwrap().tick_range.end_tick, 7 * 3840);
+}
+
+#[test]
+fn test_total_fit_compresses_slightly_to_avoid_a_near_empty_system() {
+    // 1050 units of music overflow a 1000-unit system by 5%: greedy needs a
+    // second system for one measure, total fit compresses instead
+    let widths = [210.0; 5];
+    let greedy = break_into_systems(&measures(&widths), 1000.0, 200.0, 100.0);
+    assert_eq!(counts(&greedy), vec![4, 1]);
+    assert_eq!(counts(&total_fit(&widths, &[], None, false)), vec![5]);
+
+    // Beyond 10% compression the measures no longer share a system
+    let wide = [230.0; 5];
+    assert_eq!(counts(&total_fit(&wide, &[], None, false)).len(), 2);
+}
+
+#[test]
+fn test_total_fit_target_measures_per_system() {
+    let widths = [100.0; 12];
+    // Without a target, evenly filled systems
+    assert_eq!(counts(&total_fit(&widths, &[], None, false)), vec![6, 6]);
+    // With a target, that many measures per system
+    assert_eq!(
+        counts(&total_fit(&widths, &[], Some(4), false)),
+        vec![4, 4, 4]
+    );
+    // A ragged last system may hold fewer
+    assert_eq!(
+        counts(&total_fit(&[100.0; 10], &[], Some(4), true)),
+        vec![4, 4, 2]
+    );
+}
+
+#[test]
+fn test_ragged_last_system_keeps_its_natural_width() {
+    let systems = total_fit(&[300.0; 7], &[], None, true);
+    assert_eq!(counts(&systems), vec![3, 3, 1]);
+    assert_eq!(systems[0].bounding_box.width, 1000.0);
+    assert_eq!(systems[2].bounding_box.width, 300.0);
+
+    // Through compute_layout the ragged system ends at its content
+    let score = import_file("Bach_InventionNo1.mxl");
+    let config = LayoutConfig {
+        line_breaking: LineBreaking::TotalFit {
+            target_measures_per_system: None,
+            ragged_last_system: true,
+        },
+        ..LayoutConfig::default()
+    };
+    let layout = layout_with(&score, &config);
+    let (last, rest) = layout.systems.split_last().unwrap();
+    assert!(
+        rest.iter()
+            .all(|s| s.bounding_box.width == config.max_system_width)
+    );
+    assert!(last.bounding_box.width <= config.max_system_width);
+    let staff = &last.staff_groups[0].staves[0];
+    assert!(
+        staff
+            .staff_lines
+            .iter()
+            .all(|l| l.end_x == last.bounding_box.width)
+    );
+}
+
+#[test]
+fn test_total_fit_honors_forced_breaks() {
+    let widths = [100.0; 12];
+    let systems = total_fit(&widths, &[3, 5], None, false);
+    let starts: Vec<u32> = systems
+        .iter()
+        .map(|s| s.tick_range.start_tick / 3840)
+        .collect();
+    assert!(starts.contains(&3) && starts.contains(&5));
+    assert_eq!(counts(&systems), vec![3, 2, 7]);
+}
+
+#[test]
+fn test_import_system_breaks() {
+    let score = import_file("Chopin_NocturneOp9No2.mxl");
+    assert!(!score.system_breaks.is_empty());
+    // Page breaks are system breaks too
+    for page_break in &score.page_breaks {
+        assert!(score.system_breaks.contains(page_break));
+    }
+    assert!(score.system_breaks.windows(2).all(|w| w[0] < w[1]));
+    assert!(!score.system_breaks.contains(&0));
+}
+
+#[test]
+fn test_layout_follows_engraver_system_breaks() {
+    let score = import_file("Pachelbel_CanonD.mxl");
+    assert!(!score.system_breaks.is_empty());
+    let line_breakings = [
+        LineBreaking::Greedy,
+        LineBreaking::TotalFit {
+            target_measures_per_system: None,
+            ragged_last_system: false,
+        },
+    ];
+    // Paginated and scrolling layout alike
+    for (page, line_breaking) in [None, Some(PageConfig::default())]
+        .into_iter()
+        .flat_map(|page| line_breakings.map(|line_breaking| (page.clone(), line_breaking)))
+    {
+        let config = LayoutConfig {
+            page,
+            line_breaking,
+            ..LayoutConfig::default()
+        };
+        let layout = layout_with(&score, &config);
+        let starts: Vec<u32> = layout
+            .systems
+            .iter()
+            .map(|s| s.measure_number.as_ref().unwrap().number - 1)
+            .collect();
+        for &measure in &score.system_breaks {
+            assert!(
+                starts.contains(&measure),
+                "{config:?}: no system starts at measure index {measure}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_new_system_break_without_pagination() {
+    let score = import_file("Pachelbel_CanonD.mxl");
+    // Wide enough for the whole score on one system
+    let config = LayoutConfig {
+        max_system_width: 100_000.0,
+        ..LayoutConfig::default()
+    };
+    let mut unhinted = score.clone();
+    unhinted.system_breaks.clear();
+    assert_eq!(layout_with(&unhinted, &config).systems.len(), 1);
+
+    // The break starts a second system at its measure
+    let mut hinted = unhinted.clone();
+    hinted.system_breaks = vec![score.system_breaks[0]];
+    let systems = layout_with(&hinted, &config).systems;
+    assert_eq!(systems.len(), 2);
+    let first = systems[1].measure_number.as_ref().unwrap().number - 1;
+    assert_eq!(first, score.system_breaks[0]);
+}
+
+#[test]
+fn test_single_line_holds_every_measure_at_natural_width() {
+    let score = import_file("Bach_InventionNo1.mxl");
+    let greedy = layout_with(&score, &LayoutConfig::default());
+    let config = LayoutConfig {
+        line_breaking: LineBreaking::single_line(),
+        ..LayoutConfig::default()
+    };
+    let single = layout_with(&score, &config);
+
+    assert_eq!(single.systems.len(), 1);
+    let line = &single.systems[0];
+    assert_eq!(
+        line.tick_range.start_tick,
+        greedy.systems[0].tick_range.start_tick
+    );
+    assert_eq!(
+        line.tick_range.end_tick,
+        greedy.systems.last().unwrap().tick_range.end_tick
+    );
+    assert!(line.bounding_box.width > config.max_system_width * 3.0);
+    assert!(
+        line.staff_groups[0].staves[0]
+            .staff_lines
+            .iter()
+            .all(|l| l.end_x == line.bounding_box.width)
+    );
+
+    // Neither the viewport width nor pages affect a single line
+    let json = |layout: &GlobalLayout| serde_json::to_string(layout).unwrap();
+    for other in [
+        LayoutConfig {
+            max_system_width: 800.0,
+            ..config.clone()
+        },
+        LayoutConfig {
+            page: Some(PageConfig::default()),
+            ..config.clone()
+        },
+    ] {
+        assert_eq!(json(&layout_with(&score, &other)), json(&single));
+    }
+
+    // Measure boxes tile the line and hold every glyph centred in them
+    let measures = &line.measure_layouts;
+    assert_eq!(measures[0].start_tick, line.tick_range.start_tick);
+    for pair in measures.windows(2) {
+        assert_eq!(pair[0].end_tick, pair[1].start_tick);
+        assert_eq!(pair[0].x_end, pair[1].x_start);
+    }
+    let glyphs = line
+        .staff_groups
+        .iter()
+        .flat_map(|g| &g.staves)
+        .flat_map(|s| &s.glyph_runs)
+        .flat_map(|r| &r.glyphs);
+    for glyph in glyphs {
+        let b = glyph.bounding_box;
+        let center = b.x + b.width / 2.0;
+        let Some(measure) = measures
+            .iter()
+            .find(|m| center >= m.x_start && center < m.x_end)
+        else {
+            continue;
+        };
+        let m = measure.bounding_box;
+        assert!(m.x <= measure.x_start && m.x + m.width >= measure.x_end);
+        assert!(
+            b.x >= m.x && b.x + b.width <= m.x + m.width + 1e-3,
+            "{glyph:?} outside {measure:?}"
+        );
+        assert!(b.y >= m.y && b.y + b.height <= m.y + m.height + 1e-3);
+    }
+}
+
+#[test]
+fn test_single_line_sticky_signatures_follow_clef_changes() {
+    let config = LayoutConfig {
+        line_breaking: LineBreaking::SingleLine {
+            sticky_interval: 100.0,
+        },
+        ..LayoutConfig::default()
+    };
+
+    // Entries every 100 units, starting with the line's own clef and key
+    let mut json = serde_json::to_value(ScoreDto::from(&import_file("Bach_InventionNo1.mxl")))
+        .expect("DTO serialization failed");
+    json["instruments"][0]["staves"][0]["key_signature_events"] =
+        serde_json::json!([{ "tick": 0, "sharps": 2 }]);
+    let d_major = compute_layout(&json, &config);
+    let line = &d_major.systems[0];
+    let staff = &line.staff_groups[0].staves[0];
+    let entries = &staff.sticky_signatures;
+    assert_eq!(
+        entries.len(),
+        (line.bounding_box.width / 100.0).ceil() as usize
+    );
+    assert!(
+        entries
+            .iter()
+            .enumerate()
+            .all(|(k, e)| e.x == k as f32 * 100.0)
+    );
+    // Clef and two sharps, placed exactly like the line's start
+    for entry in entries {
+        assert_eq!(entry.glyphs.len(), 3);
+        for (sticky, start) in entry.glyphs.iter().zip(&staff.structural_glyphs) {
+            assert_eq!(sticky.codepoint, start.codepoint);
+            assert_eq!(
+                (sticky.position.x, sticky.position.y),
+                (start.position.x, start.position.y)
+            );
+        }
+    }
+
+    // A mid-line clef change is picked up once it has scrolled past
+    let clefs = layout_with(&import_file("clef.mxl"), &config);
+    let staff = &clefs.systems[0].staff_groups[0].staves[0];
+    let start_clef = &staff.structural_glyphs[0];
+    let change = staff.structural_glyphs.last().unwrap();
+    assert_ne!(start_clef.codepoint, change.codepoint);
+    for entry in &staff.sticky_signatures {
+        let expected = if entry.x < change.position.x {
+            start_clef
+        } else {
+            change
+        };
+        // Before the change the start clef; well after it the new clef
+        if (entry.x - change.position.x).abs() > 100.0 {
+            assert_eq!(entry.glyphs[0].codepoint, expected.codepoint, "{entry:?}");
+        }
+    }
+    assert_eq!(
+        staff.sticky_signatures.last().unwrap().glyphs[0].codepoint,
+        change.codepoint
+    );
+
+    // Other modes have none
+    let scrolling = layout_with(&import_file("clef.mxl"), &LayoutConfig::default());
+    assert!(
+        scrolling.systems[0].staff_groups[0].staves[0]
+            .sticky_signatures
+            .is_empty()
+    );
+}
@@ -17,6 +17,7 @@ const CONFIG: LayoutConfig = LayoutConfig {
     units_per_space: 20.0,
     system_spacing: 200.0,
     system_height: 200.0,
+    ..LayoutConfig::DEFAULT
 };
 
 const TICKS_PER_MEASURE: u64 = 5760;
@@ -173,7 +174,7 @@ fn test_diagnostic_dump_tick_ranges() {
             .as_u64()
             .or_else(|| note["start_tick"]["value"].as_u64())
             .unwrap_or(0);
-        if st >= 110000 && st < 125000 {
+        if (110000..125000).contains(&st) {
             let pitch = note["pitch"]
                 .as_u64()
                 .or_else(|| note["pitch"]["midi_number"].as_u64())
@@ -248,7 +249,7 @@ fn test_m21_lh_eb4_has_explicit_accidental_in_json() {
                         .unwrap_or(0);
 
                     // Check if note is in M21 range and on bass staff
-                    if start_tick >= M21_START && start_tick < M21_END && staff_idx == 1 {
+                    if (M21_START..M21_END).contains(&start_tick) && staff_idx == 1 {
                         let pitch = note["pitch"]
                             .as_u64()
                             .or_else(|| note["pitch"]["midi_number"].as_u64())
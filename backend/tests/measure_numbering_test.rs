@@ -0,0 +1,205 @@
+//! Integration tests for measure numbering: printed numbers from MusicXML
+//! (pickups, `implicit="yes"`, suffixed numbers such as "12a") and the
+//! measure-numbering policy of the layout.
+
+mod common;
+
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::events::direction::Enclosure;
+use musicore_backend::domain::score::Score;
+use musicore_backend::layout::render::{SvgOptions, render_svg};
+use musicore_backend::layout::{
+    GlobalLayout, LayoutConfig, MeasureNumberPlacement, MeasureNumbering,
+};
+
+/// Single-staff 4/4 score; each measure is `(number attribute, implicit, beats)`
+fn musicxml(measures: &[(&str, bool, u32)]) -> String {
+    let mut body = String::new();
+    for (i, (number, implicit, beats)) in measures.iter().enumerate() {
+        let implicit = if *implicit { r#" implicit="yes""# } else { "" };
+        let attributes = if i == 0 {
+            "<attributes><divisions>1</divisions><time><beats>4</beats><beat-type>4</beat-type></time><clef><sign>G</sign><line>2</line></clef></attributes>"
+        } else {
+            ""
+        };
+        let notes = "<note><pitch><step>C</step><octave>5</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>"
+            .repeat(*beats as usize);
+        body.push_str(&format!(
+            r#"<measure number="{number}"{implicit}>{attributes}{notes}</measure>"#
+        ));
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Flute</part-name></score-part></part-list>
+  <part id="P1">{body}</part>
+</score-partwise>"#
+    )
+}
+
+fn import(measures: &[(&str, bool, u32)]) -> Score {
+    common::import(&musicxml(measures))
+}
+
+fn layout(score: &Score, measure_numbers: MeasureNumbering) -> GlobalLayout {
+    let config = LayoutConfig {
+        measure_numbers,
+        ..LayoutConfig::default()
+    };
+    common::layout_with(score, &config)
+}
+
+fn placement(placement: MeasureNumberPlacement) -> MeasureNumbering {
+    MeasureNumbering {
+        placement,
+        ..MeasureNumbering::standard()
+    }
+}
+
+/// Printed labels of all systems, in order
+fn labels(layout: &GlobalLayout) -> Vec<(u32, String)> {
+    layout
+        .systems
+        .iter()
+        .flat_map(|s| &s.measure_number_labels)
+        .map(|l| (l.measure_index, l.text.clone()))
+        .collect()
+}
+
+/// Pickup, three full measures, a measure split into "3" and "3a", then "4"
+const SPLIT_MEASURE: &[(&str, bool, u32)] = &[
+    ("0", true, 1),
+    ("1", false, 4),
+    ("2", false, 4),
+    ("3", false, 2),
+    ("3a", false, 2),
+    ("4", false, 4),
+];
+
+#[test]
+fn test_import_keeps_printed_numbers() {
+    let score = import(SPLIT_MEASURE);
+    assert_eq!(
+        score.measure_numbers,
+        vec![
+            None,
+            Some("1".to_string()),
+            Some("2".to_string()),
+            Some("3".to_string()),
+            Some("3a".to_string()),
+            Some("4".to_string()),
+        ]
+    );
+    let dto = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert_eq!(dto["measure_numbers"][0], serde_json::Value::Null);
+    assert_eq!(dto["measure_numbers"][4], "3a");
+}
+
+#[test]
+fn test_default_numbering_is_not_stored() {
+    // An implicit pickup followed by 1, 2, ... is what layout assumes anyway
+    let score = import(&[("1", true, 1), ("1", false, 4), ("2", false, 4)]);
+    assert!(score.pickup_ticks > 0);
+    assert!(score.measure_numbers.is_empty());
+
+    let layout = layout(&score, placement(MeasureNumberPlacement::EveryMeasure));
+    assert_eq!(
+        labels(&layout),
+        vec![(1, "1".to_string()), (2, "2".to_string())]
+    );
+}
+
+#[test]
+fn test_every_measure_numbers_all_but_the_pickup() {
+    let layout = layout(
+        &import(SPLIT_MEASURE),
+        placement(MeasureNumberPlacement::EveryMeasure),
+    );
+    let texts: Vec<String> = labels(&layout).into_iter().map(|(_, t)| t).collect();
+    assert_eq!(texts, ["1", "2", "3", "3a", "4"]);
+
+    // Each number sits above the top staff at its measure's start
+    for system in &layout.systems {
+        let top = system.staff_groups[0].staves[0].top_line_y();
+        for label in &system.measure_number_labels {
+            assert!(label.bounding_box.y + label.bounding_box.height < top);
+            let measure = system
+                .measure_layouts
+                .iter()
+                .find(|m| label.position.x >= m.x_start && label.position.x < m.x_end)
+                .expect("label over a measure");
+            assert!(label.position.x - measure.x_start < 3.0 * layout.units_per_space);
+        }
+    }
+}
+
+#[test]
+fn test_interval_and_system_start_placement() {
+    let score = import(SPLIT_MEASURE);
+    let every_two = layout(
+        &score,
+        placement(MeasureNumberPlacement::Interval { every: 2 }),
+    );
+    assert_eq!(
+        labels(&every_two),
+        vec![(2, "2".to_string()), (5, "4".to_string())]
+    );
+
+    let system_start = layout(&score, MeasureNumbering::standard());
+    for system in &system_start.systems {
+        assert!(system.measure_number_labels.len() <= 1);
+        // Over the clef, left of the first measure's content
+        for label in &system.measure_number_labels {
+            assert_eq!(label.position.x, 3.0 * system_start.units_per_space);
+        }
+    }
+    // The first system starts with the unnumbered pickup
+    assert!(system_start.systems[0].measure_number_labels.is_empty());
+
+    let hidden = layout(&score, placement(MeasureNumberPlacement::Hidden));
+    assert!(labels(&hidden).is_empty());
+}
+
+#[test]
+fn test_system_measure_number_follows_the_labels() {
+    let score = import(SPLIT_MEASURE);
+    let system_start = layout(&score, MeasureNumbering::standard());
+    // Not over the unnumbered pickup
+    assert!(system_start.systems[0].measure_number.is_none());
+    for system in &system_start.systems {
+        let number = system.measure_number.as_ref().map(|n| n.number.to_string());
+        let label = system.measure_number_labels.first();
+        assert_eq!(
+            number.as_deref(),
+            label.map(|l| l.text.trim_end_matches(|c: char| c.is_ascii_alphabetic()))
+        );
+    }
+
+    let hidden = layout(&score, placement(MeasureNumberPlacement::Hidden));
+    assert!(hidden.systems.iter().all(|s| s.measure_number.is_none()));
+}
+
+#[test]
+fn test_enclosed_measure_numbers() {
+    let score = import(SPLIT_MEASURE);
+    let layout = layout(
+        &score,
+        MeasureNumbering {
+            placement: MeasureNumberPlacement::EveryMeasure,
+            enclosure: Enclosure::Rectangle,
+        },
+    );
+    let label = &layout.systems[0].measure_number_labels[0];
+    let frame = label
+        .enclosure
+        .as_ref()
+        .expect("framed number")
+        .bounding_box;
+    assert_eq!(frame.x, label.bounding_box.x);
+    assert!(frame.x < label.position.x);
+    assert!(frame.y + frame.height > label.position.y);
+
+    let svg = render_svg(&layout, &SvgOptions::default());
+    assert!(svg.contains(r#"<rect x=""#));
+    assert!(svg.contains(">3a</text>"));
+}
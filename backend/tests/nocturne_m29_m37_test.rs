@@ -23,6 +23,7 @@ const CONFIG: LayoutConfig = LayoutConfig {
     units_per_space: 20.0,
     system_spacing: 200.0,
     system_height: 200.0,
+    ..LayoutConfig::DEFAULT
 };
 
 const TICKS_PER_MEASURE: u64 = 5760;
@@ -39,6 +40,7 @@ fn measure_start(m: u64) -> u64 {
     PICKUP_TICKS + (m - 1) * TICKS_PER_MEASURE
 }
 
+#[allow(dead_code)]
 fn measure_end(m: u64) -> u64 {
     measure_start(m) + TICKS_PER_MEASURE
 }
@@ -57,10 +59,10 @@ fn load_nocturne_layout() -> (serde_json::Value, serde_json::Value) {
 }
 
 /// Shared helper: find the system containing a given measure start tick.
-fn find_system_for_measure<'a>(
-    layout_json: &'a serde_json::Value,
+fn find_system_for_measure(
+    layout_json: &serde_json::Value,
     measure_start_tick: u64,
-) -> &'a serde_json::Value {
+) -> &serde_json::Value {
     let systems = layout_json["systems"].as_array().expect("systems array");
     systems
         .iter()
@@ -230,7 +232,7 @@ fn test_nocturne_m34_m36_rest_centering() {
     // Search last 4 systems for rest glyphs (M34-M36 are in the final systems)
     let mut whole_rest_count = 0;
     let search_start = systems.len().saturating_sub(4);
-    for (si, system) in systems[search_start..].iter().enumerate() {
+    for system in &systems[search_start..] {
         let staves = match system["staff_groups"][0]["staves"].as_array() {
             Some(s) => s,
             None => continue,
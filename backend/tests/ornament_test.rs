@@ -0,0 +1,170 @@
+//! Integration tests for arpeggios, glissandos and tremolos: import,
+//! playback expansion and layout.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::domain::events::ornament::{ArpeggioStyle, TremoloKind};
+use musicore_backend::layout::{Glyph, Staff};
+
+/// Measure 1: arpeggiated chord C4-E4-G#4, wavy glissando D4 → A4, single
+/// tremolo on C5. Measure 2: two-note tremolo between half notes E4 and G4.
+const ORNAMENTS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Piano</part-name></score-part></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>2</divisions>
+        <time><beats>4</beats><beat-type>4</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+      </attributes>
+      <note><pitch><step>C</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type><notations><arpeggiate/></notations></note>
+      <note><chord/><pitch><step>E</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type><notations><arpeggiate/></notations></note>
+      <note><chord/><pitch><step>G</step><alter>1</alter><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type><accidental>sharp</accidental><notations><arpeggiate/></notations></note>
+      <note><pitch><step>D</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type><notations><glissando type="start" number="1"/></notations></note>
+      <note><pitch><step>A</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type><notations><glissando type="stop" number="1"/></notations></note>
+      <note><pitch><step>C</step><octave>5</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type><notations><ornaments><tremolo type="single">3</tremolo></ornaments></notations></note>
+    </measure>
+    <measure number="2">
+      <note><pitch><step>E</step><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>half</type><notations><ornaments><tremolo type="start">2</tremolo></ornaments></notations></note>
+      <note><pitch><step>G</step><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>half</type><notations><ornaments><tremolo type="stop">2</tremolo></ornaments></notations></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+/// Piano chord arpeggiated across both staves
+const CROSS_STAFF_ARPEGGIO: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Piano</part-name></score-part></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>1</divisions>
+        <time><beats>4</beats><beat-type>4</beat-type></time>
+        <staves>2</staves>
+        <clef number="1"><sign>G</sign><line>2</line></clef>
+        <clef number="2"><sign>F</sign><line>4</line></clef>
+      </attributes>
+      <note><pitch><step>E</step><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type><staff>1</staff><notations><arpeggiate direction="up"/></notations></note>
+      <note><chord/><pitch><step>C</step><octave>5</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type><staff>1</staff><notations><arpeggiate direction="up"/></notations></note>
+      <backup><duration>4</duration></backup>
+      <note><pitch><step>C</step><octave>3</octave></pitch><duration>4</duration><voice>5</voice><type>whole</type><staff>2</staff><notations><arpeggiate direction="up"/></notations></note>
+      <note><chord/><pitch><step>G</step><octave>3</octave></pitch><duration>4</duration><voice>5</voice><type>whole</type><staff>2</staff><notations><arpeggiate direction="up"/></notations></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+fn glyphs(staff: &Staff) -> Vec<&Glyph> {
+    staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| run.glyphs.iter())
+        .collect()
+}
+
+#[test]
+fn test_import_arpeggio_glissando_tremolo() {
+    let score = import(ORNAMENTS);
+    let notes = &score.instruments[0].staves[0].voices[0].interval_events;
+    assert_eq!(notes.len(), 8);
+
+    assert!(
+        notes[..3]
+            .iter()
+            .all(|n| n.arpeggio == Some(ArpeggioStyle::Wavy))
+    );
+    assert_eq!(notes[3].gliss_next, Some(notes[4].id));
+    assert!(notes[3].gliss_wavy, "<glissando> defaults to a wavy line");
+    assert_eq!(notes[4].gliss_next, None);
+
+    let single = notes[5].tremolo.expect("single tremolo");
+    assert_eq!((single.kind, single.strokes), (TremoloKind::Single, 3));
+    assert_eq!(notes[6].tremolo.map(|t| t.kind), Some(TremoloKind::Start));
+    assert_eq!(notes[7].tremolo.map(|t| t.kind), Some(TremoloKind::Stop));
+}
+
+#[test]
+fn test_playback_expands_tremolos_and_arpeggios() {
+    let score = import(ORNAMENTS);
+    let voice = &score.instruments[0].staves[0].voices[0];
+    let played = voice.playback_notes();
+
+    // Rolled chord: bottom-up, one step apart
+    let chord: Vec<(u8, u32)> = played
+        .iter()
+        .filter(|p| p.start_tick < 960)
+        .map(|p| (p.pitch.value(), p.start_tick))
+        .collect();
+    assert_eq!(chord, vec![(60, 0), (64, 60), (68, 120)]);
+
+    // Single tremolo, 3 strokes: a quarter played as eight 32nds
+    let single = played
+        .iter()
+        .filter(|p| p.start_tick >= 2880 && p.start_tick < 3840)
+        .count();
+    assert_eq!(single, 8);
+
+    // Two-note tremolo, 2 strokes: two halves alternating in 16ths
+    let alternating: Vec<u8> = played
+        .iter()
+        .filter(|p| p.start_tick >= 3840)
+        .map(|p| p.pitch.value())
+        .collect();
+    assert_eq!(alternating.len(), 16);
+    assert_eq!(&alternating[..4], &[64, 67, 64, 67]);
+}
+
+#[test]
+fn test_layout_draws_arpeggio_glissando_and_tremolo_strokes() {
+    let layout = layout(&import(ORNAMENTS));
+    let staff = &layout.systems[0].staff_groups[0].staves[0];
+    let glyphs = glyphs(staff);
+
+    // Arpeggio left of the chord's noteheads and its accidental
+    assert_eq!(staff.arpeggios.len(), 1);
+    let arpeggio = &staff.arpeggios[0];
+    let chord_left = glyphs
+        .iter()
+        .filter(|g| g.source_reference.event_index < 3 && g.codepoint != "\u{0000}")
+        .map(|g| g.bounding_box.x)
+        .fold(f32::MAX, f32::min);
+    assert!(arpeggio.x < chord_left);
+    assert!(arpeggio.y_bottom - arpeggio.y_top > 40.0);
+    assert!(arpeggio.points.len() > 4, "wavy arpeggio is a zigzag");
+    assert!(arpeggio.arrow.is_empty());
+
+    // Wavy glissando rising from D4 to A4
+    assert_eq!(staff.glissandos.len(), 1);
+    let gliss = &staff.glissandos[0];
+    assert!(gliss.wavy && !gliss.open_start && !gliss.open_end);
+    let (start, end) = (gliss.points[0], *gliss.points.last().unwrap());
+    assert!(start.x < end.x && start.y > end.y);
+
+    // Single tremolo: three-stroke glyph on the stem
+    assert!(glyphs.iter().any(|g| g.codepoint == "\u{E222}"));
+
+    // Two-note tremolo: two strokes between the half-note stems
+    let strokes: Vec<_> = glyphs
+        .iter()
+        .filter(|g| g.codepoint == "\u{0001}")
+        .collect();
+    assert_eq!(strokes.len(), 2);
+    assert!(strokes[0].bounding_box.width > 0.0);
+}
+
+#[test]
+fn test_cross_staff_arpeggio_drawn_once_across_both_staves() {
+    let layout = layout(&import(CROSS_STAFF_ARPEGGIO));
+    let group = &layout.systems[0].staff_groups[0];
+    let (treble, bass) = (&group.staves[0], &group.staves[1]);
+
+    assert_eq!(treble.arpeggios.len(), 1);
+    assert!(bass.arpeggios.is_empty());
+    let arpeggio = &treble.arpeggios[0];
+    assert_eq!(arpeggio.style, ArpeggioStyle::Up);
+    assert!(arpeggio.y_top < treble.staff_lines[4].y_position);
+    assert!(arpeggio.y_bottom > bass.staff_lines[0].y_position);
+    // Arrowhead at the top
+    assert_eq!(arpeggio.arrow[1].y, arpeggio.y_top);
+}
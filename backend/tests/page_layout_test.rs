@@ -0,0 +1,153 @@
+//! Integration tests for paginated layout: page breaks from
+//! `<print new-page="yes">`, vertical justification and page furniture.
+
+mod common;
+
+use common::{import_file, layout_with};
+use musicore_backend::domain::score::Score;
+use musicore_backend::layout::{GlobalLayout, LayoutConfig, PageConfig, TextAlign};
+
+fn layout(score: &Score, page: Option<PageConfig>) -> GlobalLayout {
+    let config = LayoutConfig {
+        page,
+        ..LayoutConfig::default()
+    };
+    layout_with(score, &config)
+}
+
+#[test]
+fn test_import_page_breaks() {
+    // The nocturne's engraver started pages at printed measures 10, 21 and 31
+    // (the pickup is measure 0)
+    let score = import_file("Chopin_NocturneOp9No2.mxl");
+    assert_eq!(score.page_breaks, vec![9, 20, 30]);
+    // Pachelbel has system breaks only
+    assert!(import_file("Pachelbel_CanonD.mxl").page_breaks.is_empty());
+}
+
+#[test]
+fn test_systems_fit_on_pages_and_honor_page_breaks() {
+    let score = import_file("Chopin_NocturneOp9No2.mxl");
+    let config = PageConfig {
+        header: Some("Nocturne".to_string()),
+        footer: Some("Public domain".to_string()),
+        ..PageConfig::default()
+    };
+    let layout = layout(&score, Some(config.clone()));
+
+    assert_eq!(layout.pages.len(), 4);
+    let mut next_system = 0;
+    for (i, page) in layout.pages.iter().enumerate() {
+        assert_eq!(page.number as usize, i + 1);
+        // Systems are assigned in order without gaps
+        assert!(!page.system_indices.is_empty());
+        for &index in &page.system_indices {
+            assert_eq!(index, next_system);
+            next_system += 1;
+            let b = layout.systems[index].bounding_box;
+            let content = page.content_box;
+            assert!(b.y >= content.y - 0.01 && b.y + b.height <= content.y + content.height + 0.01);
+            assert!(b.width <= content.width + 0.01);
+        }
+    }
+    assert_eq!(next_system, layout.systems.len());
+
+    // Each hinted page starts with the hinted measure
+    let measure_at_page_start: Vec<u32> = layout.pages[1..]
+        .iter()
+        .map(|p| {
+            layout.systems[p.system_indices[0]]
+                .measure_number
+                .as_ref()
+                .unwrap()
+                .number
+        })
+        .collect();
+    assert_eq!(measure_at_page_start, vec![10, 21, 31]);
+
+    // Title space on the first page only
+    let mm = config.units_per_mm(layout.units_per_space);
+    let first = &layout.pages[0];
+    let second = &layout.pages[1];
+    assert!((first.content_box.y - first.bounding_box.y - 45.0 * mm).abs() < 0.01);
+    assert!((second.content_box.y - second.bounding_box.y - 15.0 * mm).abs() < 0.01);
+
+    // Header and page number from page 2; page numbers on the outer corner
+    assert!(first.header.is_none() && first.number_label.is_none());
+    assert_eq!(second.header.as_ref().unwrap().text, "Nocturne");
+    let even = second.number_label.as_ref().unwrap();
+    assert_eq!((even.text.as_str(), even.align), ("2", TextAlign::Start));
+    let odd = layout.pages[2].number_label.as_ref().unwrap();
+    assert_eq!((odd.text.as_str(), odd.align), ("3", TextAlign::End));
+    assert!(layout.pages.iter().all(|p| p.footer.is_some()));
+    // Margins hold the page text
+    let footer = first.footer.as_ref().unwrap();
+    assert!(footer.position.y > first.content_box.y + first.content_box.height);
+    assert!(even.position.y < second.content_box.y);
+
+    // Pages do not overlap
+    for pair in layout.pages.windows(2) {
+        let a = pair[0].bounding_box;
+        assert!(a.y + a.height < pair[1].bounding_box.y);
+    }
+    let last = layout.pages.last().unwrap().bounding_box;
+    assert_eq!(layout.total_height, last.y + last.height);
+}
+
+#[test]
+fn test_full_pages_are_justified_to_the_bottom_margin() {
+    // Without page-break hints and with a small page, every page but the
+    // last is full and stretched to the bottom margin
+    let mut score = import_file("Bach_InventionNo1.mxl");
+    score.page_breaks.clear();
+    let config = PageConfig {
+        height_mm: 150.0,
+        ..PageConfig::default()
+    };
+    let layout = layout(&score, Some(config));
+    assert!(layout.pages.len() > 2);
+
+    let bottom = |page: &musicore_backend::layout::Page| {
+        let last = &layout.systems[*page.system_indices.last().unwrap()].bounding_box;
+        last.y + last.height
+    };
+    for page in &layout.pages[..layout.pages.len() - 1] {
+        let content = page.content_box;
+        if page.system_indices.len() > 1 {
+            assert!((bottom(page) - (content.y + content.height)).abs() < 0.5);
+        }
+    }
+
+    // Systems move as a whole: staves stay inside their system
+    for system in &layout.systems {
+        let b = system.bounding_box;
+        for staff in system.staff_groups.iter().flat_map(|g| &g.staves) {
+            let (top, bottom) = (
+                staff.staff_lines[0].y_position,
+                staff.staff_lines[4].y_position,
+            );
+            assert!(top >= b.y && bottom <= b.y + b.height);
+        }
+    }
+}
+
+#[test]
+fn test_scroll_mode_has_no_pages() {
+    let score = import_file("Chopin_NocturneOp9No2.mxl");
+    let scrolling = layout(&score, None);
+    assert!(scrolling.pages.is_empty());
+    let json = serde_json::to_value(&scrolling).unwrap();
+    assert!(json.get("pages").is_none());
+
+    // Page breaks do not split systems when scrolling
+    let mut unhinted = score.clone();
+    unhinted.page_breaks.clear();
+    let ticks = |layout: &GlobalLayout| -> Vec<u32> {
+        layout
+            .systems
+            .iter()
+            .map(|s| s.tick_range.start_tick)
+            .collect()
+    };
+    assert_eq!(ticks(&scrolling), ticks(&layout(&unhinted, None)));
+}
@@ -0,0 +1,181 @@
+//! Integration tests for part groups: import of `<part-group>`, nested
+//! group brackets, barlines joined through a group, and instruments with
+//! more than two staves.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::part_group::{GroupSymbol, PartGroup};
+use musicore_backend::layout::BracketType;
+use musicore_backend::layout::render::{SvgOptions, render_svg};
+
+/// One-measure part with a whole note on the given clef
+fn part(id: &str, clef: &str) -> String {
+    format!(
+        r#"<part id="{id}"><measure number="1">
+      <attributes><divisions>1</divisions><time><beats>4</beats><beat-type>4</beat-type></time>{clef}</attributes>
+      <note><pitch><step>C</step><octave>4</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type></note>
+    </measure></part>"#
+    )
+}
+
+/// A string quartet bracketed as "Strings" with barlines joined through it
+/// and the two violins in a square sub-bracket, then a three-staff organ.
+fn quartet_and_organ() -> String {
+    let treble = "<clef><sign>G</sign><line>2</line></clef>";
+    let organ = r#"<part id="P5"><measure number="1">
+      <attributes><divisions>1</divisions><time><beats>4</beats><beat-type>4</beat-type></time>
+        <staves>3</staves>
+        <clef number="1"><sign>G</sign><line>2</line></clef>
+        <clef number="2"><sign>F</sign><line>4</line></clef>
+        <clef number="3"><sign>F</sign><line>4</line></clef>
+      </attributes>
+      <note><pitch><step>C</step><octave>5</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type><staff>1</staff></note>
+      <backup><duration>4</duration></backup>
+      <note><pitch><step>C</step><octave>3</octave></pitch><duration>4</duration><voice>2</voice><type>whole</type><staff>2</staff></note>
+      <backup><duration>4</duration></backup>
+      <note><pitch><step>C</step><octave>2</octave></pitch><duration>4</duration><voice>3</voice><type>whole</type><staff>3</staff></note>
+    </measure></part>"#;
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list>
+    <part-group type="start" number="1">
+      <group-name>Strings</group-name>
+      <group-symbol>bracket</group-symbol>
+      <group-barline>yes</group-barline>
+    </part-group>
+    <part-group type="start" number="2"><group-symbol>square</group-symbol></part-group>
+    <score-part id="P1"><part-name>Violin I</part-name></score-part>
+    <score-part id="P2"><part-name>Violin II</part-name></score-part>
+    <part-group type="stop" number="2"/>
+    <score-part id="P3"><part-name>Viola</part-name></score-part>
+    <score-part id="P4"><part-name>Cello</part-name></score-part>
+    <part-group type="stop" number="1"/>
+    <score-part id="P5"><part-name>Organ</part-name></score-part>
+  </part-list>
+  {}
+  {}
+  {}
+  {}
+  {organ}
+</score-partwise>"#,
+        part("P1", treble),
+        part("P2", treble),
+        part("P3", "<clef><sign>C</sign><line>3</line></clef>"),
+        part("P4", "<clef><sign>F</sign><line>4</line></clef>"),
+    )
+}
+
+#[test]
+fn test_import_part_groups() {
+    let score = import(&quartet_and_organ());
+    assert_eq!(score.instruments.len(), 5);
+    assert_eq!(score.instruments[4].staves.len(), 3);
+
+    assert_eq!(
+        score.part_groups,
+        vec![
+            PartGroup::new(0, 3, GroupSymbol::Bracket)
+                .with_barline(true)
+                .with_name("Strings"),
+            PartGroup::new(0, 1, GroupSymbol::Square),
+        ]
+    );
+
+    let dto = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert_eq!(dto["part_groups"][0]["symbol"], "Bracket");
+    assert_eq!(dto["part_groups"][1]["last_instrument"], 1);
+}
+
+#[test]
+fn test_nested_group_brackets() {
+    let layout = layout(&import(&quartet_and_organ()));
+    let system = &layout.systems[0];
+    let groups = &system.staff_groups;
+
+    let brackets = &system.group_brackets;
+    assert_eq!(brackets.len(), 2);
+    let (outer, inner) = (&brackets[0], &brackets[1]);
+    assert_eq!(outer.bracket_type, BracketType::Bracket);
+    assert_eq!(inner.bracket_type, BracketType::Square);
+    assert_eq!((outer.depth, inner.depth), (1, 0));
+    assert!(outer.bracket_glyph.x < inner.bracket_glyph.x);
+
+    // Each bracket spans its instruments' staves
+    let top = groups[0].staves[0].top_line_y();
+    assert_eq!(outer.bracket_glyph.bounding_box.y, top);
+    assert_eq!(inner.bracket_glyph.bounding_box.y, top);
+    let cello_bottom = groups[3].staves[0].bottom_line_y();
+    let outer_bottom = outer.bracket_glyph.bounding_box.y + outer.bracket_glyph.bounding_box.height;
+    assert!((outer_bottom - cello_bottom).abs() < 0.01);
+    let violin_bottom = groups[1].staves[0].bottom_line_y();
+    let inner_bottom = inner.bracket_glyph.bounding_box.y + inner.bracket_glyph.bounding_box.height;
+    assert!((inner_bottom - violin_bottom).abs() < 0.01);
+
+    // Instrument names stay clear of the outermost bracket
+    for group in groups {
+        let label = group.name_label.as_ref().unwrap();
+        assert!(label.position.x < outer.bracket_glyph.bounding_box.x);
+    }
+}
+
+#[test]
+fn test_group_barlines_span_the_group() {
+    let layout = layout(&import(&quartet_and_organ()));
+    let groups = &layout.systems[0].staff_groups;
+
+    // The quartet's barlines run from the first violin to the cello
+    let top = groups[0].staves[0].top_line_y();
+    let cello_bottom = groups[3].staves[0].bottom_line_y();
+    let bar_lines = &groups[0].staves[0].bar_lines;
+    assert!(!bar_lines.is_empty());
+    for segment in bar_lines.iter().flat_map(|b| &b.segments) {
+        assert!((segment.y_start - top).abs() < 0.01);
+        assert!((segment.y_end - cello_bottom).abs() < 0.01);
+    }
+    for group in &groups[1..4] {
+        assert!(group.staves.iter().all(|s| s.bar_lines.is_empty()));
+    }
+
+    // The organ is outside the group and keeps its own barlines
+    assert!(!groups[4].staves[0].bar_lines.is_empty());
+}
+
+#[test]
+fn test_three_staff_instrument() {
+    let layout = layout(&import(&quartet_and_organ()));
+    let organ = &layout.systems[0].staff_groups[4];
+    assert_eq!(organ.staves.len(), 3);
+    assert!(organ.staves[0].bottom_line_y() < organ.staves[1].top_line_y());
+    assert!(organ.staves[1].bottom_line_y() < organ.staves[2].top_line_y());
+
+    // One brace and one set of barlines across all three staves
+    assert_eq!(organ.bracket_type, BracketType::Brace);
+    let brace = organ.bracket_glyph.as_ref().unwrap().bounding_box;
+    let (top, bottom) = (
+        organ.staves[0].top_line_y(),
+        organ.staves[2].bottom_line_y(),
+    );
+    assert!((brace.y - top).abs() < 0.01);
+    assert!((brace.y + brace.height - bottom).abs() < 0.01);
+    let segment = &organ.staves[0].bar_lines[0].segments[0];
+    assert!((segment.y_end - bottom).abs() < 0.01);
+    assert!(organ.staves[1..].iter().all(|s| s.bar_lines.is_empty()));
+
+    // Each staff has its note
+    for staff in &organ.staves {
+        assert!(!staff.glyph_runs.is_empty());
+    }
+}
+
+#[test]
+fn test_svg_draws_group_brackets() {
+    let layout = layout(&import(&quartet_and_organ()));
+    let svg = render_svg(&layout, &SvgOptions::default());
+    // The outer bracket's vertical bar, left of the system
+    let outer_x = layout.systems[0].group_brackets[0].bracket_glyph.x;
+    assert!(outer_x < 0.0);
+    assert!(svg.contains(&format!(r#"<line x1="{outer_x}" "#)));
+}
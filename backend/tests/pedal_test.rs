@@ -0,0 +1,172 @@
+//! Integration tests for piano pedal (`<pedal>`) import and layout.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::events::pedal::PedalKind;
+use musicore_backend::domain::value_objects::Tick;
+
+/// One 4/4 measure of eighth notes (divisions = 2), optionally preceded by directions
+fn measure(number: usize, directions: &str) -> String {
+    let attributes = if number == 1 {
+        "<attributes><divisions>2</divisions><time><beats>4</beats><beat-type>4</beat-type></time><clef><sign>G</sign><line>2</line></clef></attributes>"
+    } else {
+        ""
+    };
+    let notes: String = ["C", "D", "E", "F", "G", "A", "B", "C"]
+        .iter()
+        .map(|step| {
+            format!(
+                "<note><pitch><step>{step}</step><octave>4</octave></pitch><duration>1</duration><voice>1</voice><type>eighth</type></note>"
+            )
+        })
+        .collect();
+    format!("<measure number=\"{number}\">{attributes}{directions}{notes}</measure>")
+}
+
+fn direction(content: &str) -> String {
+    format!(
+        "<direction placement=\"below\"><direction-type>{content}</direction-type><staff>1</staff></direction>"
+    )
+}
+
+fn score(measures: &[String]) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Piano</part-name></score-part></part-list>
+  <part id="P1">{}</part>
+</score-partwise>"#,
+        measures.concat()
+    )
+}
+
+#[test]
+fn test_import_pedal_start_change_stop() {
+    let xml = score(&[
+        measure(1, &direction(r#"<pedal type="start" line="yes"/>"#)),
+        // Change on beat 3: offset of 4 divisions = 1920 ticks
+        measure(
+            2,
+            &direction(r#"<pedal type="change" line="yes"/>"#)
+                .replace("<staff>", "<offset>4</offset><staff>"),
+        ),
+        // Release and re-press at the barline, as a sign-style pedal
+        measure(
+            3,
+            &format!(
+                "{}{}",
+                direction(r#"<pedal type="stop" line="yes"/>"#),
+                direction(r#"<pedal type="start"/>"#)
+            ),
+        ),
+        measure(4, &direction(r#"<pedal type="stop"/>"#)),
+    ]);
+    let score = import(&xml);
+    let staff = &score.instruments[0].staves[0];
+
+    assert_eq!(staff.pedal_regions.len(), 2);
+    let first = &staff.pedal_regions[0];
+    assert_eq!(first.kind, PedalKind::Sustain);
+    assert_eq!((first.start_tick, first.end_tick), (0, 7680));
+    assert_eq!(first.change_ticks, vec![3840 + 1920]);
+    assert!(first.line);
+    assert!(!first.sign, "sign defaults to off for line-style pedals");
+    assert_eq!(first.press_spans(), vec![(0, 5760), (5760, 7680)]);
+
+    let second = &staff.pedal_regions[1];
+    assert_eq!((second.start_tick, second.end_tick), (7680, 11520));
+    assert!(second.sign && !second.line);
+
+    assert!(staff.is_pedal_down_at(PedalKind::Sustain, Tick::new(100)));
+    assert!(!staff.is_pedal_down_at(PedalKind::Sustain, Tick::new(11520)));
+    assert!(!staff.is_pedal_down_at(PedalKind::UnaCorda, Tick::new(100)));
+}
+
+#[test]
+fn test_sound_only_pedals_are_playback_regions() {
+    let xml = score(&[
+        measure(
+            1,
+            r#"<direction><direction-type><words>una corda</words></direction-type><sound soft-pedal="yes"/></direction>"#,
+        ),
+        measure(
+            2,
+            r#"<direction><direction-type><words>tre corde</words></direction-type><sound soft-pedal="no"/></direction>"#,
+        ),
+        // Unclosed sustain pedal runs to the end of the piece
+        measure(3, &direction(r#"<pedal type="start"/>"#)),
+    ]);
+    let score = import(&xml);
+    let regions = &score.instruments[0].staves[0].pedal_regions;
+
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].kind, PedalKind::UnaCorda);
+    assert_eq!((regions[0].start_tick, regions[0].end_tick), (0, 3840));
+    assert!(!regions[0].sign && !regions[0].line);
+    assert_eq!(regions[1].kind, PedalKind::Sustain);
+    assert_eq!((regions[1].start_tick, regions[1].end_tick), (7680, 11520));
+
+    let layout = layout(&score);
+    let marks: Vec<_> = layout
+        .systems
+        .iter()
+        .flat_map(|s| s.staff_groups[0].staves[0].pedal_marks.iter())
+        .collect();
+    // The una corda region has no visible sign, only playback data
+    assert_eq!(marks.len(), 1);
+    assert_eq!(marks[0].kind, PedalKind::Sustain);
+}
+
+#[test]
+fn test_pedal_line_split_across_systems() {
+    let mut measures = vec![measure(
+        1,
+        &direction(r#"<pedal type="start" line="yes"/>"#),
+    )];
+    for n in 2..16 {
+        measures.push(measure(n, ""));
+    }
+    // Release on beat 3 of the last measure
+    measures.push(measure(
+        16,
+        &direction(r#"<pedal type="stop" line="yes"/>"#)
+            .replace("<staff>", "<offset>4</offset><staff>"),
+    ));
+    let score = import(&score(&measures));
+    let json = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert!(json["instruments"][0]["staves"][0]["pedal_regions"].is_array());
+
+    let layout = layout(&score);
+    assert!(
+        layout.systems.len() > 1,
+        "score should need several systems"
+    );
+
+    let marks: Vec<_> = layout
+        .systems
+        .iter()
+        .filter_map(|s| s.staff_groups[0].staves[0].pedal_marks.first())
+        .collect();
+    assert_eq!(marks.len(), layout.systems.len());
+
+    let first = marks.first().unwrap();
+    let last = marks.last().unwrap();
+    assert!(!first.open_start && first.open_end);
+    assert!(last.open_start && !last.open_end);
+    for mark in &marks[1..marks.len() - 1] {
+        assert!(mark.open_start && mark.open_end);
+    }
+
+    // Hooks only at the real start and end of the region
+    let first_points = &first.line.as_ref().unwrap().points;
+    assert!(first_points[0].y < first_points[1].y);
+    assert_eq!(
+        first_points.last().unwrap().y,
+        first_points[first_points.len() - 2].y
+    );
+    let last_points = &last.line.as_ref().unwrap().points;
+    assert_eq!(last_points[0].y, last_points[1].y);
+    assert!(last_points.last().unwrap().y < last_points[0].y);
+}
@@ -0,0 +1,234 @@
+//! Integration tests for rest filling: gaps of scores built note by note are
+//! completed with rests split along the beats, empty measures get a
+//! whole-measure rest, and refilling after edits replaces stale rests.
+
+mod common;
+
+use common::layout;
+use musicore_backend::domain::events::{note::Note, time_signature::TimeSignatureEvent};
+use musicore_backend::domain::instrument::Instrument;
+use musicore_backend::domain::lint::ScoreLinter;
+use musicore_backend::domain::score::Score;
+use musicore_backend::domain::staff::Staff;
+use musicore_backend::domain::value_objects::{Pitch, Tick};
+use musicore_backend::domain::voice::Voice;
+
+const WHOLE: u32 = 3840;
+const HALF: u32 = 1920;
+const QUARTER: u32 = 960;
+const EIGHTH: u32 = 480;
+
+fn note(start: u32, duration: u32) -> Note {
+    Note::new(Tick::new(start), duration, Pitch::new(67).unwrap()).unwrap()
+}
+
+/// A piano score whose upper staff's first voice holds `notes`
+fn piano(notes: Vec<Note>) -> Score {
+    let mut score = Score::new();
+    let mut instrument = Instrument::new("Piano".to_string());
+    instrument.staves[0].voices[0].interval_events = notes;
+    score.add_instrument(instrument);
+    score
+}
+
+fn voice(score: &Score, staff: usize, voice: usize) -> &Voice {
+    &score.instruments[0].staves[staff].voices[voice]
+}
+
+/// `(start, duration, note type)` of each rest of a voice
+fn rests(voice: &Voice) -> Vec<(u32, u32, Option<&str>)> {
+    voice
+        .rest_events
+        .iter()
+        .map(|r| {
+            (
+                r.start_tick.value(),
+                r.duration_ticks,
+                r.note_type.as_deref(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_gaps_split_along_beats_in_common_time() {
+    // Beat 2 of measure 1; beats 1-3 of measure 2; beats 2-3 of measure 3
+    let mut score = piano(vec![
+        note(QUARTER, QUARTER),
+        note(WHOLE, HALF + QUARTER),
+        note(2 * WHOLE + QUARTER, HALF),
+    ]);
+    assert_eq!(score.fill_rests(), 5);
+    assert_eq!(
+        rests(voice(&score, 0, 0)),
+        vec![
+            (0, QUARTER, Some("quarter")),
+            (HALF, HALF, Some("half")),
+            (WHOLE + HALF + QUARTER, QUARTER, Some("quarter")),
+            (2 * WHOLE, QUARTER, Some("quarter")),
+            (2 * WHOLE + HALF + QUARTER, QUARTER, Some("quarter")),
+        ]
+    );
+    assert!(ScoreLinter::new().lint(&score).is_clean());
+}
+
+#[test]
+fn test_no_rest_across_the_middle_of_the_measure() {
+    // A quarter then silence: a quarter rest before the half, not a dotted half
+    let mut score = piano(vec![note(0, QUARTER), note(WHOLE, WHOLE)]);
+    score.fill_rests();
+    assert_eq!(
+        rests(voice(&score, 0, 0)),
+        vec![
+            (QUARTER, QUARTER, Some("quarter")),
+            (HALF, HALF, Some("half")),
+        ]
+    );
+
+    // An eighth off the beat is completed to the beat first
+    let mut score = piano(vec![note(0, EIGHTH)]);
+    score.fill_rests();
+    assert_eq!(
+        rests(voice(&score, 0, 0)),
+        vec![
+            (EIGHTH, EIGHTH, Some("eighth")),
+            (QUARTER, QUARTER, Some("quarter")),
+            (HALF, HALF, Some("half")),
+        ]
+    );
+}
+
+#[test]
+fn test_compound_meter_rests_per_beat() {
+    // A 4/4 measure, then an eighth in 6/8
+    let mut score = piano(vec![note(0, WHOLE), note(WHOLE, EIGHTH)]);
+    score
+        .add_time_signature_event(TimeSignatureEvent::new(Tick::new(WHOLE), 6, 8))
+        .unwrap();
+    score.fill_rests();
+    // Two eighths to the end of beat 1, then a dotted quarter for beat 2
+    assert_eq!(
+        rests(voice(&score, 0, 0)),
+        vec![
+            (WHOLE + EIGHTH, EIGHTH, Some("eighth")),
+            (WHOLE + QUARTER, EIGHTH, Some("eighth")),
+            (WHOLE + 3 * EIGHTH, 3 * EIGHTH, Some("quarter")),
+        ]
+    );
+    assert!(ScoreLinter::new().lint(&score).is_clean());
+
+    // The dotted quarter rest is drawn with its dot
+    let layout = layout(&score);
+    let dots: usize = layout
+        .systems
+        .iter()
+        .map(|s| s.staff_groups[0].staves[0].notation_dots.len())
+        .sum();
+    assert_eq!(dots, 1);
+}
+
+#[test]
+fn test_empty_measures_get_whole_measure_rests() {
+    // 4/4 then 3/4; the second and third measures and the lower staff are empty
+    let bar = 3 * QUARTER;
+    let mut score = piano(vec![note(0, WHOLE), note(WHOLE + 2 * bar, QUARTER)]);
+    score.instruments[0].add_staff(Staff::new());
+    score
+        .add_time_signature_event(TimeSignatureEvent::new(Tick::new(WHOLE), 3, 4))
+        .unwrap();
+    score.fill_rests();
+
+    let upper = voice(&score, 0, 0);
+    assert_eq!(
+        rests(upper),
+        vec![
+            (WHOLE, bar, Some("whole")),
+            (WHOLE + bar, bar, Some("whole")),
+            // No half rest from beat 2 of 3/4
+            (WHOLE + 2 * bar + QUARTER, QUARTER, Some("quarter")),
+            (WHOLE + 2 * bar + HALF, QUARTER, Some("quarter")),
+        ]
+    );
+    assert!(upper.rest_events[0].is_measure_rest);
+    assert!(!upper.rest_events[2].is_measure_rest);
+
+    let lower = &voice(&score, 1, 0).rest_events;
+    assert_eq!(lower.len(), 4);
+    assert!(lower.iter().all(|r| r.is_measure_rest && r.staff == 2));
+    assert_eq!(lower[0].duration_ticks, WHOLE);
+    assert!(ScoreLinter::new().lint(&score).is_clean());
+}
+
+#[test]
+fn test_pickup_beats_count_back_from_its_end() {
+    // A three-beat pickup starting with a quarter: beats 3 and 4 make a half
+    let mut score = piano(vec![note(0, QUARTER), note(HALF + QUARTER, WHOLE)]);
+    score.pickup_ticks = HALF + QUARTER;
+    score.fill_rests();
+    assert_eq!(
+        rests(voice(&score, 0, 0)),
+        vec![(QUARTER, HALF, Some("half"))]
+    );
+}
+
+#[test]
+fn test_secondary_voices_only_where_they_sing() {
+    let mut score = piano(vec![note(0, WHOLE), note(WHOLE, WHOLE)]);
+    let mut alto = Voice::new();
+    alto.interval_events = vec![note(WHOLE + QUARTER, QUARTER)];
+    score.instruments[0].staves[0].voices.push(alto);
+    score.fill_rests();
+
+    let alto = voice(&score, 0, 1);
+    assert_eq!(
+        rests(alto),
+        vec![
+            (WHOLE, QUARTER, Some("quarter")),
+            (WHOLE + HALF, HALF, Some("half")),
+        ]
+    );
+    assert!(
+        alto.rest_events
+            .iter()
+            .all(|r| r.voice == 2 && r.staff == 1)
+    );
+    assert!(voice(&score, 0, 0).rest_events.is_empty());
+}
+
+#[test]
+fn test_refilling_after_edits() {
+    let mut score = piano(vec![note(0, QUARTER)]);
+    score.fill_rests();
+    assert_eq!(score.fill_rests(), 0);
+
+    // A note added where rests were replaces them
+    score.instruments[0].staves[0].voices[0]
+        .add_note(note(HALF, QUARTER))
+        .unwrap();
+    score.fill_rests();
+    assert_eq!(
+        rests(voice(&score, 0, 0)),
+        vec![
+            (QUARTER, QUARTER, Some("quarter")),
+            (HALF + QUARTER, QUARTER, Some("quarter")),
+        ]
+    );
+    assert!(ScoreLinter::new().lint(&score).is_clean());
+}
+
+#[test]
+fn test_filled_rests_are_laid_out() {
+    let mut score = piano(vec![note(0, QUARTER)]);
+    score.fill_rests();
+    let layout = layout(&score);
+
+    let codepoints: Vec<&str> = layout.systems[0].staff_groups[0].staves[0]
+        .glyph_runs
+        .iter()
+        .flat_map(|run| &run.glyphs)
+        .map(|g| g.codepoint.as_str())
+        .collect();
+    // Quarter rest, half rest
+    assert!(codepoints.contains(&"\u{E4E5}"));
+    assert!(codepoints.contains(&"\u{E4E4}"));
+}
@@ -10,6 +10,7 @@ const CONFIG: LayoutConfig = LayoutConfig {
     units_per_space: 20.0,
     system_spacing: 100.0,
     system_height: 200.0,
+    ..LayoutConfig::DEFAULT
 };
 
 /// Helper: collect all glyph codepoints from every glyph_run in the first system.
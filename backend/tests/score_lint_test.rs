@@ -0,0 +1,218 @@
+//! Integration tests for the score linter: each rule on a small score built
+//! through the domain API, and a clean report for well-formed scores.
+
+use musicore_backend::domain::events::{
+    key_signature::KeySignatureEvent, note::Note, rest::RestEvent,
+    time_signature::TimeSignatureEvent,
+};
+use musicore_backend::domain::ids::NoteId;
+use musicore_backend::domain::instrument::Instrument;
+use musicore_backend::domain::instrument_catalog;
+use musicore_backend::domain::lint::{
+    LintLocation, LintReport, LintRule, LintSeverity, ScoreLinter,
+};
+use musicore_backend::domain::score::Score;
+use musicore_backend::domain::value_objects::{KeySignature, Pitch, Tick};
+use musicore_backend::domain::voice::Voice;
+
+const WHOLE: u32 = 3840;
+const HALF: u32 = 1920;
+const QUARTER: u32 = 960;
+
+fn note(start: u32, duration: u32, pitch: u8) -> Note {
+    Note::new(Tick::new(start), duration, Pitch::new(pitch).unwrap()).unwrap()
+}
+
+/// A 4/4 piano score whose first voice holds `notes`
+fn piano(notes: Vec<Note>) -> Score {
+    let mut score = Score::new();
+    let mut instrument = Instrument::new("Piano".to_string());
+    instrument.staves[0].voices[0].interval_events = notes;
+    score.add_instrument(instrument);
+    score
+}
+
+fn voice(score: &mut Score) -> &mut Voice {
+    &mut score.instruments[0].staves[0].voices[0]
+}
+
+fn lint(score: &Score) -> LintReport {
+    ScoreLinter::new().lint(score)
+}
+
+fn rules(report: &LintReport) -> Vec<LintRule> {
+    report.findings.iter().map(|f| f.rule).collect()
+}
+
+#[test]
+fn test_full_measures_are_clean() {
+    let mut score = piano(vec![note(0, HALF, 60), note(HALF, HALF, 64)]);
+    voice(&mut score)
+        .rest_events
+        .push(RestEvent::new(Tick::new(WHOLE), WHOLE, None, 1, 1));
+    let report = lint(&score);
+    assert!(report.is_clean(), "{:?}", report.findings);
+    assert!(lint(&Score::new()).is_clean());
+}
+
+#[test]
+fn test_underfilled_and_overfilled_measures() {
+    // Half note, then a half-measure gap; a whole note across the barline
+    let score = piano(vec![note(0, HALF, 60), note(WHOLE + HALF, WHOLE, 62)]);
+    let report = lint(&score);
+
+    let under: Vec<_> = report.by_rule(LintRule::UnderfilledMeasure).collect();
+    assert_eq!(under.len(), 2);
+    assert_eq!(
+        under[0].location,
+        LintLocation {
+            instrument: Some(0),
+            staff: Some(0),
+            voice: Some(0),
+            measure: Some(0),
+            tick: Some(HALF),
+        }
+    );
+    assert_eq!(under[0].severity, LintSeverity::Warning);
+    assert_eq!(under[1].location.measure, Some(1));
+    assert_eq!(under[1].location.tick, Some(WHOLE));
+
+    let over: Vec<_> = report.by_rule(LintRule::OverfilledMeasure).collect();
+    assert_eq!(over.len(), 1);
+    assert_eq!(over[0].severity, LintSeverity::Error);
+    assert_eq!(over[0].location.measure, Some(1));
+    assert!(report.has_errors());
+}
+
+#[test]
+fn test_tied_notes_fill_the_next_measure() {
+    let second = note(WHOLE, WHOLE, 60).with_tie_continuation();
+    let first = note(0, WHOLE, 60).with_tie_next(second.id);
+    let report = lint(&piano(vec![first, second]));
+    assert!(report.is_clean(), "{:?}", report.findings);
+}
+
+#[test]
+fn test_pickup_measure_uses_its_own_length() {
+    let mut score = piano(vec![note(0, QUARTER, 67), note(QUARTER, WHOLE, 72)]);
+    score.pickup_ticks = QUARTER;
+    assert!(lint(&score).is_clean());
+}
+
+#[test]
+fn test_note_out_of_instrument_range() {
+    let mut score = Score::new();
+    let mut violin = Instrument::from_catalog(instrument_catalog::find("violin").unwrap());
+    violin.staves[0].voices[0].interval_events = vec![note(0, HALF, 50), note(HALF, HALF, 55)];
+    score.add_instrument(violin);
+
+    let report = lint(&score);
+    assert_eq!(rules(&report), vec![LintRule::NoteOutOfRange]);
+    assert_eq!(report.findings[0].location.tick, Some(0));
+}
+
+#[test]
+fn test_dangling_links_and_tie_pitches() {
+    let target = note(HALF, HALF, 62);
+    let mut tied = note(0, HALF, 60).with_tie_next(target.id);
+    tied.slur_next = Some(NoteId::new());
+    let dangling = note(WHOLE, WHOLE, 64).with_tie_next(NoteId::new());
+    let report = lint(&piano(vec![tied, target, dangling]));
+
+    assert_eq!(
+        rules(&report),
+        vec![
+            LintRule::TiePitchMismatch,
+            LintRule::DanglingSlur,
+            LintRule::DanglingTie,
+        ]
+    );
+    assert_eq!(report.findings[2].location.tick, Some(WHOLE));
+    assert_eq!(report.count(LintSeverity::Error), 3);
+}
+
+#[test]
+fn test_rests_overlapping_notes_and_rests() {
+    let mut score = piano(vec![note(0, HALF, 60)]);
+    let rests = &mut voice(&mut score).rest_events;
+    rests.push(RestEvent::new(Tick::new(QUARTER), HALF, None, 1, 1));
+    rests.push(RestEvent::new(Tick::new(HALF), HALF, None, 1, 1));
+
+    let report = lint(&score);
+    let overlaps: Vec<_> = report.by_rule(LintRule::OverlappingEvents).collect();
+    assert_eq!(overlaps.len(), 2);
+    assert!(overlaps[0].message.contains("a note at tick 0"));
+    assert!(overlaps[1].message.contains("another rest"));
+    assert_eq!(overlaps[1].location.tick, Some(QUARTER));
+}
+
+#[test]
+fn test_rests_overlapping_a_long_note_after_shorter_ones() {
+    // A whole note struck with a quarter; both rests fall under the whole
+    // note, though only after the quarter has ended
+    let mut score = piano(vec![note(0, WHOLE, 48), note(0, QUARTER, 72)]);
+    let rests = &mut voice(&mut score).rest_events;
+    rests.push(RestEvent::new(Tick::new(QUARTER), QUARTER, None, 1, 1));
+    rests.push(RestEvent::new(
+        Tick::new(HALF + QUARTER),
+        QUARTER,
+        None,
+        1,
+        1,
+    ));
+
+    let report = lint(&score);
+    let overlaps: Vec<_> = report.by_rule(LintRule::OverlappingEvents).collect();
+    assert_eq!(overlaps.len(), 2);
+    assert!(
+        overlaps
+            .iter()
+            .all(|f| f.message.contains("a note at tick 0"))
+    );
+    assert_eq!(overlaps[1].location.tick, Some(HALF + QUARTER));
+}
+
+#[test]
+fn test_structural_events_off_barlines() {
+    let mut score = piano(vec![note(0, WHOLE, 60), note(WHOLE, WHOLE, 60)]);
+    score
+        .add_time_signature_event(TimeSignatureEvent::new(Tick::new(WHOLE + QUARTER), 3, 4))
+        .unwrap();
+    score.instruments[0].staves[0]
+        .add_key_signature_event(KeySignatureEvent::new(
+            Tick::new(QUARTER),
+            KeySignature::new(2).unwrap(),
+        ))
+        .unwrap();
+    // On a barline: fine
+    score.instruments[0].staves[0]
+        .add_key_signature_event(KeySignatureEvent::new(
+            Tick::new(WHOLE),
+            KeySignature::new(1).unwrap(),
+        ))
+        .unwrap();
+
+    let report = lint(&score);
+    let off: Vec<_> = report
+        .by_rule(LintRule::StructuralEventOffBarline)
+        .collect();
+    assert_eq!(off.len(), 2);
+    assert!(off[0].message.starts_with("Time signature 3/4"));
+    assert_eq!(off[0].location.measure, Some(1));
+    assert_eq!(off[0].location.instrument, None);
+    assert_eq!(off[1].location.tick, Some(QUARTER));
+    assert_eq!(off[1].location.staff, Some(0));
+}
+
+#[test]
+fn test_unsorted_events() {
+    let score = piano(vec![note(HALF, HALF, 64), note(0, HALF, 60)]);
+    let report = lint(&score);
+    assert_eq!(rules(&report), vec![LintRule::UnsortedEvents]);
+    assert_eq!(report.findings[0].location.tick, Some(0));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["findings"][0]["rule"], "unsorted_events");
+    assert_eq!(json["findings"][0]["location"]["voice"], 0);
+    assert!(json["findings"][0]["location"].get("measure").is_none());
+}
@@ -0,0 +1,154 @@
+//! Integration tests for the score editing session: edits are found by id,
+//! return only what changed, and keep the layout equal to a full
+//! `compute_layout` of the edited score.
+
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::adapters::session::{ScoreChange, ScoreSession};
+use musicore_backend::domain::{
+    events::note::Note,
+    ids::{NoteId, StaffId, VoiceId},
+    value_objects::{Clef, Pitch, Tick},
+};
+use musicore_backend::layout::{LayoutConfig, compute_layout};
+
+fn note(tick: u32, pitch: u8) -> Note {
+    Note::new(Tick::new(tick), 960, Pitch::new(pitch).unwrap()).unwrap()
+}
+
+/// A session with one instrument; returns the id of its first voice
+fn piano_session() -> (ScoreSession, VoiceId) {
+    let mut session = ScoreSession::default();
+    let ScoreChange::InstrumentAdded { instrument } = session.add_instrument("Piano") else {
+        panic!("expected an added instrument");
+    };
+    (session, instrument.staves[0].voices[0].id)
+}
+
+fn assert_same_as_full(session: &mut ScoreSession) {
+    let score = serde_json::to_value(ScoreDto::from(session.score())).unwrap();
+    let full = compute_layout(&score, &LayoutConfig::default());
+    assert_eq!(
+        serde_json::to_string(session.layout()).unwrap(),
+        serde_json::to_string(&full).unwrap()
+    );
+}
+
+#[test]
+fn test_edits_return_deltas() {
+    let (mut session, voice_id) = piano_session();
+    let added = note(0, 60);
+    let note_id = added.id;
+
+    let change = session.add_note(voice_id, added).unwrap();
+    let json = serde_json::to_value(&change).unwrap();
+    assert_eq!(json["type"], "note_added");
+    assert_eq!(json["voice_id"], voice_id.to_string());
+    assert_eq!(json["note"]["pitch"], 60);
+    assert!(json.get("instruments").is_none());
+
+    let json = serde_json::to_value(session.remove_note(note_id).unwrap()).unwrap();
+    assert_eq!(json["type"], "note_removed");
+    assert_eq!(json["note_id"], note_id.to_string());
+
+    let json = serde_json::to_value(session.add_tempo_event(1920, 90).unwrap()).unwrap();
+    assert_eq!(json["type"], "tempo_event_added");
+}
+
+#[test]
+fn test_index_finds_entities_by_id() {
+    let (mut session, voice_id) = piano_session();
+    let notes: Vec<Note> = (0..4).map(|i| note(i * 960, 60 + i as u8)).collect();
+    let ids: Vec<NoteId> = notes.iter().map(|n| n.id).collect();
+    for n in notes {
+        session.add_note(voice_id, n).unwrap();
+    }
+    assert_eq!(session.voice(voice_id).unwrap().interval_events.len(), 4);
+
+    // Removing a note keeps the later ones reachable
+    session.remove_note(ids[1]).unwrap();
+    assert!(session.note(ids[1]).is_none());
+    assert_eq!(session.note(ids[2]).unwrap().pitch, Pitch::new(62).unwrap());
+    assert_eq!(session.note(ids[3]).unwrap().pitch, Pitch::new(63).unwrap());
+    session.remove_note(ids[3]).unwrap();
+    assert_eq!(session.voice(voice_id).unwrap().interval_events.len(), 2);
+
+    // Unknown and duplicate ids are rejected
+    assert!(session.remove_note(ids[1]).is_err());
+    let duplicate = session.note(ids[0]).unwrap().clone();
+    assert!(session.add_note(voice_id, duplicate).is_err());
+    assert!(session.add_voice(StaffId::new()).is_err());
+}
+
+#[test]
+fn test_removing_a_note_drops_ties_to_it() {
+    let (mut session, voice_id) = piano_session();
+    let second = note(960, 60);
+    let first = note(0, 60).with_tie_next(second.id);
+    let (first_id, second_id) = (first.id, second.id);
+    session.add_note(voice_id, first).unwrap();
+    session.add_note(voice_id, second).unwrap();
+
+    session.remove_note(second_id).unwrap();
+    assert_eq!(session.note(first_id).unwrap().tie_next, None);
+}
+
+#[test]
+fn test_layout_follows_edits() {
+    let (mut session, voice_id) = piano_session();
+    for i in 0..16 {
+        session
+            .add_note(voice_id, note(i * 960, 60 + (i % 8) as u8))
+            .unwrap();
+    }
+    assert_same_as_full(&mut session);
+
+    // Note edits re-lay out only their measures
+    let extra = note(4800, 72);
+    let extra_id = extra.id;
+    session.add_note(voice_id, extra).unwrap();
+    assert_same_as_full(&mut session);
+    session.remove_note(extra_id).unwrap();
+    assert_same_as_full(&mut session);
+
+    // Structural edits fall back to a full layout
+    let ScoreChange::StaffAdded { staff, .. } = session
+        .add_staff(session.score().instruments[0].id)
+        .unwrap()
+    else {
+        panic!("expected an added staff");
+    };
+    session.add_clef_event(staff.id, 1920, Clef::Bass).unwrap();
+    session.add_key_signature_event(staff.id, 3840, 2).unwrap();
+    session.add_time_signature_event(7680, 3, 4).unwrap();
+    assert_same_as_full(&mut session);
+}
+
+#[test]
+fn test_fill_rests_returns_each_voices_rests() {
+    let (mut session, voice_id) = piano_session();
+    session.add_note(voice_id, note(0, 60)).unwrap();
+    session.layout();
+
+    let json = serde_json::to_value(session.fill_rests()).unwrap();
+    assert_eq!(json["type"], "rests_filled");
+    assert_eq!(json["voices"][0]["voice_id"], voice_id.to_string());
+    let rests = json["voices"][0]["rest_events"].as_array().unwrap();
+    assert_eq!(rests.len(), 2);
+    assert_eq!(rests[1]["note_type"], "half");
+    assert_eq!(session.voice(voice_id).unwrap().rest_events.len(), 2);
+    assert_same_as_full(&mut session);
+}
+
+#[test]
+fn test_tempo_map_follows_tempo_edits() {
+    let (mut session, voice_id) = piano_session();
+    assert_eq!(session.tempo_map().tick_to_seconds(960), 0.5);
+
+    // Note edits keep the timing; tempo edits rebuild it
+    session.add_note(voice_id, note(0, 60)).unwrap();
+    assert_eq!(session.tempo_map().tick_to_seconds(960), 0.5);
+    session.add_tempo_event(3840, 60).unwrap();
+    assert_eq!(session.tempo_map().tick_to_seconds(4800), 3.0);
+    let map = session.tempo_map().clone();
+    assert_eq!(map, session.score().tempo_map());
+}
@@ -1,6 +1,7 @@
 use musicore_backend::domain::{
     events::{tempo::TempoEvent, time_signature::TimeSignatureEvent},
     instrument::Instrument,
+    part_group::{GroupSymbol, PartGroup},
     score::Score,
     value_objects::{BPM, Tick},
 };
@@ -133,3 +134,45 @@ fn test_score_aggregate_root_hierarchy() {
     assert_eq!(score.instruments[0].staves.len(), 1);
     assert_eq!(score.instruments[0].staves[0].voices.len(), 1);
 }
+
+#[test]
+fn test_score_add_nested_part_groups() {
+    let mut score = Score::new();
+    for name in ["Violin I", "Violin II", "Viola", "Cello"] {
+        score.add_instrument(Instrument::new(name.to_string()));
+    }
+
+    score
+        .add_part_group(PartGroup::new(0, 3, GroupSymbol::Bracket).with_barline(true))
+        .unwrap();
+    score
+        .add_part_group(PartGroup::new(0, 1, GroupSymbol::Square))
+        .unwrap();
+    assert_eq!(score.part_groups.len(), 2);
+    assert!(score.part_groups[0].encloses(&score.part_groups[1]));
+}
+
+#[test]
+fn test_score_reject_invalid_part_groups() {
+    let mut score = Score::new();
+    for name in ["Soprano", "Alto", "Tenor"] {
+        score.add_instrument(Instrument::new(name.to_string()));
+    }
+
+    // Past the last instrument
+    assert!(
+        score
+            .add_part_group(PartGroup::new(1, 3, GroupSymbol::Bracket))
+            .is_err()
+    );
+    // Overlapping without nesting
+    score
+        .add_part_group(PartGroup::new(0, 1, GroupSymbol::Bracket))
+        .unwrap();
+    assert!(
+        score
+            .add_part_group(PartGroup::new(1, 2, GroupSymbol::Bracket))
+            .is_err()
+    );
+    assert_eq!(score.part_groups.len(), 1);
+}
@@ -0,0 +1,153 @@
+//! Integration tests for skyline-based vertical spacing: staves and systems
+//! move apart only where their contours come close, and system bounding
+//! boxes follow what is actually drawn.
+
+mod common;
+
+use musicore_backend::layout::{BoundingBox, GlobalLayout, LayoutConfig, System, compute_layout};
+
+fn layout(name: &str) -> GlobalLayout {
+    common::layout(&common::import_file(name))
+}
+
+/// Piano with treble and bass notes given as (pitch, tick), 4 measures of 4/4
+fn piano(treble: &[(u8, u32)], bass: &[(u8, u32)]) -> serde_json::Value {
+    let notes = |notes: &[(u8, u32)]| -> Vec<serde_json::Value> {
+        notes
+            .iter()
+            .map(|&(pitch, tick)| serde_json::json!({ "pitch": pitch, "tick": tick, "duration": 3840 }))
+            .collect()
+    };
+    let staff = |clef: &str, notes: Vec<serde_json::Value>| {
+        serde_json::json!({
+            "clef": clef,
+            "time_signature": { "numerator": 4, "denominator": 4 },
+            "key_signature": { "sharps": 0 },
+            "voices": [{ "notes": notes }]
+        })
+    };
+    serde_json::json!({
+        "instruments": [{
+            "id": "piano",
+            "name": "Piano",
+            "staves": [staff("Treble", notes(treble)), staff("Bass", notes(bass))]
+        }]
+    })
+}
+
+fn staff_gap(layout: &GlobalLayout) -> f32 {
+    let staves = &layout.systems[0].staff_groups[0].staves;
+    staves[1].staff_lines[0].y_position - staves[0].staff_lines[0].y_position
+}
+
+/// Bounding boxes of everything drawn on a system's staves
+fn glyph_boxes(system: &System) -> Vec<BoundingBox> {
+    system
+        .staff_groups
+        .iter()
+        .flat_map(|g| &g.staves)
+        .flat_map(|s| {
+            s.glyph_runs
+                .iter()
+                .flat_map(|r| &r.glyphs)
+                .chain(&s.structural_glyphs)
+        })
+        .filter(|g| g.codepoint != "\u{0000}" && g.codepoint != "\u{0001}")
+        .map(|g| g.bounding_box)
+        .collect()
+}
+
+#[test]
+fn test_staves_only_move_apart_where_contours_meet() {
+    let config = LayoutConfig::default();
+    let nominal = config.engraving.staff_distance * config.units_per_space;
+
+    // A low treble note in the first measure and a high bass note in the
+    // last one never meet: the staves keep their nominal distance, although
+    // both notes reach into the gap between them
+    let apart = compute_layout(
+        &piano(&[(57, 0), (72, 11520)], &[(48, 0), (71, 11520)]),
+        &config,
+    );
+    assert_eq!(apart.systems.len(), 1);
+    assert_eq!(staff_gap(&apart), nominal);
+
+    // The same two notes in the same measure push the bass staff down
+    let together = compute_layout(
+        &piano(&[(57, 0), (72, 11520)], &[(71, 0), (48, 11520)]),
+        &config,
+    );
+    assert!(staff_gap(&together) > nominal, "{}", staff_gap(&together));
+
+    // ...just enough to keep the noteheads clear of each other
+    let staves = &together.systems[0].staff_groups[0].staves;
+    let notehead = |staff: usize| {
+        staves[staff]
+            .glyph_runs
+            .iter()
+            .flat_map(|r| &r.glyphs)
+            .filter(|g| ('\u{E0A0}'..='\u{E0FF}').contains(&g.codepoint.chars().next().unwrap()))
+            .min_by(|a, b| a.position.x.total_cmp(&b.position.x))
+            .unwrap()
+            .bounding_box
+    };
+    let (low, high) = (notehead(0), notehead(1));
+    let clearance = high.y - (low.y + low.height);
+    assert!(clearance >= config.units_per_space - 1e-3, "{clearance}");
+    assert!(clearance < 3.0 * config.units_per_space, "{clearance}");
+}
+
+#[test]
+fn test_system_bounding_box_follows_the_contours() {
+    let layout = layout("Chopin_NocturneOp9No2.mxl");
+    for system in &layout.systems {
+        let b = system.bounding_box;
+        let boxes = glyph_boxes(system);
+        let top = boxes.iter().map(|g| g.y).fold(f32::INFINITY, f32::min);
+        let bottom = boxes
+            .iter()
+            .map(|g| g.y + g.height)
+            .fold(f32::NEG_INFINITY, f32::max);
+        assert!(b.y <= top + 1e-3 && b.y + b.height >= bottom - 1e-3);
+        let number = system.measure_number.as_ref().unwrap();
+        assert!(b.y < number.position.y);
+    }
+
+    // Without slurs, pedals or directions the box ends at the lowest glyph
+    let whole_notes = compute_layout(
+        &piano(&[(72, 0)], &[(24, 0), (48, 3840)]),
+        &LayoutConfig::default(),
+    );
+    let system = &whole_notes.systems[0];
+    let lowest = glyph_boxes(system)
+        .iter()
+        .map(|g| g.y + g.height)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let b = system.bounding_box;
+    assert!((b.y + b.height - lowest).abs() < 1e-3, "{b:?} vs {lowest}");
+}
+
+#[test]
+fn test_consecutive_systems_never_overlap() {
+    for name in ["Chopin_NocturneOp9No2.mxl", "Bach_InventionNo1.mxl"] {
+        let layout = layout(name);
+        for pair in layout.systems.windows(2) {
+            let upper = glyph_boxes(&pair[0]);
+            let lower = glyph_boxes(&pair[1]);
+            for a in &upper {
+                for b in lower
+                    .iter()
+                    .filter(|b| b.x < a.x + a.width && a.x < b.x + b.width)
+                {
+                    assert!(
+                        a.y + a.height <= b.y,
+                        "{name}: systems {} and {} overlap at x = {}",
+                        pair[0].index,
+                        pair[1].index,
+                        a.x
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Integration tests for staves with other than five lines: import of
+//! `<staff-lines>`, and layout of one-line and three-line staves.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::layout::{GlobalLayout, LayoutConfig, Staff};
+
+/// A one-line rhythm part and a three-line part, each with a note far
+/// above and a note far below the staff.
+const STAFF_LINES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list>
+    <score-part id="P1"><part-name>Rhythm</part-name></score-part>
+    <score-part id="P2"><part-name>Three</part-name></score-part>
+  </part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>1</divisions>
+        <key><fifths>2</fifths></key>
+        <time><beats>2</beats><beat-type>4</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+        <staff-details><staff-lines>1</staff-lines></staff-details>
+      </attributes>
+      <note><pitch><step>C</step><alter>1</alter><octave>6</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+      <note><pitch><step>A</step><octave>3</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+    </measure>
+  </part>
+  <part id="P2">
+    <measure number="1">
+      <attributes>
+        <divisions>1</divisions>
+        <time><beats>2</beats><beat-type>4</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+        <staff-details number="1"><staff-lines>3</staff-lines></staff-details>
+      </attributes>
+      <note><pitch><step>C</step><octave>6</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+      <note><pitch><step>A</step><octave>3</octave></pitch><duration>1</duration><voice>1</voice><type>quarter</type></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+fn staves(layout: &GlobalLayout) -> Vec<&Staff> {
+    layout.systems[0]
+        .staff_groups
+        .iter()
+        .flat_map(|g| &g.staves)
+        .collect()
+}
+
+/// Y of the quarter notes (noteQuarterUp / noteQuarterDown) on a staff
+fn note_ys(staff: &Staff) -> Vec<f32> {
+    staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| &run.glyphs)
+        .filter(|g| g.codepoint == "\u{E1D5}" || g.codepoint == "\u{E1D6}")
+        .map(|g| g.position.y)
+        .collect()
+}
+
+#[test]
+fn test_import_staff_lines() {
+    let score = import(STAFF_LINES);
+    assert_eq!(score.instruments[0].staves[0].staff_lines, 1);
+    assert_eq!(score.instruments[1].staves[0].staff_lines, 3);
+
+    let dto = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert_eq!(dto["instruments"][0]["staves"][0]["staff_lines"], 1);
+    assert_eq!(dto["instruments"][1]["staves"][0]["staff_lines"], 3);
+}
+
+#[test]
+fn test_five_line_staves_omit_staff_lines() {
+    let mut score = import(STAFF_LINES);
+    score.instruments[0].staves[0].set_staff_lines(5).unwrap();
+    let dto = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    assert!(
+        dto["instruments"][0]["staves"][0]
+            .get("staff_lines")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_one_line_staff_layout() {
+    let layout = layout(&import(STAFF_LINES));
+    let ups = LayoutConfig::default().units_per_space;
+    let staff = staves(&layout)[0];
+
+    assert_eq!(staff.staff_lines.len(), 1);
+    let line_y = staff.top_line_y();
+    assert_eq!(line_y, staff.bottom_line_y());
+
+    // Both notes sit on the line, whatever their pitch
+    let ys = note_ys(staff);
+    assert_eq!(ys.len(), 2);
+    for y in ys {
+        assert!((y + 0.5 * ups - line_y).abs() < 0.01, "note at {y}");
+    }
+    assert!(staff.ledger_lines.is_empty());
+
+    // No accidentals and no key signature
+    let has_sharp = staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| &run.glyphs)
+        .chain(&staff.structural_glyphs)
+        .any(|g| g.codepoint == "\u{E262}");
+    assert!(!has_sharp);
+
+    // Barlines reach a space either side of the line
+    let segment = &staff.bar_lines[0].segments[0];
+    assert!((segment.y_start - (line_y - ups)).abs() < 0.01);
+    assert!((segment.y_end - (line_y + ups)).abs() < 0.01);
+}
+
+#[test]
+fn test_three_line_staff_layout() {
+    let layout = layout(&import(STAFF_LINES));
+    let ups = LayoutConfig::default().units_per_space;
+    let staff = staves(&layout)[1];
+
+    assert_eq!(staff.staff_lines.len(), 3);
+    let top = staff.top_line_y();
+    let bottom = staff.bottom_line_y();
+    assert!((bottom - top - 2.0 * ups).abs() < 0.01);
+
+    // The three lines are the middle three of a five-line staff, so C6 and
+    // A3 need one more ledger line each than on five lines
+    assert!(!staff.ledger_lines.is_empty());
+    assert!(
+        staff
+            .ledger_lines
+            .iter()
+            .any(|l| (l.y_position - (top - ups)).abs() < 0.01)
+    );
+    assert!(
+        staff
+            .ledger_lines
+            .iter()
+            .any(|l| (l.y_position - (bottom + ups)).abs() < 0.01)
+    );
+    assert!(
+        staff
+            .ledger_lines
+            .iter()
+            .all(|l| l.y_position < top || l.y_position > bottom)
+    );
+
+    let segment = &staff.bar_lines[0].segments[0];
+    assert!((segment.y_start - top).abs() < 0.01);
+    assert!((segment.y_end - bottom).abs() < 0.01);
+}
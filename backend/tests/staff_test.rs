@@ -1,7 +1,11 @@
 use musicore_backend::domain::{
-    events::{clef::ClefEvent, key_signature::KeySignatureEvent},
+    events::{
+        chord_symbol::{ChordKind, ChordSymbolEvent},
+        clef::ClefEvent,
+        key_signature::KeySignatureEvent,
+    },
     staff::Staff,
-    value_objects::{Clef, KeySignature, Tick},
+    value_objects::{Clef, KeySignature, NoteSpelling, Tick},
 };
 
 #[test]
@@ -98,3 +102,44 @@ fn test_staff_reject_duplicate_key_signature_at_same_tick() {
     let result = staff.add_key_signature_event(key_event);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_add_chord_symbol_keeps_tick_order_and_replaces_same_tick() {
+    let mut staff = Staff::new();
+    let root = |step| Some(NoteSpelling { step, alter: 0 });
+
+    staff.add_chord_symbol(ChordSymbolEvent::new(
+        Tick::new(1920),
+        root('G'),
+        ChordKind::Major,
+    ));
+    staff.add_chord_symbol(ChordSymbolEvent::new(
+        Tick::new(0),
+        root('C'),
+        ChordKind::Major,
+    ));
+    staff.add_chord_symbol(ChordSymbolEvent::new(
+        Tick::new(1920),
+        root('G'),
+        ChordKind::Dominant,
+    ));
+
+    assert_eq!(staff.chord_symbols.len(), 2);
+    assert_eq!(staff.chord_symbols[0].tick, Tick::new(0));
+    assert_eq!(staff.chord_symbols[1].kind, ChordKind::Dominant);
+}
+
+#[test]
+fn test_staff_lines_default_and_range() {
+    let mut staff = Staff::new();
+    assert_eq!(staff.staff_lines, 5);
+
+    staff.set_staff_lines(1).unwrap();
+    assert_eq!(staff.staff_lines, 1);
+    staff.set_staff_lines(6).unwrap();
+    assert_eq!(staff.staff_lines, 6);
+
+    assert!(staff.set_staff_lines(0).is_err());
+    assert!(staff.set_staff_lines(9).is_err());
+    assert_eq!(staff.staff_lines, 6);
+}
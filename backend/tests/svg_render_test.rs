@@ -0,0 +1,122 @@
+//! Golden-file tests for server-side SVG rendering.
+//!
+//! Each test renders the opening systems of a score from `scores/` and
+//! compares the result byte for byte with `tests/fixtures/svg/<name>.svg`.
+//! Run with `UPDATE_GOLDEN=1` to regenerate the files after an intentional
+//! layout or rendering change, and review the diff before committing.
+
+mod common;
+
+use common::{import_file, layout};
+use musicore_backend::layout::GlobalLayout;
+use musicore_backend::layout::render::{SvgOptions, render_svg};
+use std::path::Path;
+
+/// Systems per golden file; keeps the fixtures small and reviewable
+const GOLDEN_SYSTEMS: usize = 2;
+
+fn layout_score(name: &str) -> GlobalLayout {
+    layout(&import_file(name))
+}
+
+/// Render the first systems of a score
+fn render_opening(name: &str) -> String {
+    let mut layout = layout_score(name);
+    layout.systems.truncate(GOLDEN_SYSTEMS);
+    if let Some(last) = layout.systems.last() {
+        layout.total_height = last.bounding_box.y + last.bounding_box.height;
+    }
+    render_svg(&layout, &SvgOptions::default())
+}
+
+fn assert_golden(golden: &str, actual: &str) {
+    let path = Path::new("tests/fixtures/svg").join(golden);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Missing golden file {} ({e}); run with UPDATE_GOLDEN=1",
+            path.display()
+        )
+    });
+    if expected != actual {
+        let line = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e != a)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "{} differs at line {}:\n  expected: {}\n  actual:   {}\nRun with UPDATE_GOLDEN=1 to accept the change",
+            path.display(),
+            line + 1,
+            expected.lines().nth(line).unwrap_or("<eof>"),
+            actual.lines().nth(line).unwrap_or("<eof>")
+        );
+    }
+}
+
+#[test]
+fn test_golden_bach_invention() {
+    assert_golden(
+        "bach_invention_no1.svg",
+        &render_opening("Bach_InventionNo1.mxl"),
+    );
+}
+
+#[test]
+fn test_golden_fur_elise() {
+    assert_golden(
+        "beethoven_fur_elise.svg",
+        &render_opening("Beethoven_FurElise.mxl"),
+    );
+}
+
+#[test]
+fn test_golden_chopin_nocturne() {
+    assert_golden(
+        "chopin_nocturne_op9_no2.svg",
+        &render_opening("Chopin_NocturneOp9No2.mxl"),
+    );
+}
+
+#[test]
+fn test_golden_c_major_scale() {
+    assert_golden("c_major_scale.svg", &render_opening("scales/C_major.mxl"));
+}
+
+#[test]
+fn test_render_draws_every_system_and_staff() {
+    let layout = layout_score("Burgmuller_LaCandeur.mxl");
+    let svg = render_svg(&layout, &SvgOptions::default());
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert_eq!(
+        svg.matches("<g class=\"system\"").count(),
+        layout.systems.len()
+    );
+    let staves: usize = layout
+        .systems
+        .iter()
+        .flat_map(|s| &s.staff_groups)
+        .map(|g| g.staves.len())
+        .sum();
+    assert_eq!(svg.matches("<g class=\"staff\">").count(), staves);
+    assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+    // Piano staves are joined by a brace
+    assert!(svg.contains("class=\"brace\""));
+    // Every tie and slur becomes a Bézier path
+    let arcs: usize = layout
+        .systems
+        .iter()
+        .flat_map(|s| &s.staff_groups)
+        .flat_map(|g| &g.staves)
+        .map(|s| s.tie_arcs.len() + s.slur_arcs.len())
+        .sum();
+    assert_eq!(
+        svg.matches("class=\"tie\"").count() + svg.matches("class=\"slur\"").count(),
+        arcs
+    );
+}
@@ -0,0 +1,205 @@
+//! Integration tests for tablature staves: import of TAB clefs, string
+//! tunings and written string/fret, automatic fret assignment, and layout
+//! of fret numbers and rhythm stems.
+
+mod common;
+
+use common::{import, layout};
+use musicore_backend::adapters::dtos::ScoreDto;
+use musicore_backend::domain::events::note::TabPosition;
+use musicore_backend::domain::tablature::Tablature;
+use musicore_backend::layout::{GlobalLayout, LayoutConfig, Staff};
+
+/// A six-string guitar tab part in drop D: a written B3 on string 2, a C4
+/// tied over to the next beat, and an E2 and D2 left for the layout to
+/// place.
+const GUITAR_TAB: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list>
+    <score-part id="P1"><part-name>Guitar</part-name></score-part>
+  </part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>2</divisions>
+        <time><beats>4</beats><beat-type>4</beat-type></time>
+        <clef><sign>TAB</sign><line>5</line></clef>
+        <staff-details>
+          <staff-lines>6</staff-lines>
+          <staff-tuning line="1"><tuning-step>D</tuning-step><tuning-octave>2</tuning-octave></staff-tuning>
+          <staff-tuning line="2"><tuning-step>A</tuning-step><tuning-octave>2</tuning-octave></staff-tuning>
+          <staff-tuning line="3"><tuning-step>D</tuning-step><tuning-octave>3</tuning-octave></staff-tuning>
+          <staff-tuning line="4"><tuning-step>G</tuning-step><tuning-octave>3</tuning-octave></staff-tuning>
+          <staff-tuning line="5"><tuning-step>B</tuning-step><tuning-octave>3</tuning-octave></staff-tuning>
+          <staff-tuning line="6"><tuning-step>E</tuning-step><tuning-octave>4</tuning-octave></staff-tuning>
+        </staff-details>
+      </attributes>
+      <note>
+        <pitch><step>B</step><octave>3</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type>
+        <notations><technical><string>2</string><fret>0</fret></technical></notations>
+      </note>
+      <note>
+        <pitch><step>C</step><octave>4</octave></pitch><duration>2</duration><tie type="start"/><voice>1</voice><type>quarter</type>
+        <notations><tied type="start"/><technical><string>2</string><fret>1</fret></technical></notations>
+      </note>
+      <note>
+        <pitch><step>C</step><octave>4</octave></pitch><duration>2</duration><tie type="stop"/><voice>1</voice><type>quarter</type>
+        <notations><tied type="stop"/></notations>
+      </note>
+      <note>
+        <pitch><step>E</step><octave>2</octave></pitch><duration>1</duration><voice>1</voice><type>eighth</type>
+      </note>
+      <note>
+        <pitch><step>D</step><octave>2</octave></pitch><duration>1</duration><voice>1</voice><type>eighth</type>
+      </note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+fn staff(layout: &GlobalLayout) -> &Staff {
+    &layout.systems[0].staff_groups[0].staves[0]
+}
+
+#[test]
+fn test_import_tablature() {
+    let score = import(GUITAR_TAB);
+    let staff = &score.instruments[0].staves[0];
+    assert_eq!(staff.staff_lines, 6);
+
+    let tablature = staff.tablature.as_ref().expect("tab staff");
+    let open: Vec<u8> = tablature.strings.iter().map(|p| p.value()).collect();
+    assert_eq!(open, vec![64, 59, 55, 50, 45, 38]);
+
+    let notes = &staff.voices[0].interval_events;
+    assert_eq!(notes[0].tab, Some(TabPosition { string: 2, fret: 0 }));
+    assert_eq!(notes[1].tab, Some(TabPosition { string: 2, fret: 1 }));
+    assert_eq!(notes[2].tab, None);
+
+    let dto = serde_json::to_value(ScoreDto::from(&score)).unwrap();
+    let staff_dto = &dto["instruments"][0]["staves"][0];
+    assert_eq!(
+        staff_dto["tablature"]["strings"].as_array().unwrap().len(),
+        6
+    );
+    assert_eq!(
+        staff_dto["voices"][0]["interval_events"][1]["tab"]["fret"],
+        1
+    );
+}
+
+#[test]
+fn test_tab_clef_without_tuning_defaults_to_guitar() {
+    let xml = GUITAR_TAB.replace(
+        &GUITAR_TAB[GUITAR_TAB.find("<staff-details>").unwrap()
+            ..GUITAR_TAB.find("</staff-details>").unwrap() + "</staff-details>".len()],
+        "",
+    );
+    let score = import(&xml);
+    let staff = &score.instruments[0].staves[0];
+    assert_eq!(staff.tablature, Some(Tablature::standard_guitar()));
+    assert_eq!(staff.staff_lines, 6);
+}
+
+#[test]
+fn test_tab_staff_layout() {
+    let layout = layout(&import(GUITAR_TAB));
+    let ups = LayoutConfig::default().units_per_space;
+    let staff = staff(&layout);
+    assert_eq!(staff.staff_lines.len(), 6);
+    let top = staff.top_line_y();
+    let bottom = staff.bottom_line_y();
+
+    let frets: Vec<(&str, u8)> = staff
+        .tab_frets
+        .iter()
+        .map(|f| (f.text.as_str(), f.string))
+        .collect();
+    // The tied C4 is parenthesised on the string it started on; the low E
+    // and D both go to the dropped sixth string
+    assert_eq!(
+        frets,
+        vec![("0", 2), ("1", 2), ("(1)", 2), ("2", 6), ("0", 6)]
+    );
+    for fret in &staff.tab_frets {
+        let line_y = top + f32::from(fret.string - 1) * ups;
+        assert!((fret.y - line_y).abs() < 0.01, "fret at {}", fret.y);
+    }
+
+    // Rhythm stems hang below the staff; there are no noteheads
+    let glyphs: Vec<_> = staff
+        .glyph_runs
+        .iter()
+        .flat_map(|run| &run.glyphs)
+        .collect();
+    let stems: Vec<_> = glyphs
+        .iter()
+        .filter(|g| g.codepoint == "\u{0000}")
+        .collect();
+    assert_eq!(stems.len(), 5);
+    assert!(stems.iter().all(|s| s.position.y > bottom));
+    // The unbeamed eighths get downward flags
+    assert_eq!(
+        glyphs.iter().filter(|g| g.codepoint == "\u{E241}").count(),
+        2
+    );
+    assert!(!glyphs.iter().any(|g| {
+        g.codepoint
+            .chars()
+            .next()
+            .is_some_and(|c| ('\u{E0A0}'..='\u{E0FF}').contains(&c))
+    }));
+    assert!(staff.tie_arcs.is_empty());
+
+    // The clef is the TAB sign
+    assert!(
+        staff
+            .structural_glyphs
+            .iter()
+            .any(|g| g.codepoint == "\u{E06D}")
+    );
+}
+
+#[test]
+fn test_tie_chain_keeps_its_string_in_chords() {
+    // Standard guitar: an A4 tied on twice, and a B4 struck with the end of
+    // the chain
+    let a4 = |tie: &str| {
+        format!(
+            r#"<note><pitch><step>A</step><octave>4</octave></pitch><duration>2</duration>{tie}<voice>1</voice><type>quarter</type></note>"#
+        )
+    };
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list>
+    <score-part id="P1"><part-name>Guitar</part-name></score-part>
+  </part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>2</divisions>
+        <time><beats>3</beats><beat-type>4</beat-type></time>
+        <clef><sign>TAB</sign><line>5</line></clef>
+      </attributes>
+      {}
+      {}
+      {}
+      <note><chord/><pitch><step>B</step><octave>4</octave></pitch><duration>2</duration><voice>1</voice><type>quarter</type></note>
+    </measure>
+  </part>
+</score-partwise>"#,
+        a4(r#"<tie type="start"/>"#),
+        a4(r#"<tie type="stop"/><tie type="start"/>"#),
+        a4(r#"<tie type="stop"/>"#),
+    );
+    let layout = layout(&import(&xml));
+    let mut frets: Vec<(&str, u8)> = staff(&layout)
+        .tab_frets
+        .iter()
+        .map(|f| (f.text.as_str(), f.string))
+        .collect();
+    frets.sort();
+    // The held A4 stays on string 1; the B4 takes string 2 rather than the
+    // 7th fret of string 1
+    assert_eq!(frets, vec![("(5)", 1), ("(5)", 1), ("12", 2), ("5", 1)]);
+}
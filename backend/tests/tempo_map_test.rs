@@ -0,0 +1,152 @@
+//! Integration tests for tempo import (metronome beat units, rit./a tempo,
+//! `<sound tempo>`, fermatas) and tick ↔ seconds conversion.
+
+mod common;
+
+use musicore_backend::domain::events::tempo::FermataHold;
+use musicore_backend::domain::score::Score;
+
+/// 6/8 at dotted quarter = 60 (90 quarters per minute); "rit." over
+/// measure 2, "a tempo" in measure 3 with a fermata on its last note,
+/// then `<sound tempo="100.5"/>` in measure 4.
+const TEMPO_CHANGES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Flute</part-name></score-part></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>2</divisions>
+        <time><beats>6</beats><beat-type>8</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+      </attributes>
+      <direction placement="above"><direction-type><metronome><beat-unit>quarter</beat-unit><beat-unit-dot/><per-minute>60</per-minute></metronome></direction-type></direction>
+      <note><pitch><step>C</step><octave>5</octave></pitch><duration>3</duration><voice>1</voice><type>quarter</type><dot/></note>
+      <note><pitch><step>D</step><octave>5</octave></pitch><duration>3</duration><voice>1</voice><type>quarter</type><dot/></note>
+    </measure>
+    <measure number="2">
+      <direction placement="above"><direction-type><words>rit.</words></direction-type></direction>
+      <note><pitch><step>E</step><octave>5</octave></pitch><duration>3</duration><voice>1</voice><type>quarter</type><dot/></note>
+      <note><pitch><step>F</step><octave>5</octave></pitch><duration>3</duration><voice>1</voice><type>quarter</type><dot/></note>
+    </measure>
+    <measure number="3">
+      <direction placement="above"><direction-type><words>a tempo</words></direction-type></direction>
+      <note><pitch><step>G</step><octave>5</octave></pitch><duration>3</duration><voice>1</voice><type>quarter</type><dot/></note>
+      <note><pitch><step>A</step><octave>5</octave></pitch><duration>3</duration><voice>1</voice><type>quarter</type><dot/><notations><fermata type="upright"/></notations></note>
+    </measure>
+    <measure number="4">
+      <direction><direction-type><words> </words></direction-type><sound tempo="100.5"/></direction>
+      <note><pitch><step>B</step><octave>5</octave></pitch><duration>6</duration><voice>1</voice><type>half</type><dot/></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+/// `<sound tempo="NaN"/>` in measure 1, a metronome mark of `inf` in
+/// measure 2 and `<sound tempo="90"/>` in measure 3
+const UNPLAYABLE_TEMPOS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<score-partwise version="3.1">
+  <part-list><score-part id="P1"><part-name>Flute</part-name></score-part></part-list>
+  <part id="P1">
+    <measure number="1">
+      <attributes>
+        <divisions>1</divisions>
+        <time><beats>4</beats><beat-type>4</beat-type></time>
+        <clef><sign>G</sign><line>2</line></clef>
+      </attributes>
+      <direction><direction-type><words>Presto</words></direction-type><sound tempo="NaN"/></direction>
+      <note><pitch><step>C</step><octave>5</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type></note>
+    </measure>
+    <measure number="2">
+      <direction placement="above"><direction-type><metronome><beat-unit>quarter</beat-unit><per-minute>inf</per-minute></metronome></direction-type></direction>
+      <note><pitch><step>D</step><octave>5</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type></note>
+    </measure>
+    <measure number="3">
+      <direction><sound tempo="90"/></direction>
+      <note><pitch><step>E</step><octave>5</octave></pitch><duration>4</duration><voice>1</voice><type>whole</type></note>
+    </measure>
+  </part>
+</score-partwise>"#;
+
+fn import() -> Score {
+    common::import(TEMPO_CHANGES)
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn test_import_tempo_events_ramps_and_fermatas() {
+    let score = import();
+    let events = score.tempo_events();
+    assert_eq!(events.len(), 4);
+
+    // Dotted quarter = 60
+    assert_eq!(events[0].tick.value(), 0);
+    assert_eq!(events[0].beat_ticks, 1440);
+    assert_close(events[0].beats_per_minute(), 60.0);
+    assert_close(events[0].quarter_bpm(), 90.0);
+
+    // rit. over measure 2, ending (by default) at 3/4 of the tempo
+    let rit = events[1].ramp.expect("rit. starts a ramp");
+    assert_eq!(events[1].tick.value(), 2880);
+    assert_eq!(rit.end_tick, 5760);
+    assert_eq!(rit.end_bpm_milli, 45_000);
+
+    // a tempo restores dotted quarter = 60
+    assert_eq!(events[2].tick.value(), 5760);
+    assert!(events[2].ramp.is_none());
+    assert_close(events[2].quarter_bpm(), 90.0);
+
+    // Fractional <sound tempo> in quarters per minute
+    assert_eq!(events[3].tick.value(), 8640);
+    assert_close(events[3].quarter_bpm(), 100.5);
+    assert_eq!(events[3].bpm.value(), 101);
+
+    assert_eq!(score.fermatas, vec![FermataHold::new(7200, 1440)]);
+}
+
+#[test]
+fn test_tempo_map_converts_ticks_and_seconds() {
+    let map = import().tempo_map();
+
+    // Measure 1: 2880 ticks at 90 quarters per minute
+    assert_close(map.tick_to_seconds(2880), 2.0);
+
+    // Measure 2: linear ramp from 90 to 67.5
+    let k = (67.5 - 90.0) / 2880.0;
+    let ramp = 60.0 / (960.0 * k) * (67.5f64 / 90.0).ln();
+    assert_close(map.tick_to_seconds(5760), 2.0 + ramp);
+    assert_close(map.quarter_bpm_at(4320), 78.75);
+
+    // Measure 3: one beat in tempo, then the fermata beat held twice as long
+    assert_close(map.tick_to_seconds(7200), 3.0 + ramp);
+    assert_close(map.tick_to_seconds(8640), 5.0 + ramp);
+
+    for tick in [0, 1000, 2880, 4000, 5760, 7500, 8640, 10000] {
+        assert_close(
+            map.seconds_to_tick(map.tick_to_seconds(tick)),
+            f64::from(tick),
+        );
+    }
+}
+
+#[test]
+fn test_unplayable_tempos_are_ignored() {
+    let score = common::import(UNPLAYABLE_TEMPOS);
+    let events = score.tempo_events();
+    assert!(
+        events
+            .iter()
+            .all(|e| e.quarter_bpm().is_finite() && e.quarter_bpm() > 0.0),
+        "{events:?}"
+    );
+    let last = events.last().expect("sound tempo 90 imported");
+    assert_eq!(last.tick.value(), 7680);
+    assert_close(last.quarter_bpm(), 90.0);
+
+    let map = score.tempo_map();
+    assert_close(map.tick_to_seconds(960), 0.5);
+}
@@ -12,8 +12,22 @@ const SCORES_STORE = 'scores';
  * Increment when data structure changes (e.g., v2 added active_clef)
  * v4: repeat_barlines added
  * v6: pickup_ticks added for anacrusis/pickup measure support
+ * v10: pedal_regions added to StaffDto
+ * v11: cross_staff added to notes
+ * v12: grace annotations added to notes, grace_timings added to StaffDto
+ * v13: arpeggio, gliss_next/gliss_wavy and tremolo added to notes
+ * v14: exact tempo (bpm_milli, beat_ticks, ramp) on tempo events, fermatas added to ScoreDto
+ * v15: printed tempo marks, text_directions added to StaffDto, rehearsal_marks added to ScoreDto
+ * v16: page_breaks added to ScoreDto
+ * v17: system_breaks added to ScoreDto
+ * v18: staff_lines added to StaffDto
+ * v19: tablature added to StaffDto, tab (string/fret) added to notes
+ * v20: part_groups added to ScoreDto
+ * v21: midi added to InstrumentDto
+ * v22: short_name added to InstrumentDto
+ * v23: measure_numbers added to ScoreDto
  */
-export const CURRENT_SCHEMA_VERSION = 9;
+export const CURRENT_SCHEMA_VERSION = 23;
 
 /**
  * Initialize IndexedDB database